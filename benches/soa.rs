@@ -74,6 +74,36 @@ impl Big {
     }
 }
 
+#[derive(StructOfArray)]
+#[soa(storage = "single_buffer")]
+pub struct BigBuffer {
+    position: (f64, f64, f64),
+    velocity: (f64, f64, f64),
+    data: [usize; 18],
+    name: String,
+    userdata: String
+}
+
+impl BigBuffer {
+    fn new() -> BigBuffer {
+        BigBuffer {
+            position: (1.0, 0.2, -2.3),
+            velocity: (1.0, 0.2, -2.3),
+            data: [67; 18],
+            name: "foo".into(),
+            userdata: "bar".into()
+        }
+    }
+
+    fn soa_vec(size: usize) -> BigBufferVec {
+        let mut vec = BigBufferVec::new();
+        for _ in 0..size {
+            vec.push(BigBuffer::new())
+        }
+        return vec;
+    }
+}
+
 fn aos_small_push(bencher: &mut Bencher) {
     let mut vec = Vec::new();
     bencher.iter(||{
@@ -168,6 +198,40 @@ fn soa_big_do_work_100k(bencher: &mut Bencher) {
     })
 }
 
+fn single_buffer_big_push(bencher: &mut Bencher) {
+    let mut vec = BigBufferVec::new();
+    bencher.iter(||{
+        vec.push(BigBuffer::new())
+    })
+}
+
+fn soa_big_reserve(bencher: &mut Bencher) {
+    bencher.iter(||{
+        let mut vec = BigVec::new();
+        vec.reserve(1000);
+        vec
+    })
+}
+
+fn single_buffer_big_reserve(bencher: &mut Bencher) {
+    bencher.iter(||{
+        let mut vec = BigBufferVec::new();
+        vec.reserve(1000);
+        vec
+    })
+}
+
+fn single_buffer_big_do_work_100k(bencher: &mut Bencher) {
+    let vec = BigBuffer::soa_vec(100_000);
+    bencher.iter(||{
+        let slice = vec.as_slice();
+        let mut s = 0.0;
+        for (position, velocity) in slice.position.iter().zip(slice.velocity) {
+            s += position.0 + velocity.0;
+        }
+        s
+    })
+}
 
 benchmark_group!(aos,
     aos_small_push, aos_big_push, aos_small_do_work_100k, aos_big_do_work_10k,
@@ -175,6 +239,9 @@ benchmark_group!(aos,
 );
 benchmark_group!(soa,
     soa_small_push, soa_big_push, soa_small_do_work_100k, soa_big_do_work_10k,
-    soa_big_do_work_100k
+    soa_big_do_work_100k, soa_big_reserve
+);
+benchmark_group!(single_buffer,
+    single_buffer_big_push, single_buffer_big_reserve, single_buffer_big_do_work_100k
 );
-benchmark_main!(soa, aos);
+benchmark_main!(soa, single_buffer, aos);
@@ -1,7 +1,17 @@
 use rustc_version::Version;
 
+// The UI tests compare compiler error messages, which change between
+// compiler versions. They only run with this version, which is also used in
+// the `ui-tests` CI job.
+const UI_TESTS_RUSTC_VERSION: (u64, u64) = (1, 95);
+
 fn main() {
-    if rustc_version::version().unwrap() >= Version::parse("1.78.0").unwrap() {
+    let version = rustc_version::version().unwrap();
+    if version >= Version::parse("1.78.0").unwrap() {
         println!("cargo:rustc-cfg=rustc_is_at_least_1_78");
     }
+
+    if (version.major, version.minor) == UI_TESTS_RUSTC_VERSION {
+        println!("cargo:rustc-cfg=rustc_runs_ui_tests");
+    }
 }
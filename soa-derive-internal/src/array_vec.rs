@@ -0,0 +1,425 @@
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+use syn::Index;
+
+use crate::input::Input;
+
+/// Generate the fixed-capacity `ArrayVec` type. Each field is stored inline,
+/// in a `[MaybeUninit<T>; CAPACITY]` array (or in the `ArrayVec` of the field
+/// type for nested fields), and the type keeps track of how many elements are
+/// initialized.
+pub fn derive(input: &Input) -> TokenStream {
+    let name = &input.name;
+    let visibility = &input.visibility;
+    let array_vec_name = &input.names.array_vec;
+    let slice_name = &input.names.slice;
+    let slice_mut_name = &input.names.slice_mut;
+    let ref_name = &input.names.ref_;
+    let ref_mut_name = &input.names.ref_mut;
+    let iter_name = &input.names.iter;
+    let iter_mut_name = &input.names.iter_mut;
+
+    let generic_params = input.generic_params();
+    let generic_args = input.generic_args();
+    let where_predicates = input.where_predicates();
+
+    let doc_url = format!("[`{0}`](struct.{0}.html)", name);
+    let ref_doc_url = format!("[`{0}`](struct.{0}.html)", ref_name);
+    let ref_mut_doc_url = format!("[`{0}`](struct.{0}.html)", ref_mut_name);
+
+    let fields_names = &input.fields_names();
+    let skipped_fields_init = input.skipped_fields_init();
+    let drop_skipped_value = input.drop_skipped_fields(&quote! { value });
+    let drop_skipped_element = input.drop_skipped_fields(&quote! { element });
+
+    let fields_names_hygienic = input.fields.iter()
+        .enumerate()
+        .map(|(i, _)| Ident::new(&format!("___soa_derive_private_{}", i), Span::call_site()))
+        .collect::<Vec<_>>();
+
+    // the columns are stored in a tuple, and accessed with `self.data.#index`
+    let field_index = |member: &syn::Member| {
+        let position = fields_names.iter().position(|name| name == member).expect("missing field");
+        Index::from(position)
+    };
+
+    let array_fields_types = input.map_fields_nested_or(
+        |_, field_type| quote! { <#field_type as ::soa_derive::SoAArrayVec<__SOA_CAPACITY>>::ArrayVec },
+        |_, field_type| quote! { [::core::mem::MaybeUninit<#field_type>; __SOA_CAPACITY] },
+    ).collect::<Vec<_>>();
+
+    let array_new = input.map_fields_nested_or(
+        |_, field_type| quote! { <#field_type as ::soa_derive::SoAArrayVec<__SOA_CAPACITY>>::ArrayVec::new() },
+        // an array of `MaybeUninit` does not require initialization
+        |_, field_type| quote! {
+            unsafe { ::core::mem::MaybeUninit::<[::core::mem::MaybeUninit<#field_type>; __SOA_CAPACITY]>::uninit().assume_init() }
+        },
+    ).collect::<Vec<_>>();
+
+    let array_push = input.map_fields_nested_or(
+        |member, _| {
+            let index = field_index(member);
+            quote! {
+                if let Err(field) = self.data.#index.push(::core::ptr::read(&value.#member)) {
+                    ::core::mem::forget(field);
+                    unreachable!("nested array vector is full");
+                }
+            }
+        },
+        |member, _| {
+            let index = field_index(member);
+            quote! {
+                self.data.#index[self.len] = ::core::mem::MaybeUninit::new(::core::ptr::read(&value.#member));
+            }
+        },
+    ).collect::<Vec<_>>();
+
+    let array_insert = input.map_fields_nested_or(
+        |member, _| {
+            let index = field_index(member);
+            quote! {
+                if let Err(field) = self.data.#index.insert(index, ::core::ptr::read(&element.#member)) {
+                    ::core::mem::forget(field);
+                    unreachable!("nested array vector is full");
+                }
+            }
+        },
+        |member, _| {
+            let index = field_index(member);
+            quote! {
+                let column = self.data.#index.as_mut_ptr();
+                ::core::ptr::copy(column.add(index), column.add(index + 1), self.len - index);
+                column.add(index).write(::core::mem::MaybeUninit::new(::core::ptr::read(&element.#member)));
+            }
+        },
+    ).collect::<Vec<_>>();
+
+    // these are used after decreasing `self.len` by one
+    let array_pop = input.map_fields_nested_or(
+        |member, _| {
+            let index = field_index(member);
+            quote! { self.data.#index.pop().expect("nested array vector is empty") }
+        },
+        |member, _| {
+            let index = field_index(member);
+            quote! { self.data.#index[self.len].as_ptr().read() }
+        },
+    ).collect::<Vec<_>>();
+
+    let array_remove = input.map_fields_nested_or(
+        |member, _| {
+            let index = field_index(member);
+            quote! { self.data.#index.remove(index) }
+        },
+        |member, _| {
+            let index = field_index(member);
+            quote! {{
+                let column = self.data.#index.as_mut_ptr();
+                let value = column.add(index).read().assume_init();
+                ::core::ptr::copy(column.add(index + 1), column.add(index), self.len - index);
+                value
+            }}
+        },
+    ).collect::<Vec<_>>();
+
+    let array_swap_remove = input.map_fields_nested_or(
+        |member, _| {
+            let index = field_index(member);
+            quote! { self.data.#index.swap_remove(index) }
+        },
+        |member, _| {
+            let index = field_index(member);
+            quote! {{
+                let column = self.data.#index.as_mut_ptr();
+                let value = column.add(index).read().assume_init();
+                ::core::ptr::copy(column.add(self.len), column.add(index), 1);
+                value
+            }}
+        },
+    ).collect::<Vec<_>>();
+
+    let array_truncate = input.map_fields_nested_or(
+        |member, _| {
+            let index = field_index(member);
+            quote! { self.data.#index.truncate(len); }
+        },
+        |member, field_type| {
+            let index = field_index(member);
+            quote! {
+                ::core::ptr::drop_in_place(::core::ptr::slice_from_raw_parts_mut(
+                    self.data.#index.as_mut_ptr().add(len).cast::<#field_type>(),
+                    removed,
+                ));
+            }
+        },
+    ).collect::<Vec<_>>();
+
+    let array_as_slice = input.map_fields_nested_or(
+        |member, _| {
+            let index = field_index(member);
+            quote! { self.data.#index.as_slice() }
+        },
+        |member, field_type| {
+            let index = field_index(member);
+            quote! {
+                unsafe { ::core::slice::from_raw_parts(self.data.#index.as_ptr().cast::<#field_type>(), self.len) }
+            }
+        },
+    ).collect::<Vec<_>>();
+
+    let array_as_mut_slice = input.map_fields_nested_or(
+        |member, _| {
+            let index = field_index(member);
+            quote! { self.data.#index.as_mut_slice() }
+        },
+        |member, field_type| {
+            let index = field_index(member);
+            quote! {
+                unsafe { ::core::slice::from_raw_parts_mut(self.data.#index.as_mut_ptr().cast::<#field_type>(), self.len) }
+            }
+        },
+    ).collect::<Vec<_>>();
+
+    quote! {
+        /// A fixed-capacity vector of
+        #[doc = #doc_url]
+        /// with Struct of Array (SoA) layout, storing up to `CAPACITY`
+        /// elements inline, without allocating.
+        #[allow(dead_code)]
+        #visibility struct #array_vec_name<#generic_params const __SOA_CAPACITY: usize> where #where_predicates {
+            data: (#(#array_fields_types,)*),
+            len: usize,
+        }
+
+        impl<#generic_params const __SOA_CAPACITY: usize> Default for #array_vec_name<#generic_args __SOA_CAPACITY> where #where_predicates {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl<#generic_params const __SOA_CAPACITY: usize> ::soa_derive::SoAArrayVec<__SOA_CAPACITY> for #name<#generic_args> where #where_predicates {
+            type ArrayVec = #array_vec_name<#generic_args __SOA_CAPACITY>;
+        }
+
+        #[allow(dead_code)]
+        #[allow(clippy::forget_non_drop)]
+        impl<#generic_params const __SOA_CAPACITY: usize> #array_vec_name<#generic_args __SOA_CAPACITY> where #where_predicates {
+            /// Create a new empty vector, with space for `CAPACITY` elements.
+            pub const fn new() -> Self {
+                #array_vec_name {
+                    data: (#(#array_new,)*),
+                    len: 0,
+                }
+            }
+
+            /// Get the maximal number of elements this vector can hold.
+            pub const fn capacity(&self) -> usize {
+                __SOA_CAPACITY
+            }
+
+            /// Similar to [`Vec::len()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.len).
+            pub const fn len(&self) -> usize {
+                self.len
+            }
+
+            /// Similar to [`Vec::is_empty()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.is_empty).
+            pub const fn is_empty(&self) -> bool {
+                self.len == 0
+            }
+
+            /// Check if this vector is full, i.e. if its length is equal to
+            /// its capacity.
+            pub const fn is_full(&self) -> bool {
+                self.len == __SOA_CAPACITY
+            }
+
+            /// Similar to [`Vec::push()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.push),
+            /// giving back the `value` in `Err` if the vector is full.
+            #[allow(clippy::forget_non_drop)]
+            pub fn push(&mut self, value: #name<#generic_args>) -> Result<(), #name<#generic_args>> {
+                if self.is_full() {
+                    return Err(value);
+                }
+
+                // We need to use ptr read/write instead of moving out of the
+                // fields in case the value struct implements Drop.
+                unsafe {
+                    #(#array_push)*
+                }
+                self.len += 1;
+                #drop_skipped_value
+                // if value implements Drop, we don't want to run it here, only
+                // when the vec itself will be dropped.
+                ::core::mem::forget(value);
+                Ok(())
+            }
+
+            /// Similar to [`Vec::pop()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.pop).
+            pub fn pop(&mut self) -> Option<#name<#generic_args>> {
+                if self.is_empty() {
+                    return None;
+                }
+
+                self.len -= 1;
+                unsafe {
+                    #(let #fields_names_hygienic = #array_pop;)*
+                    Some(#name{#(#fields_names: #fields_names_hygienic,)* #skipped_fields_init})
+                }
+            }
+
+            /// Similar to [`Vec::insert()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.insert),
+            /// giving back the `element` in `Err` if the vector is full.
+            #[allow(clippy::forget_non_drop)]
+            pub fn insert(&mut self, index: usize, element: #name<#generic_args>) -> Result<(), #name<#generic_args>> {
+                if index > self.len {
+                    panic!("index out of bounds: the len is {} but the index is {}", self.len, index);
+                }
+
+                if self.is_full() {
+                    return Err(element);
+                }
+
+                // similar to push, we can not use move and have to rely on ptr
+                // read/write
+                unsafe {
+                    #(#array_insert)*
+                }
+                self.len += 1;
+                #drop_skipped_element
+                // if value implements Drop, we don't want to run it here, only
+                // when the vec itself will be dropped.
+                ::core::mem::forget(element);
+                Ok(())
+            }
+
+            /// Similar to [`Vec::remove()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.remove).
+            pub fn remove(&mut self, index: usize) -> #name<#generic_args> {
+                if index >= self.len {
+                    panic!("removal index (is {}) should be < len (is {})", index, self.len);
+                }
+
+                self.len -= 1;
+                unsafe {
+                    #(let #fields_names_hygienic = #array_remove;)*
+                    #name{#(#fields_names: #fields_names_hygienic,)* #skipped_fields_init}
+                }
+            }
+
+            /// Similar to [`Vec::swap_remove()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.swap_remove).
+            pub fn swap_remove(&mut self, index: usize) -> #name<#generic_args> {
+                if index >= self.len {
+                    panic!("swap_remove index (is {}) should be < len (is {})", index, self.len);
+                }
+
+                self.len -= 1;
+                unsafe {
+                    #(let #fields_names_hygienic = #array_swap_remove;)*
+                    #name{#(#fields_names: #fields_names_hygienic,)* #skipped_fields_init}
+                }
+            }
+
+            /// Similar to [`Vec::truncate()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.truncate).
+            #[allow(unused_variables)]
+            pub fn truncate(&mut self, len: usize) {
+                if len >= self.len {
+                    return;
+                }
+
+                let removed = self.len - len;
+                self.len = len;
+                unsafe {
+                    #(#array_truncate)*
+                }
+            }
+
+            /// Similar to [`Vec::clear()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.clear).
+            pub fn clear(&mut self) {
+                self.truncate(0);
+            }
+
+            /// Get a slice containing all the elements in this vector.
+            pub fn as_slice(&self) -> #slice_name<'_, #generic_args> {
+                #slice_name {
+                    #(#fields_names: #array_as_slice, )*
+                }
+            }
+
+            /// Get a mutable slice containing all the elements in this vector.
+            pub fn as_mut_slice(&mut self) -> #slice_mut_name<'_, #generic_args> {
+                #slice_mut_name {
+                    #(#fields_names: #array_as_mut_slice, )*
+                }
+            }
+
+            /// Similar to [`<[T]>::get()`](https://doc.rust-lang.org/std/primitive.slice.html#method.get).
+            pub fn get<'__soa_a, __SoaI>(&'__soa_a self, index: __SoaI) -> Option<__SoaI::RefOutput>
+            where
+                __SoaI: ::soa_derive::SoAIndex<#slice_name<'__soa_a, #generic_args>>
+            {
+                index.get(self.as_slice())
+            }
+
+            /// Similar to [`<[T]>::index()`](https://doc.rust-lang.org/std/primitive.slice.html).
+            pub fn index<'__soa_a, __SoaI>(&'__soa_a self, index: __SoaI) -> __SoaI::RefOutput
+            where
+                __SoaI: ::soa_derive::SoAIndex<#slice_name<'__soa_a, #generic_args>>
+            {
+                index.index(self.as_slice())
+            }
+
+            /// Similar to [`<[T]>::get_mut()`](https://doc.rust-lang.org/std/primitive.slice.html#method.get_mut).
+            pub fn get_mut<'__soa_a, __SoaI>(&'__soa_a mut self, index: __SoaI) -> Option<__SoaI::MutOutput>
+            where
+                __SoaI: ::soa_derive::SoAIndexMut<#slice_mut_name<'__soa_a, #generic_args>>
+            {
+                index.get_mut(self.as_mut_slice())
+            }
+
+            /// Similar to [`<[T]>::index_mut()`](https://doc.rust-lang.org/std/primitive.slice.html).
+            pub fn index_mut<'__soa_a, __SoaI>(&'__soa_a mut self, index: __SoaI) -> __SoaI::MutOutput
+            where
+                __SoaI: ::soa_derive::SoAIndexMut<#slice_mut_name<'__soa_a, #generic_args>>
+            {
+                index.index_mut(self.as_mut_slice())
+            }
+
+            /// Get an iterator over the
+            #[doc = #ref_doc_url]
+            /// in this vector
+            pub fn iter(&self) -> #iter_name<'_, #generic_args> {
+                self.as_slice().into_iter()
+            }
+
+            /// Get a mutable iterator over the
+            #[doc = #ref_mut_doc_url]
+            /// in this vector
+            pub fn iter_mut(&mut self) -> #iter_mut_name<'_, #generic_args> {
+                self.as_mut_slice().into_iter()
+            }
+        }
+
+        impl<#generic_params const __SOA_CAPACITY: usize> Drop for #array_vec_name<#generic_args __SOA_CAPACITY> where #where_predicates {
+            fn drop(&mut self) {
+                self.clear();
+            }
+        }
+
+        impl<'__soa_a, #generic_params const __SOA_CAPACITY: usize> IntoIterator for &'__soa_a #array_vec_name<#generic_args __SOA_CAPACITY> where #where_predicates {
+            type Item = #ref_name<'__soa_a, #generic_args>;
+            type IntoIter = #iter_name<'__soa_a, #generic_args>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                self.as_slice().into_iter()
+            }
+        }
+
+        impl<'__soa_a, #generic_params const __SOA_CAPACITY: usize> IntoIterator for &'__soa_a mut #array_vec_name<#generic_args __SOA_CAPACITY> where #where_predicates {
+            type Item = #ref_mut_name<'__soa_a, #generic_args>;
+            type IntoIter = #iter_mut_name<'__soa_a, #generic_args>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                self.as_mut_slice().into_iter()
+            }
+        }
+    }
+}
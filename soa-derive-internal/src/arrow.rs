@@ -0,0 +1,123 @@
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+
+use crate::input::{Input, Storage};
+
+/// Generate the conversions between the `Vec` type and Arrow record batches,
+/// only when the `arrow` feature is enabled. The columns are converted with
+/// `soa_derive::arrow::ArrowColumn`, which is also implemented for the `Vec`
+/// type to support nested fields as struct arrays.
+pub fn derive(input: &Input) -> TokenStream {
+    if input.storage == Storage::SingleBuffer {
+        // the columns of single buffer vectors can not be moved in and out
+        return TokenStream::new();
+    }
+
+    let vec_name = &input.names.vec;
+
+    let generic_params = input.generic_params();
+    let generic_args = input.generic_args();
+    let where_predicates = input.where_predicates();
+
+    let fields_names = &input.fields_names();
+    let fields_names_hygienic = fields_names.iter()
+        .enumerate()
+        .map(|(i, _)| Ident::new(&format!("___soa_derive_private_{}", i), Span::call_site()))
+        .collect::<Vec<_>>();
+
+    let vec_fields_types = &input.map_fields_nested_or(
+        |_, field_type| quote! { <#field_type as ::soa_derive::StructOfArray>::Type },
+        |_, field_type| quote! { ::soa_derive::alloc::vec::Vec<#field_type> },
+    ).collect::<Vec<_>>();
+
+    // Only expose the conversions if all columns can be converted. The
+    // `for<'b>` makes sure these bounds are not checked for structs without
+    // generic parameters.
+    // https://github.com/rust-lang/rust/issues/48214#issuecomment-1150463333
+    let arrow_columns = quote! {
+        #( for<'__soa_b> #vec_fields_types: ::soa_derive::arrow::ArrowColumn, )*
+    };
+
+    quote! {
+        #[allow(dead_code)]
+        impl<#generic_params> #vec_name<#generic_args> where #arrow_columns #where_predicates {
+            /// Get the Arrow schema of the record batches created by
+            /// `to_record_batch()`, with one non-nullable field for each
+            /// field of this vector.
+            pub fn arrow_schema() -> ::soa_derive::arrow_schema::Schema {
+                ::soa_derive::arrow_schema::Schema::new(::soa_derive::alloc::vec![#(
+                    ::soa_derive::arrow_schema::Field::new(
+                        stringify!(#fields_names),
+                        <#vec_fields_types as ::soa_derive::arrow::ArrowColumn>::arrow_data_type(),
+                        false,
+                    ),
+                )*])
+            }
+
+            /// Convert this vector to an Arrow record batch, copying the data.
+            pub fn to_record_batch(&self) -> Result<::soa_derive::arrow_array::RecordBatch, ::soa_derive::arrow_schema::ArrowError> {
+                ::soa_derive::arrow_array::RecordBatch::try_new(
+                    ::soa_derive::alloc::sync::Arc::new(Self::arrow_schema()),
+                    ::soa_derive::alloc::vec![#(
+                        ::soa_derive::arrow::ArrowColumn::to_arrow_array(&self.#fields_names)?,
+                    )*],
+                )
+            }
+
+            /// Convert this vector to an Arrow record batch. Columns of
+            /// primitive types are moved to the record batch without copying
+            /// them.
+            pub fn into_record_batch(self) -> Result<::soa_derive::arrow_array::RecordBatch, ::soa_derive::arrow_schema::ArrowError> {
+                let (#(#fields_names_hygienic,)*) = self.into_columns();
+                ::soa_derive::arrow_array::RecordBatch::try_new(
+                    ::soa_derive::alloc::sync::Arc::new(Self::arrow_schema()),
+                    ::soa_derive::alloc::vec![#(
+                        ::soa_derive::arrow::ArrowColumn::into_arrow_array(#fields_names_hygienic)?,
+                    )*],
+                )
+            }
+
+            /// Create a vector from an Arrow record batch, which must contain
+            /// a column for each field of this vector with the type given in
+            /// `arrow_schema()`. Additional columns are ignored.
+            pub fn try_from_record_batch(batch: &::soa_derive::arrow_array::RecordBatch) -> Result<#vec_name<#generic_args>, ::soa_derive::arrow_schema::ArrowError> {
+                let array = ::soa_derive::arrow_array::StructArray::from(batch.clone());
+                <Self as ::soa_derive::arrow::ArrowColumn>::try_from_arrow_array(&array)
+            }
+        }
+
+        impl<#generic_params> ::soa_derive::arrow::ArrowColumn for #vec_name<#generic_args> where #arrow_columns #where_predicates {
+            fn arrow_data_type() -> ::soa_derive::arrow_schema::DataType {
+                ::soa_derive::arrow_schema::DataType::Struct(Self::arrow_schema().fields().clone())
+            }
+
+            fn to_arrow_array(&self) -> Result<::soa_derive::arrow_array::ArrayRef, ::soa_derive::arrow_schema::ArrowError> {
+                ::soa_derive::arrow::struct_array(Self::arrow_data_type(), ::soa_derive::alloc::vec![#(
+                    ::soa_derive::arrow::ArrowColumn::to_arrow_array(&self.#fields_names)?,
+                )*])
+            }
+
+            fn into_arrow_array(self) -> Result<::soa_derive::arrow_array::ArrayRef, ::soa_derive::arrow_schema::ArrowError> {
+                let data_type = Self::arrow_data_type();
+                let (#(#fields_names_hygienic,)*) = self.into_columns();
+                ::soa_derive::arrow::struct_array(data_type, ::soa_derive::alloc::vec![#(
+                    ::soa_derive::arrow::ArrowColumn::into_arrow_array(#fields_names_hygienic)?,
+                )*])
+            }
+
+            fn try_from_arrow_array(array: &dyn ::soa_derive::arrow_array::Array) -> Result<Self, ::soa_derive::arrow_schema::ArrowError> {
+                let array = ::soa_derive::arrow::downcast_struct(array)?;
+
+                #(
+                    let column = ::soa_derive::arrow::struct_column(array, stringify!(#fields_names))?;
+                    let #fields_names_hygienic = <#vec_fields_types as ::soa_derive::arrow::ArrowColumn>::try_from_arrow_array(column.as_ref())
+                        .map_err(|error| ::soa_derive::arrow::column_error(stringify!(#fields_names), error))?;
+                )*
+
+                #vec_name::from_columns(#(#fields_names_hygienic),*).map_err(|error| {
+                    ::soa_derive::arrow_schema::ArrowError::InvalidArgumentError(::soa_derive::alloc::string::ToString::to_string(&error))
+                })
+            }
+        }
+    }
+}
@@ -0,0 +1,488 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::input::Input;
+
+/// Generate the iterators over chunks of slices: `chunks_exact()` yields
+/// slices of a given length, and `array_chunks()` yields types containing
+/// references to fixed-size arrays for each field, which can be used directly
+/// by SIMD code.
+pub fn derive(input: &Input) -> TokenStream {
+    let name = &input.name;
+    let visibility = &input.visibility;
+    let vec_name = &input.names.vec;
+    let slice_name = &input.names.slice;
+    let slice_mut_name = &input.names.slice_mut;
+    let chunks_exact_name = &input.names.chunks_exact;
+    let chunks_exact_mut_name = &input.names.chunks_exact_mut;
+    let array_chunk_name = &input.names.array_chunk;
+    let array_chunk_mut_name = &input.names.array_chunk_mut;
+    let array_chunks_iter_name = &input.names.array_chunks;
+    let array_chunks_iter_mut_name = &input.names.array_chunks_mut;
+
+    let generic_params = input.generic_params();
+    let generic_args = input.generic_args();
+    let where_predicates = input.where_predicates();
+    let outlive_a = input.outlive_predicates(&quote! { '__soa_a });
+    let nested_outlive_a = input.nested_outlive_predicates(&quote! { '__soa_a });
+
+    let doc_url = format!("[`{0}`](struct.{0}.html)", name);
+    let vec_doc_url = format!("[`{0}`](struct.{0}.html)", vec_name);
+    let slice_doc_url = format!("[`{0}`](struct.{0}.html)", slice_name);
+    let slice_mut_doc_url = format!("[`{0}`](struct.{0}.html)", slice_mut_name);
+    let array_chunk_doc_url = format!("[`{0}`](struct.{0}.html)", array_chunk_name);
+    let array_chunk_mut_doc_url = format!("[`{0}`](struct.{0}.html)", array_chunk_mut_name);
+
+    let fields_names = &input.fields_names();
+
+    let array_chunk_fields_types = input.map_fields_nested_or(
+        |_, field_type| quote! { <#field_type as ::soa_derive::SoAArrayChunks<'__soa_a, __SOA_CHUNK>>::Chunk },
+        |_, field_type| quote! { &'__soa_a [#field_type; __SOA_CHUNK] },
+    ).collect::<Vec<_>>();
+
+    let array_chunk_mut_fields_types = input.map_fields_nested_or(
+        |_, field_type| quote! { <#field_type as ::soa_derive::SoAArrayChunks<'__soa_a, __SOA_CHUNK>>::ChunkMut },
+        |_, field_type| quote! { &'__soa_a mut [#field_type; __SOA_CHUNK] },
+    ).collect::<Vec<_>>();
+
+    let to_array_chunk = input.map_fields_nested_or(
+        |ident, _| quote! { self.#ident.__private_array_chunk::<__SOA_CHUNK>() },
+        |ident, _| quote! {
+            ::core::convert::TryInto::try_into(self.#ident).expect("all fields should have CHUNK elements")
+        },
+    ).collect::<Vec<_>>();
+
+    let to_array_chunk_mut = input.map_fields_nested_or(
+        |ident, _| quote! { self.#ident.__private_array_chunk_mut::<__SOA_CHUNK>() },
+        |ident, _| quote! {
+            ::core::convert::TryInto::try_into(self.#ident).expect("all fields should have CHUNK elements")
+        },
+    ).collect::<Vec<_>>();
+
+    let fields_docs = fields_names.iter().map(|field| quote! {
+        /// reference to the `
+        #[doc = stringify!(#field)]
+        ///` fields of `CHUNK` consecutive
+        #[doc = #doc_url]
+        /// inside a
+        #[doc = #vec_doc_url]
+    }).collect::<Vec<_>>();
+    let array_chunk_body = input.struct_body(Some(&quote! { '__soa_a }), &fields_docs, &array_chunk_fields_types);
+    let array_chunk_mut_body = input.struct_body(Some(&quote! { '__soa_a }), &fields_docs, &array_chunk_mut_fields_types);
+
+    quote! {
+        /// An iterator over a
+        #[doc = #slice_doc_url]
+        /// in non-overlapping chunks of `chunk_size` elements, similar to
+        /// [`std::slice::ChunksExact`](https://doc.rust-lang.org/std/slice/struct.ChunksExact.html).
+        ///
+        /// The elements at the end of the slice which do not fit in a full
+        /// chunk are available with `remainder()`.
+        #[allow(missing_debug_implementations)]
+        #visibility struct #chunks_exact_name<'__soa_a, #generic_params> where #nested_outlive_a #where_predicates {
+            slice: #slice_name<'__soa_a, #generic_args>,
+            remainder: #slice_name<'__soa_a, #generic_args>,
+            chunk_size: usize,
+        }
+
+        impl<'__soa_a, #generic_params> #chunks_exact_name<'__soa_a, #generic_args> where #where_predicates {
+            /// Get the elements at the end of the slice which do not fit in a
+            /// full chunk.
+            pub fn remainder(&self) -> #slice_name<'__soa_a, #generic_args> {
+                self.remainder
+            }
+        }
+
+        impl<'__soa_a, #generic_params> Iterator for #chunks_exact_name<'__soa_a, #generic_args> where #where_predicates {
+            type Item = #slice_name<'__soa_a, #generic_args>;
+
+            #[inline]
+            fn next(&mut self) -> Option<#slice_name<'__soa_a, #generic_args>> {
+                if self.slice.len() < self.chunk_size {
+                    None
+                } else {
+                    let (chunk, rest) = self.slice.split_at(self.chunk_size);
+                    self.slice = rest;
+                    Some(chunk)
+                }
+            }
+
+            #[inline]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                let len = self.slice.len() / self.chunk_size;
+                (len, Some(len))
+            }
+        }
+
+        impl<'__soa_a, #generic_params> DoubleEndedIterator for #chunks_exact_name<'__soa_a, #generic_args> where #where_predicates {
+            #[inline]
+            fn next_back(&mut self) -> Option<#slice_name<'__soa_a, #generic_args>> {
+                if self.slice.len() < self.chunk_size {
+                    None
+                } else {
+                    let (rest, chunk) = self.slice.split_at(self.slice.len() - self.chunk_size);
+                    self.slice = rest;
+                    Some(chunk)
+                }
+            }
+        }
+
+        impl<'__soa_a, #generic_params> ExactSizeIterator for #chunks_exact_name<'__soa_a, #generic_args> where #where_predicates {}
+        impl<'__soa_a, #generic_params> ::core::iter::FusedIterator for #chunks_exact_name<'__soa_a, #generic_args> where #where_predicates {}
+
+        /// An iterator over a
+        #[doc = #slice_mut_doc_url]
+        /// in non-overlapping chunks of `chunk_size` elements, similar to
+        /// [`std::slice::ChunksExactMut`](https://doc.rust-lang.org/std/slice/struct.ChunksExactMut.html).
+        ///
+        /// The elements at the end of the slice which do not fit in a full
+        /// chunk are available with `into_remainder()`.
+        #[allow(missing_debug_implementations)]
+        #visibility struct #chunks_exact_mut_name<'__soa_a, #generic_params> where #nested_outlive_a #where_predicates {
+            slice: #slice_mut_name<'__soa_a, #generic_args>,
+            remainder: #slice_mut_name<'__soa_a, #generic_args>,
+            chunk_size: usize,
+        }
+
+        impl<'__soa_a, #generic_params> #chunks_exact_mut_name<'__soa_a, #generic_args> where #where_predicates {
+            /// Get the elements at the end of the slice which do not fit in a
+            /// full chunk.
+            pub fn into_remainder(self) -> #slice_mut_name<'__soa_a, #generic_args> {
+                self.remainder
+            }
+        }
+
+        impl<'__soa_a, #generic_params> Iterator for #chunks_exact_mut_name<'__soa_a, #generic_args> where #where_predicates {
+            type Item = #slice_mut_name<'__soa_a, #generic_args>;
+
+            #[inline]
+            fn next(&mut self) -> Option<#slice_mut_name<'__soa_a, #generic_args>> {
+                if self.slice.len() < self.chunk_size {
+                    None
+                } else {
+                    let slice = ::core::mem::take(&mut self.slice);
+                    let (chunk, rest) = slice.split_at_mut(self.chunk_size);
+                    self.slice = rest;
+                    Some(chunk)
+                }
+            }
+
+            #[inline]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                let len = self.slice.len() / self.chunk_size;
+                (len, Some(len))
+            }
+        }
+
+        impl<'__soa_a, #generic_params> DoubleEndedIterator for #chunks_exact_mut_name<'__soa_a, #generic_args> where #where_predicates {
+            #[inline]
+            fn next_back(&mut self) -> Option<#slice_mut_name<'__soa_a, #generic_args>> {
+                if self.slice.len() < self.chunk_size {
+                    None
+                } else {
+                    let slice = ::core::mem::take(&mut self.slice);
+                    let mid = slice.len() - self.chunk_size;
+                    let (rest, chunk) = slice.split_at_mut(mid);
+                    self.slice = rest;
+                    Some(chunk)
+                }
+            }
+        }
+
+        impl<'__soa_a, #generic_params> ExactSizeIterator for #chunks_exact_mut_name<'__soa_a, #generic_args> where #where_predicates {}
+        impl<'__soa_a, #generic_params> ::core::iter::FusedIterator for #chunks_exact_mut_name<'__soa_a, #generic_args> where #where_predicates {}
+
+        /// References to the fields of `CHUNK` consecutive
+        #[doc = #doc_url]
+        /// as fixed-size arrays, created by `array_chunks()`.
+        #visibility struct #array_chunk_name<'__soa_a, #generic_params const __SOA_CHUNK: usize> #array_chunk_body
+
+        // Chunks are always Copy, regardless of the fields types, so we can
+        // not use `#[derive(Copy, Clone)]` which would require `T: Copy`.
+        impl<'__soa_a, #generic_params const __SOA_CHUNK: usize> Copy for #array_chunk_name<'__soa_a, #generic_args __SOA_CHUNK> where #where_predicates {}
+        impl<'__soa_a, #generic_params const __SOA_CHUNK: usize> Clone for #array_chunk_name<'__soa_a, #generic_args __SOA_CHUNK> where #where_predicates {
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+
+        /// Mutable references to the fields of `CHUNK` consecutive
+        #[doc = #doc_url]
+        /// as fixed-size arrays, created by `array_chunks_mut()`.
+        #visibility struct #array_chunk_mut_name<'__soa_a, #generic_params const __SOA_CHUNK: usize> #array_chunk_mut_body
+
+        impl<'__soa_a, #generic_params const __SOA_CHUNK: usize> ::soa_derive::SoAArrayChunks<'__soa_a, __SOA_CHUNK> for #name<#generic_args> where #outlive_a #where_predicates {
+            type Chunk = #array_chunk_name<'__soa_a, #generic_args __SOA_CHUNK>;
+            type ChunkMut = #array_chunk_mut_name<'__soa_a, #generic_args __SOA_CHUNK>;
+        }
+
+        /// An iterator over a
+        #[doc = #slice_doc_url]
+        /// in non-overlapping
+        #[doc = #array_chunk_doc_url]
+        /// of `CHUNK` elements, similar to
+        /// [`std::slice::ArrayChunks`](https://doc.rust-lang.org/std/slice/struct.ArrayChunks.html).
+        ///
+        /// The elements at the end of the slice which do not fit in a full
+        /// chunk are available with `remainder()`.
+        #[allow(missing_debug_implementations)]
+        #visibility struct #array_chunks_iter_name<'__soa_a, #generic_params const __SOA_CHUNK: usize> where #nested_outlive_a #where_predicates {
+            slice: #slice_name<'__soa_a, #generic_args>,
+            remainder: #slice_name<'__soa_a, #generic_args>,
+        }
+
+        impl<'__soa_a, #generic_params const __SOA_CHUNK: usize> #array_chunks_iter_name<'__soa_a, #generic_args __SOA_CHUNK> where #where_predicates {
+            /// Get the elements at the end of the slice which do not fit in a
+            /// full chunk.
+            pub fn remainder(&self) -> #slice_name<'__soa_a, #generic_args> {
+                self.remainder
+            }
+        }
+
+        impl<'__soa_a, #generic_params const __SOA_CHUNK: usize> Iterator for #array_chunks_iter_name<'__soa_a, #generic_args __SOA_CHUNK> where #where_predicates {
+            type Item = #array_chunk_name<'__soa_a, #generic_args __SOA_CHUNK>;
+
+            #[inline]
+            fn next(&mut self) -> Option<#array_chunk_name<'__soa_a, #generic_args __SOA_CHUNK>> {
+                if self.slice.len() < __SOA_CHUNK {
+                    None
+                } else {
+                    let (chunk, rest) = self.slice.split_at(__SOA_CHUNK);
+                    self.slice = rest;
+                    Some(chunk.__private_array_chunk::<__SOA_CHUNK>())
+                }
+            }
+
+            #[inline]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                let len = self.slice.len() / __SOA_CHUNK;
+                (len, Some(len))
+            }
+        }
+
+        impl<'__soa_a, #generic_params const __SOA_CHUNK: usize> DoubleEndedIterator for #array_chunks_iter_name<'__soa_a, #generic_args __SOA_CHUNK> where #where_predicates {
+            #[inline]
+            fn next_back(&mut self) -> Option<#array_chunk_name<'__soa_a, #generic_args __SOA_CHUNK>> {
+                if self.slice.len() < __SOA_CHUNK {
+                    None
+                } else {
+                    let (rest, chunk) = self.slice.split_at(self.slice.len() - __SOA_CHUNK);
+                    self.slice = rest;
+                    Some(chunk.__private_array_chunk::<__SOA_CHUNK>())
+                }
+            }
+        }
+
+        impl<'__soa_a, #generic_params const __SOA_CHUNK: usize> ExactSizeIterator for #array_chunks_iter_name<'__soa_a, #generic_args __SOA_CHUNK> where #where_predicates {}
+        impl<'__soa_a, #generic_params const __SOA_CHUNK: usize> ::core::iter::FusedIterator for #array_chunks_iter_name<'__soa_a, #generic_args __SOA_CHUNK> where #where_predicates {}
+
+        /// An iterator over a
+        #[doc = #slice_mut_doc_url]
+        /// in non-overlapping
+        #[doc = #array_chunk_mut_doc_url]
+        /// of `CHUNK` elements, similar to
+        /// [`std::slice::ArrayChunksMut`](https://doc.rust-lang.org/std/slice/struct.ArrayChunksMut.html).
+        ///
+        /// The elements at the end of the slice which do not fit in a full
+        /// chunk are available with `into_remainder()`.
+        #[allow(missing_debug_implementations)]
+        #visibility struct #array_chunks_iter_mut_name<'__soa_a, #generic_params const __SOA_CHUNK: usize> where #nested_outlive_a #where_predicates {
+            slice: #slice_mut_name<'__soa_a, #generic_args>,
+            remainder: #slice_mut_name<'__soa_a, #generic_args>,
+        }
+
+        impl<'__soa_a, #generic_params const __SOA_CHUNK: usize> #array_chunks_iter_mut_name<'__soa_a, #generic_args __SOA_CHUNK> where #where_predicates {
+            /// Get the elements at the end of the slice which do not fit in a
+            /// full chunk.
+            pub fn into_remainder(self) -> #slice_mut_name<'__soa_a, #generic_args> {
+                self.remainder
+            }
+        }
+
+        impl<'__soa_a, #generic_params const __SOA_CHUNK: usize> Iterator for #array_chunks_iter_mut_name<'__soa_a, #generic_args __SOA_CHUNK> where #where_predicates {
+            type Item = #array_chunk_mut_name<'__soa_a, #generic_args __SOA_CHUNK>;
+
+            #[inline]
+            fn next(&mut self) -> Option<#array_chunk_mut_name<'__soa_a, #generic_args __SOA_CHUNK>> {
+                if self.slice.len() < __SOA_CHUNK {
+                    None
+                } else {
+                    let slice = ::core::mem::take(&mut self.slice);
+                    let (chunk, rest) = slice.split_at_mut(__SOA_CHUNK);
+                    self.slice = rest;
+                    Some(chunk.__private_array_chunk_mut::<__SOA_CHUNK>())
+                }
+            }
+
+            #[inline]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                let len = self.slice.len() / __SOA_CHUNK;
+                (len, Some(len))
+            }
+        }
+
+        impl<'__soa_a, #generic_params const __SOA_CHUNK: usize> DoubleEndedIterator for #array_chunks_iter_mut_name<'__soa_a, #generic_args __SOA_CHUNK> where #where_predicates {
+            #[inline]
+            fn next_back(&mut self) -> Option<#array_chunk_mut_name<'__soa_a, #generic_args __SOA_CHUNK>> {
+                if self.slice.len() < __SOA_CHUNK {
+                    None
+                } else {
+                    let slice = ::core::mem::take(&mut self.slice);
+                    let mid = slice.len() - __SOA_CHUNK;
+                    let (rest, chunk) = slice.split_at_mut(mid);
+                    self.slice = rest;
+                    Some(chunk.__private_array_chunk_mut::<__SOA_CHUNK>())
+                }
+            }
+        }
+
+        impl<'__soa_a, #generic_params const __SOA_CHUNK: usize> ExactSizeIterator for #array_chunks_iter_mut_name<'__soa_a, #generic_args __SOA_CHUNK> where #where_predicates {}
+        impl<'__soa_a, #generic_params const __SOA_CHUNK: usize> ::core::iter::FusedIterator for #array_chunks_iter_mut_name<'__soa_a, #generic_args __SOA_CHUNK> where #where_predicates {}
+
+        impl<'__soa_a, #generic_params> #slice_name<'__soa_a, #generic_args> where #where_predicates {
+            /// Similar to [`<[T]>::chunks_exact()`](https://doc.rust-lang.org/std/primitive.slice.html#method.chunks_exact),
+            /// returning an iterator over non-overlapping slices of
+            /// `chunk_size` elements.
+            ///
+            /// # Panics
+            ///
+            /// If `chunk_size` is 0.
+            pub fn chunks_exact(&self, chunk_size: usize) -> #chunks_exact_name<'__soa_a, #generic_args> {
+                assert!(chunk_size != 0, "chunk size must be non-zero");
+                let (slice, remainder) = self.split_at(self.len() - self.len() % chunk_size);
+                #chunks_exact_name { slice, remainder, chunk_size }
+            }
+
+            /// Similar to [`<[T]>::array_chunks()`](https://doc.rust-lang.org/std/primitive.slice.html#method.array_chunks),
+            /// returning an iterator over non-overlapping chunks of `CHUNK`
+            /// elements, with a `&[T; CHUNK]` array for each field.
+            ///
+            /// # Panics
+            ///
+            /// If `CHUNK` is 0.
+            pub fn array_chunks<const __SOA_CHUNK: usize>(&self) -> #array_chunks_iter_name<'__soa_a, #generic_args __SOA_CHUNK> {
+                assert!(__SOA_CHUNK != 0, "chunk size must be non-zero");
+                let (slice, remainder) = self.split_at(self.len() - self.len() % __SOA_CHUNK);
+                #array_chunks_iter_name { slice, remainder }
+            }
+
+            #[doc(hidden)]
+            pub fn __private_array_chunk<const __SOA_CHUNK: usize>(self) -> #array_chunk_name<'__soa_a, #generic_args __SOA_CHUNK> {
+                #array_chunk_name {
+                    #( #fields_names: #to_array_chunk, )*
+                }
+            }
+        }
+
+        impl<'__soa_a, #generic_params> #slice_mut_name<'__soa_a, #generic_args> where #where_predicates {
+            /// Similar to [`<[T]>::chunks_exact()`](https://doc.rust-lang.org/std/primitive.slice.html#method.chunks_exact),
+            /// returning an iterator over non-overlapping slices of
+            /// `chunk_size` elements.
+            ///
+            /// # Panics
+            ///
+            /// If `chunk_size` is 0.
+            pub fn chunks_exact(&self, chunk_size: usize) -> #chunks_exact_name<'_, #generic_args> {
+                self.as_slice().chunks_exact(chunk_size)
+            }
+
+            /// Similar to [`<[T]>::chunks_exact_mut()`](https://doc.rust-lang.org/std/primitive.slice.html#method.chunks_exact_mut),
+            /// returning an iterator over non-overlapping mutable slices of
+            /// `chunk_size` elements.
+            ///
+            /// # Panics
+            ///
+            /// If `chunk_size` is 0.
+            pub fn chunks_exact_mut(&mut self, chunk_size: usize) -> #chunks_exact_mut_name<'_, #generic_args> {
+                assert!(chunk_size != 0, "chunk size must be non-zero");
+                let mid = self.len() - self.len() % chunk_size;
+                let (slice, remainder) = self.reborrow().split_at_mut(mid);
+                #chunks_exact_mut_name { slice, remainder, chunk_size }
+            }
+
+            /// Similar to [`<[T]>::array_chunks()`](https://doc.rust-lang.org/std/primitive.slice.html#method.array_chunks),
+            /// returning an iterator over non-overlapping chunks of `CHUNK`
+            /// elements, with a `&[T; CHUNK]` array for each field.
+            ///
+            /// # Panics
+            ///
+            /// If `CHUNK` is 0.
+            pub fn array_chunks<const __SOA_CHUNK: usize>(&self) -> #array_chunks_iter_name<'_, #generic_args __SOA_CHUNK> {
+                self.as_slice().array_chunks::<__SOA_CHUNK>()
+            }
+
+            /// Similar to [`<[T]>::array_chunks_mut()`](https://doc.rust-lang.org/std/primitive.slice.html#method.array_chunks_mut),
+            /// returning an iterator over non-overlapping chunks of `CHUNK`
+            /// elements, with a `&mut [T; CHUNK]` array for each field.
+            ///
+            /// # Panics
+            ///
+            /// If `CHUNK` is 0.
+            pub fn array_chunks_mut<const __SOA_CHUNK: usize>(&mut self) -> #array_chunks_iter_mut_name<'_, #generic_args __SOA_CHUNK> {
+                assert!(__SOA_CHUNK != 0, "chunk size must be non-zero");
+                let mid = self.len() - self.len() % __SOA_CHUNK;
+                let (slice, remainder) = self.reborrow().split_at_mut(mid);
+                #array_chunks_iter_mut_name { slice, remainder }
+            }
+
+            #[doc(hidden)]
+            pub fn __private_array_chunk_mut<const __SOA_CHUNK: usize>(self) -> #array_chunk_mut_name<'__soa_a, #generic_args __SOA_CHUNK> {
+                #array_chunk_mut_name {
+                    #( #fields_names: #to_array_chunk_mut, )*
+                }
+            }
+        }
+
+        impl<#generic_params> #vec_name<#generic_args> where #where_predicates {
+            /// Similar to [`<[T]>::chunks_exact()`](https://doc.rust-lang.org/std/primitive.slice.html#method.chunks_exact),
+            /// returning an iterator over non-overlapping slices of
+            /// `chunk_size` elements.
+            ///
+            /// # Panics
+            ///
+            /// If `chunk_size` is 0.
+            pub fn chunks_exact(&self, chunk_size: usize) -> #chunks_exact_name<'_, #generic_args> {
+                self.as_slice().chunks_exact(chunk_size)
+            }
+
+            /// Similar to [`<[T]>::chunks_exact_mut()`](https://doc.rust-lang.org/std/primitive.slice.html#method.chunks_exact_mut),
+            /// returning an iterator over non-overlapping mutable slices of
+            /// `chunk_size` elements.
+            ///
+            /// # Panics
+            ///
+            /// If `chunk_size` is 0.
+            pub fn chunks_exact_mut(&mut self, chunk_size: usize) -> #chunks_exact_mut_name<'_, #generic_args> {
+                assert!(chunk_size != 0, "chunk size must be non-zero");
+                let len = self.len();
+                let (slice, remainder) = self.as_mut_slice().split_at_mut(len - len % chunk_size);
+                #chunks_exact_mut_name { slice, remainder, chunk_size }
+            }
+
+            /// Similar to [`<[T]>::array_chunks()`](https://doc.rust-lang.org/std/primitive.slice.html#method.array_chunks),
+            /// returning an iterator over non-overlapping chunks of `CHUNK`
+            /// elements, with a `&[T; CHUNK]` array for each field.
+            ///
+            /// # Panics
+            ///
+            /// If `CHUNK` is 0.
+            pub fn array_chunks<const __SOA_CHUNK: usize>(&self) -> #array_chunks_iter_name<'_, #generic_args __SOA_CHUNK> {
+                self.as_slice().array_chunks::<__SOA_CHUNK>()
+            }
+
+            /// Similar to [`<[T]>::array_chunks_mut()`](https://doc.rust-lang.org/std/primitive.slice.html#method.array_chunks_mut),
+            /// returning an iterator over non-overlapping chunks of `CHUNK`
+            /// elements, with a `&mut [T; CHUNK]` array for each field.
+            ///
+            /// # Panics
+            ///
+            /// If `CHUNK` is 0.
+            pub fn array_chunks_mut<const __SOA_CHUNK: usize>(&mut self) -> #array_chunks_iter_mut_name<'_, #generic_args __SOA_CHUNK> {
+                assert!(__SOA_CHUNK != 0, "chunk size must be non-zero");
+                let len = self.len();
+                let (slice, remainder) = self.as_mut_slice().split_at_mut(len - len % __SOA_CHUNK);
+                #array_chunks_iter_mut_name { slice, remainder }
+            }
+        }
+    }
+}
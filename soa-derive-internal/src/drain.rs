@@ -0,0 +1,328 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::input::Input;
+
+/// Generate the `drain()`, `splice()` and `extract_if()` methods of the `Vec`
+/// type, and the corresponding iterators. These only access the data through
+/// `as_mut_ptr()` and `set_len()`, and work the same way for all storages and
+/// for nested fields.
+///
+/// Like for `std::vec::Vec`, the length of the vector is reduced while the
+/// iterators are alive, so that forgetting an iterator or panicking in a
+/// user-provided function leaks elements instead of dropping them twice.
+pub fn derive(input: &Input) -> TokenStream {
+    let name = &input.name;
+    let visibility = &input.visibility;
+    let vec_name_str = format!("Vec<{}>", name);
+    let vec_name = &input.names.vec;
+    let ref_mut_name = &input.names.ref_mut;
+    let drain_name = &input.names.drain;
+    let splice_name = &input.names.splice;
+    let extract_if_name = &input.names.extract_if;
+
+    let generic_params = input.generic_params();
+    let generic_args = input.generic_args();
+    let where_predicates = input.where_predicates();
+
+    let doc_url = format!("[`{0}`](struct.{0}.html)", name);
+    let vec_doc_url = format!("[`{0}`](struct.{0}.html)", vec_name);
+
+    quote! {
+        /// A draining iterator for
+        #[doc = #vec_doc_url]
+        /// , yielding owned
+        #[doc = #doc_url]
+        /// . This is created by the `drain()` method, and similar to
+        /// [`std::vec::Drain`](https://doc.rust-lang.org/std/vec/struct.Drain.html).
+        #[allow(missing_debug_implementations)]
+        #visibility struct #drain_name<'__soa_a, #generic_params> where #where_predicates {
+            vec: &'__soa_a mut #vec_name<#generic_args>,
+            // range of elements which have not been yielded yet
+            start: usize,
+            end: usize,
+            // elements after the drained range, moved back on drop
+            tail_start: usize,
+            tail_len: usize,
+        }
+
+        impl<'__soa_a, #generic_params> #drain_name<'__soa_a, #generic_args> where #where_predicates {
+            /// Move the elements after the drained range right after the
+            /// current end of the vector, and restore the vector length.
+            fn move_tail(&mut self) {
+                let start = self.vec.len();
+                if self.tail_start != start {
+                    let data = self.vec.as_mut_ptr();
+                    unsafe {
+                        data.add(self.tail_start).copy_to(data.add(start), self.tail_len);
+                    }
+                }
+                unsafe {
+                    self.vec.set_len(start + self.tail_len);
+                }
+            }
+        }
+
+        impl<'__soa_a, #generic_params> Iterator for #drain_name<'__soa_a, #generic_args> where #where_predicates {
+            type Item = #name<#generic_args>;
+
+            #[inline]
+            fn next(&mut self) -> Option<#name<#generic_args>> {
+                if self.start == self.end {
+                    None
+                } else {
+                    let value = unsafe { self.vec.as_mut_ptr().add(self.start).read() };
+                    self.start += 1;
+                    Some(value)
+                }
+            }
+
+            #[inline]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                let len = self.end - self.start;
+                (len, Some(len))
+            }
+        }
+
+        impl<'__soa_a, #generic_params> DoubleEndedIterator for #drain_name<'__soa_a, #generic_args> where #where_predicates {
+            #[inline]
+            fn next_back(&mut self) -> Option<#name<#generic_args>> {
+                if self.start == self.end {
+                    None
+                } else {
+                    self.end -= 1;
+                    Some(unsafe { self.vec.as_mut_ptr().add(self.end).read() })
+                }
+            }
+        }
+
+        impl<'__soa_a, #generic_params> ExactSizeIterator for #drain_name<'__soa_a, #generic_args> where #where_predicates {}
+        impl<'__soa_a, #generic_params> ::core::iter::FusedIterator for #drain_name<'__soa_a, #generic_args> where #where_predicates {}
+
+        impl<'__soa_a, #generic_params> Drop for #drain_name<'__soa_a, #generic_args> where #where_predicates {
+            fn drop(&mut self) {
+                // if dropping one of the remaining elements panics, the
+                // elements after it and the tail are leaked
+                self.by_ref().for_each(::core::mem::drop);
+                self.move_tail();
+            }
+        }
+
+        /// A splicing iterator for
+        #[doc = #vec_doc_url]
+        /// , yielding the removed
+        #[doc = #doc_url]
+        /// . This is created by the `splice()` method, and similar to
+        /// [`std::vec::Splice`](https://doc.rust-lang.org/std/vec/struct.Splice.html).
+        #[allow(missing_debug_implementations)]
+        #visibility struct #splice_name<'__soa_a, #generic_params __SoaI> where __SoaI: Iterator<Item = #name<#generic_args>>, #where_predicates {
+            drain: #drain_name<'__soa_a, #generic_args>,
+            replace_with: __SoaI,
+        }
+
+        impl<'__soa_a, #generic_params __SoaI> Iterator for #splice_name<'__soa_a, #generic_args __SoaI> where __SoaI: Iterator<Item = #name<#generic_args>>, #where_predicates {
+            type Item = #name<#generic_args>;
+
+            #[inline]
+            fn next(&mut self) -> Option<#name<#generic_args>> {
+                self.drain.next()
+            }
+
+            #[inline]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.drain.size_hint()
+            }
+        }
+
+        impl<'__soa_a, #generic_params __SoaI> DoubleEndedIterator for #splice_name<'__soa_a, #generic_args __SoaI> where __SoaI: Iterator<Item = #name<#generic_args>>, #where_predicates {
+            #[inline]
+            fn next_back(&mut self) -> Option<#name<#generic_args>> {
+                self.drain.next_back()
+            }
+        }
+
+        impl<'__soa_a, #generic_params __SoaI> ExactSizeIterator for #splice_name<'__soa_a, #generic_args __SoaI> where __SoaI: Iterator<Item = #name<#generic_args>>, #where_predicates {}
+
+        impl<'__soa_a, #generic_params __SoaI> Drop for #splice_name<'__soa_a, #generic_args __SoaI> where __SoaI: Iterator<Item = #name<#generic_args>>, #where_predicates {
+            fn drop(&mut self) {
+                self.drain.by_ref().for_each(::core::mem::drop);
+
+                // fill the space left by the removed elements, the tail is
+                // moved right after the new elements when dropping the drain
+                let vec = &mut *self.drain.vec;
+                while vec.len() < self.drain.tail_start {
+                    match self.replace_with.next() {
+                        Some(value) => unsafe {
+                            let len = vec.len();
+                            vec.as_mut_ptr().add(len).write(value);
+                            vec.set_len(len + 1);
+                        },
+                        None => return,
+                    }
+                }
+
+                let mut remaining = self.replace_with.by_ref().collect::<#vec_name<#generic_args>>();
+                if remaining.is_empty() {
+                    return;
+                }
+
+                // the vector is fully initialized again, insert the
+                // remaining elements before the tail
+                let tail_start = self.drain.tail_start;
+                unsafe {
+                    vec.set_len(tail_start + self.drain.tail_len);
+                }
+                self.drain.tail_len = 0;
+
+                let mut tail = vec.split_off(tail_start);
+                vec.append(&mut remaining);
+                vec.append(&mut tail);
+            }
+        }
+
+        /// An iterator for
+        #[doc = #vec_doc_url]
+        /// removing and yielding the
+        #[doc = #doc_url]
+        /// matching a predicate. This is created by the `extract_if()` method,
+        /// and similar to [`std::vec::ExtractIf`](https://doc.rust-lang.org/std/vec/struct.ExtractIf.html).
+        #[allow(missing_debug_implementations)]
+        #visibility struct #extract_if_name<'__soa_a, #generic_params __SoaF> where #where_predicates {
+            vec: &'__soa_a mut #vec_name<#generic_args>,
+            // index of the next element to check
+            index: usize,
+            // end of the range of elements to check
+            end: usize,
+            // number of elements removed so far
+            removed: usize,
+            // length of the vector before calling `extract_if()`
+            old_len: usize,
+            predicate: __SoaF,
+            // calls `predicate`, which allows the `Iterator` implementation
+            // to not require `F: FnMut(FooRefMut<'_>) -> bool`. This bound
+            // can not be written for generic structs in `SoAVec::ExtractIf`.
+            call_predicate: fn(&mut __SoaF, #ref_mut_name<'_, #generic_args>) -> bool,
+        }
+
+        impl<'__soa_a, #generic_params __SoaF> Iterator for #extract_if_name<'__soa_a, #generic_args __SoaF> where #where_predicates {
+            type Item = #name<#generic_args>;
+
+            fn next(&mut self) -> Option<#name<#generic_args>> {
+                let data = self.vec.as_mut_ptr();
+                while self.index < self.end {
+                    let i = self.index;
+                    let extract = unsafe {
+                        (self.call_predicate)(&mut self.predicate, data.add(i).as_mut_unchecked())
+                    };
+                    self.index += 1;
+
+                    if extract {
+                        self.removed += 1;
+                        return Some(unsafe { data.add(i).read() });
+                    } else if self.removed > 0 {
+                        unsafe {
+                            data.add(i - self.removed).write(data.add(i).read());
+                        }
+                    }
+                }
+                return None;
+            }
+
+            #[inline]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                (0, Some(self.end - self.index))
+            }
+        }
+
+        impl<'__soa_a, #generic_params __SoaF> Drop for #extract_if_name<'__soa_a, #generic_args __SoaF> where #where_predicates {
+            fn drop(&mut self) {
+                // move the unchecked elements to close the gap left by the
+                // removed ones
+                if self.removed > 0 {
+                    let data = self.vec.as_mut_ptr();
+                    unsafe {
+                        data.add(self.index).copy_to(data.add(self.index - self.removed), self.old_len - self.index);
+                    }
+                }
+                unsafe {
+                    self.vec.set_len(self.old_len - self.removed);
+                }
+            }
+        }
+
+        #[allow(dead_code)]
+        impl<#generic_params> #vec_name<#generic_args> where #where_predicates {
+            /// Similar to [`
+            #[doc = #vec_name_str]
+            /// ::drain()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.drain).
+            ///
+            /// The elements in the range are removed even if the iterator is
+            /// not fully consumed.
+            pub fn drain<__SoaR>(&mut self, range: __SoaR) -> #drain_name<'_, #generic_args>
+            where
+                __SoaR: ::core::ops::RangeBounds<usize>
+            {
+                let len = self.len();
+                let range = ::soa_derive::drain::range(range, len);
+                unsafe {
+                    self.set_len(range.start);
+                }
+
+                #drain_name {
+                    vec: self,
+                    start: range.start,
+                    end: range.end,
+                    tail_start: range.end,
+                    tail_len: len - range.end,
+                }
+            }
+
+            /// Similar to [`
+            #[doc = #vec_name_str]
+            /// ::splice()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.splice).
+            ///
+            /// The elements in the range are replaced when the returned
+            /// iterator is dropped.
+            pub fn splice<__SoaR, __SoaI>(&mut self, range: __SoaR, replace_with: __SoaI) -> #splice_name<'_, #generic_args __SoaI::IntoIter>
+            where
+                __SoaR: ::core::ops::RangeBounds<usize>,
+                __SoaI: IntoIterator<Item = #name<#generic_args>>,
+            {
+                #splice_name {
+                    drain: self.drain(range),
+                    replace_with: replace_with.into_iter(),
+                }
+            }
+
+            /// Similar to [`
+            #[doc = #vec_name_str]
+            /// ::extract_if()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.extract_if).
+            ///
+            /// Only the elements in `range` are checked, and the elements for
+            /// which `filter` returns `true` are removed and yielded. If the
+            /// iterator is not fully consumed, the remaining elements are
+            /// kept in the vector.
+            pub fn extract_if<__SoaF, __SoaR>(&mut self, range: __SoaR, filter: __SoaF) -> #extract_if_name<'_, #generic_args __SoaF>
+            where
+                __SoaF: FnMut(#ref_mut_name<'_, #generic_args>) -> bool,
+                __SoaR: ::core::ops::RangeBounds<usize>,
+            {
+                let old_len = self.len();
+                let range = ::soa_derive::drain::range(range, old_len);
+                unsafe {
+                    self.set_len(0);
+                }
+
+                #extract_if_name {
+                    vec: self,
+                    index: range.start,
+                    end: range.end,
+                    removed: 0,
+                    old_len: old_len,
+                    predicate: filter,
+                    call_predicate: |filter, element| filter(element),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,662 @@
+use proc_macro2::{Ident, Literal, Span, TokenStream};
+use quote::quote;
+
+use syn::ext::IdentExt;
+use syn::{Attribute, Data, DeriveInput, Fields, Member, Variant};
+
+use crate::names::{self, NamesConfig};
+
+/// Get the name of the struct containing the fields of the given `variant`
+/// of the enum `name`
+fn variant_struct_name(name: &Ident, variant: &Ident) -> Ident {
+    Ident::new(&format!("{}{}", name, variant), Span::call_site())
+}
+
+/// Convert a `CamelCase` variant name to the `snake_case` name used for the
+/// corresponding field in the generated vector and iterators. Runs of capital
+/// letters are kept together (`HTTPRequest` becomes `http_request`), and
+/// names which are keywords are used as raw identifiers (`Type` becomes
+/// `r#type`).
+fn snake_case(name: &Ident) -> syn::Result<Ident> {
+    let chars = name.to_string().chars().collect::<Vec<_>>();
+    let mut snake = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i != 0 {
+            let previous = chars[i - 1];
+            let next_is_lowercase = chars.get(i + 1).map_or(false, |next| next.is_lowercase());
+            if previous.is_lowercase() || previous.is_numeric() || (previous.is_uppercase() && next_is_lowercase) {
+                snake.push('_');
+            }
+        }
+        snake.extend(c.to_lowercase());
+    }
+
+    if syn::parse_str::<Ident>(&snake).is_ok() {
+        return Ok(Ident::new(&snake, Span::call_site()));
+    }
+
+    if snake == "self" || snake == "super" || snake == "crate" {
+        return Err(syn::Error::new_spanned(name, format!(
+            "#[derive(StructOfArray)] can not use `{}` as a field name, please rename this variant", snake
+        )));
+    }
+
+    return Ok(Ident::new_raw(&snake, Span::call_site()));
+}
+
+/// Get the traits to derive from `#[soa_derive]` attributes
+fn soa_derives(attrs: &[Attribute]) -> syn::Result<Vec<Ident>> {
+    let mut derives = Vec::new();
+    for attr in attrs {
+        if attr.path().is_ident("soa_derive") {
+            attr.parse_nested_meta(|meta| {
+                match meta.path.get_ident() {
+                    Some(ident) if ident == "Copy" => return Err(meta.error("can not derive Copy for SoA vectors (references are always Copy)")),
+                    Some(ident) => derives.push(ident.clone()),
+                    None => return Err(meta.error("expected #[soa_derive(Traits, To, Derive)]")),
+                }
+                Ok(())
+            })?;
+        }
+    }
+    Ok(derives)
+}
+
+/// A single variant of the input enum
+struct EnumVariant {
+    /// Name of the variant
+    name: Ident,
+    /// Value of the tag used for this variant
+    tag: Literal,
+    /// Name of the field containing this variant data in the generated types,
+    /// `None` for variants without fields
+    field: Option<Ident>,
+    /// Name of the struct containing this variant fields
+    struct_name: Ident,
+    /// Names of the fields of the variant
+    fields_names: Vec<Member>,
+    /// Hygienic names used to bind the fields of the variant
+    fields_names_hygienic: Vec<Ident>,
+}
+
+impl EnumVariant {
+    fn new(enum_name: &Ident, tag: usize, variant: &Variant) -> syn::Result<EnumVariant> {
+        let fields_names = variant.fields.iter().enumerate().map(|(i, field)| {
+            match &field.ident {
+                Some(ident) => Member::Named(ident.clone()),
+                None => Member::from(i),
+            }
+        }).collect::<Vec<_>>();
+
+        let field = if fields_names.is_empty() { None } else { Some(snake_case(&variant.ident)?) };
+        if let Some(field) = &field {
+            if field == "tags" || field == "indices" {
+                return Err(syn::Error::new_spanned(&variant.ident, format!(
+                    "#[derive(StructOfArray)] uses the `{}` field internally, please rename this variant", field
+                )));
+            }
+        }
+
+        let fields_names_hygienic = fields_names.iter()
+            .enumerate()
+            .map(|(i, _)| Ident::new(&format!("___soa_derive_private_{}", i), Span::call_site()))
+            .collect();
+
+        Ok(EnumVariant {
+            name: variant.ident.clone(),
+            tag: Literal::usize_unsuffixed(tag),
+            field: field,
+            struct_name: variant_struct_name(enum_name, &variant.ident),
+            fields_names: fields_names,
+            fields_names_hygienic: fields_names_hygienic,
+        })
+    }
+}
+
+pub fn derive(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let name = &input.ident;
+    let visibility = &input.vis;
+    let variants = match &input.data {
+        Data::Enum(data) => &data.variants,
+        _ => unreachable!("enums::derive called on a struct"),
+    };
+
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(&input.generics, "#[derive(StructOfArray)] does not support generic enums"));
+    }
+    if variants.iter().all(|variant| variant.fields.is_empty()) {
+        return Err(syn::Error::new_spanned(
+            &input.ident, "#[derive(StructOfArray)] only supports enums with at least one variant with fields"
+        ));
+    }
+    for attr in &input.attrs {
+        if attr.path().is_ident("soa_attr") {
+            return Err(syn::Error::new_spanned(attr, "#[soa_attr] is not supported on enums"));
+        }
+    }
+
+    let mut names = NamesConfig::default();
+    for attr in &input.attrs {
+        if attr.path().is_ident("soa") {
+            attr.parse_nested_meta(|meta| {
+                if names.parse_meta(&meta)? {
+                    return Ok(());
+                }
+                Err(meta.error("unknown #[soa] attribute on enum"))
+            })?;
+        }
+    }
+    let names = names.build(name);
+
+    let vec_name = &names.vec;
+    let ref_name = &names.ref_;
+    let ref_mut_name = &names.ref_mut;
+    let iter_name = &names.iter;
+    let iter_mut_name = &names.iter_mut;
+
+    let doc_url = format!("[`{0}`](enum.{0}.html)", name);
+    let vec_doc_url = format!("[`{0}`](struct.{0}.html)", vec_name);
+    let ref_doc_url = format!("[`{0}`](enum.{0}.html)", ref_name);
+    let ref_mut_doc_url = format!("[`{0}`](enum.{0}.html)", ref_mut_name);
+
+    let soa_derive_attrs = input.attrs.iter()
+        .filter(|attr| attr.path().is_ident("soa_derive"))
+        .collect::<Vec<_>>();
+    let derives = soa_derives(&input.attrs)?;
+    // Deserialize is implemented manually to validate the tags and indices
+    let vec_derives = derives.iter()
+        .filter(|ident| *ident != "Default" && *ident != "Deserialize")
+        .collect::<Vec<_>>();
+    let ref_derives = vec_derives.iter()
+        .filter(|ident| **ident != "Clone" && **ident != "Serialize" && **ident != "Deserialize")
+        .collect::<Vec<_>>();
+
+    let all_variants = variants.iter()
+        .enumerate()
+        .map(|(tag, variant)| EnumVariant::new(name, tag, variant))
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let all_fields_types = variants.iter()
+        .flat_map(|variant| variant.fields.iter().map(|field| &field.ty))
+        .collect::<Vec<_>>();
+
+    let variants_structs = variants.iter().zip(&all_variants).filter(|(_, v)| v.field.is_some()).map(|(variant, v)| {
+        let struct_name = &v.struct_name;
+        let variant_name = &v.name;
+        let fields = variant.fields.iter().map(|field| {
+            let nested = field.attrs.iter().filter(|attr| attr.path().is_ident("nested_soa") || attr.path().is_ident("soa"));
+            let docs = field.attrs.iter().filter(|attr| attr.path().is_ident("doc"));
+            let ident = field.ident.as_ref().map(|ident| quote! { #ident: });
+            let ty = &field.ty;
+            quote! { #(#docs)* #(#nested)* pub #ident #ty }
+        });
+
+        let body = if let Fields::Named(_) = variant.fields {
+            quote! { { #(#fields,)* } }
+        } else {
+            quote! { ( #(#fields,)* ); }
+        };
+
+        let variant_doc = format!("Fields of the `{}` variant of [`{}`](enum.{}.html)", variant_name, name, name);
+        quote! {
+            #[doc = #variant_doc]
+            #[derive(::soa_derive::StructOfArray)]
+            #(#soa_derive_attrs)*
+            #visibility struct #struct_name #body
+        }
+    }).collect::<Vec<_>>();
+
+    let with_fields = all_variants.iter().filter(|v| v.field.is_some()).collect::<Vec<_>>();
+    let storage_names = with_fields.iter().map(|v| &v.field).collect::<Vec<_>>();
+    let storage_vec_types = with_fields.iter().map(|v| names::vec_name(&v.struct_name)).collect::<Vec<_>>();
+    let storage_iter_types = with_fields.iter().map(|v| names::iter_name(&v.struct_name)).collect::<Vec<_>>();
+    let storage_iter_mut_types = with_fields.iter().map(|v| names::iter_mut_name(&v.struct_name)).collect::<Vec<_>>();
+    let storage_docs = with_fields.iter().map(|v| {
+        format!("Values of the `{}` variant, in the same order as in this vector", v.name)
+    }).collect::<Vec<_>>();
+
+    let ref_variants = all_variants.iter().map(|v| {
+        let variant_name = &v.name;
+        if v.field.is_some() {
+            let variant_ref = names::ref_name(&v.struct_name);
+            quote! { #variant_name(#variant_ref<'__soa_a>) }
+        } else {
+            quote! { #variant_name }
+        }
+    }).collect::<Vec<_>>();
+
+    let ref_mut_variants = all_variants.iter().map(|v| {
+        let variant_name = &v.name;
+        if v.field.is_some() {
+            let variant_ref_mut = names::ref_mut_name(&v.struct_name);
+            quote! { #variant_name(#variant_ref_mut<'__soa_a>) }
+        } else {
+            quote! { #variant_name }
+        }
+    }).collect::<Vec<_>>();
+
+    let push_arms = all_variants.iter().map(|v| {
+        let variant_name = &v.name;
+        let fields_names = &v.fields_names;
+        let fields_names_hygienic = &v.fields_names_hygienic;
+        let tag = &v.tag;
+        if let Some(field) = &v.field {
+            let struct_name = &v.struct_name;
+            quote! {
+                #name::#variant_name { #(#fields_names: #fields_names_hygienic,)* } => {
+                    self.tags.push(#tag);
+                    self.indices.push(self.#field.len());
+                    self.#field.push(#struct_name { #(#fields_names: #fields_names_hygienic,)* });
+                }
+            }
+        } else {
+            quote! {
+                #name::#variant_name {} => {
+                    self.tags.push(#tag);
+                    self.indices.push(0);
+                }
+            }
+        }
+    }).collect::<Vec<_>>();
+
+    let pop_arms = all_variants.iter().map(|v| {
+        let variant_name = &v.name;
+        let fields_names = &v.fields_names;
+        let tag = &v.tag;
+        if let Some(field) = &v.field {
+            quote! {
+                #tag => {
+                    let value = self.#field.pop().expect("missing value in variant storage");
+                    #name::#variant_name { #(#fields_names: value.#fields_names,)* }
+                }
+            }
+        } else {
+            quote! { #tag => #name::#variant_name {}, }
+        }
+    }).collect::<Vec<_>>();
+
+    let make_ref_arms = |access: TokenStream, ref_type: &Ident| {
+        all_variants.iter().map(|v| {
+            let variant_name = &v.name;
+            let tag = &v.tag;
+            if let Some(field) = &v.field {
+                quote! { #tag => #ref_type::#variant_name(self.#field.#access), }
+            } else {
+                quote! { #tag => #ref_type::#variant_name, }
+            }
+        }).collect::<Vec<_>>()
+    };
+    let get_arms = make_ref_arms(quote! { index(index) }, ref_name);
+    let get_mut_arms = make_ref_arms(quote! { index_mut(index) }, ref_mut_name);
+    let iter_arms = make_ref_arms(quote! { next().expect("missing value in variant storage") }, ref_name);
+    let iter_mut_arms = make_ref_arms(quote! { next().expect("missing value in variant storage") }, ref_mut_name);
+
+    let to_owned_arms = all_variants.iter().map(|v| {
+        let variant_name = &v.name;
+        let fields_names = &v.fields_names;
+        if v.field.is_some() {
+            quote! {
+                #ref_name::#variant_name(value) => {
+                    let value = value.to_owned();
+                    #name::#variant_name { #(#fields_names: value.#fields_names,)* }
+                }
+            }
+        } else {
+            quote! { #ref_name::#variant_name => #name::#variant_name {}, }
+        }
+    }).collect::<Vec<_>>();
+
+    let deserialize = match derives.iter().find(|ident| *ident == "Deserialize") {
+        Some(derive) => derive_deserialize(derive, vec_name, &all_variants),
+        None => TokenStream::new(),
+    };
+
+    Ok(quote! {
+        #(#variants_structs)*
+
+        #deserialize
+
+        /// An analog to `
+        #[doc = stringify!(Vec<#name>)]
+        /// ` with Struct of Array (SoA) layout. Each element is stored as a
+        /// tag identifying the variant, and the index of the element inside
+        /// the storage of this variant.
+        #[allow(dead_code)]
+        #[derive(Default)]
+        #[derive(#(#vec_derives),*)]
+        #visibility struct #vec_name {
+            /// tag of the variant of each element
+            tags: ::soa_derive::alloc::vec::Vec<u32>,
+            /// index of each element in the corresponding variant storage
+            indices: ::soa_derive::alloc::vec::Vec<usize>,
+            #(
+                #[doc = #storage_docs]
+                pub #storage_names: #storage_vec_types,
+            )*
+        }
+
+        #[allow(dead_code)]
+        impl #vec_name {
+            /// Similar to [`
+            #[doc = stringify!(Vec<#name>)]
+            /// ::new()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.new)
+            pub fn new() -> #vec_name {
+                Default::default()
+            }
+
+            /// Similar to [`
+            #[doc = stringify!(Vec<#name>)]
+            /// ::len()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.len).
+            pub fn len(&self) -> usize {
+                self.tags.len()
+            }
+
+            /// Similar to [`
+            #[doc = stringify!(Vec<#name>)]
+            /// ::is_empty()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.is_empty).
+            pub fn is_empty(&self) -> bool {
+                self.tags.is_empty()
+            }
+
+            /// Similar to [`
+            #[doc = stringify!(Vec<#name>)]
+            /// ::push()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.push).
+            pub fn push(&mut self, value: #name) {
+                match value {
+                    #(#push_arms)*
+                }
+            }
+
+            /// Similar to [`
+            #[doc = stringify!(Vec<#name>)]
+            /// ::pop()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.pop).
+            pub fn pop(&mut self) -> Option<#name> {
+                let tag = self.tags.pop()?;
+                self.indices.pop();
+                let value = match tag {
+                    #(#pop_arms)*
+                    _ => unreachable!("invalid tag in enum vector"),
+                };
+                Some(value)
+            }
+
+            /// Similar to [`
+            #[doc = stringify!(Vec<#name>)]
+            /// ::clear()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.clear).
+            pub fn clear(&mut self) {
+                self.tags.clear();
+                self.indices.clear();
+                #(self.#storage_names.clear();)*
+            }
+
+            /// Get a
+            #[doc = #ref_doc_url]
+            /// to the element at `index`, or `None` if the index is out of
+            /// bounds.
+            pub fn get(&self, index: usize) -> Option<#ref_name<'_>> {
+                let tag = *self.tags.get(index)?;
+                let index = self.indices[index];
+                let value = match tag {
+                    #(#get_arms)*
+                    _ => unreachable!("invalid tag in enum vector"),
+                };
+                Some(value)
+            }
+
+            /// Get a
+            #[doc = #ref_mut_doc_url]
+            /// to the element at `index`, or `None` if the index is out of
+            /// bounds.
+            pub fn get_mut(&mut self, index: usize) -> Option<#ref_mut_name<'_>> {
+                let tag = *self.tags.get(index)?;
+                let index = self.indices[index];
+                let value = match tag {
+                    #(#get_mut_arms)*
+                    _ => unreachable!("invalid tag in enum vector"),
+                };
+                Some(value)
+            }
+
+            /// Get an iterator over the
+            #[doc = #ref_doc_url]
+            /// in this vector
+            pub fn iter(&self) -> #iter_name<'_> {
+                #iter_name {
+                    tags: self.tags.iter(),
+                    #( #storage_names: self.#storage_names.iter(), )*
+                }
+            }
+
+            /// Get a mutable iterator over the
+            #[doc = #ref_mut_doc_url]
+            /// in this vector
+            pub fn iter_mut(&mut self) -> #iter_mut_name<'_> {
+                #iter_mut_name {
+                    tags: self.tags.iter(),
+                    #( #storage_names: self.#storage_names.iter_mut(), )*
+                }
+            }
+        }
+
+        /// A reference to a
+        #[doc = #doc_url]
+        /// stored inside a
+        #[doc = #vec_doc_url]
+        #[derive(Copy, Clone)]
+        #[derive(#(#ref_derives),*)]
+        #visibility enum #ref_name<'__soa_a> {
+            #(#ref_variants,)*
+        }
+
+        impl<'__soa_a> #ref_name<'__soa_a> {
+            /// Convert a reference to
+            #[doc = #doc_url]
+            /// into an owned value. This is only available if all fields
+            /// implement `Clone`.
+            pub fn to_owned(&self) -> #name
+                // only expose to_owned if all fields are Clone
+                // https://github.com/rust-lang/rust/issues/48214#issuecomment-1150463333
+                where #( for<'__soa_b> #all_fields_types: Clone, )*
+            {
+                match self {
+                    #(#to_owned_arms)*
+                }
+            }
+        }
+
+        /// A mutable reference to a
+        #[doc = #doc_url]
+        /// stored inside a
+        #[doc = #vec_doc_url]
+        #[derive(#(#ref_derives),*)]
+        #visibility enum #ref_mut_name<'__soa_a> {
+            #(#ref_mut_variants,)*
+        }
+
+        /// Iterator over
+        #[doc = #vec_doc_url]
+        #[allow(missing_debug_implementations)]
+        #visibility struct #iter_name<'__soa_a> {
+            tags: ::core::slice::Iter<'__soa_a, u32>,
+            #( #storage_names: #storage_iter_types<'__soa_a>, )*
+        }
+
+        impl<'__soa_a> Iterator for #iter_name<'__soa_a> {
+            type Item = #ref_name<'__soa_a>;
+
+            #[inline]
+            fn next(&mut self) -> Option<#ref_name<'__soa_a>> {
+                let value = match *self.tags.next()? {
+                    #(#iter_arms)*
+                    _ => unreachable!("invalid tag in enum vector"),
+                };
+                Some(value)
+            }
+
+            #[inline]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.tags.size_hint()
+            }
+        }
+
+        impl<'__soa_a> ExactSizeIterator for #iter_name<'__soa_a> {
+            fn len(&self) -> usize {
+                self.tags.len()
+            }
+        }
+
+        /// Mutable iterator over
+        #[doc = #vec_doc_url]
+        #[allow(missing_debug_implementations)]
+        #visibility struct #iter_mut_name<'__soa_a> {
+            tags: ::core::slice::Iter<'__soa_a, u32>,
+            #( #storage_names: #storage_iter_mut_types<'__soa_a>, )*
+        }
+
+        impl<'__soa_a> Iterator for #iter_mut_name<'__soa_a> {
+            type Item = #ref_mut_name<'__soa_a>;
+
+            #[inline]
+            fn next(&mut self) -> Option<#ref_mut_name<'__soa_a>> {
+                let value = match *self.tags.next()? {
+                    #(#iter_mut_arms)*
+                    _ => unreachable!("invalid tag in enum vector"),
+                };
+                Some(value)
+            }
+
+            #[inline]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.tags.size_hint()
+            }
+        }
+
+        impl<'__soa_a> ExactSizeIterator for #iter_mut_name<'__soa_a> {
+            fn len(&self) -> usize {
+                self.tags.len()
+            }
+        }
+
+        impl<'__soa_a> IntoIterator for &'__soa_a #vec_name {
+            type Item = #ref_name<'__soa_a>;
+            type IntoIter = #iter_name<'__soa_a>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                self.iter()
+            }
+        }
+
+        impl<'__soa_a> IntoIterator for &'__soa_a mut #vec_name {
+            type Item = #ref_mut_name<'__soa_a>;
+            type IntoIter = #iter_mut_name<'__soa_a>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                self.iter_mut()
+            }
+        }
+
+        impl ::core::iter::FromIterator<#name> for #vec_name {
+            fn from_iter<__SoaI: IntoIterator<Item=#name>>(iter: __SoaI) -> Self {
+                let mut result = #vec_name::new();
+                for element in iter {
+                    result.push(element);
+                }
+                result
+            }
+        }
+
+        impl Extend<#name> for #vec_name {
+            fn extend<__SoaI: IntoIterator<Item = #name>>(&mut self, iter: __SoaI) {
+                for item in iter {
+                    self.push(item)
+                }
+            }
+        }
+
+        impl soa_derive::StructOfArray for #name {
+            type Type = #vec_name;
+        }
+    })
+}
+
+/// Implement `Deserialize` for the vector `vec_name`, going through a helper
+/// struct with the same fields. The tags and indices are then checked, so
+/// that all the elements of the vector can be accessed.
+fn derive_deserialize(derive: &Ident, vec_name: &Ident, variants: &[EnumVariant]) -> TokenStream {
+    let vec_name_str = vec_name.to_string();
+
+    let with_fields = variants.iter().filter(|v| v.field.is_some()).collect::<Vec<_>>();
+    let storage_names = with_fields.iter().map(|v| &v.field).collect::<Vec<_>>();
+    let storage_vec_types = with_fields.iter().map(|v| names::vec_name(&v.struct_name)).collect::<Vec<_>>();
+    // name of the columns in serialized data, without `r#` for keywords
+    let storage_names_str = storage_names.iter()
+        .map(|field| field.as_ref().map(|field| field.unraw().to_string()))
+        .collect::<Vec<_>>();
+    let storage_counts = with_fields.iter()
+        .enumerate()
+        .map(|(i, _)| Ident::new(&format!("___soa_derive_private_{}", i), Span::call_site()))
+        .collect::<Vec<_>>();
+    let tags_with_fields = with_fields.iter().map(|v| &v.tag).collect::<Vec<_>>();
+    let tags_without_fields = variants.iter().filter(|v| v.field.is_none()).map(|v| &v.tag).collect::<Vec<_>>();
+
+    quote! {
+        const _: () = {
+            #[derive(#derive)]
+            #[serde(rename = #vec_name_str)]
+            struct ___soa_derive_private_columns {
+                tags: ::soa_derive::alloc::vec::Vec<u32>,
+                indices: ::soa_derive::alloc::vec::Vec<usize>,
+                #( #storage_names: #storage_vec_types, )*
+            }
+
+            impl<'__soa_de> ::serde::Deserialize<'__soa_de> for #vec_name {
+                fn deserialize<__SoaD: ::serde::Deserializer<'__soa_de>>(deserializer: __SoaD) -> Result<Self, __SoaD::Error> {
+                    let columns = ___soa_derive_private_columns::deserialize(deserializer)?;
+                    if columns.tags.len() != columns.indices.len() {
+                        return Err(<__SoaD::Error as ::serde::de::Error>::custom(format_args!(
+                            "invalid {}: column `indices` has length {}, expected {}",
+                            #vec_name_str, columns.indices.len(), columns.tags.len(),
+                        )));
+                    }
+
+                    // the elements of each variant must be stored in the same
+                    // order as in the vector, as done by `push()`
+                    #( let mut #storage_counts = 0; )*
+                    for (&tag, &index) in columns.tags.iter().zip(&columns.indices) {
+                        let expected = match tag {
+                            #( #tags_with_fields => {
+                                let expected = #storage_counts;
+                                #storage_counts += 1;
+                                expected
+                            } )*
+                            #( #tags_without_fields => 0, )*
+                            _ => return Err(<__SoaD::Error as ::serde::de::Error>::custom(format_args!(
+                                "invalid {}: unknown tag {}", #vec_name_str, tag,
+                            ))),
+                        };
+                        if index != expected {
+                            return Err(<__SoaD::Error as ::serde::de::Error>::custom(format_args!(
+                                "invalid {}: index {} for tag {}, expected {}", #vec_name_str, index, tag, expected,
+                            )));
+                        }
+                    }
+
+                    #(
+                        if columns.#storage_names.len() != #storage_counts {
+                            return Err(<__SoaD::Error as ::serde::de::Error>::custom(format_args!(
+                                "invalid {}: column `{}` has length {}, expected {}",
+                                #vec_name_str, #storage_names_str, columns.#storage_names.len(), #storage_counts,
+                            )));
+                        }
+                    )*
+
+                    Ok(#vec_name {
+                        tags: columns.tags,
+                        indices: columns.indices,
+                        #( #storage_names: columns.#storage_names, )*
+                    })
+                }
+            }
+        };
+    }
+}
@@ -2,22 +2,25 @@ use proc_macro2::TokenStream;
 use quote::quote;
 
 use crate::input::Input;
-use crate::names;
 
 
 pub fn derive_slice(input: &Input) -> TokenStream {
     let name = &input.name;
-    let slice_name = names::slice_name(name);
-    let ref_name = names::ref_name(&input.name);
-    let ptr_name = names::ptr_name(&input.name);
-    let iter_name = names::iter_name(name);
+    let slice_name = &input.names.slice;
+    let ref_name = &input.names.ref_;
+    let ptr_name = &input.names.ptr;
+    let iter_name = &input.names.iter;
+
+    let generic_params = input.generic_params();
+    let generic_args = input.generic_args();
+    let where_predicates = input.where_predicates();
 
     let generated = quote! {
-        impl<'a> ::soa_derive::SoASlice<#name> for #slice_name<'a> {
-            type Ref<'t>  = #ref_name<'t> where Self: 't, 'a: 't;
-            type Slice<'t> = #slice_name<'t> where Self: 't, 'a: 't;
-            type Iter<'t> = #iter_name<'t> where Self: 't, 'a: 't;
-            type Ptr = #ptr_name;
+        impl<'__soa_a, #generic_params> ::soa_derive::SoASlice<#name<#generic_args>> for #slice_name<'__soa_a, #generic_args> where #where_predicates {
+            type Ref<'__soa_t>  = #ref_name<'__soa_t, #generic_args> where Self: '__soa_t, '__soa_a: '__soa_t;
+            type Slice<'__soa_t> = #slice_name<'__soa_t, #generic_args> where Self: '__soa_t, '__soa_a: '__soa_t;
+            type Iter<'__soa_t> = #iter_name<'__soa_t, #generic_args> where Self: '__soa_t, '__soa_a: '__soa_t;
+            type Ptr = #ptr_name<#generic_args>;
 
             fn len(&self) -> usize {
                 self.len()
@@ -27,33 +30,33 @@ pub fn derive_slice(input: &Input) -> TokenStream {
                 self.is_empty()
             }
 
-            fn as_slice<'c>(&'c self) -> Self::Slice<'c> {
-                self.reborrow::<'c>()
+            fn as_slice<'__soa_c>(&'__soa_c self) -> Self::Slice<'__soa_c> {
+                self.reborrow::<'__soa_c>()
             }
 
-            fn slice<'c, 'b: 'c>(&'c self, index: impl core::ops::RangeBounds<usize>) -> Self::Slice<'c> where Self: 'b {
+            fn slice<'__soa_c, '__soa_b: '__soa_c>(&'__soa_c self, index: impl core::ops::RangeBounds<usize>) -> Self::Slice<'__soa_c> where Self: '__soa_b {
                 let start = match index.start_bound() {
-                    std::ops::Bound::Included(i) | std::ops::Bound::Excluded(i) => *i,
-                    std::ops::Bound::Unbounded => 0,
+                    ::core::ops::Bound::Included(i) | ::core::ops::Bound::Excluded(i) => *i,
+                    ::core::ops::Bound::Unbounded => 0,
                 };
                 let n = self.len();
                 let end = match index.end_bound() {
-                    std::ops::Bound::Included(i) => (*i + 1).min(n),
-                    std::ops::Bound::Excluded(i) => *i,
-                    std::ops::Bound::Unbounded => n,
+                    ::core::ops::Bound::Included(i) => (*i + 1).min(n),
+                    ::core::ops::Bound::Excluded(i) => *i,
+                    ::core::ops::Bound::Unbounded => n,
                 };
                 self.index(start..end)
             }
 
-            fn get<'c>(&'c self, index: usize) -> Option<Self::Ref<'c>> {
+            fn get<'__soa_c>(&'__soa_c self, index: usize) -> Option<Self::Ref<'__soa_c>> {
                 self.get(index)
             }
 
-            fn index<'c>(&'c self, index: usize) -> Self::Ref<'c> {
+            fn index<'__soa_c>(&'__soa_c self, index: usize) -> Self::Ref<'__soa_c> {
                 self.index(index)
             }
 
-            fn iter<'c>(&'c self) -> Self::Iter<'c> {
+            fn iter<'__soa_c>(&'__soa_c self) -> Self::Iter<'__soa_c> {
                 self.iter()
             }
 
@@ -68,27 +71,31 @@ pub fn derive_slice(input: &Input) -> TokenStream {
 
 pub fn derive_slice_mut(input: &Input) -> TokenStream {
     let name = &input.name;
-    let slice_name = names::slice_name(name);
-    let slice_mut_name = names::slice_mut_name(&input.name);
-    let ref_name = names::ref_name(&input.name);
-    let ref_mut_name = names::ref_mut_name(&input.name);
-    let ptr_name = names::ptr_name(&input.name);
-    let ptr_mut_name = names::ptr_mut_name(&input.name);
-    let iter_name = names::iter_name(name);
-    let iter_mut_name = names::iter_mut_name(name);
+    let slice_name = &input.names.slice;
+    let slice_mut_name = &input.names.slice_mut;
+    let ref_name = &input.names.ref_;
+    let ref_mut_name = &input.names.ref_mut;
+    let ptr_name = &input.names.ptr;
+    let ptr_mut_name = &input.names.ptr_mut;
+    let iter_name = &input.names.iter;
+    let iter_mut_name = &input.names.iter_mut;
+
+    let generic_params = input.generic_params();
+    let generic_args = input.generic_args();
+    let where_predicates = input.where_predicates();
 
     let generated = quote! {
 
-        impl<'a> ::soa_derive::SoASliceMut<#name> for #slice_mut_name<'a> {
-            type Ref<'t>  = #ref_name<'t> where Self: 't;
-            type Slice<'t> = #slice_name<'t> where Self: 't;
-            type Iter<'t> = #iter_name<'t> where Self: 't;
-            type Ptr = #ptr_name;
+        impl<'__soa_a, #generic_params> ::soa_derive::SoASliceMut<#name<#generic_args>> for #slice_mut_name<'__soa_a, #generic_args> where #where_predicates {
+            type Ref<'__soa_t>  = #ref_name<'__soa_t, #generic_args> where Self: '__soa_t;
+            type Slice<'__soa_t> = #slice_name<'__soa_t, #generic_args> where Self: '__soa_t;
+            type Iter<'__soa_t> = #iter_name<'__soa_t, #generic_args> where Self: '__soa_t;
+            type Ptr = #ptr_name<#generic_args>;
 
-            type RefMut<'t> = #ref_mut_name<'t> where Self: 't;
-            type SliceMut<'t> = #slice_mut_name<'t> where Self: 't;
-            type IterMut<'t> = #iter_mut_name<'t> where Self: 't;
-            type PtrMut = #ptr_mut_name;
+            type RefMut<'__soa_t> = #ref_mut_name<'__soa_t, #generic_args> where Self: '__soa_t;
+            type SliceMut<'__soa_t> = #slice_mut_name<'__soa_t, #generic_args> where Self: '__soa_t;
+            type IterMut<'__soa_t> = #iter_mut_name<'__soa_t, #generic_args> where Self: '__soa_t;
+            type PtrMut = #ptr_mut_name<#generic_args>;
 
             fn len(&self) -> usize {
                 self.len()
@@ -98,68 +105,88 @@ pub fn derive_slice_mut(input: &Input) -> TokenStream {
                 self.is_empty()
             }
 
-            fn as_slice<'c>(&'c self) -> Self::Slice<'c> {
+            fn as_slice<'__soa_c>(&'__soa_c self) -> Self::Slice<'__soa_c> {
                 self.as_slice()
             }
 
-            fn slice<'c, 'b: 'c>(&'c self, index: impl core::ops::RangeBounds<usize>) -> Self::Slice<'c> where Self: 'b {
+            fn slice<'__soa_c, '__soa_b: '__soa_c>(&'__soa_c self, index: impl core::ops::RangeBounds<usize>) -> Self::Slice<'__soa_c> where Self: '__soa_b {
                 let start = match index.start_bound() {
-                    std::ops::Bound::Included(i) | std::ops::Bound::Excluded(i) => *i,
-                    std::ops::Bound::Unbounded => 0,
+                    ::core::ops::Bound::Included(i) | ::core::ops::Bound::Excluded(i) => *i,
+                    ::core::ops::Bound::Unbounded => 0,
                 };
                 let n = self.len();
                 let end = match index.end_bound() {
-                    std::ops::Bound::Included(i) => (*i + 1).min(n),
-                    std::ops::Bound::Excluded(i) => *i,
-                    std::ops::Bound::Unbounded => n,
+                    ::core::ops::Bound::Included(i) => (*i + 1).min(n),
+                    ::core::ops::Bound::Excluded(i) => *i,
+                    ::core::ops::Bound::Unbounded => n,
                 };
                 self.index(start..end)
             }
 
-            fn get<'c>(&'c self, index: usize) -> Option<Self::Ref<'c>> {
+            fn get<'__soa_c>(&'__soa_c self, index: usize) -> Option<Self::Ref<'__soa_c>> {
                 self.get(index)
             }
 
-            fn index<'c>(&'c self, index: usize) -> Self::Ref<'c> {
+            fn index<'__soa_c>(&'__soa_c self, index: usize) -> Self::Ref<'__soa_c> {
                 self.index(index)
             }
 
-            fn iter<'c>(&'c self) -> Self::Iter<'c> {
+            fn iter<'__soa_c>(&'__soa_c self) -> Self::Iter<'__soa_c> {
                 self.as_ref().into_iter()
             }
 
-            fn as_mut_slice<'c: 'b, 'b>(&'c mut self) -> Self::SliceMut<'c> where Self: 'b {
+            fn as_mut_slice<'__soa_c: '__soa_b, '__soa_b>(&'__soa_c mut self) -> Self::SliceMut<'__soa_c> where Self: '__soa_b {
                 self.reborrow()
             }
 
-            fn slice_mut<'c>(&'c mut self, index: impl core::ops::RangeBounds<usize>) -> Self::SliceMut<'c> {
+            fn slice_mut<'__soa_c>(&'__soa_c mut self, index: impl core::ops::RangeBounds<usize>) -> Self::SliceMut<'__soa_c> {
                 let start = match index.start_bound() {
-                    std::ops::Bound::Included(i) | std::ops::Bound::Excluded(i) => *i,
-                    std::ops::Bound::Unbounded => 0,
+                    ::core::ops::Bound::Included(i) | ::core::ops::Bound::Excluded(i) => *i,
+                    ::core::ops::Bound::Unbounded => 0,
                 };
                 let n = self.len();
                 let end = match index.end_bound() {
-                    std::ops::Bound::Included(i) => (*i + 1).min(n),
-                    std::ops::Bound::Excluded(i) => *i,
-                    std::ops::Bound::Unbounded => n,
+                    ::core::ops::Bound::Included(i) => (*i + 1).min(n),
+                    ::core::ops::Bound::Excluded(i) => *i,
+                    ::core::ops::Bound::Unbounded => n,
                 };
                 self.index_mut(start..end)
             }
 
-            fn get_mut<'c>(&'c mut self, index: usize) -> Option<Self::RefMut<'c>> {
+            fn get_mut<'__soa_c>(&'__soa_c mut self, index: usize) -> Option<Self::RefMut<'__soa_c>> {
                 self.get_mut(index)
             }
 
-            fn index_mut<'c>(&'c mut self, index: usize) -> Self::RefMut<'c> {
+            fn index_mut<'__soa_c>(&'__soa_c mut self, index: usize) -> Self::RefMut<'__soa_c> {
                 self.index_mut(index)
             }
 
-            fn iter_mut<'c>(&'c mut self) -> Self::IterMut<'c> {
+            fn iter_mut<'__soa_c>(&'__soa_c mut self) -> Self::IterMut<'__soa_c> {
                 self.iter_mut()
             }
 
             fn apply_index(&mut self, indices: &[usize]) {
-                self.__private_apply_permutation(&mut ::soa_derive::Permutation::oneline(indices).inverse());
+                self.__private_apply_permutation(&mut ::soa_derive::Permutation::from_sorted_indices(indices));
+            }
+
+            fn reverse(&mut self) {
+                self.reverse();
+            }
+
+            fn rotate_left(&mut self, mid: usize) {
+                self.rotate_left(mid);
+            }
+
+            fn rotate_right(&mut self, k: usize) {
+                self.rotate_right(k);
+            }
+
+            fn fill(&mut self, value: #name<#generic_args>) where for<'__soa_b> #name<#generic_args>: Clone {
+                self.fill(value);
+            }
+
+            fn swap_with_slice<'__soa_c>(&'__soa_c mut self, other: Self::SliceMut<'__soa_c>) {
+                self.swap_with_slice(other);
             }
 
             fn as_ptr(&self) -> Self::Ptr {
@@ -177,28 +204,39 @@ pub fn derive_slice_mut(input: &Input) -> TokenStream {
 
 pub fn derive_vec(input: &Input) -> TokenStream {
     let name = &input.name;
-    let vec_name = names::vec_name(&input.name);
-    let slice_name = names::slice_name(name);
-    let slice_mut_name = names::slice_mut_name(&input.name);
-    let ref_name = names::ref_name(&input.name);
-    let ref_mut_name = names::ref_mut_name(&input.name);
-    let ptr_name = names::ptr_name(&input.name);
-    let ptr_mut_name = names::ptr_mut_name(&input.name);
-    let iter_name = names::iter_name(name);
-    let iter_mut_name = names::iter_mut_name(name);
+    let vec_name = &input.names.vec;
+    let slice_name = &input.names.slice;
+    let slice_mut_name = &input.names.slice_mut;
+    let ref_name = &input.names.ref_;
+    let ref_mut_name = &input.names.ref_mut;
+    let ptr_name = &input.names.ptr;
+    let ptr_mut_name = &input.names.ptr_mut;
+    let iter_name = &input.names.iter;
+    let iter_mut_name = &input.names.iter_mut;
+    let drain = &input.names.drain;
+    let splice = &input.names.splice;
+    let extract_if = &input.names.extract_if;
+
+    let generic_params = input.generic_params();
+    let generic_args = input.generic_args();
+    let where_predicates = input.where_predicates();
 
     let generated = quote! {
 
-        impl ::soa_derive::SoAVec<#name> for #vec_name {
-            type Ref<'t> = #ref_name<'t>;
-            type Slice<'t> = #slice_name<'t>;
-            type Iter<'t> = #iter_name<'t>;
-            type Ptr = #ptr_name;
+        impl<#generic_params> ::soa_derive::SoAVec<#name<#generic_args>> for #vec_name<#generic_args> where #where_predicates {
+            type Ref<'__soa_t> = #ref_name<'__soa_t, #generic_args> where Self: '__soa_t;
+            type Slice<'__soa_t> = #slice_name<'__soa_t, #generic_args> where Self: '__soa_t;
+            type Iter<'__soa_t> = #iter_name<'__soa_t, #generic_args> where Self: '__soa_t;
+            type Ptr = #ptr_name<#generic_args>;
+
+            type RefMut<'__soa_t> = #ref_mut_name<'__soa_t, #generic_args> where Self: '__soa_t;
+            type SliceMut<'__soa_t> = #slice_mut_name<'__soa_t, #generic_args> where Self: '__soa_t;
+            type IterMut<'__soa_t> = #iter_mut_name<'__soa_t, #generic_args> where Self: '__soa_t;
+            type PtrMut = #ptr_mut_name<#generic_args>;
 
-            type RefMut<'t> = #ref_mut_name<'t>;
-            type SliceMut<'t> = #slice_mut_name<'t>;
-            type IterMut<'t> = #iter_mut_name<'t>;
-            type PtrMut = #ptr_mut_name;
+            type Drain<'__soa_t> = #drain<'__soa_t, #generic_args> where Self: '__soa_t;
+            type Splice<'__soa_t, __SoaI> = #splice<'__soa_t, #generic_args __SoaI> where Self: '__soa_t, __SoaI: Iterator<Item=#name<#generic_args>>;
+            type ExtractIf<'__soa_t, __SoaF> = #extract_if<'__soa_t, #generic_args __SoaF> where Self: '__soa_t;
 
             fn len(&self) -> usize {
                 self.len()
@@ -208,63 +246,63 @@ pub fn derive_vec(input: &Input) -> TokenStream {
                 self.is_empty()
             }
 
-            fn as_slice<'c, 'a: 'c>(&'c self) -> Self::Slice<'c> where Self: 'a {
+            fn as_slice<'__soa_c, '__soa_a: '__soa_c>(&'__soa_c self) -> Self::Slice<'__soa_c> where Self: '__soa_a {
                 self.as_slice()
             }
 
-            fn slice<'c, 'a: 'c>(&'c self, index: impl core::ops::RangeBounds<usize>) -> Self::Slice<'c> where Self: 'a {
+            fn slice<'__soa_c, '__soa_a: '__soa_c>(&'__soa_c self, index: impl core::ops::RangeBounds<usize>) -> Self::Slice<'__soa_c> where Self: '__soa_a {
                 let start = match index.start_bound() {
-                    std::ops::Bound::Included(i) | std::ops::Bound::Excluded(i) => *i,
-                    std::ops::Bound::Unbounded => 0,
+                    ::core::ops::Bound::Included(i) | ::core::ops::Bound::Excluded(i) => *i,
+                    ::core::ops::Bound::Unbounded => 0,
                 };
                 let n = self.len();
                 let end = match index.end_bound() {
-                    std::ops::Bound::Included(i) => (*i + 1).min(n),
-                    std::ops::Bound::Excluded(i) => *i,
-                    std::ops::Bound::Unbounded => n,
+                    ::core::ops::Bound::Included(i) => (*i + 1).min(n),
+                    ::core::ops::Bound::Excluded(i) => *i,
+                    ::core::ops::Bound::Unbounded => n,
                 };
                 self.index(start..end)
             }
 
-            fn get<'c>(&'c self, index: usize) -> Option<Self::Ref<'c>> {
+            fn get<'__soa_c>(&'__soa_c self, index: usize) -> Option<Self::Ref<'__soa_c>> {
                 self.get(index)
             }
 
-            fn index<'c>(&'c self, index: usize) -> Self::Ref<'c> {
+            fn index<'__soa_c>(&'__soa_c self, index: usize) -> Self::Ref<'__soa_c> {
                 self.index(index)
             }
 
-            fn iter<'c>(&'c self) -> Self::Iter<'c> {
+            fn iter<'__soa_c>(&'__soa_c self) -> Self::Iter<'__soa_c> {
                 self.iter()
             }
 
-            fn as_mut_slice<'c, 'a: 'c>(&'c mut self) -> Self::SliceMut<'c> where Self: 'a {
+            fn as_mut_slice<'__soa_c, '__soa_a: '__soa_c>(&'__soa_c mut self) -> Self::SliceMut<'__soa_c> where Self: '__soa_a {
                 self.as_mut_slice()
             }
 
-            fn slice_mut<'c>(&'c mut self, index: impl core::ops::RangeBounds<usize>) -> Self::SliceMut<'c> {
+            fn slice_mut<'__soa_c>(&'__soa_c mut self, index: impl core::ops::RangeBounds<usize>) -> Self::SliceMut<'__soa_c> {
                 let start = match index.start_bound() {
-                    std::ops::Bound::Included(i) | std::ops::Bound::Excluded(i) => *i,
-                    std::ops::Bound::Unbounded => 0,
+                    ::core::ops::Bound::Included(i) | ::core::ops::Bound::Excluded(i) => *i,
+                    ::core::ops::Bound::Unbounded => 0,
                 };
                 let n = self.len();
                 let end = match index.end_bound() {
-                    std::ops::Bound::Included(i) => (*i + 1).min(n),
-                    std::ops::Bound::Excluded(i) => *i,
-                    std::ops::Bound::Unbounded => n,
+                    ::core::ops::Bound::Included(i) => (*i + 1).min(n),
+                    ::core::ops::Bound::Excluded(i) => *i,
+                    ::core::ops::Bound::Unbounded => n,
                 };
                 self.index_mut(start..end)
             }
 
-            fn get_mut<'c>(&'c mut self, index: usize) -> Option<Self::RefMut<'c>> {
+            fn get_mut<'__soa_c>(&'__soa_c mut self, index: usize) -> Option<Self::RefMut<'__soa_c>> {
                 self.get_mut(index)
             }
 
-            fn index_mut<'c>(&'c mut self, index: usize) -> Self::RefMut<'c> {
+            fn index_mut<'__soa_c>(&'__soa_c mut self, index: usize) -> Self::RefMut<'__soa_c> {
                 self.index_mut(index)
             }
 
-            fn iter_mut<'c>(&'c mut self) -> Self::IterMut<'c> {
+            fn iter_mut<'__soa_c>(&'__soa_c mut self) -> Self::IterMut<'__soa_c> {
                 self.iter_mut()
             }
 
@@ -273,6 +311,26 @@ pub fn derive_vec(input: &Input) -> TokenStream {
                 self.as_mut_slice().apply_index(indices);
             }
 
+            fn reverse(&mut self) {
+                self.as_mut_slice().reverse();
+            }
+
+            fn rotate_left(&mut self, mid: usize) {
+                self.as_mut_slice().rotate_left(mid);
+            }
+
+            fn rotate_right(&mut self, k: usize) {
+                self.as_mut_slice().rotate_right(k);
+            }
+
+            fn fill(&mut self, value: #name<#generic_args>) where for<'__soa_b> #name<#generic_args>: Clone {
+                self.as_mut_slice().fill(value);
+            }
+
+            fn swap_with_slice<'__soa_c>(&'__soa_c mut self, other: Self::SliceMut<'__soa_c>) {
+                self.as_mut_slice().swap_with_slice(other);
+            }
+
             fn new() -> Self {
                 Self::new()
             }
@@ -301,27 +359,27 @@ pub fn derive_vec(input: &Input) -> TokenStream {
                 self.truncate(len);
             }
 
-            fn push(&mut self, value: #name) {
+            fn push(&mut self, value: #name<#generic_args>) {
                 self.push(value);
             }
 
-            fn swap_remove(&mut self, index: usize) -> #name {
+            fn swap_remove(&mut self, index: usize) -> #name<#generic_args> {
                 self.swap_remove(index)
             }
 
-            fn insert(&mut self, index: usize, element: #name) {
+            fn insert(&mut self, index: usize, element: #name<#generic_args>) {
                 self.insert(index, element);
             }
 
-            fn replace(&mut self, index: usize, element: #name) -> #name {
+            fn replace(&mut self, index: usize, element: #name<#generic_args>) -> #name<#generic_args> {
                 self.replace(index, element)
             }
 
-            fn remove(&mut self, index: usize) -> #name {
+            fn remove(&mut self, index: usize) -> #name<#generic_args> {
                 self.remove(index)
             }
 
-            fn pop(&mut self) -> Option<#name> {
+            fn pop(&mut self) -> Option<#name<#generic_args>> {
                 self.pop()
             }
 
@@ -337,6 +395,30 @@ pub fn derive_vec(input: &Input) -> TokenStream {
                 self.split_off(at)
             }
 
+            fn drain<__SoaR>(&mut self, range: __SoaR) -> Self::Drain<'_> where __SoaR: core::ops::RangeBounds<usize> {
+                self.drain(range)
+            }
+
+            fn splice<__SoaR, __SoaI>(&mut self, range: __SoaR, replace_with: __SoaI) -> Self::Splice<'_, __SoaI::IntoIter>
+            where
+                __SoaR: core::ops::RangeBounds<usize>,
+                __SoaI: IntoIterator<Item=#name<#generic_args>>,
+            {
+                self.splice(range, replace_with)
+            }
+
+            fn extract_if<__SoaF, __SoaR>(&mut self, range: __SoaR, filter: __SoaF) -> Self::ExtractIf<'_, __SoaF>
+            where
+                __SoaF: FnMut(#ref_mut_name<'_, #generic_args>) -> bool,
+                __SoaR: core::ops::RangeBounds<usize>,
+            {
+                self.extract_if(range, filter)
+            }
+
+            fn dedup_by<__SoaF>(&mut self, same_bucket: __SoaF) where __SoaF: FnMut(#ref_mut_name<'_, #generic_args>, #ref_mut_name<'_, #generic_args>) -> bool {
+                self.dedup_by(same_bucket);
+            }
+
             fn as_ptr(&self) -> Self::Ptr {
                 self.as_ptr()
             }
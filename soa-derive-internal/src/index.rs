@@ -3,18 +3,19 @@ use proc_macro2::TokenStream;
 use quote::quote;
 
 use crate::input::Input;
-use crate::names;
 
 pub fn derive(input: &Input) -> TokenStream {
-    let vec_name = names::vec_name(&input.name);
-    let slice_name = names::slice_name(&input.name);
-    let slice_mut_name = names::slice_mut_name(&input.name);
-    let ref_name = names::ref_name(&input.name);
-    let ref_mut_name = names::ref_mut_name(&input.name);
-
-    let fields_names = &input.fields.iter()
-        .map(|field| field.ident.clone().unwrap())
-        .collect::<Vec<_>>();
+    let vec_name = &input.names.vec;
+    let slice_name = &input.names.slice;
+    let slice_mut_name = &input.names.slice_mut;
+    let ref_name = &input.names.ref_;
+    let ref_mut_name = &input.names.ref_mut;
+
+    let generic_params = input.generic_params();
+    let generic_args = input.generic_args();
+    let where_predicates = input.where_predicates();
+
+    let fields_names = &input.fields_names();
     let first_field_name = &fields_names[0];
 
 
@@ -40,11 +41,11 @@ pub fn derive(input: &Input) -> TokenStream {
 
     quote!{
         // usize
-        impl<'a> ::soa_derive::SoAIndex<&'a #vec_name> for usize {
-            type RefOutput = #ref_name<'a>;
+        impl<'__soa_a, #generic_params> ::soa_derive::SoAIndex<&'__soa_a #vec_name<#generic_args>> for usize where #where_predicates {
+            type RefOutput = #ref_name<'__soa_a, #generic_args>;
 
             #[inline]
-            fn get(self, soa: &'a #vec_name) -> Option<Self::RefOutput> {
+            fn get(self, soa: &'__soa_a #vec_name<#generic_args>) -> Option<Self::RefOutput> {
                 if self < soa.len() {
                     Some(unsafe { ::soa_derive::SoAIndex::get_unchecked(self, soa) })
                 } else {
@@ -53,21 +54,21 @@ pub fn derive(input: &Input) -> TokenStream {
             }
 
             #[inline]
-            unsafe fn get_unchecked(self, soa: &'a #vec_name) -> Self::RefOutput {
+            unsafe fn get_unchecked(self, soa: &'__soa_a #vec_name<#generic_args>) -> Self::RefOutput {
                 ::soa_derive::SoAIndex::get_unchecked(self, soa.as_slice())
             }
 
             #[inline]
-            fn index(self, soa: &'a #vec_name) -> Self::RefOutput {
+            fn index(self, soa: &'__soa_a #vec_name<#generic_args>) -> Self::RefOutput {
                 ::soa_derive::SoAIndex::index(self, soa.as_slice())
             }
         }
 
-        impl<'a> ::soa_derive::SoAIndexMut<&'a mut #vec_name> for usize {
-            type MutOutput = #ref_mut_name<'a>;
+        impl<'__soa_a, #generic_params> ::soa_derive::SoAIndexMut<&'__soa_a mut #vec_name<#generic_args>> for usize where #where_predicates {
+            type MutOutput = #ref_mut_name<'__soa_a, #generic_args>;
 
             #[inline]
-            fn get_mut(self, soa: &'a mut #vec_name) -> Option<Self::MutOutput> {
+            fn get_mut(self, soa: &'__soa_a mut #vec_name<#generic_args>) -> Option<Self::MutOutput> {
                 if self < soa.len() {
                     Some(unsafe { ::soa_derive::SoAIndexMut::get_unchecked_mut(self, soa) })
                 } else {
@@ -76,12 +77,12 @@ pub fn derive(input: &Input) -> TokenStream {
             }
 
             #[inline]
-            unsafe fn get_unchecked_mut(self, soa: &'a mut #vec_name) -> Self::MutOutput {
+            unsafe fn get_unchecked_mut(self, soa: &'__soa_a mut #vec_name<#generic_args>) -> Self::MutOutput {
                 ::soa_derive::SoAIndexMut::get_unchecked_mut(self, soa.as_mut_slice())
             }
 
             #[inline]
-            fn index_mut(self, soa: &'a mut #vec_name) -> Self::MutOutput {
+            fn index_mut(self, soa: &'__soa_a mut #vec_name<#generic_args>) -> Self::MutOutput {
                 ::soa_derive::SoAIndexMut::index_mut(self, soa.as_mut_slice())
             }
         }
@@ -89,11 +90,11 @@ pub fn derive(input: &Input) -> TokenStream {
 
 
         // Range<usize>
-        impl<'a> ::soa_derive::SoAIndex<&'a #vec_name> for ::std::ops::Range<usize> {
-            type RefOutput = #slice_name<'a>;
+        impl<'__soa_a, #generic_params> ::soa_derive::SoAIndex<&'__soa_a #vec_name<#generic_args>> for ::core::ops::Range<usize> where #where_predicates {
+            type RefOutput = #slice_name<'__soa_a, #generic_args>;
 
             #[inline]
-            fn get(self, soa: &'a #vec_name) -> Option<Self::RefOutput> {
+            fn get(self, soa: &'__soa_a #vec_name<#generic_args>) -> Option<Self::RefOutput> {
                 if self.start <= self.end && self.end <= soa.len() {
                     unsafe { Some(::soa_derive::SoAIndex::get_unchecked(self, soa)) }
                 } else {
@@ -102,21 +103,21 @@ pub fn derive(input: &Input) -> TokenStream {
             }
 
             #[inline]
-            unsafe fn get_unchecked(self, soa: &'a #vec_name) -> Self::RefOutput {
+            unsafe fn get_unchecked(self, soa: &'__soa_a #vec_name<#generic_args>) -> Self::RefOutput {
                 ::soa_derive::SoAIndex::get_unchecked(self, soa.as_slice())
             }
 
             #[inline]
-            fn index(self, soa: &'a #vec_name) -> Self::RefOutput {
+            fn index(self, soa: &'__soa_a #vec_name<#generic_args>) -> Self::RefOutput {
                 ::soa_derive::SoAIndex::index(self, soa.as_slice())
             }
         }
 
-        impl<'a> ::soa_derive::SoAIndexMut<&'a mut #vec_name> for ::std::ops::Range<usize> {
-            type MutOutput = #slice_mut_name<'a>;
+        impl<'__soa_a, #generic_params> ::soa_derive::SoAIndexMut<&'__soa_a mut #vec_name<#generic_args>> for ::core::ops::Range<usize> where #where_predicates {
+            type MutOutput = #slice_mut_name<'__soa_a, #generic_args>;
 
             #[inline]
-            fn get_mut(self, soa: &'a mut #vec_name) -> Option<Self::MutOutput> {
+            fn get_mut(self, soa: &'__soa_a mut #vec_name<#generic_args>) -> Option<Self::MutOutput> {
                 if self.start <= self.end && self.end <= soa.len() {
                     unsafe { Some(::soa_derive::SoAIndexMut::get_unchecked_mut(self, soa)) }
                 } else {
@@ -125,139 +126,139 @@ pub fn derive(input: &Input) -> TokenStream {
             }
 
             #[inline]
-            unsafe fn get_unchecked_mut(self, soa: &'a mut #vec_name) -> Self::MutOutput {
+            unsafe fn get_unchecked_mut(self, soa: &'__soa_a mut #vec_name<#generic_args>) -> Self::MutOutput {
                 ::soa_derive::SoAIndexMut::get_unchecked_mut(self, soa.as_mut_slice())
             }
 
             #[inline]
-            fn index_mut(self, soa: &'a mut #vec_name) -> Self::MutOutput {
+            fn index_mut(self, soa: &'__soa_a mut #vec_name<#generic_args>) -> Self::MutOutput {
                ::soa_derive::SoAIndexMut::index_mut(self, soa.as_mut_slice())
             }
         }
 
         // RangeTo<usize>
-        impl<'a> ::soa_derive::SoAIndex<&'a #vec_name> for ::std::ops::RangeTo<usize> {
-            type RefOutput = #slice_name<'a>;
+        impl<'__soa_a, #generic_params> ::soa_derive::SoAIndex<&'__soa_a #vec_name<#generic_args>> for ::core::ops::RangeTo<usize> where #where_predicates {
+            type RefOutput = #slice_name<'__soa_a, #generic_args>;
 
             #[inline]
-            fn get(self, soa: &'a #vec_name) -> Option<Self::RefOutput> {
+            fn get(self, soa: &'__soa_a #vec_name<#generic_args>) -> Option<Self::RefOutput> {
                 ::soa_derive::SoAIndex::get(0..self.end, soa)
             }
 
             #[inline]
-            unsafe fn get_unchecked(self, soa: &'a #vec_name) -> Self::RefOutput {
+            unsafe fn get_unchecked(self, soa: &'__soa_a #vec_name<#generic_args>) -> Self::RefOutput {
                 ::soa_derive::SoAIndex::get_unchecked(0..self.end, soa)
             }
 
             #[inline]
-            fn index(self, soa: &'a #vec_name) -> Self::RefOutput {
+            fn index(self, soa: &'__soa_a #vec_name<#generic_args>) -> Self::RefOutput {
                 ::soa_derive::SoAIndex::index(0..self.end, soa)
             }
         }
 
-        impl<'a> ::soa_derive::SoAIndexMut<&'a mut #vec_name> for ::std::ops::RangeTo<usize> {
-            type MutOutput = #slice_mut_name<'a>;
+        impl<'__soa_a, #generic_params> ::soa_derive::SoAIndexMut<&'__soa_a mut #vec_name<#generic_args>> for ::core::ops::RangeTo<usize> where #where_predicates {
+            type MutOutput = #slice_mut_name<'__soa_a, #generic_args>;
 
             #[inline]
-            fn get_mut(self, soa: &'a mut #vec_name) -> Option<Self::MutOutput> {
+            fn get_mut(self, soa: &'__soa_a mut #vec_name<#generic_args>) -> Option<Self::MutOutput> {
                 ::soa_derive::SoAIndexMut::get_mut(0..self.end, soa)
             }
 
             #[inline]
-            unsafe fn get_unchecked_mut(self, soa: &'a mut #vec_name) -> Self::MutOutput {
+            unsafe fn get_unchecked_mut(self, soa: &'__soa_a mut #vec_name<#generic_args>) -> Self::MutOutput {
                 ::soa_derive::SoAIndexMut::get_unchecked_mut(0..self.end, soa)
             }
 
             #[inline]
-            fn index_mut(self, soa: &'a mut #vec_name) -> Self::MutOutput {
+            fn index_mut(self, soa: &'__soa_a mut #vec_name<#generic_args>) -> Self::MutOutput {
                 ::soa_derive::SoAIndexMut::index_mut(0..self.end, soa)
             }
         }
 
         // RangeFrom<usize>
-        impl<'a> ::soa_derive::SoAIndex<&'a #vec_name> for ::std::ops::RangeFrom<usize> {
-            type RefOutput = #slice_name<'a>;
+        impl<'__soa_a, #generic_params> ::soa_derive::SoAIndex<&'__soa_a #vec_name<#generic_args>> for ::core::ops::RangeFrom<usize> where #where_predicates {
+            type RefOutput = #slice_name<'__soa_a, #generic_args>;
 
             #[inline]
-            fn get(self, soa: &'a #vec_name) -> Option<Self::RefOutput> {
+            fn get(self, soa: &'__soa_a #vec_name<#generic_args>) -> Option<Self::RefOutput> {
                 ::soa_derive::SoAIndex::get(self.start..soa.len(), soa)
             }
 
             #[inline]
-            unsafe fn get_unchecked(self, soa: &'a #vec_name) -> Self::RefOutput {
+            unsafe fn get_unchecked(self, soa: &'__soa_a #vec_name<#generic_args>) -> Self::RefOutput {
                 ::soa_derive::SoAIndex::get_unchecked(self.start..soa.len(), soa)
             }
 
             #[inline]
-            fn index(self, soa: &'a #vec_name) -> Self::RefOutput {
+            fn index(self, soa: &'__soa_a #vec_name<#generic_args>) -> Self::RefOutput {
                 ::soa_derive::SoAIndex::index(self.start..soa.len(), soa)
             }
         }
 
-        impl<'a> ::soa_derive::SoAIndexMut<&'a mut #vec_name> for ::std::ops::RangeFrom<usize> {
-            type MutOutput = #slice_mut_name<'a>;
+        impl<'__soa_a, #generic_params> ::soa_derive::SoAIndexMut<&'__soa_a mut #vec_name<#generic_args>> for ::core::ops::RangeFrom<usize> where #where_predicates {
+            type MutOutput = #slice_mut_name<'__soa_a, #generic_args>;
 
             #[inline]
-            fn get_mut(self, soa: &'a mut #vec_name) -> Option<Self::MutOutput> {
+            fn get_mut(self, soa: &'__soa_a mut #vec_name<#generic_args>) -> Option<Self::MutOutput> {
                ::soa_derive::SoAIndexMut::get_mut(self.start..soa.len(), soa)
             }
 
             #[inline]
-            unsafe fn get_unchecked_mut(self, soa: &'a mut #vec_name) -> Self::MutOutput {
+            unsafe fn get_unchecked_mut(self, soa: &'__soa_a mut #vec_name<#generic_args>) -> Self::MutOutput {
                 ::soa_derive::SoAIndexMut::get_unchecked_mut(self.start..soa.len(), soa)
             }
 
             #[inline]
-            fn index_mut(self, soa: &'a mut #vec_name) -> Self::MutOutput {
+            fn index_mut(self, soa: &'__soa_a mut #vec_name<#generic_args>) -> Self::MutOutput {
                 ::soa_derive::SoAIndexMut::index_mut(self.start..soa.len(), soa)
             }
         }
 
         // RangeFull
-        impl<'a> ::soa_derive::SoAIndex<&'a #vec_name> for ::std::ops::RangeFull {
-            type RefOutput = #slice_name<'a>;
+        impl<'__soa_a, #generic_params> ::soa_derive::SoAIndex<&'__soa_a #vec_name<#generic_args>> for ::core::ops::RangeFull where #where_predicates {
+            type RefOutput = #slice_name<'__soa_a, #generic_args>;
 
             #[inline]
-            fn get(self, soa: &'a #vec_name) -> Option<Self::RefOutput> {
+            fn get(self, soa: &'__soa_a #vec_name<#generic_args>) -> Option<Self::RefOutput> {
                 Some(soa.as_slice())
             }
 
             #[inline]
-            unsafe fn get_unchecked(self, soa: &'a #vec_name) -> Self::RefOutput {
+            unsafe fn get_unchecked(self, soa: &'__soa_a #vec_name<#generic_args>) -> Self::RefOutput {
                 soa.as_slice()
             }
 
             #[inline]
-            fn index(self, soa: &'a #vec_name) -> Self::RefOutput {
+            fn index(self, soa: &'__soa_a #vec_name<#generic_args>) -> Self::RefOutput {
                 soa.as_slice()
             }
         }
 
-        impl<'a> ::soa_derive::SoAIndexMut<&'a mut #vec_name> for ::std::ops::RangeFull {
-            type MutOutput = #slice_mut_name<'a>;
+        impl<'__soa_a, #generic_params> ::soa_derive::SoAIndexMut<&'__soa_a mut #vec_name<#generic_args>> for ::core::ops::RangeFull where #where_predicates {
+            type MutOutput = #slice_mut_name<'__soa_a, #generic_args>;
 
             #[inline]
-            fn get_mut(self, soa: &'a mut #vec_name) -> Option<Self::MutOutput> {
+            fn get_mut(self, soa: &'__soa_a mut #vec_name<#generic_args>) -> Option<Self::MutOutput> {
                 Some(soa.as_mut_slice())
             }
 
             #[inline]
-            unsafe fn get_unchecked_mut(self, soa: &'a mut #vec_name) -> Self::MutOutput {
+            unsafe fn get_unchecked_mut(self, soa: &'__soa_a mut #vec_name<#generic_args>) -> Self::MutOutput {
                 soa.as_mut_slice()
             }
 
             #[inline]
-            fn index_mut(self, soa: &'a mut #vec_name) -> Self::MutOutput {
+            fn index_mut(self, soa: &'__soa_a mut #vec_name<#generic_args>) -> Self::MutOutput {
                 soa.as_mut_slice()
             }
         }
 
         // RangeInclusive<usize>
-        impl<'a> ::soa_derive::SoAIndex<&'a #vec_name> for ::std::ops::RangeInclusive<usize> {
-            type RefOutput = #slice_name<'a>;
+        impl<'__soa_a, #generic_params> ::soa_derive::SoAIndex<&'__soa_a #vec_name<#generic_args>> for ::core::ops::RangeInclusive<usize> where #where_predicates {
+            type RefOutput = #slice_name<'__soa_a, #generic_args>;
 
             #[inline]
-            fn get(self, soa: &'a #vec_name) -> Option<Self::RefOutput> {
+            fn get(self, soa: &'__soa_a #vec_name<#generic_args>) -> Option<Self::RefOutput> {
                 if *self.end() == usize::MAX {
                     None
                 } else {
@@ -266,21 +267,21 @@ pub fn derive(input: &Input) -> TokenStream {
             }
 
             #[inline]
-            unsafe fn get_unchecked(self, soa: &'a #vec_name) -> Self::RefOutput {
+            unsafe fn get_unchecked(self, soa: &'__soa_a #vec_name<#generic_args>) -> Self::RefOutput {
                 ::soa_derive::SoAIndex::get_unchecked(*self.start()..self.end() + 1, soa)
             }
 
             #[inline]
-            fn index(self, soa: &'a #vec_name) -> Self::RefOutput {
+            fn index(self, soa: &'__soa_a #vec_name<#generic_args>) -> Self::RefOutput {
                 ::soa_derive::SoAIndex::index(*self.start()..self.end() + 1, soa)
             }
         }
 
-        impl<'a> ::soa_derive::SoAIndexMut<&'a mut #vec_name> for ::std::ops::RangeInclusive<usize> {
-            type MutOutput = #slice_mut_name<'a>;
+        impl<'__soa_a, #generic_params> ::soa_derive::SoAIndexMut<&'__soa_a mut #vec_name<#generic_args>> for ::core::ops::RangeInclusive<usize> where #where_predicates {
+            type MutOutput = #slice_mut_name<'__soa_a, #generic_args>;
 
             #[inline]
-            fn get_mut(self, soa: &'a mut #vec_name) -> Option<Self::MutOutput> {
+            fn get_mut(self, soa: &'__soa_a mut #vec_name<#generic_args>) -> Option<Self::MutOutput> {
                 if *self.end() == usize::MAX {
                     None
                 } else {
@@ -289,61 +290,61 @@ pub fn derive(input: &Input) -> TokenStream {
             }
 
             #[inline]
-            unsafe fn get_unchecked_mut(self, soa: &'a mut #vec_name) -> Self::MutOutput {
+            unsafe fn get_unchecked_mut(self, soa: &'__soa_a mut #vec_name<#generic_args>) -> Self::MutOutput {
                 ::soa_derive::SoAIndexMut::get_unchecked_mut(*self.start()..self.end() + 1, soa)
             }
 
             #[inline]
-            fn index_mut(self, soa: &'a mut #vec_name) -> Self::MutOutput {
+            fn index_mut(self, soa: &'__soa_a mut #vec_name<#generic_args>) -> Self::MutOutput {
                 ::soa_derive::SoAIndexMut::index_mut(*self.start()..self.end() + 1, soa)
             }
         }
 
         // RangeToInclusive<usize>
-        impl<'a> ::soa_derive::SoAIndex<&'a #vec_name> for ::std::ops::RangeToInclusive<usize> {
-            type RefOutput = #slice_name<'a>;
+        impl<'__soa_a, #generic_params> ::soa_derive::SoAIndex<&'__soa_a #vec_name<#generic_args>> for ::core::ops::RangeToInclusive<usize> where #where_predicates {
+            type RefOutput = #slice_name<'__soa_a, #generic_args>;
 
             #[inline]
-            fn get(self, soa: &'a #vec_name) -> Option<Self::RefOutput> {
+            fn get(self, soa: &'__soa_a #vec_name<#generic_args>) -> Option<Self::RefOutput> {
                 ::soa_derive::SoAIndex::get(0..=self.end, soa)
             }
 
             #[inline]
-            unsafe fn get_unchecked(self, soa: &'a #vec_name) -> Self::RefOutput {
+            unsafe fn get_unchecked(self, soa: &'__soa_a #vec_name<#generic_args>) -> Self::RefOutput {
                 ::soa_derive::SoAIndex::get_unchecked(0..=self.end, soa)
             }
 
             #[inline]
-            fn index(self, soa: &'a #vec_name) -> Self::RefOutput {
+            fn index(self, soa: &'__soa_a #vec_name<#generic_args>) -> Self::RefOutput {
                 ::soa_derive::SoAIndex::index(0..=self.end, soa)
             }
         }
 
-        impl<'a> ::soa_derive::SoAIndexMut<&'a mut #vec_name> for ::std::ops::RangeToInclusive<usize> {
-            type MutOutput = #slice_mut_name<'a>;
+        impl<'__soa_a, #generic_params> ::soa_derive::SoAIndexMut<&'__soa_a mut #vec_name<#generic_args>> for ::core::ops::RangeToInclusive<usize> where #where_predicates {
+            type MutOutput = #slice_mut_name<'__soa_a, #generic_args>;
 
             #[inline]
-            fn get_mut(self, soa: &'a mut #vec_name) -> Option<Self::MutOutput> {
+            fn get_mut(self, soa: &'__soa_a mut #vec_name<#generic_args>) -> Option<Self::MutOutput> {
                 ::soa_derive::SoAIndexMut::get_mut(0..=self.end, soa)
             }
 
             #[inline]
-            unsafe fn get_unchecked_mut(self, soa: &'a mut #vec_name) -> Self::MutOutput {
+            unsafe fn get_unchecked_mut(self, soa: &'__soa_a mut #vec_name<#generic_args>) -> Self::MutOutput {
                 ::soa_derive::SoAIndexMut::get_unchecked_mut(0..=self.end, soa)
             }
 
             #[inline]
-            fn index_mut(self, soa: &'a mut #vec_name) -> Self::MutOutput {
+            fn index_mut(self, soa: &'__soa_a mut #vec_name<#generic_args>) -> Self::MutOutput {
                 ::soa_derive::SoAIndexMut::index_mut(0..=self.end, soa)
             }
         }
 
         // usize
-        impl<'a> ::soa_derive::SoAIndex<#slice_name<'a>> for usize {
-            type RefOutput = #ref_name<'a>;
+        impl<'__soa_a, #generic_params> ::soa_derive::SoAIndex<#slice_name<'__soa_a, #generic_args>> for usize where #where_predicates {
+            type RefOutput = #ref_name<'__soa_a, #generic_args>;
 
             #[inline]
-            fn get(self, slice: #slice_name<'a>) -> Option<Self::RefOutput> {
+            fn get(self, slice: #slice_name<'__soa_a, #generic_args>) -> Option<Self::RefOutput> {
                 if self < slice.#first_field_name.len() {
                     Some(unsafe { ::soa_derive::SoAIndex::get_unchecked(self, slice) })
                 } else {
@@ -352,25 +353,25 @@ pub fn derive(input: &Input) -> TokenStream {
             }
 
             #[inline]
-            unsafe fn get_unchecked(self, slice: #slice_name<'a>) -> Self::RefOutput {
+            unsafe fn get_unchecked(self, slice: #slice_name<'__soa_a, #generic_args>) -> Self::RefOutput {
                 #ref_name {
                     #( #fields_names: #get_unchecked, )*
                 }
             }
 
             #[inline]
-            fn index(self, slice: #slice_name<'a>) -> Self::RefOutput {
+            fn index(self, slice: #slice_name<'__soa_a, #generic_args>) -> Self::RefOutput {
                 #ref_name {
                     #( #fields_names: #index, )*
                 }
             }
         }
 
-        impl<'a> ::soa_derive::SoAIndexMut<#slice_mut_name<'a>> for usize {
-            type MutOutput = #ref_mut_name<'a>;
+        impl<'__soa_a, #generic_params> ::soa_derive::SoAIndexMut<#slice_mut_name<'__soa_a, #generic_args>> for usize where #where_predicates {
+            type MutOutput = #ref_mut_name<'__soa_a, #generic_args>;
 
             #[inline]
-            fn get_mut(self, slice: #slice_mut_name<'a>) -> Option<Self::MutOutput> {
+            fn get_mut(self, slice: #slice_mut_name<'__soa_a, #generic_args>) -> Option<Self::MutOutput> {
                 if self < slice.len() {
                     Some(unsafe { ::soa_derive::SoAIndexMut::get_unchecked_mut(self, slice) })
                 } else {
@@ -379,14 +380,14 @@ pub fn derive(input: &Input) -> TokenStream {
             }
 
             #[inline]
-            unsafe fn get_unchecked_mut(self, slice: #slice_mut_name<'a>) -> Self::MutOutput {
+            unsafe fn get_unchecked_mut(self, slice: #slice_mut_name<'__soa_a, #generic_args>) -> Self::MutOutput {
                 #ref_mut_name {
                     #( #fields_names: #get_unchecked_mut, )*
                 }
             }
 
             #[inline]
-            fn index_mut(self, slice: #slice_mut_name<'a>) -> Self::MutOutput {
+            fn index_mut(self, slice: #slice_mut_name<'__soa_a, #generic_args>) -> Self::MutOutput {
                 #ref_mut_name {
                     #( #fields_names: #index_mut, )*
                 }
@@ -396,11 +397,11 @@ pub fn derive(input: &Input) -> TokenStream {
 
 
         // Range<usize>
-        impl<'a> ::soa_derive::SoAIndex<#slice_name<'a>> for ::std::ops::Range<usize> {
-            type RefOutput = #slice_name<'a>;
+        impl<'__soa_a, #generic_params> ::soa_derive::SoAIndex<#slice_name<'__soa_a, #generic_args>> for ::core::ops::Range<usize> where #where_predicates {
+            type RefOutput = #slice_name<'__soa_a, #generic_args>;
 
             #[inline]
-            fn get(self, slice: #slice_name<'a>) -> Option<Self::RefOutput> {
+            fn get(self, slice: #slice_name<'__soa_a, #generic_args>) -> Option<Self::RefOutput> {
                 if self.start <= self.end && self.end <= slice.#first_field_name.len() {
                     unsafe { Some(::soa_derive::SoAIndex::get_unchecked(self, slice)) }
                 } else {
@@ -409,25 +410,25 @@ pub fn derive(input: &Input) -> TokenStream {
             }
 
             #[inline]
-            unsafe fn get_unchecked(self, slice: #slice_name<'a>) -> Self::RefOutput {
+            unsafe fn get_unchecked(self, slice: #slice_name<'__soa_a, #generic_args>) -> Self::RefOutput {
                 #slice_name {
                     #( #fields_names: #get_unchecked, )*
                 }
             }
 
             #[inline]
-            fn index(self, slice: #slice_name<'a>) -> Self::RefOutput {
+            fn index(self, slice: #slice_name<'__soa_a, #generic_args>) -> Self::RefOutput {
                 #slice_name {
                     #( #fields_names: #index, )*
                 }
             }
         }
 
-        impl<'a> ::soa_derive::SoAIndexMut<#slice_mut_name<'a>> for ::std::ops::Range<usize> {
-            type MutOutput = #slice_mut_name<'a>;
+        impl<'__soa_a, #generic_params> ::soa_derive::SoAIndexMut<#slice_mut_name<'__soa_a, #generic_args>> for ::core::ops::Range<usize> where #where_predicates {
+            type MutOutput = #slice_mut_name<'__soa_a, #generic_args>;
 
             #[inline]
-            fn get_mut(self, slice: #slice_mut_name<'a>) -> Option<Self::MutOutput> {
+            fn get_mut(self, slice: #slice_mut_name<'__soa_a, #generic_args>) -> Option<Self::MutOutput> {
                 if self.start <= self.end && self.end <= slice.#first_field_name.len() {
                     unsafe { Some(::soa_derive::SoAIndexMut::get_unchecked_mut(self, slice)) }
                 } else {
@@ -436,14 +437,14 @@ pub fn derive(input: &Input) -> TokenStream {
             }
 
             #[inline]
-            unsafe fn get_unchecked_mut(self, slice: #slice_mut_name<'a>) -> Self::MutOutput {
+            unsafe fn get_unchecked_mut(self, slice: #slice_mut_name<'__soa_a, #generic_args>) -> Self::MutOutput {
                 #slice_mut_name {
                     #( #fields_names: #get_unchecked_mut, )*
                 }
             }
 
             #[inline]
-            fn index_mut(self, slice: #slice_mut_name<'a>) -> Self::MutOutput {
+            fn index_mut(self, slice: #slice_mut_name<'__soa_a, #generic_args>) -> Self::MutOutput {
                 #slice_mut_name {
                     #( #fields_names: #index_mut, )*
                 }
@@ -453,131 +454,131 @@ pub fn derive(input: &Input) -> TokenStream {
 
 
         // RangeTo<usize>
-        impl<'a> ::soa_derive::SoAIndex<#slice_name<'a>> for ::std::ops::RangeTo<usize> {
-            type RefOutput = #slice_name<'a>;
+        impl<'__soa_a, #generic_params> ::soa_derive::SoAIndex<#slice_name<'__soa_a, #generic_args>> for ::core::ops::RangeTo<usize> where #where_predicates {
+            type RefOutput = #slice_name<'__soa_a, #generic_args>;
 
             #[inline]
-            fn get(self, slice: #slice_name<'a>) -> Option<Self::RefOutput> {
+            fn get(self, slice: #slice_name<'__soa_a, #generic_args>) -> Option<Self::RefOutput> {
                 ::soa_derive::SoAIndex::get(0..self.end, slice)
             }
 
             #[inline]
-            unsafe fn get_unchecked(self, slice: #slice_name<'a>) -> Self::RefOutput {
+            unsafe fn get_unchecked(self, slice: #slice_name<'__soa_a, #generic_args>) -> Self::RefOutput {
                 ::soa_derive::SoAIndex::get_unchecked(0..self.end, slice)
             }
 
             #[inline]
-            fn index(self, slice: #slice_name<'a>) -> Self::RefOutput {
+            fn index(self, slice: #slice_name<'__soa_a, #generic_args>) -> Self::RefOutput {
                 ::soa_derive::SoAIndex::index(0..self.end, slice)
             }
         }
 
-        impl<'a> ::soa_derive::SoAIndexMut<#slice_mut_name<'a>> for ::std::ops::RangeTo<usize> {
-            type MutOutput = #slice_mut_name<'a>;
+        impl<'__soa_a, #generic_params> ::soa_derive::SoAIndexMut<#slice_mut_name<'__soa_a, #generic_args>> for ::core::ops::RangeTo<usize> where #where_predicates {
+            type MutOutput = #slice_mut_name<'__soa_a, #generic_args>;
 
             #[inline]
-            fn get_mut(self, slice: #slice_mut_name<'a>) -> Option<Self::MutOutput> {
+            fn get_mut(self, slice: #slice_mut_name<'__soa_a, #generic_args>) -> Option<Self::MutOutput> {
                 ::soa_derive::SoAIndexMut::get_mut(0..self.end, slice)
             }
 
             #[inline]
-            unsafe fn get_unchecked_mut(self, slice: #slice_mut_name<'a>) -> Self::MutOutput {
+            unsafe fn get_unchecked_mut(self, slice: #slice_mut_name<'__soa_a, #generic_args>) -> Self::MutOutput {
                 ::soa_derive::SoAIndexMut::get_unchecked_mut(0..self.end, slice)
             }
 
             #[inline]
-            fn index_mut(self, slice: #slice_mut_name<'a>) -> Self::MutOutput {
+            fn index_mut(self, slice: #slice_mut_name<'__soa_a, #generic_args>) -> Self::MutOutput {
                 ::soa_derive::SoAIndexMut::index_mut(0..self.end, slice)
             }
         }
 
 
         // RangeFrom<usize>
-        impl<'a> ::soa_derive::SoAIndex<#slice_name<'a>> for ::std::ops::RangeFrom<usize> {
-            type RefOutput = #slice_name<'a>;
+        impl<'__soa_a, #generic_params> ::soa_derive::SoAIndex<#slice_name<'__soa_a, #generic_args>> for ::core::ops::RangeFrom<usize> where #where_predicates {
+            type RefOutput = #slice_name<'__soa_a, #generic_args>;
 
             #[inline]
-            fn get(self, slice: #slice_name<'a>) -> Option<Self::RefOutput> {
+            fn get(self, slice: #slice_name<'__soa_a, #generic_args>) -> Option<Self::RefOutput> {
                 ::soa_derive::SoAIndex::get(self.start..slice.len(), slice)
             }
 
             #[inline]
-            unsafe fn get_unchecked(self, slice: #slice_name<'a>) -> Self::RefOutput {
+            unsafe fn get_unchecked(self, slice: #slice_name<'__soa_a, #generic_args>) -> Self::RefOutput {
                 ::soa_derive::SoAIndex::get_unchecked(self.start..slice.len(), slice)
             }
 
             #[inline]
-            fn index(self, slice: #slice_name<'a>) -> Self::RefOutput {
+            fn index(self, slice: #slice_name<'__soa_a, #generic_args>) -> Self::RefOutput {
                 ::soa_derive::SoAIndex::index(self.start..slice.len(), slice)
             }
         }
 
-        impl<'a> ::soa_derive::SoAIndexMut<#slice_mut_name<'a>> for ::std::ops::RangeFrom<usize> {
-            type MutOutput = #slice_mut_name<'a>;
+        impl<'__soa_a, #generic_params> ::soa_derive::SoAIndexMut<#slice_mut_name<'__soa_a, #generic_args>> for ::core::ops::RangeFrom<usize> where #where_predicates {
+            type MutOutput = #slice_mut_name<'__soa_a, #generic_args>;
 
             #[inline]
-            fn get_mut(self, slice: #slice_mut_name<'a>) -> Option<Self::MutOutput> {
+            fn get_mut(self, slice: #slice_mut_name<'__soa_a, #generic_args>) -> Option<Self::MutOutput> {
                 ::soa_derive::SoAIndexMut::get_mut(self.start..slice.len(), slice)
             }
 
             #[inline]
-            unsafe fn get_unchecked_mut(self, slice: #slice_mut_name<'a>) -> Self::MutOutput {
+            unsafe fn get_unchecked_mut(self, slice: #slice_mut_name<'__soa_a, #generic_args>) -> Self::MutOutput {
                 ::soa_derive::SoAIndexMut::get_unchecked_mut(self.start..slice.len(), slice)
             }
 
             #[inline]
-            fn index_mut(self, slice: #slice_mut_name<'a>) -> Self::MutOutput {
+            fn index_mut(self, slice: #slice_mut_name<'__soa_a, #generic_args>) -> Self::MutOutput {
                 ::soa_derive::SoAIndexMut::index_mut(self.start..slice.len(), slice)
             }
         }
 
 
         // RangeFull
-        impl<'a> ::soa_derive::SoAIndex<#slice_name<'a>> for ::std::ops::RangeFull {
-            type RefOutput = #slice_name<'a>;
+        impl<'__soa_a, #generic_params> ::soa_derive::SoAIndex<#slice_name<'__soa_a, #generic_args>> for ::core::ops::RangeFull where #where_predicates {
+            type RefOutput = #slice_name<'__soa_a, #generic_args>;
 
             #[inline]
-            fn get(self, slice: #slice_name<'a>) -> Option<Self::RefOutput> {
+            fn get(self, slice: #slice_name<'__soa_a, #generic_args>) -> Option<Self::RefOutput> {
                 Some(slice)
             }
 
             #[inline]
-            unsafe fn get_unchecked(self, slice: #slice_name<'a>) -> Self::RefOutput {
+            unsafe fn get_unchecked(self, slice: #slice_name<'__soa_a, #generic_args>) -> Self::RefOutput {
                 slice
             }
 
             #[inline]
-            fn index(self, slice: #slice_name<'a>) -> Self::RefOutput {
+            fn index(self, slice: #slice_name<'__soa_a, #generic_args>) -> Self::RefOutput {
                 slice
             }
         }
 
-        impl<'a> ::soa_derive::SoAIndexMut<#slice_mut_name<'a>> for ::std::ops::RangeFull {
-            type MutOutput = #slice_mut_name<'a>;
+        impl<'__soa_a, #generic_params> ::soa_derive::SoAIndexMut<#slice_mut_name<'__soa_a, #generic_args>> for ::core::ops::RangeFull where #where_predicates {
+            type MutOutput = #slice_mut_name<'__soa_a, #generic_args>;
 
             #[inline]
-            fn get_mut(self, slice: #slice_mut_name<'a>) -> Option<Self::MutOutput> {
+            fn get_mut(self, slice: #slice_mut_name<'__soa_a, #generic_args>) -> Option<Self::MutOutput> {
                 Some(slice)
             }
 
             #[inline]
-            unsafe fn get_unchecked_mut(self, slice: #slice_mut_name<'a>) -> Self::MutOutput {
+            unsafe fn get_unchecked_mut(self, slice: #slice_mut_name<'__soa_a, #generic_args>) -> Self::MutOutput {
                 slice
             }
 
             #[inline]
-            fn index_mut(self, slice: #slice_mut_name<'a>) -> Self::MutOutput {
+            fn index_mut(self, slice: #slice_mut_name<'__soa_a, #generic_args>) -> Self::MutOutput {
                 slice
             }
         }
 
 
         // RangeInclusive<usize>
-        impl<'a> ::soa_derive::SoAIndex<#slice_name<'a>> for ::std::ops::RangeInclusive<usize> {
-            type RefOutput = #slice_name<'a>;
+        impl<'__soa_a, #generic_params> ::soa_derive::SoAIndex<#slice_name<'__soa_a, #generic_args>> for ::core::ops::RangeInclusive<usize> where #where_predicates {
+            type RefOutput = #slice_name<'__soa_a, #generic_args>;
 
             #[inline]
-            fn get(self, slice: #slice_name<'a>) -> Option<Self::RefOutput> {
+            fn get(self, slice: #slice_name<'__soa_a, #generic_args>) -> Option<Self::RefOutput> {
                 if *self.end() == usize::MAX {
                     None
                 } else {
@@ -586,21 +587,21 @@ pub fn derive(input: &Input) -> TokenStream {
             }
 
             #[inline]
-            unsafe fn get_unchecked(self, slice: #slice_name<'a>) -> Self::RefOutput {
+            unsafe fn get_unchecked(self, slice: #slice_name<'__soa_a, #generic_args>) -> Self::RefOutput {
                 ::soa_derive::SoAIndex::get_unchecked(*self.start()..self.end() + 1, slice)
             }
 
             #[inline]
-            fn index(self, slice: #slice_name<'a>) -> Self::RefOutput {
+            fn index(self, slice: #slice_name<'__soa_a, #generic_args>) -> Self::RefOutput {
                 ::soa_derive::SoAIndex::index(*self.start()..self.end() + 1, slice)
             }
         }
 
-        impl<'a> ::soa_derive::SoAIndexMut<#slice_mut_name<'a>> for ::std::ops::RangeInclusive<usize> {
-            type MutOutput = #slice_mut_name<'a>;
+        impl<'__soa_a, #generic_params> ::soa_derive::SoAIndexMut<#slice_mut_name<'__soa_a, #generic_args>> for ::core::ops::RangeInclusive<usize> where #where_predicates {
+            type MutOutput = #slice_mut_name<'__soa_a, #generic_args>;
 
             #[inline]
-            fn get_mut(self, slice: #slice_mut_name<'a>) -> Option<Self::MutOutput> {
+            fn get_mut(self, slice: #slice_mut_name<'__soa_a, #generic_args>) -> Option<Self::MutOutput> {
                 if *self.end() == usize::MAX {
                     None
                 } else {
@@ -609,52 +610,52 @@ pub fn derive(input: &Input) -> TokenStream {
             }
 
             #[inline]
-            unsafe fn get_unchecked_mut(self, slice: #slice_mut_name<'a>) -> Self::MutOutput {
+            unsafe fn get_unchecked_mut(self, slice: #slice_mut_name<'__soa_a, #generic_args>) -> Self::MutOutput {
                 ::soa_derive::SoAIndexMut::get_unchecked_mut(*self.start()..self.end() + 1, slice)
             }
 
             #[inline]
-            fn index_mut(self, slice: #slice_mut_name<'a>) -> Self::MutOutput {
+            fn index_mut(self, slice: #slice_mut_name<'__soa_a, #generic_args>) -> Self::MutOutput {
                 ::soa_derive::SoAIndexMut::index_mut(*self.start()..self.end() + 1, slice)
             }
         }
 
 
         // RangeToInclusive<usize>
-        impl<'a> ::soa_derive::SoAIndex<#slice_name<'a>> for ::std::ops::RangeToInclusive<usize> {
-            type RefOutput = #slice_name<'a>;
+        impl<'__soa_a, #generic_params> ::soa_derive::SoAIndex<#slice_name<'__soa_a, #generic_args>> for ::core::ops::RangeToInclusive<usize> where #where_predicates {
+            type RefOutput = #slice_name<'__soa_a, #generic_args>;
 
             #[inline]
-            fn get(self, slice: #slice_name<'a>) -> Option<Self::RefOutput> {
+            fn get(self, slice: #slice_name<'__soa_a, #generic_args>) -> Option<Self::RefOutput> {
                 ::soa_derive::SoAIndex::get(0..=self.end, slice)
             }
 
             #[inline]
-            unsafe fn get_unchecked(self, slice: #slice_name<'a>) -> Self::RefOutput {
+            unsafe fn get_unchecked(self, slice: #slice_name<'__soa_a, #generic_args>) -> Self::RefOutput {
                 ::soa_derive::SoAIndex::get_unchecked(0..=self.end, slice)
             }
 
             #[inline]
-            fn index(self, slice: #slice_name<'a>) -> Self::RefOutput {
+            fn index(self, slice: #slice_name<'__soa_a, #generic_args>) -> Self::RefOutput {
                 ::soa_derive::SoAIndex::index(0..=self.end, slice)
             }
         }
 
-        impl<'a> ::soa_derive::SoAIndexMut<#slice_mut_name<'a>> for ::std::ops::RangeToInclusive<usize> {
-            type MutOutput = #slice_mut_name<'a>;
+        impl<'__soa_a, #generic_params> ::soa_derive::SoAIndexMut<#slice_mut_name<'__soa_a, #generic_args>> for ::core::ops::RangeToInclusive<usize> where #where_predicates {
+            type MutOutput = #slice_mut_name<'__soa_a, #generic_args>;
 
             #[inline]
-            fn get_mut(self, slice: #slice_mut_name<'a>) -> Option<Self::MutOutput> {
+            fn get_mut(self, slice: #slice_mut_name<'__soa_a, #generic_args>) -> Option<Self::MutOutput> {
                 ::soa_derive::SoAIndexMut::get_mut(0..=self.end, slice)
             }
 
             #[inline]
-            unsafe fn get_unchecked_mut(self, slice: #slice_mut_name<'a>) -> Self::MutOutput {
+            unsafe fn get_unchecked_mut(self, slice: #slice_mut_name<'__soa_a, #generic_args>) -> Self::MutOutput {
                 ::soa_derive::SoAIndexMut::get_unchecked_mut(0..=self.end, slice)
             }
 
             #[inline]
-            fn index_mut(self, slice: #slice_mut_name<'a>) -> Self::MutOutput {
+            fn index_mut(self, slice: #slice_mut_name<'__soa_a, #generic_args>) -> Self::MutOutput {
                 ::soa_derive::SoAIndexMut::index_mut(0..=self.end, slice)
             }
         }
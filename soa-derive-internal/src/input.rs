@@ -1,28 +1,76 @@
 use proc_macro2::{Span, TokenStream};
-use quote::quote;
+use quote::{quote, ToTokens};
 
 use syn::punctuated::Punctuated;
-use syn::{Attribute, Data, DeriveInput, Field, Path, Token, Visibility};
+use syn::{Attribute, Data, DeriveInput, Field, Fields, GenericParam, Member, Generics, Path, Token, Type, Visibility};
 use syn::{Meta, MetaList};
 
+use crate::names::{Names, NamesConfig};
+
 /// Representing the struct we are deriving
 pub struct Input {
     /// The input struct name
     pub name: syn::Ident,
     /// The list of fields in the struct
     pub fields: Vec<Field>,
-    /// Is field marked with `#[nested_soa]`
+    /// Is the input a tuple struct, with unnamed fields
+    pub is_tuple: bool,
+    /// Is field marked with `#[nested_soa]` or `#[soa(explode)]`
     pub field_is_nested: Vec<bool>,
+    /// The fields marked with `#[soa(skip)]`, which are not stored in the
+    /// generated types, together with the expression used to re-create them
+    pub skipped_fields: Vec<(Member, TokenStream)>,
     /// The struct overall visibility
     pub visibility: Visibility,
+    /// The generic parameters and where clause of the struct
+    pub generics: Generics,
+    /// The names of the generated types
+    pub names: Names,
+    /// How the `Vec` type stores the fields
+    pub storage: Storage,
+    /// Are the fields of the `Vec` type private, with accessors generated
+    /// instead? This is set by `#[soa(private_columns)]`
+    pub private_columns: bool,
+    /// How the `Vec` and slices types are serialized and deserialized
+    pub serde_layout: SerdeLayout,
     /// Additional attributes requested with `#[soa_attr(...)]` or
     /// `#[soa_derive()]`
     pub attrs: ExtraAttributes,
 }
 
+/// The different ways a `Vec` type can store its fields, selected with
+/// `#[soa(storage = "...")]`
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Storage {
+    /// Each field is stored in a separate `Vec`, which is the default
+    Vec,
+    /// All fields are stored in a single allocation, one after the other
+    SingleBuffer,
+}
+
+/// The different ways the `Vec` and slices types can be serialized with
+/// serde, selected with `#[soa(serde = "...")]`
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum SerdeLayout {
+    /// Serialize one sequence for each field, which is the default
+    Columns,
+    /// Serialize a sequence of the input struct
+    Rows,
+}
+
+#[allow(clippy::struct_excessive_bools)]
 pub struct ExtraAttributes {
     // did the user explicitly asked us to derive clone?
     pub derive_clone: bool,
+    // did the user asked us to derive Debug/PartialEq? This is only used with
+    // single buffer storage, where these traits are implemented manually for
+    // the Vec type
+    pub derive_debug: bool,
+    pub derive_partial_eq: bool,
+    // did the user asked us to derive Serialize/Deserialize? The
+    // corresponding attributes are added in `add_serde_attributes`
+    pub derive_serialize: bool,
+    pub derive_deserialize: bool,
 
     pub vec: Vec<Meta>,
     pub slice: Vec<Meta>,
@@ -31,12 +79,23 @@ pub struct ExtraAttributes {
     pub ref_mut: Vec<Meta>,
     pub ptr: Vec<Meta>,
     pub ptr_mut: Vec<Meta>,
+    // `#[serde(...)]` attributes of the input struct, forwarded to the
+    // references when deriving Serialize
+    pub serde: Vec<Meta>,
+    // attributes of the helper struct used to deserialize the Vec with the
+    // columns serde layout, this is empty unless the user asked us to derive
+    // Deserialize
+    pub deserialize: Vec<Meta>,
 }
 
 impl ExtraAttributes {
     fn new() -> ExtraAttributes {
         ExtraAttributes {
             derive_clone: false,
+            derive_debug: false,
+            derive_partial_eq: false,
+            derive_serialize: false,
+            derive_deserialize: false,
             vec: Vec::new(),
             slice: Vec::new(),
             slice_mut: Vec::new(),
@@ -44,13 +103,15 @@ impl ExtraAttributes {
             ref_mut: Vec::new(),
             ptr: Vec::new(),
             ptr_mut: Vec::new(),
+            serde: Vec::new(),
+            deserialize: Vec::new(),
         }
     }
 
     /// Add a single trait from `#[soa_derive]`
     fn add_derive(&mut self, ident: &proc_macro2::Ident) {
         let derive_only_vec = |ident| {
-            static EXCEPTIONS: &[&str] = &["Clone", "Deserialize", "Serialize"];
+            static EXCEPTIONS: &[&str] = &["Clone"];
             for exception in EXCEPTIONS {
                 if ident == exception {
                     return true;
@@ -59,12 +120,17 @@ impl ExtraAttributes {
             return false;
         };
 
-        let derive = Meta::List(MetaList {
-            path: Path::from(syn::Ident::new("derive", Span::call_site())),
-            delimiter: syn::MacroDelimiter::Paren(syn::token::Paren(Span::call_site())),
-            tokens: quote!{ #ident },
-        });
+        if ident == "Serialize" {
+            // the types deriving serde traits depend on the serde layout,
+            // see `add_serde_attributes`
+            self.derive_serialize = true;
+            return;
+        } else if ident == "Deserialize" {
+            self.derive_deserialize = true;
+            return;
+        }
 
+        let derive = derive_meta(ident);
         if !derive_only_vec(ident) {
             self.slice.push(derive.clone());
             self.slice_mut.push(derive.clone());
@@ -81,6 +147,88 @@ impl ExtraAttributes {
             self.derive_clone = true;
         }
     }
+
+    /// Add the attributes required to derive `Serialize` and `Deserialize`,
+    /// if the user asked for them.
+    ///
+    /// References always derive `Serialize`, using the name and the
+    /// `#[serde(...)]` attributes of the input struct so they produce exactly
+    /// the same output. With the columns layout, the `Vec` and slices derive
+    /// `Serialize` as well, using the name and the `#[serde(...)]` attributes
+    /// of the `Vec`; and the `Vec` is deserialized through a helper struct
+    /// which checks that all the columns have the same length. With the rows
+    /// layout, these implementations are generated manually.
+    fn add_serde_attributes(&mut self, name: &syn::Ident, vec_name: &syn::Ident, layout: SerdeLayout) -> syn::Result<()> {
+        let name = name.to_string();
+        let vec_name = vec_name.to_string();
+        let rename: Meta = syn::parse_quote! { serde(rename = #name) };
+        let rename_vec: Meta = syn::parse_quote! { serde(rename = #vec_name) };
+
+        let (mut struct_serde, struct_renamed) = serde_attributes(&self.serde)?;
+        if !struct_renamed {
+            struct_serde.push(rename);
+        }
+
+        // forward the `#[serde(...)]` attributes of the `Vec` to the slices
+        // and the deserialization helper, making sure they use the same name
+        // as the `Vec`
+        let (mut vec_serde, vec_renamed) = serde_attributes(&self.vec)?;
+        if !vec_renamed {
+            vec_serde.push(rename_vec);
+        }
+
+        if self.derive_serialize {
+            // derives must come before the `#[serde]` attributes given with
+            // `#[soa_attr]`
+            let derive = derive_meta(&syn::Ident::new("Serialize", Span::call_site()));
+            self.ref_.insert(0, derive.clone());
+            self.ref_.extend(struct_serde.iter().cloned());
+            self.ref_mut.insert(0, derive.clone());
+            self.ref_mut.extend(struct_serde);
+
+            if layout == SerdeLayout::Columns {
+                self.slice.insert(0, derive.clone());
+                self.slice.extend(vec_serde.iter().cloned());
+                self.slice_mut.insert(0, derive.clone());
+                self.slice_mut.extend(vec_serde.iter().cloned());
+                self.vec.insert(0, derive);
+            }
+        }
+
+        if self.derive_deserialize && layout == SerdeLayout::Columns {
+            self.deserialize.push(derive_meta(&syn::Ident::new("Deserialize", Span::call_site())));
+            self.deserialize.extend(vec_serde);
+        }
+
+        Ok(())
+    }
+}
+
+/// Get the `serde(...)` attributes in `metas`, and whether one of them
+/// renames the type
+fn serde_attributes(metas: &[Meta]) -> syn::Result<(Vec<Meta>, bool)> {
+    let mut serde = Vec::new();
+    let mut renamed = false;
+    for meta in metas {
+        if !meta.path().is_ident("serde") {
+            continue;
+        }
+
+        renamed |= meta.require_list()?.tokens.clone().into_iter().any(|token| {
+            matches!(token, proc_macro2::TokenTree::Ident(ident) if ident == "rename")
+        });
+        serde.push(meta.clone());
+    }
+    Ok((serde, renamed))
+}
+
+/// Create the `derive(#ident)` attribute
+fn derive_meta(ident: &proc_macro2::Ident) -> Meta {
+    Meta::List(MetaList {
+        path: Path::from(syn::Ident::new("derive", Span::call_site())),
+        delimiter: syn::MacroDelimiter::Paren(syn::token::Paren(Span::call_site())),
+        tokens: quote!{ #ident },
+    })
 }
 
 fn contains_nested_soa(attrs: &[Attribute]) -> bool {
@@ -92,99 +240,518 @@ fn contains_nested_soa(attrs: &[Attribute]) -> bool {
     return false;
 }
 
+/// Options given to a field with `#[soa(...)]`
+struct FieldOptions {
+    /// If the field is marked with `#[soa(skip)]`, the expression used to
+    /// re-create the field value: either `Default::default()` or a call to the
+    /// function given with `#[soa(skip, default = "path::to::function")]`.
+    skip: Option<TokenStream>,
+    /// Is the field marked with `#[soa(explode)]`
+    explode: bool,
+}
+
+fn field_options(field: &Field) -> syn::Result<FieldOptions> {
+    let mut skip = None;
+    let mut default = None;
+    let mut explode = None;
+    for attr in &field.attrs {
+        if attr.path().is_ident("soa") {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("skip") {
+                    skip = Some(attr);
+                } else if meta.path.is_ident("default") {
+                    let function: syn::LitStr = meta.value()?.parse()?;
+                    let function: syn::ExprPath = function.parse()?;
+                    default = Some((attr, quote! { #function() }));
+                } else if meta.path.is_ident("explode") {
+                    explode = Some(attr);
+                } else {
+                    return Err(meta.error("unknown #[soa] attribute on field, expected `skip`, `default` or `explode`"));
+                }
+                Ok(())
+            })?;
+        }
+    }
+
+    let skip = match (skip, default) {
+        (Some(_), Some((_, default))) => Some(default),
+        (Some(_), None) => Some(quote! { ::core::default::Default::default() }),
+        (None, Some((attr, _))) => return Err(syn::Error::new_spanned(
+            attr, "#[soa(default = ...)] can only be used together with #[soa(skip)]"
+        )),
+        (None, None) => None,
+    };
+
+    if let Some(attr) = explode {
+        if skip.is_some() {
+            return Err(syn::Error::new_spanned(
+                attr, "#[soa(explode)] can not be used together with #[soa(skip)]"
+            ));
+        }
+        check_explode_type(&field.ty)?;
+    }
+
+    Ok(FieldOptions {
+        skip,
+        explode: explode.is_some(),
+    })
+}
+
+/// The largest tuple supported by `#[soa(explode)]`, matching the tuples
+/// implementing `StructOfArray` in the main crate.
+const MAX_EXPLODED_TUPLE: usize = 12;
+
+/// Check that a field marked with `#[soa(explode)]` is a non-empty array or
+/// tuple.
+fn check_explode_type(ty: &Type) -> syn::Result<()> {
+    match ty {
+        Type::Array(array) => {
+            if let syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Int(len), .. }) = &array.len {
+                if len.base10_parse::<usize>()? == 0 {
+                    return Err(syn::Error::new_spanned(
+                        ty, "#[soa(explode)] can not be used on empty arrays"
+                    ));
+                }
+            }
+            Ok(())
+        }
+        Type::Tuple(tuple) => {
+            if tuple.elems.is_empty() || tuple.elems.len() > MAX_EXPLODED_TUPLE {
+                return Err(syn::Error::new_spanned(ty, format!(
+                    "#[soa(explode)] can only be used on tuples with 1 to {} elements", MAX_EXPLODED_TUPLE
+                )));
+            }
+            Ok(())
+        }
+        Type::Paren(paren) => check_explode_type(&paren.elem),
+        Type::Group(group) => check_explode_type(&group.elem),
+        _ => Err(syn::Error::new_spanned(
+            ty, "#[soa(explode)] can only be used on array or tuple fields"
+        )),
+    }
+}
+
+/// Check that a struct using `#[soa(storage = "single_buffer")]` can be
+/// stored in a single buffer. Since the `Vec` type only contains raw pointers
+/// in this case, `#[derive]` can not be used on it: this function removes the
+/// derives from the `Vec` attributes and records the ones we know how to
+/// implement manually.
+fn check_single_buffer(fields: &[Field], field_is_nested: &[bool], field_is_exploded: &[bool], attrs: &mut ExtraAttributes) -> syn::Result<()> {
+    for ((field, &is_nested), &is_exploded) in fields.iter().zip(field_is_nested).zip(field_is_exploded) {
+        if is_exploded {
+            return Err(syn::Error::new_spanned(
+                field, "#[soa(explode)] can not be used together with #[soa(storage = \"single_buffer\")]"
+            ));
+        } else if is_nested {
+            return Err(syn::Error::new_spanned(
+                field, "#[nested_soa] can not be used together with #[soa(storage = \"single_buffer\")]"
+            ));
+        }
+    }
+
+    for meta in attrs.vec.iter().chain(&attrs.deserialize) {
+        if !meta.path().is_ident("derive") {
+            continue;
+        }
+
+        let traits = meta.require_list()?.parse_args_with(Punctuated::<Path, Token![,]>::parse_terminated)?;
+        for path in traits {
+            if path.is_ident("Clone") {
+                attrs.derive_clone = true;
+            } else if path.is_ident("Debug") {
+                attrs.derive_debug = true;
+            } else if path.is_ident("PartialEq") {
+                attrs.derive_partial_eq = true;
+            } else {
+                return Err(syn::Error::new_spanned(&path, format!(
+                    "can not derive {} for a vector using #[soa(storage = \"single_buffer\")], \
+                    only Clone, Debug and PartialEq are supported",
+                    path.to_token_stream()
+                )));
+            }
+        }
+    }
+    attrs.vec.retain(|meta| !meta.path().is_ident("derive"));
+    attrs.deserialize.clear();
+
+    Ok(())
+}
+
 impl Input {
-    pub fn new(input: DeriveInput) -> Input {
+    pub fn new(input: DeriveInput) -> syn::Result<Input> {
         let mut fields = Vec::new();
         let mut field_is_nested = Vec::new();
+        let mut field_is_exploded = Vec::new();
+        let mut skipped_fields = Vec::new();
+        let is_tuple;
         match input.data {
             Data::Struct(s) => {
-                for field in s.fields.iter().cloned() {
-                    fields.push(field.clone());
-                    field_is_nested.push(contains_nested_soa(&field.attrs));
+                is_tuple = matches!(s.fields, Fields::Unnamed(_));
+                for (i, field) in s.fields.iter().enumerate() {
+                    let options = field_options(field)?;
+                    if let Some(default) = options.skip {
+                        if contains_nested_soa(&field.attrs) {
+                            return Err(syn::Error::new_spanned(
+                                field, "#[soa(skip)] can not be used together with #[nested_soa]"
+                            ));
+                        }
+                        let member = match &field.ident {
+                            Some(ident) => Member::Named(ident.clone()),
+                            None => Member::from(i),
+                        };
+                        skipped_fields.push((member, default));
+                    } else {
+                        if is_tuple && !skipped_fields.is_empty() {
+                            return Err(syn::Error::new_spanned(
+                                field, "#[soa(skip)] can only be used on the last fields of a tuple struct"
+                            ));
+                        }
+                        let is_nested = contains_nested_soa(&field.attrs);
+                        if options.explode && is_nested {
+                            return Err(syn::Error::new_spanned(
+                                field, "#[soa(explode)] can not be used together with #[nested_soa]"
+                            ));
+                        }
+                        fields.push(field.clone());
+                        // exploded fields are stored as nested struct of
+                        // arrays, using the implementations of the
+                        // `StructOfArray` family of traits for arrays and
+                        // tuples from the main crate
+                        field_is_nested.push(is_nested || options.explode);
+                        field_is_exploded.push(options.explode);
+                    }
                 }
-            }
-            _ => panic!("#[derive(StructOfArray)] only supports struct"),
-        };
 
-        assert!(!fields.is_empty(), "#[derive(StructOfArray)] only supports struct with fields");
+                if fields.is_empty() {
+                    return Err(syn::Error::new_spanned(
+                        &input.ident, "#[derive(StructOfArray)] only supports struct with fields"
+                    ));
+                }
+            }
+            Data::Union(u) => {
+                return Err(syn::Error::new_spanned(
+                    u.union_token, "#[derive(StructOfArray)] only supports struct and enum"
+                ));
+            }
+            Data::Enum(e) => {
+                return Err(syn::Error::new_spanned(
+                    e.enum_token, "enums should use enums::derive instead of Input"
+                ));
+            }
+        }
 
         let mut extra_attrs = ExtraAttributes::new();
+        let mut names = NamesConfig::default();
+        let mut storage = Storage::Vec;
+        // path of the `private_columns` attribute, used for error messages
+        let mut private_columns = None;
+        let mut serde_layout = SerdeLayout::Columns;
 
         for attr in input.attrs {
             if attr.path().is_ident("soa_derive") {
                 attr.parse_nested_meta(|meta| {
                     match meta.path.get_ident() {
                         Some(ident) => {
-                            assert!(ident != "Copy", "can not derive Copy for SoA vectors");
+                            if ident == "Copy" {
+                                return Err(meta.error("can not derive Copy for SoA vectors (slices, references and pointers are always Copy)"));
+                            }
                             if ident != "Default" {
                                 // ignore as Default is already derived for SoA vectors, slices and mut slices
                                 extra_attrs.add_derive(ident);
                             }
                         }
                         None => {
-                            panic!(
-                                "expected #[soa_derive(Traits, To, Derive)], got #[{}]",
-                                quote!(attr)
-                            );
+                            return Err(meta.error("expected #[soa_derive(Traits, To, Derive)]"));
                         }
                     }
                     Ok(())
-                }).expect("failed to parse soa_derive");
+                })?;
+            }
+
+            if attr.path().is_ident("soa") {
+                attr.parse_nested_meta(|meta| {
+                    if names.parse_meta(&meta)? {
+                        return Ok(());
+                    }
+                    if meta.path.is_ident("storage") {
+                        let value = meta.value()?.parse::<syn::LitStr>()?;
+                        storage = match value.value().as_str() {
+                            "vec" => Storage::Vec,
+                            "single_buffer" => Storage::SingleBuffer,
+                            _ => return Err(syn::Error::new_spanned(
+                                value, "unknown storage, expected `vec` or `single_buffer`"
+                            )),
+                        };
+                        return Ok(());
+                    }
+                    if meta.path.is_ident("private_columns") {
+                        private_columns = Some(meta.path.clone());
+                        return Ok(());
+                    }
+                    if meta.path.is_ident("serde") {
+                        let value = meta.value()?.parse::<syn::LitStr>()?;
+                        serde_layout = match value.value().as_str() {
+                            "columns" => SerdeLayout::Columns,
+                            "rows" => SerdeLayout::Rows,
+                            _ => return Err(syn::Error::new_spanned(
+                                value, "unknown serde layout, expected `columns` or `rows`"
+                            )),
+                        };
+                        return Ok(());
+                    }
+                    Err(meta.error(
+                        "unknown #[soa] attribute on struct, expected one of `prefix`, `suffix`, \
+                        `vec`, `array_vec`, `slice`, `slice_mut`, `ref`, `ref_mut`, `ptr`, `ptr_mut`, \
+                        `iter`, `iter_mut`, `storage`, `private_columns` or `serde`"
+                    ))
+                })?;
+            }
+
+            if attr.path().is_ident("serde") {
+                extra_attrs.serde.push(attr.meta.clone());
             }
 
             if attr.path().is_ident("soa_attr") {
-                let nested = attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
-                    .expect("expected attribute like #[soa_attr(<Type>, <attr>)]");
-                assert!(nested.len() == 2, "expected attribute like #[soa_attr(<Type>, <attr>)]");
+                let nested = attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+                if nested.len() != 2 {
+                    return Err(syn::Error::new_spanned(
+                        &attr, "expected attribute like #[soa_attr(<Type>, <attr>)]"
+                    ));
+                }
 
                 let soa_type = nested.first().expect("should have 2 elements");
                 let attr = nested.last().expect("should have 2 elements").clone();
 
                 match soa_type.path().get_ident() {
-                    Some(ident) => {
-                        if ident == "Vec" {
-                            extra_attrs.vec.push(attr);
-                        } else if ident == "Slice" {
-                            extra_attrs.slice.push(attr);
-                        } else if ident == "SliceMut" {
-                            extra_attrs.slice_mut.push(attr);
-                        } else if ident == "Ref" {
-                            extra_attrs.ref_.push(attr);
-                        } else if ident == "RefMut" {
-                            extra_attrs.ref_mut.push(attr);
-                        } else if ident == "Ptr" {
-                            extra_attrs.ptr.push(attr);
-                        } else if ident == "PtrMut" {
-                            extra_attrs.ptr_mut.push(attr);
-                        } else {
-                            panic!("expected one of the SoA type, got {}", quote!(#soa_type));
-                        }
-                    }
-                    None => panic!("expected one of the SoA type, got {}", quote!(#soa_type))
+                    Some(ident) if ident == "Vec" => extra_attrs.vec.push(attr),
+                    Some(ident) if ident == "Slice" => extra_attrs.slice.push(attr),
+                    Some(ident) if ident == "SliceMut" => extra_attrs.slice_mut.push(attr),
+                    Some(ident) if ident == "Ref" => extra_attrs.ref_.push(attr),
+                    Some(ident) if ident == "RefMut" => extra_attrs.ref_mut.push(attr),
+                    Some(ident) if ident == "Ptr" => extra_attrs.ptr.push(attr),
+                    Some(ident) if ident == "PtrMut" => extra_attrs.ptr_mut.push(attr),
+                    _ => return Err(syn::Error::new_spanned(
+                        soa_type, "expected one of the SoA type: Vec, Slice, SliceMut, Ref, RefMut, Ptr or PtrMut"
+                    )),
                 }
             }
         }
 
-        Input {
+        if let (Storage::SingleBuffer, Some(private_columns)) = (storage, &private_columns) {
+            return Err(syn::Error::new_spanned(
+                private_columns, "#[soa(private_columns)] can not be used together with #[soa(storage = \"single_buffer\")], \
+                which never exposes the columns"
+            ));
+        }
+
+        let names = names.build(&input.ident);
+        extra_attrs.add_serde_attributes(&input.ident, &names.vec, serde_layout)?;
+
+        if storage == Storage::SingleBuffer {
+            check_single_buffer(&fields, &field_is_nested, &field_is_exploded, &mut extra_attrs)?;
+        }
+
+        Ok(Input {
             name: input.ident,
             fields: fields,
+            is_tuple,
             visibility: input.vis,
+            generics: input.generics,
+            names: names,
             attrs: extra_attrs,
+            storage,
+            private_columns: private_columns.is_some(),
+            serde_layout,
             field_is_nested,
+            skipped_fields,
+        })
+    }
+
+    /// Get the generic parameters of the input struct with their bounds but
+    /// without default values, each followed by a comma. This is intended to
+    /// be used as `impl<'a, #generic_params>`.
+    pub(crate) fn generic_params(&self) -> TokenStream {
+        let params = self.generics.params.iter().map(|param| {
+            match param.clone() {
+                GenericParam::Type(mut param) => {
+                    param.eq_token = None;
+                    param.default = None;
+                    quote! { #param }
+                }
+                GenericParam::Const(mut param) => {
+                    param.eq_token = None;
+                    param.default = None;
+                    quote! { #param }
+                }
+                GenericParam::Lifetime(param) => quote! { #param },
+            }
+        });
+        quote! { #(#params,)* }
+    }
+
+    /// Get the generic arguments corresponding to the generic parameters of
+    /// the input struct, each followed by a comma. This is intended to be used
+    /// as `#vec_name<#generic_args>` or `#slice_name<'a, #generic_args>`.
+    pub(crate) fn generic_args(&self) -> TokenStream {
+        let args = self.generics.params.iter().map(|param| {
+            match param {
+                GenericParam::Type(param) => {
+                    let ident = &param.ident;
+                    quote! { #ident }
+                }
+                GenericParam::Const(param) => {
+                    let ident = &param.ident;
+                    quote! { #ident }
+                }
+                GenericParam::Lifetime(param) => {
+                    let lifetime = &param.lifetime;
+                    quote! { #lifetime }
+                }
+            }
+        });
+        quote! { #(#args,)* }
+    }
+
+    /// Get the predicates in the where clause of the input struct, each
+    /// followed by a comma. This is intended to be used as
+    /// `where #where_predicates`.
+    pub(crate) fn where_predicates(&self) -> TokenStream {
+        match &self.generics.where_clause {
+            Some(where_clause) => {
+                let predicates = where_clause.predicates.iter();
+                quote! { #(#predicates,)* }
+            }
+            None => quote! {},
+        }
+    }
+
+    /// Get where predicates requiring all the generic type and lifetime
+    /// parameters of the input struct to outlive the given `lifetime`, each
+    /// followed by a comma.
+    pub(crate) fn outlive_predicates(&self, lifetime: &TokenStream) -> TokenStream {
+        let predicates = self.generics.params.iter().filter_map(|param| {
+            match param {
+                GenericParam::Type(param) => {
+                    let ident = &param.ident;
+                    Some(quote! { #ident: #lifetime })
+                }
+                GenericParam::Lifetime(param) => {
+                    let param = &param.lifetime;
+                    Some(quote! { #param: #lifetime })
+                }
+                GenericParam::Const(_) => None,
+            }
+        });
+        quote! { #(#predicates,)* }
+    }
+
+    /// Get the outlive predicates (see `outlive_predicates`) required in the
+    /// definition of generated types with a `lifetime` when the struct
+    /// contains nested struct of arrays. The types of nested fields are
+    /// projections such as `<Nested<T> as SoAIter<'a>>::Ref`, which are only
+    /// well-formed if `T: 'a`. Other structs don't need these predicates,
+    /// which would prevent using higher-ranked bounds like
+    /// `for<'b> FooRef<'b, T>: Ord` with non-`'static` parameters.
+    pub(crate) fn nested_outlive_predicates(&self, lifetime: &TokenStream) -> TokenStream {
+        if self.field_is_nested.iter().any(|&is_nested| is_nested) {
+            self.outlive_predicates(lifetime)
+        } else {
+            quote! {}
+        }
+    }
+
+    /// Get the names of all the fields in the struct, as they should be used
+    /// to access the field (`self.#name`) or to create the struct
+    /// (`Struct { #name: value }`). For tuple struct, this is the index of
+    /// the field.
+    pub(crate) fn fields_names(&self) -> Vec<Member> {
+        self.fields.iter().enumerate().map(|(i, field)| {
+            match &field.ident {
+                Some(ident) => Member::Named(ident.clone()),
+                None => Member::from(i),
+            }
+        }).collect()
+    }
+
+    /// Generate the body of a generated struct definition, including the
+    /// where clause. The generated struct uses named fields if the input
+    /// struct does, and is a tuple struct otherwise. `fields_docs` contains
+    /// the documentation of each field, and `fields_types` their types.
+    /// Types with a lifetime must pass it as `lifetime`, see
+    /// `nested_outlive_predicates`.
+    pub(crate) fn struct_body(&self, lifetime: Option<&TokenStream>, fields_docs: &[TokenStream], fields_types: &[TokenStream]) -> TokenStream {
+        self.struct_body_with_visibility(lifetime, fields_docs, fields_types, &quote! { pub })
+    }
+
+    /// Same as `struct_body`, using `fields_visibility` for all the fields
+    /// instead of making them public.
+    pub(crate) fn struct_body_with_visibility(
+        &self,
+        lifetime: Option<&TokenStream>,
+        fields_docs: &[TokenStream],
+        fields_types: &[TokenStream],
+        fields_visibility: &TokenStream,
+    ) -> TokenStream {
+        let mut where_predicates = self.where_predicates();
+        if let Some(lifetime) = lifetime {
+            let outlive = self.nested_outlive_predicates(lifetime);
+            where_predicates = quote! { #outlive #where_predicates };
+        }
+        if self.is_tuple {
+            quote! {
+                ( #( #fields_docs #fields_visibility #fields_types, )* ) where #where_predicates;
+            }
+        } else {
+            let fields_names = self.fields_names();
+            quote! {
+                where #where_predicates {
+                    #( #fields_docs #fields_visibility #fields_names: #fields_types, )*
+                }
+            }
+        }
+    }
+
+    /// Get the `#[serde(...)]` attributes of each field when deriving
+    /// `Serialize`, which are forwarded to the fields of the references.
+    pub(crate) fn fields_serde_attributes(&self) -> Vec<Vec<&Attribute>> {
+        self.fields.iter().map(|field| {
+            if self.attrs.derive_serialize {
+                field.attrs.iter().filter(|attr| attr.path().is_ident("serde")).collect()
+            } else {
+                Vec::new()
+            }
+        }).collect()
+    }
+
+    /// Get the initialization of the fields marked with `#[soa(skip)]`, each
+    /// followed by a comma. This is intended to be used when creating an
+    /// instance of the input struct as `#name { ..., #skipped_fields_init }`.
+    pub(crate) fn skipped_fields_init(&self) -> TokenStream {
+        let init = self.skipped_fields.iter().map(|(member, default)| quote! { #member: #default });
+        quote! { #(#init,)* }
+    }
+
+    /// Get the code dropping the fields marked with `#[soa(skip)]` in
+    /// `value`. This must be used when the other fields have been moved out
+    /// of `value` with `ptr::read`, right before calling `mem::forget(value)`.
+    pub(crate) fn drop_skipped_fields(&self, value: &TokenStream) -> TokenStream {
+        let members = self.skipped_fields.iter().map(|(member, _)| member);
+        quote! {
+            #( ::core::mem::drop(unsafe { ::core::ptr::read(&#value.#members) }); )*
         }
     }
 
     /// Map over all fields in the struct, calling the first function if the
     /// field is a nested struct of array, the second function otherwise
     pub(crate) fn map_fields_nested_or<'a, A, B>(&'a self, nested: A, not_nested: B) -> impl TokenStreamIterator + 'a
-        where A: Fn(&syn::Ident, &syn::Type) -> TokenStream + 'a,
-              B: Fn(&syn::Ident, &syn::Type) -> TokenStream + 'a,
+        where A: Fn(&Member, &syn::Type) -> TokenStream + 'a,
+              B: Fn(&Member, &syn::Type) -> TokenStream + 'a,
     {
-        self.fields.iter().zip(self.field_is_nested.iter()).map(move |(field, &is_nested)| {
+        self.fields_names().into_iter().zip(&self.fields).zip(&self.field_is_nested).map(move |((name, field), &is_nested)| {
             if is_nested {
-                nested(field.ident.as_ref().expect("missing ident"), &field.ty)
+                nested(&name, &field.ty)
             } else {
-                not_nested(field.ident.as_ref().expect("missing ident"), &field.ty)
+                not_nested(&name, &field.ty)
             }
         })
     }
@@ -1,240 +1,402 @@
 use proc_macro2::TokenStream;
 use quote::quote;
 
-use crate::input::{Input, TokenStreamIterator};
-use crate::names;
+use crate::input::Input;
 
 pub fn derive(input: &Input) -> TokenStream {
     let name = &input.name;
     let visibility = &input.visibility;
-    let vec_name = names::vec_name(&input.name);
-    let slice_name = names::slice_name(name);
-    let slice_mut_name = names::slice_mut_name(&input.name);
-    let ref_name = names::ref_name(&input.name);
-    let ref_mut_name = names::ref_mut_name(&input.name);
-    let iter_name = names::iter_name(&input.name);
-    let iter_mut_name = names::iter_mut_name(&input.name);
+    let vec_name = &input.names.vec;
+    let slice_name = &input.names.slice;
+    let slice_mut_name = &input.names.slice_mut;
+    let ref_name = &input.names.ref_;
+    let ref_mut_name = &input.names.ref_mut;
+    let iter_name = &input.names.iter;
+    let iter_mut_name = &input.names.iter_mut;
+    let ptr_name = &input.names.ptr;
+    let ptr_mut_name = &input.names.ptr_mut;
+
+    let generic_params = input.generic_params();
+    let generic_args = input.generic_args();
+    let where_predicates = input.where_predicates();
+    let outlive_a = input.outlive_predicates(&quote! { '__soa_a });
+    let nested_outlive_a = input.nested_outlive_predicates(&quote! { '__soa_a });
 
     let doc_url = format!("[`{0}`](struct.{0}.html)", name);
     let ref_doc_url = format!("[`{0}`](struct.{0}.html)", ref_name);
     let ref_mut_doc_url = format!("[`{0}`](struct.{0}.html)", ref_mut_name);
 
-    let fields_names = &input.fields.iter()
-        .map(|field| field.ident.clone().unwrap())
-        .collect::<Vec<_>>();
+    let fields_names = &input.fields_names();
 
     let fields_types = &input.fields.iter()
         .map(|field| field.ty.clone())
         .collect::<Vec<_>>();
 
-    let iter_type = input.map_fields_nested_or(
-        |_, field_type| quote! { <#field_type as soa_derive::SoAIter<'a>>::Iter },
-        |_, field_type| quote! { ::std::slice::Iter<'a, #field_type> },
-    ).concat_by(
-        |seq, next| { quote! { ::std::iter::Zip<#seq, #next> } }
-    );
-
-    let iter_mut_type = input.map_fields_nested_or(
-        |_, field_type| quote! { <#field_type as soa_derive::SoAIter<'a>>::IterMut },
-        |_, field_type| quote! { ::std::slice::IterMut<'a, #field_type> },
-    ).concat_by(
-        |seq, next| { quote! { ::std::iter::Zip<#seq, #next> } }
-    );
-
-    let create_into_iter = input.map_fields_nested_or(
-        |ident, _| quote! { self.#ident.into_iter() },
-        |ident, _| quote! { self.#ident.iter() },
-    ).concat_by(
-        |seq, next| { quote! { #seq.zip(#next) } }
-    );
-
-    let create_mut_into_iter = input.map_fields_nested_or(
-        |ident, _| quote! { self.#ident.into_iter() },
-        |ident, _| quote! { self.#ident.iter_mut() },
-    ).concat_by(
-        |seq, next| { quote! { #seq.zip(#next) } }
-    );
-
-    let iter_pat = fields_names.iter().fold(None, |seq, ident| {
-        if let Some(seq) = seq {
-            Some(quote! { (#seq, #ident) })
-        } else {
-            Some(quote!{ #ident })
-        }
-    }).expect("should be Some");
-
-    let create_iter = fields_names.iter().fold(None, |seq, ident| {
-        if let Some(seq) = seq {
-            Some(quote! { #seq.zip(self.#ident.iter()) })
-        } else {
-            Some(quote! { self.#ident.iter() })
-        }
-    }).expect("should be Some");
+    let deref_ptr = input.map_fields_nested_or(
+        |ident, _| quote! { ptr.#ident.as_ref_unchecked() },
+        |ident, _| quote! { &*ptr.#ident },
+    ).collect::<Vec<_>>();
 
-    let create_iter_mut = fields_names.iter().fold(None, |seq, ident| {
-        if let Some(seq) = seq {
-            Some(quote! { #seq.zip(self.#ident.iter_mut()) })
-        } else {
-            Some(quote! { self.#ident.iter_mut() })
-        }
-    }).expect("should be Some");
+    let deref_ptr_mut = input.map_fields_nested_or(
+        |ident, _| quote! { ptr.#ident.as_mut_unchecked() },
+        |ident, _| quote! { &mut *ptr.#ident },
+    ).collect::<Vec<_>>();
 
     let generated = quote! {
         /// Iterator over
         #[doc = #doc_url]
+        /// , yielding
+        #[doc = #ref_doc_url]
+        /// . This is similar to [`std::slice::Iter`](https://doc.rust-lang.org/std/slice/struct.Iter.html).
         #[allow(missing_debug_implementations)]
-        #visibility struct #iter_name<'a>(#iter_type);
+        #visibility struct #iter_name<'__soa_a, #generic_params> where #nested_outlive_a #where_predicates {
+            // pointer to the start of the slice used to create this iterator
+            data: #ptr_name<#generic_args>,
+            // range of elements which have not been yielded yet
+            start: usize,
+            end: usize,
+            marker: ::core::marker::PhantomData<#slice_name<'__soa_a, #generic_args>>,
+        }
 
-        impl<'a> Iterator for #iter_name<'a> {
-            type Item = #ref_name<'a>;
+        impl<'__soa_a, #generic_params> #iter_name<'__soa_a, #generic_args> where #where_predicates {
+            fn new(slice: #slice_name<'__soa_a, #generic_args>) -> Self {
+                #iter_name {
+                    data: slice.as_ptr(),
+                    start: 0,
+                    end: slice.len(),
+                    marker: ::core::marker::PhantomData,
+                }
+            }
 
+            /// Get the element at `index`, which must be in the range of
+            /// elements of the slice used to create this iterator.
             #[inline]
-            fn next(&mut self) -> Option<#ref_name<'a>> {
-                self.0.next().and_then(|#iter_pat|
-                    Some(#ref_name{
-                        #(#fields_names,)*
-                    })
-                )
+            unsafe fn item(&self, index: usize) -> #ref_name<'__soa_a, #generic_args> {
+                let ptr = self.data.add(index);
+                #ref_name {
+                    #(#fields_names: #deref_ptr,)*
+                }
+            }
+
+            /// Get the remaining elements of this iterator as a slice,
+            /// similar to [`std::slice::Iter::as_slice()`](https://doc.rust-lang.org/std/slice/struct.Iter.html#method.as_slice).
+            pub fn as_slice(&self) -> #slice_name<'__soa_a, #generic_args> {
+                unsafe {
+                    #slice_name::from_raw_parts(self.data.add(self.start), self.end - self.start)
+                }
+            }
+        }
+
+        impl<'__soa_a, #generic_params> Clone for #iter_name<'__soa_a, #generic_args> where #where_predicates {
+            fn clone(&self) -> Self {
+                #iter_name {
+                    data: self.data,
+                    start: self.start,
+                    end: self.end,
+                    marker: ::core::marker::PhantomData,
+                }
+            }
+        }
+
+        impl<'__soa_a, #generic_params> Iterator for #iter_name<'__soa_a, #generic_args> where #where_predicates {
+            type Item = #ref_name<'__soa_a, #generic_args>;
+
+            #[inline]
+            fn next(&mut self) -> Option<#ref_name<'__soa_a, #generic_args>> {
+                if self.start == self.end {
+                    None
+                } else {
+                    let item = unsafe { self.item(self.start) };
+                    self.start += 1;
+                    Some(item)
+                }
             }
 
             #[inline]
             fn size_hint(&self) -> (usize, Option<usize>) {
-                self.0.size_hint()
+                let len = self.end - self.start;
+                (len, Some(len))
+            }
+
+            #[inline]
+            fn nth(&mut self, n: usize) -> Option<#ref_name<'__soa_a, #generic_args>> {
+                if n >= self.end - self.start {
+                    self.start = self.end;
+                    None
+                } else {
+                    self.start += n;
+                    self.next()
+                }
+            }
+
+            #[inline]
+            fn count(self) -> usize {
+                self.end - self.start
+            }
+
+            #[inline]
+            fn last(mut self) -> Option<#ref_name<'__soa_a, #generic_args>> {
+                self.next_back()
             }
         }
 
-        impl<'a> DoubleEndedIterator for #iter_name<'a> {
+        impl<'__soa_a, #generic_params> DoubleEndedIterator for #iter_name<'__soa_a, #generic_args> where #where_predicates {
+            #[inline]
+            fn next_back(&mut self) -> Option<#ref_name<'__soa_a, #generic_args>> {
+                if self.start == self.end {
+                    None
+                } else {
+                    self.end -= 1;
+                    Some(unsafe { self.item(self.end) })
+                }
+            }
+
             #[inline]
-            fn next_back(&mut self) -> Option<#ref_name<'a>> {
-                self.0.next_back().and_then(|#iter_pat|
-                    Some(#ref_name{
-                        #(#fields_names,)*
-                    })
-                )
+            fn nth_back(&mut self, n: usize) -> Option<#ref_name<'__soa_a, #generic_args>> {
+                if n >= self.end - self.start {
+                    self.end = self.start;
+                    None
+                } else {
+                    self.end -= n;
+                    self.next_back()
+                }
             }
         }
 
-        impl<'a> ExactSizeIterator for #iter_name<'a> {
+        impl<'__soa_a, #generic_params> ExactSizeIterator for #iter_name<'__soa_a, #generic_args> where #where_predicates {
+            #[inline]
             fn len(&self) -> usize {
-                self.0.len()
+                self.end - self.start
             }
         }
 
-        impl #vec_name {
+        impl<'__soa_a, #generic_params> ::core::iter::FusedIterator for #iter_name<'__soa_a, #generic_args> where #where_predicates {}
+
+        // the iterator behaves like the slice it was created from
+        unsafe impl<'__soa_a, #generic_params> Send for #iter_name<'__soa_a, #generic_args> where #slice_name<'__soa_a, #generic_args>: Send, #where_predicates {}
+        unsafe impl<'__soa_a, #generic_params> Sync for #iter_name<'__soa_a, #generic_args> where #slice_name<'__soa_a, #generic_args>: Sync, #where_predicates {}
+
+        impl<#generic_params> #vec_name<#generic_args> where #where_predicates {
             /// Get an iterator over the
             #[doc = #ref_doc_url]
             /// in this vector
-            pub fn iter(&self) -> #iter_name {
+            pub fn iter(&self) -> #iter_name<'_, #generic_args> {
                 self.as_slice().into_iter()
             }
         }
 
-        impl<'a> #slice_name<'a> {
+        impl<'__soa_a, #generic_params> #slice_name<'__soa_a, #generic_args> where #where_predicates {
             /// Get an iterator over the
             #[doc = #ref_doc_url]
             /// in this slice.
-            pub fn iter(&self) -> #iter_name {
-                #iter_name(#create_iter)
+            pub fn iter(&self) -> #iter_name<'_, #generic_args> {
+                #iter_name::new(self.reborrow())
             }
 
             /// Get an iterator over the
             #[doc = #ref_doc_url]
             /// in this slice.
-            pub fn into_iter(self) -> #iter_name<'a> {
-                #iter_name(#create_into_iter)
+            pub fn into_iter(self) -> #iter_name<'__soa_a, #generic_args> {
+                #iter_name::new(self)
             }
         }
 
         /// Mutable iterator over
         #[doc = #doc_url]
+        /// , yielding
+        #[doc = #ref_mut_doc_url]
+        /// . This is similar to [`std::slice::IterMut`](https://doc.rust-lang.org/std/slice/struct.IterMut.html).
         #[allow(missing_debug_implementations)]
-        #visibility struct #iter_mut_name<'a>(#iter_mut_type);
+        #visibility struct #iter_mut_name<'__soa_a, #generic_params> where #nested_outlive_a #where_predicates {
+            // pointer to the start of the slice used to create this iterator
+            data: #ptr_mut_name<#generic_args>,
+            // range of elements which have not been yielded yet
+            start: usize,
+            end: usize,
+            marker: ::core::marker::PhantomData<#slice_mut_name<'__soa_a, #generic_args>>,
+        }
 
-        impl<'a> Iterator for #iter_mut_name<'a> {
-            type Item = #ref_mut_name<'a>;
+        impl<'__soa_a, #generic_params> #iter_mut_name<'__soa_a, #generic_args> where #where_predicates {
+            fn new(mut slice: #slice_mut_name<'__soa_a, #generic_args>) -> Self {
+                #iter_mut_name {
+                    data: slice.as_mut_ptr(),
+                    start: 0,
+                    end: slice.len(),
+                    marker: ::core::marker::PhantomData,
+                }
+            }
 
+            /// Get the element at `index`, which must be in the range of
+            /// elements which have not been yielded yet.
             #[inline]
-            fn next(&mut self) -> Option<#ref_mut_name<'a>> {
-                self.0.next().and_then(|#iter_pat|
-                    Some(#ref_mut_name{
-                        #(#fields_names,)*
-                    })
-                )
+            unsafe fn item(&mut self, index: usize) -> #ref_mut_name<'__soa_a, #generic_args> {
+                let ptr = self.data.add(index);
+                #ref_mut_name {
+                    #(#fields_names: #deref_ptr_mut,)*
+                }
+            }
+
+            /// Get the remaining elements of this iterator as a slice,
+            /// similar to [`std::slice::IterMut::as_slice()`](https://doc.rust-lang.org/std/slice/struct.IterMut.html#method.as_slice).
+            pub fn as_slice(&self) -> #slice_name<'_, #generic_args> {
+                unsafe {
+                    #slice_name::from_raw_parts(self.data.as_ptr().add(self.start), self.end - self.start)
+                }
+            }
+
+            /// Convert this iterator to a mutable slice containing the
+            /// remaining elements, similar to [`std::slice::IterMut::into_slice()`](https://doc.rust-lang.org/std/slice/struct.IterMut.html#method.into_slice).
+            pub fn into_slice(self) -> #slice_mut_name<'__soa_a, #generic_args> {
+                unsafe {
+                    #slice_mut_name::from_raw_parts_mut(self.data.add(self.start), self.end - self.start)
+                }
+            }
+        }
+
+        impl<'__soa_a, #generic_params> Iterator for #iter_mut_name<'__soa_a, #generic_args> where #where_predicates {
+            type Item = #ref_mut_name<'__soa_a, #generic_args>;
+
+            #[inline]
+            fn next(&mut self) -> Option<#ref_mut_name<'__soa_a, #generic_args>> {
+                if self.start == self.end {
+                    None
+                } else {
+                    let item = unsafe { self.item(self.start) };
+                    self.start += 1;
+                    Some(item)
+                }
             }
 
             #[inline]
             fn size_hint(&self) -> (usize, Option<usize>) {
-                self.0.size_hint()
+                let len = self.end - self.start;
+                (len, Some(len))
+            }
+
+            #[inline]
+            fn nth(&mut self, n: usize) -> Option<#ref_mut_name<'__soa_a, #generic_args>> {
+                if n >= self.end - self.start {
+                    self.start = self.end;
+                    None
+                } else {
+                    self.start += n;
+                    self.next()
+                }
+            }
+
+            #[inline]
+            fn count(self) -> usize {
+                self.end - self.start
+            }
+
+            #[inline]
+            fn last(mut self) -> Option<#ref_mut_name<'__soa_a, #generic_args>> {
+                self.next_back()
             }
         }
 
-        impl<'a> DoubleEndedIterator for #iter_mut_name<'a> {
+        impl<'__soa_a, #generic_params> DoubleEndedIterator for #iter_mut_name<'__soa_a, #generic_args> where #where_predicates {
+            #[inline]
+            fn next_back(&mut self) -> Option<#ref_mut_name<'__soa_a, #generic_args>> {
+                if self.start == self.end {
+                    None
+                } else {
+                    self.end -= 1;
+                    Some(unsafe { self.item(self.end) })
+                }
+            }
+
             #[inline]
-            fn next_back(&mut self) -> Option<#ref_mut_name<'a>> {
-                self.0.next_back().and_then(|#iter_pat|
-                    Some(#ref_mut_name{
-                        #(#fields_names,)*
-                    })
-                )
+            fn nth_back(&mut self, n: usize) -> Option<#ref_mut_name<'__soa_a, #generic_args>> {
+                if n >= self.end - self.start {
+                    self.end = self.start;
+                    None
+                } else {
+                    self.end -= n;
+                    self.next_back()
+                }
             }
         }
-        impl<'a> ExactSizeIterator for #iter_mut_name<'a> {
+
+        impl<'__soa_a, #generic_params> ExactSizeIterator for #iter_mut_name<'__soa_a, #generic_args> where #where_predicates {
+            #[inline]
             fn len(&self) -> usize {
-                self.0.len()
+                self.end - self.start
             }
         }
 
-        impl #vec_name {
+        impl<'__soa_a, #generic_params> ::core::iter::FusedIterator for #iter_mut_name<'__soa_a, #generic_args> where #where_predicates {}
+
+        // the iterator behaves like the mutable slice it was created from
+        unsafe impl<'__soa_a, #generic_params> Send for #iter_mut_name<'__soa_a, #generic_args> where #slice_mut_name<'__soa_a, #generic_args>: Send, #where_predicates {}
+        unsafe impl<'__soa_a, #generic_params> Sync for #iter_mut_name<'__soa_a, #generic_args> where #slice_mut_name<'__soa_a, #generic_args>: Sync, #where_predicates {}
+
+        impl<#generic_params> #vec_name<#generic_args> where #where_predicates {
             /// Get a mutable iterator over the
             #[doc = #ref_mut_doc_url]
             /// in this vector
-            pub fn iter_mut(&mut self) -> #iter_mut_name {
+            pub fn iter_mut(&mut self) -> #iter_mut_name<'_, #generic_args> {
                 self.as_mut_slice().into_iter()
             }
         }
 
-        impl<'a> #slice_mut_name<'a> {
+        impl<'__soa_a, #generic_params> #slice_mut_name<'__soa_a, #generic_args> where #where_predicates {
             /// Get an iterator over the
             #[doc = #ref_doc_url]
             /// in this vector
-            pub fn iter(&mut self) -> #iter_name {
+            pub fn iter(&mut self) -> #iter_name<'_, #generic_args> {
                 self.as_ref().into_iter()
             }
 
             /// Get a mutable iterator over the
             #[doc = #ref_mut_doc_url]
             /// in this vector
-            pub fn iter_mut(&mut self) -> #iter_mut_name {
-                #iter_mut_name(#create_iter_mut)
+            pub fn iter_mut(&mut self) -> #iter_mut_name<'_, #generic_args> {
+                #iter_mut_name::new(self.reborrow())
             }
 
             /// Get a mutable iterator over the
             #[doc = #ref_mut_doc_url]
             /// in this vector
-            pub fn into_iter(self) -> #iter_mut_name<'a> {
-                #iter_mut_name(#create_mut_into_iter)
+            pub fn into_iter(self) -> #iter_mut_name<'__soa_a, #generic_args> {
+                #iter_mut_name::new(self)
             }
         }
 
-        impl<'a> soa_derive::SoAIter<'a> for #name {
-            type Ref = #ref_name<'a>;
-            type RefMut = #ref_mut_name<'a>;
-            type Iter = #iter_name<'a>;
-            type IterMut = #iter_mut_name<'a>;
+        impl<'__soa_a, #generic_params> soa_derive::SoAIter<'__soa_a> for #name<#generic_args> where #outlive_a #where_predicates {
+            type Ref = #ref_name<'__soa_a, #generic_args>;
+            type RefMut = #ref_mut_name<'__soa_a, #generic_args>;
+            type Iter = #iter_name<'__soa_a, #generic_args>;
+            type IterMut = #iter_mut_name<'__soa_a, #generic_args>;
+        }
+
+        impl<'__soa_a, #generic_params> ::soa_derive::SoASlices<'__soa_a> for #name<#generic_args> where #outlive_a #where_predicates {
+            type Slice = #slice_name<'__soa_a, #generic_args>;
+            type SliceMut = #slice_mut_name<'__soa_a, #generic_args>;
+        }
+
+        impl<'__soa_a, #generic_params> ::soa_derive::SoAAsRef<'__soa_a> for #name<#generic_args> where #outlive_a #where_predicates {
+            fn as_soa_ref(&'__soa_a self) -> #ref_name<'__soa_a, #generic_args> {
+                self.as_ref()
+            }
+
+            fn as_soa_mut(&'__soa_a mut self) -> #ref_mut_name<'__soa_a, #generic_args> {
+                self.as_mut()
+            }
         }
 
-        impl<'a> IntoIterator for #slice_name<'a> {
-            type Item = #ref_name<'a>;
-            type IntoIter = #iter_name<'a>;
+        impl<'__soa_a, #generic_params> IntoIterator for #slice_name<'__soa_a, #generic_args> where #where_predicates {
+            type Item = #ref_name<'__soa_a, #generic_args>;
+            type IntoIter = #iter_name<'__soa_a, #generic_args>;
 
             fn into_iter(self) -> Self::IntoIter {
-                #iter_name(#create_into_iter)
+                #iter_name::new(self)
             }
         }
 
 
-        impl std::iter::FromIterator<#name> for #vec_name {
-            fn from_iter<T: IntoIterator<Item=#name>>(iter: T) -> Self {
+        impl<#generic_params> ::core::iter::FromIterator<#name<#generic_args>> for #vec_name<#generic_args> where #where_predicates {
+            fn from_iter<__SoaI: IntoIterator<Item=#name<#generic_args>>>(iter: __SoaI) -> Self {
                 let mut result = #vec_name::new();
                 for element in iter {
                     result.push(element);
@@ -243,61 +405,75 @@ pub fn derive(input: &Input) -> TokenStream {
             }
         }
 
-        impl<'a, 'b> IntoIterator for &'a #slice_name<'b> {
-            type Item = #ref_name<'a>;
-            type IntoIter = #iter_name<'a>;
+        impl<#generic_params> From<::soa_derive::alloc::vec::Vec<#name<#generic_args>>> for #vec_name<#generic_args> where #where_predicates {
+            fn from(vec: ::soa_derive::alloc::vec::Vec<#name<#generic_args>>) -> Self {
+                let mut result = #vec_name::with_capacity(vec.len());
+                <Self as Extend<#name<#generic_args>>>::extend(&mut result, vec);
+                result
+            }
+        }
+
+        impl<#generic_params> From<#vec_name<#generic_args>> for ::soa_derive::alloc::vec::Vec<#name<#generic_args>> where #where_predicates {
+            fn from(vec: #vec_name<#generic_args>) -> Self {
+                IntoIterator::into_iter(vec).collect()
+            }
+        }
+
+        impl<'__soa_a, '__soa_b, #generic_params> IntoIterator for &'__soa_a #slice_name<'__soa_b, #generic_args> where #where_predicates {
+            type Item = #ref_name<'__soa_a, #generic_args>;
+            type IntoIter = #iter_name<'__soa_a, #generic_args>;
 
             fn into_iter(self) -> Self::IntoIter {
-                #iter_name(#create_into_iter)
+                #iter_name::new(self.reborrow())
             }
         }
 
-        impl<'a> IntoIterator for &'a #vec_name {
-            type Item = #ref_name<'a>;
-            type IntoIter = #iter_name<'a>;
+        impl<'__soa_a, #generic_params> IntoIterator for &'__soa_a #vec_name<#generic_args> where #where_predicates {
+            type Item = #ref_name<'__soa_a, #generic_args>;
+            type IntoIter = #iter_name<'__soa_a, #generic_args>;
 
             fn into_iter(self) -> Self::IntoIter {
                 self.as_slice().into_iter()
             }
         }
 
-        impl<'a> IntoIterator for #slice_mut_name<'a> {
-            type Item = #ref_mut_name<'a>;
-            type IntoIter = #iter_mut_name<'a>;
+        impl<'__soa_a, #generic_params> IntoIterator for #slice_mut_name<'__soa_a, #generic_args> where #where_predicates {
+            type Item = #ref_mut_name<'__soa_a, #generic_args>;
+            type IntoIter = #iter_mut_name<'__soa_a, #generic_args>;
 
             fn into_iter(self) -> Self::IntoIter {
-                #iter_mut_name(#create_mut_into_iter)
+                #iter_mut_name::new(self)
             }
         }
 
-        impl<'a> IntoIterator for &'a mut #vec_name {
-            type Item = #ref_mut_name<'a>;
-            type IntoIter = #iter_mut_name<'a>;
+        impl<'__soa_a, #generic_params> IntoIterator for &'__soa_a mut #vec_name<#generic_args> where #where_predicates {
+            type Item = #ref_mut_name<'__soa_a, #generic_args>;
+            type IntoIter = #iter_mut_name<'__soa_a, #generic_args>;
 
             fn into_iter(self) -> Self::IntoIter {
                 self.as_mut_slice().into_iter()
             }
         }
 
-        impl Extend<#name> for #vec_name {
-            fn extend<I: IntoIterator<Item = #name>>(&mut self, iter: I) {
+        impl<#generic_params> Extend<#name<#generic_args>> for #vec_name<#generic_args> where #where_predicates {
+            fn extend<__SoaI: IntoIterator<Item = #name<#generic_args>>>(&mut self, iter: __SoaI) {
                 for item in iter {
                     self.push(item)
                 }
             }
         }
 
-        impl<'a> Extend<#ref_name<'a>> for #vec_name
+        impl<'__soa_a, #generic_params> Extend<#ref_name<'__soa_a, #generic_args>> for #vec_name<#generic_args>
             // only expose if all fields are Clone
             // https://github.com/rust-lang/rust/issues/48214#issuecomment-1150463333
-            where #( for<'b> #fields_types: Clone, )*
+            where #( for<'__soa_b> #fields_types: Clone, )* #where_predicates
         {
-            fn extend<I: IntoIterator<Item = #ref_name<'a>>>(&mut self, iter: I) {
-                <Self as Extend<#name>>::extend(self, iter.into_iter().map(|item| item.to_owned()))
+            fn extend<__SoaI: IntoIterator<Item = #ref_name<'__soa_a, #generic_args>>>(&mut self, iter: __SoaI) {
+                <Self as Extend<#name<#generic_args>>>::extend(self, iter.into_iter().map(|item| item.to_owned()))
             }
         }
 
-        impl<'a> ::soa_derive::IntoSoAIter<'a, #name> for #slice_name<'a> {}
+        impl<'__soa_a, #generic_params> ::soa_derive::IntoSoAIter<'__soa_a, #name<#generic_args>> for #slice_name<'__soa_a, #generic_args> where #where_predicates {}
     };
 
     return generated;
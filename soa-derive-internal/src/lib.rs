@@ -9,49 +9,84 @@ extern crate proc_macro;
 use proc_macro2::TokenStream;
 use quote::TokenStreamExt;
 
+mod array_vec;
+#[cfg(feature = "arrow")]
+mod arrow;
+mod chunks;
+mod drain;
+mod enums;
 mod index;
 #[macro_use]
 mod input;
 mod iter;
+#[cfg(feature = "rayon")]
+mod parallel;
 mod ptr;
 mod refs;
+mod serde;
+mod single_buffer;
 mod slice;
 mod vec;
 mod generic;
 
 pub(crate) mod names;
 
-#[proc_macro_derive(StructOfArray, attributes(soa_derive, soa_attr, nested_soa))]
+#[proc_macro_derive(StructOfArray, attributes(soa_derive, soa_attr, nested_soa, soa))]
 pub fn soa_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    let ast = syn::parse(input).expect("Failed to parse derive macro for StructOfArray");
-    let input = input::Input::new(ast);
+    let ast = syn::parse_macro_input!(input as syn::DeriveInput);
+    let generated = if let syn::Data::Enum(_) = ast.data {
+        enums::derive(&ast)
+    } else {
+        input::Input::new(ast).map(|input| derive_struct(&input))
+    };
 
+    match generated {
+        Ok(generated) => generated.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+fn derive_struct(input: &Input) -> TokenStream {
     let mut generated = TokenStream::new();
-    generated.append_all(vec::derive(&input));
-    generated.append_all(refs::derive(&input));
-    generated.append_all(ptr::derive(&input));
-    generated.append_all(slice::derive(&input));
-    generated.append_all(slice::derive_mut(&input));
-    generated.append_all(index::derive(&input));
-    generated.append_all(iter::derive(&input));
-    generated.append_all(derive_trait(&input));
-
-    generated.append_all(generic::derive_slice(&input));
-    generated.append_all(generic::derive_slice_mut(&input));
-    generated.append_all(generic::derive_vec(&input));
-    generated.into()
+    match input.storage {
+        Storage::Vec => generated.append_all(vec::derive(input)),
+        Storage::SingleBuffer => generated.append_all(single_buffer::derive(input)),
+    }
+    generated.append_all(drain::derive(input));
+    generated.append_all(array_vec::derive(input));
+    generated.append_all(refs::derive(input));
+    generated.append_all(ptr::derive(input));
+    generated.append_all(slice::derive(input));
+    generated.append_all(slice::derive_mut(input));
+    generated.append_all(index::derive(input));
+    generated.append_all(iter::derive(input));
+    generated.append_all(chunks::derive(input));
+    generated.append_all(serde::derive(input));
+    #[cfg(feature = "rayon")]
+    generated.append_all(parallel::derive(input));
+    #[cfg(feature = "arrow")]
+    generated.append_all(arrow::derive(input));
+    generated.append_all(derive_trait(input));
+
+    generated.append_all(generic::derive_slice(input));
+    generated.append_all(generic::derive_slice_mut(input));
+    generated.append_all(generic::derive_vec(input));
+    return generated;
 }
 
-use crate::input::Input;
+use crate::input::{Input, Storage};
 use quote::quote;
 
 fn derive_trait(input: &Input) -> TokenStream {
     let name = &input.name;
-    let vec_name = names::vec_name(name);
+    let vec_name = &input.names.vec;
+    let generic_params = input.generic_params();
+    let generic_args = input.generic_args();
+    let where_predicates = input.where_predicates();
 
     quote! {
-        impl soa_derive::StructOfArray for #name {
-            type Type = #vec_name;
+        impl<#generic_params> soa_derive::StructOfArray for #name<#generic_args> where #where_predicates {
+            type Type = #vec_name<#generic_args>;
         }
     }
 }
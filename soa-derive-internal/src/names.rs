@@ -1,22 +1,13 @@
 use proc_macro2::Span;
 use quote::ToTokens;
-use syn::Ident;
+use syn::meta::ParseNestedMeta;
+use syn::{Ident, LitStr};
 
 /// Get the ident for the `Vec` type associated with `name`
 pub fn vec_name(name: impl ToTokens) -> Ident {
     Ident::new(&format!("{}Vec", name.to_token_stream()), Span::call_site())
 }
 
-/// Get the ident for the slice type associated with `name`
-pub fn slice_name(name: impl ToTokens) -> Ident {
-    Ident::new(&format!("{}Slice", name.to_token_stream()), Span::call_site())
-}
-
-/// Get the ident for the mutable slice type associated with `name`
-pub fn slice_mut_name(name: impl ToTokens) -> Ident {
-    Ident::new(&format!("{}SliceMut", name.to_token_stream()), Span::call_site())
-}
-
 /// Get the ident for the reference type associated with `name`
 pub fn ref_name(name: impl ToTokens) -> Ident {
     Ident::new(&format!("{}Ref", name.to_token_stream()), Span::call_site())
@@ -37,12 +28,150 @@ pub fn iter_mut_name(name: impl ToTokens) -> Ident {
     Ident::new(&format!("{}IterMut", name.to_token_stream()), Span::call_site())
 }
 
-/// Get the ident for the pointer type associated with `name`
-pub fn ptr_name(name: impl ToTokens) -> Ident {
-    Ident::new(&format!("{}Ptr", name.to_token_stream()), Span::call_site())
+/// The different kinds of generated types, with the `#[soa(...)]` key used to
+/// override their name and the suffix used by default
+static KINDS: &[(&str, &str)] = &[
+    ("vec", "Vec"),
+    ("array_vec", "ArrayVec"),
+    ("slice", "Slice"),
+    ("slice_mut", "SliceMut"),
+    ("ref", "Ref"),
+    ("ref_mut", "RefMut"),
+    ("ptr", "Ptr"),
+    ("ptr_mut", "PtrMut"),
+    ("iter", "Iter"),
+    ("iter_mut", "IterMut"),
+    ("into_iter", "IntoIter"),
+    ("chunks_exact", "ChunksExact"),
+    ("chunks_exact_mut", "ChunksExactMut"),
+    ("array_chunk", "ArrayChunk"),
+    ("array_chunk_mut", "ArrayChunkMut"),
+    ("array_chunks", "ArrayChunks"),
+    ("array_chunks_mut", "ArrayChunksMut"),
+    ("drain", "Drain"),
+    ("splice", "Splice"),
+    ("extract_if", "ExtractIf"),
+];
+
+/// Configuration of the generated types names, from `#[soa(...)]` attributes
+/// on the input struct
+#[derive(Default)]
+pub struct NamesConfig {
+    /// prefix added to all the default names
+    prefix: String,
+    /// suffix added to all the default names
+    suffix: String,
+    /// explicit names given to some of the types
+    overrides: Vec<(&'static str, Ident)>,
+}
+
+impl NamesConfig {
+    /// Try to parse a single `key = "value"` entry of a `#[soa(...)]`
+    /// attribute. This returns `Ok(false)` if the key is not related to
+    /// names.
+    pub fn parse_meta(&mut self, meta: &ParseNestedMeta) -> syn::Result<bool> {
+        if meta.path.is_ident("prefix") {
+            self.prefix = meta.value()?.parse::<LitStr>()?.value();
+            return Ok(true);
+        }
+
+        if meta.path.is_ident("suffix") {
+            self.suffix = meta.value()?.parse::<LitStr>()?.value();
+            return Ok(true);
+        }
+
+        for &(key, _) in KINDS {
+            if meta.path.is_ident(key) {
+                let name = meta.value()?.parse::<LitStr>()?;
+                let name = name.parse::<Ident>().map_err(|_| {
+                    syn::Error::new_spanned(&name, format!("expected a valid type name for `{}`", key))
+                })?;
+                self.overrides.push((key, name));
+                return Ok(true);
+            }
+        }
+
+        return Ok(false);
+    }
+
+    /// Get the names of all the generated types for the struct `name`
+    pub fn build(&self, name: &Ident) -> Names {
+        let get = |kind: &str| {
+            for (key, name) in &self.overrides {
+                if *key == kind {
+                    return name.clone();
+                }
+            }
+
+            let (_, suffix) = KINDS.iter().find(|(key, _)| *key == kind).expect("unknown kind");
+            Ident::new(&format!("{}{}{}{}", self.prefix, name, suffix, self.suffix), Span::call_site())
+        };
+
+        Names {
+            vec: get("vec"),
+            array_vec: get("array_vec"),
+            slice: get("slice"),
+            slice_mut: get("slice_mut"),
+            ref_: get("ref"),
+            ref_mut: get("ref_mut"),
+            ptr: get("ptr"),
+            ptr_mut: get("ptr_mut"),
+            iter: get("iter"),
+            iter_mut: get("iter_mut"),
+            into_iter: get("into_iter"),
+            chunks_exact: get("chunks_exact"),
+            chunks_exact_mut: get("chunks_exact_mut"),
+            array_chunk: get("array_chunk"),
+            array_chunk_mut: get("array_chunk_mut"),
+            array_chunks: get("array_chunks"),
+            array_chunks_mut: get("array_chunks_mut"),
+            drain: get("drain"),
+            splice: get("splice"),
+            extract_if: get("extract_if"),
+        }
+    }
 }
 
-/// Get the ident for the mutable pointer type associated with `name`
-pub fn ptr_mut_name(name: impl ToTokens) -> Ident {
-    Ident::new(&format!("{}PtrMut", name.to_token_stream()), Span::call_site())
+/// Names of all the types generated for a given struct
+pub struct Names {
+    /// name of the `Vec` type
+    pub vec: Ident,
+    /// name of the fixed-capacity `ArrayVec` type
+    pub array_vec: Ident,
+    /// name of the slice type
+    pub slice: Ident,
+    /// name of the mutable slice type
+    pub slice_mut: Ident,
+    /// name of the reference type
+    pub ref_: Ident,
+    /// name of the mutable reference type
+    pub ref_mut: Ident,
+    /// name of the pointer type
+    pub ptr: Ident,
+    /// name of the mutable pointer type
+    pub ptr_mut: Ident,
+    /// name of the iterator type
+    pub iter: Ident,
+    /// name of the mutable iterator type
+    pub iter_mut: Ident,
+    /// name of the owning iterator type
+    pub into_iter: Ident,
+    /// name of the iterator over chunks of a slice
+    pub chunks_exact: Ident,
+    /// name of the iterator over mutable chunks of a slice
+    pub chunks_exact_mut: Ident,
+    /// name of the type containing references to fixed-size arrays
+    pub array_chunk: Ident,
+    /// name of the type containing mutable references to fixed-size arrays
+    pub array_chunk_mut: Ident,
+    /// name of the iterator over fixed-size array chunks of a slice
+    pub array_chunks: Ident,
+    /// name of the iterator over mutable fixed-size array chunks of a slice
+    pub array_chunks_mut: Ident,
+    /// name of the iterator removing a range of elements from a vector
+    pub drain: Ident,
+    /// name of the iterator replacing a range of elements in a vector
+    pub splice: Ident,
+    /// name of the iterator removing the elements matching a predicate
+    pub extract_if: Ident,
 }
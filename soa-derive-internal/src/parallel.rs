@@ -0,0 +1,284 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::input::Input;
+
+/// Generate the parallel iterators using rayon, only when the `rayon`
+/// feature is enabled. The iterators themselves are implemented in
+/// `soa_derive::parallel`, and only need the slices to be splittable.
+pub fn derive(input: &Input) -> TokenStream {
+    let vec_name = &input.names.vec;
+    let slice_name = &input.names.slice;
+    let slice_mut_name = &input.names.slice_mut;
+    let ref_name = &input.names.ref_;
+    let ref_mut_name = &input.names.ref_mut;
+    let iter_name = &input.names.iter;
+    let iter_mut_name = &input.names.iter_mut;
+
+    let generic_params = input.generic_params();
+    let generic_args = input.generic_args();
+    let where_predicates = input.where_predicates();
+
+    let ref_doc_url = format!("[`{0}`](struct.{0}.html)", ref_name);
+    let ref_mut_doc_url = format!("[`{0}`](struct.{0}.html)", ref_mut_name);
+    let slice_doc_url = format!("[`{0}`](struct.{0}.html)", slice_name);
+    let slice_mut_doc_url = format!("[`{0}`](struct.{0}.html)", slice_mut_name);
+
+    // The iterators are only parallel iterators if the slices can be sent to
+    // other threads. The `for<'b>` makes sure these bounds are not checked
+    // for structs without generic parameters.
+    // https://github.com/rust-lang/rust/issues/48214#issuecomment-1150463333
+    let send_slice = quote! {
+        for<'__soa_b> #slice_name<'__soa_b, #generic_args>: Send,
+        for<'__soa_b> #ref_name<'__soa_b, #generic_args>: Send,
+    };
+    let send_slice_mut = quote! {
+        for<'__soa_b> #slice_mut_name<'__soa_b, #generic_args>: Send,
+        for<'__soa_b> #ref_mut_name<'__soa_b, #generic_args>: Send,
+    };
+
+    quote! {
+        impl<'__soa_a, #generic_params> ::soa_derive::parallel::SoAParallelSlice for #slice_name<'__soa_a, #generic_args> where #where_predicates {
+            type Item = #ref_name<'__soa_a, #generic_args>;
+            type Iter = #iter_name<'__soa_a, #generic_args>;
+
+            fn len(&self) -> usize {
+                #slice_name::len(self)
+            }
+
+            fn split_at(self, mid: usize) -> (Self, Self) {
+                #slice_name::split_at(&self, mid)
+            }
+
+            fn into_iter(self) -> #iter_name<'__soa_a, #generic_args> {
+                #slice_name::into_iter(self)
+            }
+        }
+
+        impl<'__soa_a, #generic_params> ::soa_derive::parallel::SoAParallelSlice for #slice_mut_name<'__soa_a, #generic_args> where #where_predicates {
+            type Item = #ref_mut_name<'__soa_a, #generic_args>;
+            type Iter = #iter_mut_name<'__soa_a, #generic_args>;
+
+            fn len(&self) -> usize {
+                #slice_mut_name::len(self)
+            }
+
+            fn split_at(self, mid: usize) -> (Self, Self) {
+                #slice_mut_name::split_at_mut(self, mid)
+            }
+
+            fn into_iter(self) -> #iter_mut_name<'__soa_a, #generic_args> {
+                #slice_mut_name::into_iter(self)
+            }
+        }
+
+        impl<'__soa_a, #generic_params> #slice_name<'__soa_a, #generic_args> where #where_predicates {
+            /// Get a parallel iterator over the
+            #[doc = #ref_doc_url]
+            /// in this slice.
+            pub fn par_iter(&self) -> ::soa_derive::parallel::ParIter<#slice_name<'__soa_a, #generic_args>> {
+                ::soa_derive::parallel::ParIter::new(*self)
+            }
+
+            /// Get a parallel iterator over chunks of `chunk_size` elements
+            /// of this slice, as
+            #[doc = #slice_doc_url]
+            /// The last chunk is shorter if `chunk_size` does not divide the
+            /// length of this slice.
+            ///
+            /// # Panics
+            ///
+            /// If `chunk_size` is 0.
+            pub fn par_chunks(&self, chunk_size: usize) -> ::soa_derive::parallel::ParChunks<#slice_name<'__soa_a, #generic_args>> {
+                ::soa_derive::parallel::ParChunks::new(*self, chunk_size)
+            }
+        }
+
+        impl<'__soa_a, #generic_params> #slice_mut_name<'__soa_a, #generic_args> where #where_predicates {
+            /// Get a parallel iterator over the
+            #[doc = #ref_doc_url]
+            /// in this slice.
+            pub fn par_iter(&self) -> ::soa_derive::parallel::ParIter<#slice_name<'_, #generic_args>> {
+                ::soa_derive::parallel::ParIter::new(self.as_slice())
+            }
+
+            /// Get a parallel iterator over the
+            #[doc = #ref_mut_doc_url]
+            /// in this slice.
+            pub fn par_iter_mut(&mut self) -> ::soa_derive::parallel::ParIter<#slice_mut_name<'_, #generic_args>> {
+                ::soa_derive::parallel::ParIter::new(self.reborrow())
+            }
+
+            /// Get a parallel iterator over chunks of `chunk_size` elements
+            /// of this slice, as
+            #[doc = #slice_mut_doc_url]
+            /// The last chunk is shorter if `chunk_size` does not divide the
+            /// length of this slice.
+            ///
+            /// # Panics
+            ///
+            /// If `chunk_size` is 0.
+            pub fn par_chunks_mut(&mut self, chunk_size: usize) -> ::soa_derive::parallel::ParChunks<#slice_mut_name<'_, #generic_args>> {
+                ::soa_derive::parallel::ParChunks::new(self.reborrow(), chunk_size)
+            }
+        }
+
+        impl<'__soa_a, #generic_params> #slice_mut_name<'__soa_a, #generic_args>
+        where
+            for<'__soa_b> #slice_name<'__soa_b, #generic_args>: Sync,
+            #where_predicates
+        {
+            /// Parallel version of `sort_by()`, using rayon to sort the
+            /// elements.
+            pub fn par_sort_by<__SoaF>(&mut self, f: __SoaF)
+            where
+                __SoaF: Fn(#ref_name<'_, #generic_args>, #ref_name<'_, #generic_args>) -> ::core::cmp::Ordering + Sync,
+            {
+                use ::soa_derive::rayon::slice::ParallelSliceMut;
+
+                let slice = self.as_slice();
+                let mut permutation: ::soa_derive::alloc::vec::Vec<usize> = (0..slice.len()).collect();
+                permutation.par_sort_by(|j, k| f(slice.index(*j), slice.index(*k)));
+
+                let mut permutation = ::soa_derive::Permutation::from_sorted_indices(&permutation);
+                self.__private_apply_permutation(&mut permutation);
+            }
+
+            /// Parallel version of `sort_by_key()`, using rayon to sort the
+            /// elements.
+            pub fn par_sort_by_key<__SoaF, __SoaK>(&mut self, f: __SoaF)
+            where
+                __SoaF: Fn(#ref_name<'_, #generic_args>) -> __SoaK + Sync,
+                __SoaK: Ord,
+            {
+                use ::soa_derive::rayon::slice::ParallelSliceMut;
+
+                let slice = self.as_slice();
+                let mut permutation: ::soa_derive::alloc::vec::Vec<usize> = (0..slice.len()).collect();
+                permutation.par_sort_by(|j, k| f(slice.index(*j)).cmp(&f(slice.index(*k))));
+
+                let mut permutation = ::soa_derive::Permutation::from_sorted_indices(&permutation);
+                self.__private_apply_permutation(&mut permutation);
+            }
+
+            /// Parallel version of `sort_unstable_by()`, using rayon to sort
+            /// the elements.
+            pub fn par_sort_unstable_by<__SoaF>(&mut self, f: __SoaF)
+            where
+                __SoaF: Fn(#ref_name<'_, #generic_args>, #ref_name<'_, #generic_args>) -> ::core::cmp::Ordering + Sync,
+            {
+                use ::soa_derive::rayon::slice::ParallelSliceMut;
+
+                let slice = self.as_slice();
+                let mut permutation: ::soa_derive::alloc::vec::Vec<usize> = (0..slice.len()).collect();
+                permutation.par_sort_unstable_by(|j, k| f(slice.index(*j), slice.index(*k)));
+
+                let mut permutation = ::soa_derive::Permutation::from_sorted_indices(&permutation);
+                self.__private_apply_permutation(&mut permutation);
+            }
+
+            /// Parallel version of `sort_unstable_by_key()`, using rayon to
+            /// sort the elements.
+            pub fn par_sort_unstable_by_key<__SoaF, __SoaK>(&mut self, f: __SoaF)
+            where
+                __SoaF: Fn(#ref_name<'_, #generic_args>) -> __SoaK + Sync,
+                __SoaK: Ord,
+            {
+                use ::soa_derive::rayon::slice::ParallelSliceMut;
+
+                let slice = self.as_slice();
+                let mut permutation: ::soa_derive::alloc::vec::Vec<usize> = (0..slice.len()).collect();
+                permutation.par_sort_unstable_by(|j, k| f(slice.index(*j)).cmp(&f(slice.index(*k))));
+
+                let mut permutation = ::soa_derive::Permutation::from_sorted_indices(&permutation);
+                self.__private_apply_permutation(&mut permutation);
+            }
+        }
+
+        impl<#generic_params> #vec_name<#generic_args> where #where_predicates {
+            /// Get a parallel iterator over the
+            #[doc = #ref_doc_url]
+            /// in this vector.
+            pub fn par_iter(&self) -> ::soa_derive::parallel::ParIter<#slice_name<'_, #generic_args>> {
+                ::soa_derive::parallel::ParIter::new(self.as_slice())
+            }
+
+            /// Get a parallel iterator over the
+            #[doc = #ref_mut_doc_url]
+            /// in this vector.
+            pub fn par_iter_mut(&mut self) -> ::soa_derive::parallel::ParIter<#slice_mut_name<'_, #generic_args>> {
+                ::soa_derive::parallel::ParIter::new(self.as_mut_slice())
+            }
+
+            /// Get a parallel iterator over chunks of `chunk_size` elements
+            /// of this vector, as
+            #[doc = #slice_doc_url]
+            /// The last chunk is shorter if `chunk_size` does not divide the
+            /// length of this vector.
+            ///
+            /// # Panics
+            ///
+            /// If `chunk_size` is 0.
+            pub fn par_chunks(&self, chunk_size: usize) -> ::soa_derive::parallel::ParChunks<#slice_name<'_, #generic_args>> {
+                ::soa_derive::parallel::ParChunks::new(self.as_slice(), chunk_size)
+            }
+
+            /// Get a parallel iterator over chunks of `chunk_size` elements
+            /// of this vector, as
+            #[doc = #slice_mut_doc_url]
+            /// The last chunk is shorter if `chunk_size` does not divide the
+            /// length of this vector.
+            ///
+            /// # Panics
+            ///
+            /// If `chunk_size` is 0.
+            pub fn par_chunks_mut(&mut self, chunk_size: usize) -> ::soa_derive::parallel::ParChunks<#slice_mut_name<'_, #generic_args>> {
+                ::soa_derive::parallel::ParChunks::new(self.as_mut_slice(), chunk_size)
+            }
+        }
+
+        impl<'__soa_a, #generic_params> ::soa_derive::rayon::iter::IntoParallelIterator for #slice_name<'__soa_a, #generic_args>
+            where #send_slice #where_predicates
+        {
+            type Item = #ref_name<'__soa_a, #generic_args>;
+            type Iter = ::soa_derive::parallel::ParIter<#slice_name<'__soa_a, #generic_args>>;
+
+            fn into_par_iter(self) -> Self::Iter {
+                ::soa_derive::parallel::ParIter::new(self)
+            }
+        }
+
+        impl<'__soa_a, #generic_params> ::soa_derive::rayon::iter::IntoParallelIterator for &'__soa_a #vec_name<#generic_args>
+            where #send_slice #where_predicates
+        {
+            type Item = #ref_name<'__soa_a, #generic_args>;
+            type Iter = ::soa_derive::parallel::ParIter<#slice_name<'__soa_a, #generic_args>>;
+
+            fn into_par_iter(self) -> Self::Iter {
+                ::soa_derive::parallel::ParIter::new(self.as_slice())
+            }
+        }
+
+        impl<'__soa_a, #generic_params> ::soa_derive::rayon::iter::IntoParallelIterator for #slice_mut_name<'__soa_a, #generic_args>
+            where #send_slice_mut #where_predicates
+        {
+            type Item = #ref_mut_name<'__soa_a, #generic_args>;
+            type Iter = ::soa_derive::parallel::ParIter<#slice_mut_name<'__soa_a, #generic_args>>;
+
+            fn into_par_iter(self) -> Self::Iter {
+                ::soa_derive::parallel::ParIter::new(self)
+            }
+        }
+
+        impl<'__soa_a, #generic_params> ::soa_derive::rayon::iter::IntoParallelIterator for &'__soa_a mut #vec_name<#generic_args>
+            where #send_slice_mut #where_predicates
+        {
+            type Item = #ref_mut_name<'__soa_a, #generic_args>;
+            type Iter = ::soa_derive::parallel::ParIter<#slice_mut_name<'__soa_a, #generic_args>>;
+
+            fn into_par_iter(self) -> Self::Iter {
+                ::soa_derive::parallel::ParIter::new(self.as_mut_slice())
+            }
+        }
+    }
+}
@@ -2,18 +2,21 @@ use proc_macro2::TokenStream;
 use quote::quote;
 
 use crate::input::Input;
-use crate::names;
 
 pub fn derive(input: &Input) -> TokenStream {
     let name = &input.name;
     let visibility = &input.visibility;
     let attrs = &input.attrs.ptr;
     let mut_attrs = &input.attrs.ptr_mut;
-    let vec_name = names::vec_name(&input.name);
-    let ptr_name = names::ptr_name(&input.name);
-    let ptr_mut_name = names::ptr_mut_name(&input.name);
-    let ref_name = names::ref_name(&input.name);
-    let ref_mut_name = names::ref_mut_name(&input.name);
+    let vec_name = &input.names.vec;
+    let ptr_name = &input.names.ptr;
+    let ptr_mut_name = &input.names.ptr_mut;
+    let ref_name = &input.names.ref_;
+    let ref_mut_name = &input.names.ref_mut;
+
+    let generic_params = input.generic_params();
+    let generic_args = input.generic_args();
+    let where_predicates = input.where_predicates();
 
     let doc_url = format!("[`{0}`](struct.{0}.html)", name);
     let vec_doc_url = format!("[`{0}`](struct.{0}.html)", vec_name);
@@ -22,23 +25,17 @@ pub fn derive(input: &Input) -> TokenStream {
     let ref_doc_url = format!("[`{0}`](struct.{0}.html)", ref_name);
     let ref_mut_doc_url = format!("[`{0}`](struct.{0}.html)", ref_mut_name);
 
-    let fields_names = &input.fields.iter()
-        .map(|field| field.ident.clone().unwrap())
-        .collect::<Vec<_>>();
+    let fields_names = &input.fields_names();
+    let skipped_fields_init = input.skipped_fields_init();
+    let drop_skipped_val = input.drop_skipped_fields(&quote! { val });
 
     let ptr_fields_types = input.map_fields_nested_or(
-        |_, field_type| {
-            let field_ptr_type = names::ptr_name(field_type);
-            quote! { #field_ptr_type }
-        },
+        |_, field_type| quote! { <#field_type as ::soa_derive::SoAPointers>::Ptr },
         |_, field_type| quote! { *const #field_type },
     ).collect::<Vec<_>>();
 
     let ptr_mut_fields_types = input.map_fields_nested_or(
-        |_, field_type| {
-            let field_ptr_type = names::ptr_mut_name(field_type);
-            quote! { #field_ptr_type }
-        },
+        |_, field_type| quote! { <#field_type as ::soa_derive::SoAPointers>::MutPtr },
         |_, field_type| quote! { *mut #field_type },
     ).collect::<Vec<_>>();
 
@@ -52,49 +49,62 @@ pub fn derive(input: &Input) -> TokenStream {
         |ident, _| quote! { self.#ident as *mut _ },
     ).collect::<Vec<_>>();
 
+    let deref = input.map_fields_nested_or(
+        |ident, _| quote! { self.#ident.as_ref_unchecked() },
+        |ident, _| quote! { &*self.#ident },
+    ).collect::<Vec<_>>();
+
+    let deref_mut = input.map_fields_nested_or(
+        |ident, _| quote! { self.#ident.as_mut_unchecked() },
+        |ident, _| quote! { &mut *self.#ident },
+    ).collect::<Vec<_>>();
+
+    let fields_docs = fields_names.iter().map(|field| quote! {
+        /// pointer to the `
+        #[doc = stringify!(#field)]
+        ///` field of a single
+        #[doc = #doc_url]
+        /// inside a
+        #[doc = #vec_doc_url]
+    }).collect::<Vec<_>>();
+    let ptr_body = input.struct_body(None, &fields_docs, &ptr_fields_types);
+    let ptr_mut_body = input.struct_body(None, &fields_docs, &ptr_mut_fields_types);
+
     quote! {
         /// An analog of a pointer to
         #[doc = #doc_url]
         /// with struct of array layout.
         #(#[#attrs])*
-        #[derive(Copy, Clone)]
-        #visibility struct #ptr_name {
-            #(
-                /// pointer to the `
-                #[doc = stringify!(#fields_names)]
-                ///` field of a single
-                #[doc = #doc_url]
-                /// inside a
-                #[doc = #vec_doc_url]
-                pub #fields_names: #ptr_fields_types,
-            )*
-        }
+        #visibility struct #ptr_name<#generic_params> #ptr_body
 
         /// An analog of a mutable pointer to
         #[doc = #doc_url]
         /// with struct of array layout.
         #(#[#mut_attrs])*
-        #[derive(Copy, Clone)]
-        #visibility struct #ptr_mut_name {
-            #(
-                /// pointer to the `
-                #[doc = stringify!(#fields_names)]
-                ///` field of a single
-                #[doc = #doc_url]
-                /// inside a
-                #[doc = #vec_doc_url]
-                pub #fields_names: #ptr_mut_fields_types,
-            )*
+        #visibility struct #ptr_mut_name<#generic_params> #ptr_mut_body
+
+        impl<#generic_params> Copy for #ptr_name<#generic_args> where #where_predicates {}
+        impl<#generic_params> Clone for #ptr_name<#generic_args> where #where_predicates {
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+
+        impl<#generic_params> Copy for #ptr_mut_name<#generic_args> where #where_predicates {}
+        impl<#generic_params> Clone for #ptr_mut_name<#generic_args> where #where_predicates {
+            fn clone(&self) -> Self {
+                *self
+            }
         }
 
         #[allow(dead_code)]
-        impl #ptr_name {
+        impl<#generic_params> #ptr_name<#generic_args> where #where_predicates {
             /// Convert a
             #[doc = #ptr_doc_url]
             /// to a
             #[doc = #ptr_mut_doc_url]
             /// ; *i.e.* do a `*const T as *mut T` transformation.
-            #visibility fn as_mut_ptr(&self) -> #ptr_mut_name {
+            #visibility fn as_mut_ptr(&self) -> #ptr_mut_name<#generic_args> {
                 #ptr_mut_name {
                     #( #fields_names: #as_mut_ptr, )*
                 }
@@ -107,26 +117,32 @@ pub fn derive(input: &Input) -> TokenStream {
 
             /// Similar to [`*const T::as_ref()`](https://doc.rust-lang.org/std/primitive.pointer.html#method.as_ref),
             /// with the same safety caveats.
-            pub unsafe fn as_ref<'a>(self) -> Option<#ref_name<'a>> {
+            pub unsafe fn as_ref<'__soa_a>(self) -> Option<#ref_name<'__soa_a, #generic_args>> {
                 if self.is_null() {
                     None
                 } else {
-                    Some(#ref_name {
-                        #(#fields_names: self.#fields_names.as_ref().expect("should not be null"), )*
-                    })
+                    Some(self.as_ref_unchecked())
+                }
+            }
+
+            /// Similar to [`*const T::as_ref_unchecked()`](https://doc.rust-lang.org/std/primitive.pointer.html#method.as_ref_unchecked),
+            /// with the same safety caveats.
+            pub unsafe fn as_ref_unchecked<'__soa_a>(self) -> #ref_name<'__soa_a, #generic_args> {
+                #ref_name {
+                    #(#fields_names: #deref, )*
                 }
             }
 
             /// Similar to [`*const T::offset()`](https://doc.rust-lang.org/std/primitive.pointer.html#method.offset),
             /// with the same safety caveats.
-            pub unsafe fn offset(self, count: isize) -> #ptr_name {
+            pub unsafe fn offset(self, count: isize) -> #ptr_name<#generic_args> {
                 #ptr_name {
                     #(#fields_names: self.#fields_names.offset(count), )*
                 }
             }
 
             /// Similar to [`*const T::offset()`](https://doc.rust-lang.org/std/primitive.pointer.html#method.offset).
-            pub fn wrapping_offset(self, count: isize) -> #ptr_name {
+            pub fn wrapping_offset(self, count: isize) -> #ptr_name<#generic_args> {
                 #ptr_name {
                     #(#fields_names: self.#fields_names.wrapping_offset(count), )*
                 }
@@ -134,7 +150,7 @@ pub fn derive(input: &Input) -> TokenStream {
 
             /// Similar to [`*const T::add()`](https://doc.rust-lang.org/std/primitive.pointer.html#method.add),
             /// with the same safety caveats.
-            pub unsafe fn add(self, count: usize) -> #ptr_name {
+            pub unsafe fn add(self, count: usize) -> #ptr_name<#generic_args> {
                 #ptr_name {
                     #(#fields_names: self.#fields_names.add(count), )*
                 }
@@ -142,21 +158,21 @@ pub fn derive(input: &Input) -> TokenStream {
 
             /// Similar to [`*const T::sub()`](https://doc.rust-lang.org/std/primitive.pointer.html#method.sub),
             /// with the same safety caveats.
-            pub unsafe fn sub(self, count: usize) -> #ptr_name {
+            pub unsafe fn sub(self, count: usize) -> #ptr_name<#generic_args> {
                 #ptr_name {
                     #(#fields_names: self.#fields_names.sub(count), )*
                 }
             }
 
             /// Similar to [`*const T::wrapping_add()`](https://doc.rust-lang.org/std/primitive.pointer.html#method.wrapping_add).
-            pub fn wrapping_add(self, count: usize) -> #ptr_name {
+            pub fn wrapping_add(self, count: usize) -> #ptr_name<#generic_args> {
                 #ptr_name {
                     #(#fields_names: self.#fields_names.wrapping_add(count), )*
                 }
             }
 
             /// Similar to [`*const T::wrapping_sub()`](https://doc.rust-lang.org/std/primitive.pointer.html#method.wrapping_sub).
-            pub fn wrapping_sub(self, count: usize) -> #ptr_name {
+            pub fn wrapping_sub(self, count: usize) -> #ptr_name<#generic_args> {
                 #ptr_name {
                     #(#fields_names: self.#fields_names.wrapping_sub(count), )*
                 }
@@ -164,43 +180,52 @@ pub fn derive(input: &Input) -> TokenStream {
 
             /// Similar to [`*const T::read()`](https://doc.rust-lang.org/std/primitive.pointer.html#method.read),
             /// with the same safety caveats.
-            pub unsafe fn read(self) -> #name {
+            pub unsafe fn read(self) -> #name<#generic_args> {
                 #name {
                     #(#fields_names: self.#fields_names.read(), )*
+                    #skipped_fields_init
                 }
             }
 
             /// Similar to [`*const T::read_volatile()`](https://doc.rust-lang.org/std/primitive.pointer.html#method.read_volatile),
             /// with the same safety caveats.
-            pub unsafe fn read_volatile(self) -> #name {
+            pub unsafe fn read_volatile(self) -> #name<#generic_args> {
                 #name {
                     #(#fields_names: self.#fields_names.read_volatile(), )*
+                    #skipped_fields_init
                 }
             }
 
             /// Similar to [`*const T::read_unaligned()`](https://doc.rust-lang.org/std/primitive.pointer.html#method.read_unaligned),
             /// with the same safety caveats.
-            pub unsafe fn read_unaligned(self) -> #name {
+            pub unsafe fn read_unaligned(self) -> #name<#generic_args> {
                 #name {
                     #(#fields_names: self.#fields_names.read_unaligned(), )*
+                    #skipped_fields_init
                 }
             }
+
+            /// Similar to [`*const T::copy_to()`](https://doc.rust-lang.org/std/primitive.pointer.html#method.copy_to),
+            /// with the same safety caveats. Each column is copied at once.
+            pub unsafe fn copy_to(self, dest: #ptr_mut_name<#generic_args>, count: usize) {
+                #(self.#fields_names.copy_to(dest.#fields_names, count);)*
+            }
         }
 
-        impl ::soa_derive::SoAPointers for #name {
-            type Ptr = #ptr_name;
-            type MutPtr = #ptr_mut_name;
+        impl<#generic_params> ::soa_derive::SoAPointers for #name<#generic_args> where #where_predicates {
+            type Ptr = #ptr_name<#generic_args>;
+            type MutPtr = #ptr_mut_name<#generic_args>;
         }
 
         #[allow(dead_code)]
         #[allow(clippy::forget_non_drop)]
-        impl #ptr_mut_name {
+        impl<#generic_params> #ptr_mut_name<#generic_args> where #where_predicates {
             /// Convert a
             #[doc = #ptr_mut_doc_url]
             /// to a
             #[doc = #ptr_doc_url]
             /// ; *i.e.* do a `*mut T as *const T` transformation
-            #visibility fn as_ptr(&self) -> #ptr_name {
+            #visibility fn as_ptr(&self) -> #ptr_name<#generic_args> {
                 #ptr_name {
                     #( #fields_names: #as_ptr, )*
                 }
@@ -213,38 +238,50 @@ pub fn derive(input: &Input) -> TokenStream {
 
             /// Similar to [`*mut T::as_ref()`](https://doc.rust-lang.org/std/primitive.pointer.html#method.as_ref),
             /// with the same safety caveats.
-            pub unsafe fn as_ref<'a>(self) -> Option<#ref_name<'a>> {
+            pub unsafe fn as_ref<'__soa_a>(self) -> Option<#ref_name<'__soa_a, #generic_args>> {
                 if self.is_null() {
                     None
                 } else {
-                    Some(#ref_name {
-                        #(#fields_names: self.#fields_names.as_ref().expect("should not be null"), )*
-                    })
+                    Some(self.as_ref_unchecked())
+                }
+            }
+
+            /// Similar to [`*mut T::as_ref_unchecked()`](https://doc.rust-lang.org/std/primitive.pointer.html#method.as_ref_unchecked),
+            /// with the same safety caveats.
+            pub unsafe fn as_ref_unchecked<'__soa_a>(self) -> #ref_name<'__soa_a, #generic_args> {
+                #ref_name {
+                    #(#fields_names: #deref, )*
                 }
             }
 
             /// Similar to [`*mut T::as_mut()`](https://doc.rust-lang.org/std/primitive.pointer.html#method.as_mut),
             /// with the same safety caveats.
-            pub unsafe fn as_mut<'a>(self) -> Option<#ref_mut_name<'a>> {
+            pub unsafe fn as_mut<'__soa_a>(self) -> Option<#ref_mut_name<'__soa_a, #generic_args>> {
                 if self.is_null() {
                     None
                 } else {
-                    Some(#ref_mut_name {
-                        #(#fields_names: self.#fields_names.as_mut().expect("should not be null"), )*
-                    })
+                    Some(self.as_mut_unchecked())
+                }
+            }
+
+            /// Similar to [`*mut T::as_mut_unchecked()`](https://doc.rust-lang.org/std/primitive.pointer.html#method.as_mut_unchecked),
+            /// with the same safety caveats.
+            pub unsafe fn as_mut_unchecked<'__soa_a>(self) -> #ref_mut_name<'__soa_a, #generic_args> {
+                #ref_mut_name {
+                    #(#fields_names: #deref_mut, )*
                 }
             }
 
             /// Similar to [`*mut T::offset()`](https://doc.rust-lang.org/std/primitive.pointer.html#method.offset),
             /// with the same safety caveats.
-            pub unsafe fn offset(self, count: isize) -> #ptr_mut_name {
+            pub unsafe fn offset(self, count: isize) -> #ptr_mut_name<#generic_args> {
                 #ptr_mut_name {
                     #(#fields_names: self.#fields_names.offset(count), )*
                 }
             }
 
             /// Similar to [`*mut T::wrapping_offset()`](https://doc.rust-lang.org/std/primitive.pointer.html#method.wrapping_offset)
-            pub fn wrapping_offset(self, count: isize) -> #ptr_mut_name {
+            pub fn wrapping_offset(self, count: isize) -> #ptr_mut_name<#generic_args> {
                 #ptr_mut_name {
                     #(#fields_names: self.#fields_names.wrapping_offset(count), )*
                 }
@@ -252,7 +289,7 @@ pub fn derive(input: &Input) -> TokenStream {
 
             /// Similar to [`*mut T::add()`](https://doc.rust-lang.org/std/primitive.pointer.html#method.add),
             /// with the same safety caveats.
-            pub unsafe fn add(self, count: usize) -> #ptr_mut_name {
+            pub unsafe fn add(self, count: usize) -> #ptr_mut_name<#generic_args> {
                 #ptr_mut_name {
                     #(#fields_names: self.#fields_names.add(count), )*
                 }
@@ -260,7 +297,7 @@ pub fn derive(input: &Input) -> TokenStream {
 
             /// Similar to [`*mut T::sub()`](https://doc.rust-lang.org/std/primitive.pointer.html#method.sub),
             /// with the same safety caveats.
-            pub unsafe fn sub(self, count: usize) -> #ptr_mut_name {
+            pub unsafe fn sub(self, count: usize) -> #ptr_mut_name<#generic_args> {
                 #ptr_mut_name {
                     #(#fields_names: self.#fields_names.sub(count), )*
                 }
@@ -268,7 +305,7 @@ pub fn derive(input: &Input) -> TokenStream {
 
             /// Similar to [`*mut T::wrapping_add()`](https://doc.rust-lang.org/std/primitive.pointer.html#method.wrapping_add),
             /// with the same safety caveats.
-            pub fn wrapping_add(self, count: usize) -> #ptr_mut_name {
+            pub fn wrapping_add(self, count: usize) -> #ptr_mut_name<#generic_args> {
                 #ptr_mut_name {
                     #(#fields_names: self.#fields_names.wrapping_add(count), )*
                 }
@@ -276,7 +313,7 @@ pub fn derive(input: &Input) -> TokenStream {
 
             /// Similar to [`*mut T::wrapping_sub()`](https://doc.rust-lang.org/std/primitive.pointer.html#method.wrapping_sub),
             /// with the same safety caveats.
-            pub fn wrapping_sub(self, count: usize) -> #ptr_mut_name {
+            pub fn wrapping_sub(self, count: usize) -> #ptr_mut_name<#generic_args> {
                 #ptr_mut_name {
                     #(#fields_names: self.#fields_names.wrapping_sub(count), )*
                 }
@@ -284,73 +321,85 @@ pub fn derive(input: &Input) -> TokenStream {
 
             /// Similar to [`*mut T::read()`](https://doc.rust-lang.org/std/primitive.pointer.html#method.read),
             /// with the same safety caveats.
-            pub unsafe fn read(self) -> #name {
+            pub unsafe fn read(self) -> #name<#generic_args> {
                 #name {
                     #(#fields_names: self.#fields_names.read(), )*
+                    #skipped_fields_init
                 }
             }
 
             /// Similar to [`*mut T::read_volatile()`](https://doc.rust-lang.org/std/primitive.pointer.html#method.read_volatile),
             /// with the same safety caveats.
-            pub unsafe fn read_volatile(self) -> #name {
+            pub unsafe fn read_volatile(self) -> #name<#generic_args> {
                 #name {
                     #(#fields_names: self.#fields_names.read_volatile(), )*
+                    #skipped_fields_init
                 }
             }
 
             /// Similar to [`*mut T::read_unaligned()`](https://doc.rust-lang.org/std/primitive.pointer.html#method.read_unaligned),
             /// with the same safety caveats.
-            pub unsafe fn read_unaligned(self) -> #name {
+            pub unsafe fn read_unaligned(self) -> #name<#generic_args> {
                 #name {
                     #(#fields_names: self.#fields_names.read_unaligned(), )*
+                    #skipped_fields_init
                 }
             }
 
+            /// Similar to [`*mut T::copy_to()`](https://doc.rust-lang.org/std/primitive.pointer.html#method.copy_to),
+            /// with the same safety caveats. Each column is copied at once.
+            pub unsafe fn copy_to(self, dest: #ptr_mut_name<#generic_args>, count: usize) {
+                self.as_ptr().copy_to(dest, count);
+            }
+
             /// Similar to [`*mut T::write()`](https://doc.rust-lang.org/std/primitive.pointer.html#method.write),
             /// with the same safety caveats.
             #[allow(clippy::forget_non_drop)]
-            pub unsafe fn write(self, val: #name) {
+            pub unsafe fn write(self, val: #name<#generic_args>) {
                 unsafe {
-                    #(self.#fields_names.write(::std::ptr::read(&val.#fields_names));)*
+                    #(self.#fields_names.write(::core::ptr::read(&val.#fields_names));)*
                 }
+                #drop_skipped_val
                 // if val implements Drop, we don't want to run it here, only
                 // when the vec itself will be dropped
-                ::std::mem::forget(val);
+                ::core::mem::forget(val);
             }
 
             /// Similar to [`*mut T::write_volatile()`](https://doc.rust-lang.org/std/primitive.pointer.html#method.write_volatile),
             /// with the same safety caveats.
             #[allow(clippy::forget_non_drop)]
-            pub unsafe fn write_volatile(self, val: #name) {
+            pub unsafe fn write_volatile(self, val: #name<#generic_args>) {
                 unsafe {
-                    #(self.#fields_names.write_volatile(::std::ptr::read(&val.#fields_names));)*
+                    #(self.#fields_names.write_volatile(::core::ptr::read(&val.#fields_names));)*
                 }
+                #drop_skipped_val
                 // if val implements Drop, we don't want to run it here, only
                 // when the vec itself will be dropped
-                ::std::mem::forget(val);
+                ::core::mem::forget(val);
             }
 
             /// Similar to [`*mut T::write_unaligned()`](https://doc.rust-lang.org/std/primitive.pointer.html#method.write_unaligned),
             /// with the same safety caveats.
             #[allow(clippy::forget_non_drop)]
-            pub unsafe fn write_unaligned(self, val: #name) {
+            pub unsafe fn write_unaligned(self, val: #name<#generic_args>) {
                 unsafe {
-                    #(self.#fields_names.write_unaligned(::std::ptr::read(&val.#fields_names));)*
+                    #(self.#fields_names.write_unaligned(::core::ptr::read(&val.#fields_names));)*
                 }
+                #drop_skipped_val
                 // if val implements Drop, we don't want to run it here, only
                 // when the vec itself will be dropped
-                ::std::mem::forget(val);
+                ::core::mem::forget(val);
             }
         }
 
         #[allow(dead_code)]
-        impl<'a> #ref_name<'a> {
+        impl<'__soa_a, #generic_params> #ref_name<'__soa_a, #generic_args> where #where_predicates {
             /// Convert a
             #[doc = #ref_doc_url]
             /// to a
             #[doc = #ptr_doc_url]
             /// ; *i.e.* do a `&T as *const T` transformation
-            #visibility fn as_ptr(&self) -> #ptr_name {
+            #visibility fn as_ptr(&self) -> #ptr_name<#generic_args> {
                 #ptr_name {
                     #( #fields_names: #as_ptr, )*
                 }
@@ -358,13 +407,13 @@ pub fn derive(input: &Input) -> TokenStream {
         }
 
         #[allow(dead_code)]
-        impl<'a> #ref_mut_name<'a> {
+        impl<'__soa_a, #generic_params> #ref_mut_name<'__soa_a, #generic_args> where #where_predicates {
             /// Convert a
             #[doc = #ref_mut_doc_url]
             /// to a
             #[doc = #ptr_doc_url]
             /// ; *i.e.* do a `&mut T as *const T` transformation
-            #visibility fn as_ptr(&self) -> #ptr_name {
+            #visibility fn as_ptr(&self) -> #ptr_name<#generic_args> {
                 #ptr_name {
                     #( #fields_names: #as_ptr, )*
                 }
@@ -375,7 +424,7 @@ pub fn derive(input: &Input) -> TokenStream {
             /// to a
             #[doc = #ptr_mut_doc_url]
             /// ; *i.e.* do a `&mut T as *mut T` transformation
-            #visibility fn as_mut_ptr(&mut self) -> #ptr_mut_name {
+            #visibility fn as_mut_ptr(&mut self) -> #ptr_mut_name<#generic_args> {
                 #ptr_mut_name {
                     #( #fields_names: #as_mut_ptr, )*
                 }
@@ -2,16 +2,19 @@ use proc_macro2::{Ident, Span, TokenStream};
 use quote::quote;
 
 use crate::input::Input;
-use crate::names;
 
 pub fn derive(input: &Input) -> TokenStream {
     let name = &input.name;
     let visibility = &input.visibility;
     let attrs = &input.attrs.ref_;
     let mut_attrs = &input.attrs.ref_mut;
-    let vec_name = names::vec_name(&input.name);
-    let ref_name = names::ref_name(&input.name);
-    let ref_mut_name = names::ref_mut_name(&input.name);
+    let vec_name = &input.names.vec;
+    let ref_name = &input.names.ref_;
+    let ref_mut_name = &input.names.ref_mut;
+
+    let generic_params = input.generic_params();
+    let generic_args = input.generic_args();
+    let where_predicates = input.where_predicates();
 
     let fields_types = &input.fields.iter()
         .map(|field| field.ty.clone())
@@ -22,9 +25,9 @@ pub fn derive(input: &Input) -> TokenStream {
     let ref_doc_url = format!("[`{0}`](struct.{0}.html)", ref_name);
     let ref_mut_doc_url = format!("[`{0}`](struct.{0}.html)", ref_mut_name);
 
-    let fields_names = &input.fields.iter()
-        .map(|field| field.ident.clone().unwrap())
-        .collect::<Vec<_>>();
+    let fields_names = &input.fields_names();
+    let skipped_fields_init = input.skipped_fields_init();
+    let drop_skipped_val = input.drop_skipped_fields(&quote! { val });
 
     let fields_names_hygienic = input.fields.iter()
         .enumerate()
@@ -32,28 +35,22 @@ pub fn derive(input: &Input) -> TokenStream {
         .collect::<Vec<_>>();
 
     let ref_fields_types = input.map_fields_nested_or(
-        |_, field_type| {
-            let field_ptr_type = names::ref_name(field_type);
-            quote! { #field_ptr_type<'a> }
-        },
-        |_, field_type| quote! { &'a #field_type },
+        |_, field_type| quote! { <#field_type as ::soa_derive::SoAIter<'__soa_a>>::Ref },
+        |_, field_type| quote! { &'__soa_a #field_type },
     ).collect::<Vec<_>>();
 
     let ref_mut_fields_types = input.map_fields_nested_or(
-        |_, field_type| {
-            let field_ptr_type = names::ref_mut_name(field_type);
-            quote! { #field_ptr_type<'a> }
-        },
-        |_, field_type| quote! { &'a mut #field_type },
+        |_, field_type| quote! { <#field_type as ::soa_derive::SoAIter<'__soa_a>>::RefMut },
+        |_, field_type| quote! { &'__soa_a mut #field_type },
     ).collect::<Vec<_>>();
 
     let as_ref = input.map_fields_nested_or(
-        |ident, _| quote! { self.#ident.as_ref() },
+        |ident, field_type| quote! { <#field_type as ::soa_derive::SoAAsRef<'_>>::as_soa_ref(&self.#ident) },
         |ident, _| quote! { &self.#ident },
     ).collect::<Vec<_>>();
 
     let as_mut = input.map_fields_nested_or(
-        |ident, _| quote! { self.#ident.as_mut() },
+        |ident, field_type| quote! { <#field_type as ::soa_derive::SoAAsRef<'_>>::as_soa_mut(&mut self.#ident) },
         |ident, _| quote! { &mut self.#ident },
     ).collect::<Vec<_>>();
 
@@ -64,51 +61,53 @@ pub fn derive(input: &Input) -> TokenStream {
 
     let ref_replace = input.map_fields_nested_or(
         |ident, _| quote! { self.#ident.replace(field) },
-        |ident, _| quote! { ::std::mem::replace(&mut *self.#ident, field) },
+        |ident, _| quote! { ::core::mem::replace(&mut *self.#ident, field) },
     ).collect::<Vec<_>>();
 
+    // references serialize like the input struct, using the same attributes
+    let fields_serde_attrs = input.fields_serde_attributes();
+    let fields_docs = fields_names.iter().zip(&fields_serde_attrs).map(|(field, serde_attrs)| quote! {
+        /// reference to the `
+        #[doc = stringify!(#field)]
+        ///` field of a single
+        #[doc = #doc_url]
+        /// inside a
+        #[doc = #vec_doc_url]
+        #(#serde_attrs)*
+    }).collect::<Vec<_>>();
+    let ref_body = input.struct_body(Some(&quote! { '__soa_a }), &fields_docs, &ref_fields_types);
+    let ref_mut_body = input.struct_body(Some(&quote! { '__soa_a }), &fields_docs, &ref_mut_fields_types);
+
     quote! {
         /// A reference to a
         #[doc = #doc_url]
         /// with struct of array layout.
         #(#[#attrs])*
-        #[derive(Copy, Clone)]
-        #visibility struct #ref_name<'a> {
-            #(
-                /// reference to the `
-                #[doc = stringify!(#fields_names)]
-                ///` field of a single
-                #[doc = #doc_url]
-                /// inside a
-                #[doc = #vec_doc_url]
-                pub #fields_names: #ref_fields_types,
-            )*
+        #visibility struct #ref_name<'__soa_a, #generic_params> #ref_body
+
+        // References are always Copy, regardless of the fields types, so we can
+        // not use `#[derive(Copy, Clone)]` which would require `T: Copy`.
+        impl<'__soa_a, #generic_params> Copy for #ref_name<'__soa_a, #generic_args> where #where_predicates {}
+        impl<'__soa_a, #generic_params> Clone for #ref_name<'__soa_a, #generic_args> where #where_predicates {
+            fn clone(&self) -> Self {
+                *self
+            }
         }
 
         /// A mutable reference to a
         #[doc = #doc_url]
         /// with struct of array layout.
         #(#[#mut_attrs])*
-        #visibility struct #ref_mut_name<'a> {
-            #(
-                /// reference to the `
-                #[doc = stringify!(#fields_names)]
-                ///` field of a single
-                #[doc = #doc_url]
-                /// inside a
-                #[doc = #vec_doc_url]
-                pub #fields_names: #ref_mut_fields_types,
-            )*
-        }
+        #visibility struct #ref_mut_name<'__soa_a, #generic_params> #ref_mut_body
 
         #[allow(dead_code)]
-        impl #name {
+        impl<#generic_params> #name<#generic_args> where #where_predicates {
             /// Create a
             #[doc = #ref_doc_url]
             /// from a borrowed
             #[doc = #doc_url]
             /// .
-            #visibility fn as_ref(&self) -> #ref_name {
+            #visibility fn as_ref(&self) -> #ref_name<'_, #generic_args> {
                 #ref_name {
                     #( #fields_names: #as_ref, )*
                 }
@@ -119,79 +118,82 @@ pub fn derive(input: &Input) -> TokenStream {
             /// from a mutably borrowed
             #[doc = #doc_url]
             /// .
-            #visibility fn as_mut(&mut self) -> #ref_mut_name {
+            #visibility fn as_mut(&mut self) -> #ref_mut_name<'_, #generic_args> {
                 #ref_mut_name {
                     #( #fields_names: #as_mut, )*
                 }
             }
         }
 
-        impl<'a> #ref_name<'a> {
+        impl<'__soa_a, #generic_params> #ref_name<'__soa_a, #generic_args> where #where_predicates {
             /// Convert a reference to
             #[doc = #doc_url]
             /// into an owned value. This is only available if all fields
             /// implement `Clone`.
-            pub fn to_owned(&self) -> #name
+            pub fn to_owned(&self) -> #name<#generic_args>
                 // only expose to_owned if all fields are Clone
                 // https://github.com/rust-lang/rust/issues/48214#issuecomment-1150463333
-                where #( for<'b> #fields_types: Clone, )*
+                where #( for<'__soa_b> #fields_types: Clone, )*
             {
                 #name {
                     #( #fields_names: #to_owned, )*
+                    #skipped_fields_init
                 }
             }
         }
 
-        impl<'a>  From<#ref_name<'a>> for #name where #( for<'b> #fields_types: Clone, )* {
-            fn from(value: #ref_name<'a>) -> #name {
+        impl<'__soa_a, #generic_params> From<#ref_name<'__soa_a, #generic_args>> for #name<#generic_args> where #( for<'__soa_b> #fields_types: Clone, )* #where_predicates {
+            fn from(value: #ref_name<'__soa_a, #generic_args>) -> #name<#generic_args> {
                 value.to_owned()
             }
         }
 
-        impl<'a>  From<&'a #ref_name<'a>> for #name where #( for<'b> #fields_types: Clone, )* {
-            fn from(value: &'a #ref_name<'a>) -> #name {
+        impl<'__soa_a, #generic_params> From<&'__soa_a #ref_name<'__soa_a, #generic_args>> for #name<#generic_args> where #( for<'__soa_b> #fields_types: Clone, )* #where_predicates {
+            fn from(value: &'__soa_a #ref_name<'__soa_a, #generic_args>) -> #name<#generic_args> {
                 value.to_owned()
             }
         }
 
-        impl<'a> #ref_mut_name<'a> {
+        impl<'__soa_a, #generic_params> #ref_mut_name<'__soa_a, #generic_args> where #where_predicates {
             /// Convert a mutable reference to
             #[doc = #doc_url]
             /// into an owned value. This is only available if all fields
             /// implement `Clone`.
-            pub fn to_owned(&self) -> #name
+            pub fn to_owned(&self) -> #name<#generic_args>
                 // only expose to_owned if all fields are Clone
                 // https://github.com/rust-lang/rust/issues/48214#issuecomment-1150463333
-                where #( for<'b> #fields_types: Clone, )*
+                where #( for<'__soa_b> #fields_types: Clone, )*
             {
                 #name {
                     #( #fields_names: #to_owned, )*
+                    #skipped_fields_init
                 }
             }
 
             /// Similar to [`std::mem::replace()`](https://doc.rust-lang.org/std/mem/fn.replace.html).
             #[allow(clippy::forget_non_drop)]
-            pub fn replace(&mut self, val: #name) -> #name {
+            pub fn replace(&mut self, val: #name<#generic_args>) -> #name<#generic_args> {
                 #(
-                    let field = unsafe { ::std::ptr::read(&val.#fields_names) };
+                    let field = unsafe { ::core::ptr::read(&val.#fields_names) };
                     let #fields_names_hygienic = #ref_replace;
                 )*
+                #drop_skipped_val
                 // if val implements Drop, we don't want to run it here, only
                 // when the vec itself will be dropped
-                ::std::mem::forget(val);
+                ::core::mem::forget(val);
 
-                #name{#(#fields_names: #fields_names_hygienic),*}
+                #name{#(#fields_names: #fields_names_hygienic,)* #skipped_fields_init}
             }
         }
 
-        impl<'a>  From<#ref_mut_name<'a>> for #name where #( for<'b> #fields_types: Clone, )* {
-            fn from(value: #ref_mut_name<'a>) -> #name {
+        impl<'__soa_a, #generic_params> From<#ref_mut_name<'__soa_a, #generic_args>> for #name<#generic_args> where #( for<'__soa_b> #fields_types: Clone, )* #where_predicates {
+            fn from(value: #ref_mut_name<'__soa_a, #generic_args>) -> #name<#generic_args> {
                 value.to_owned()
             }
         }
 
-        impl<'a>  From<&'a #ref_mut_name<'a>> for #name where #( for<'b> #fields_types: Clone, )* {
-            fn from(value: &'a #ref_mut_name<'a>) -> #name {
+        impl<'__soa_a, #generic_params> From<&'__soa_a #ref_mut_name<'__soa_a, #generic_args>> for #name<#generic_args> where #( for<'__soa_b> #fields_types: Clone, )* #where_predicates {
+            fn from(value: &'__soa_a #ref_mut_name<'__soa_a, #generic_args>) -> #name<#generic_args> {
                 value.to_owned()
             }
         }
@@ -0,0 +1,160 @@
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+
+use crate::input::{Input, SerdeLayout};
+
+/// Generate the serde implementations which can not be derived: the
+/// deserialization of the `Vec` type checking the length of the columns with
+/// the columns layout, and the (de)serialization of the `Vec` and slices types
+/// as sequences of the input struct with the rows layout.
+pub fn derive(input: &Input) -> TokenStream {
+    match input.serde_layout {
+        SerdeLayout::Columns => derive_columns(input),
+        SerdeLayout::Rows => derive_rows(input),
+    }
+}
+
+fn derive_columns(input: &Input) -> TokenStream {
+    if input.attrs.deserialize.is_empty() {
+        return TokenStream::new();
+    }
+
+    let vec_name = &input.names.vec;
+    let vec_name_str = vec_name.to_string();
+    let deserialize_attrs = &input.attrs.deserialize;
+
+    let generic_params = input.generic_params();
+    let generic_args = input.generic_args();
+    let where_predicates = input.where_predicates();
+
+    let fields_names = &input.fields_names();
+    let vec_fields_types = input.map_fields_nested_or(
+        |_, field_type| quote! { <#field_type as ::soa_derive::StructOfArray>::Type },
+        |_, field_type| quote! { ::soa_derive::alloc::vec::Vec<#field_type> },
+    ).collect::<Vec<_>>();
+
+    let columns_name = Ident::new("___soa_derive_private_columns", Span::call_site());
+    let columns_docs = vec![quote! {}; vec_fields_types.len()];
+    let columns_body = input.struct_body_with_visibility(None, &columns_docs, &vec_fields_types, &quote! {});
+
+    quote! {
+        // the vector is deserialized through a helper struct with the same
+        // fields, and the length of the columns is then checked by
+        // `from_columns()`
+        const _: () = {
+            #[allow(non_camel_case_types)]
+            #(#[#deserialize_attrs])*
+            struct #columns_name<#generic_params> #columns_body
+
+            impl<'__soa_de, #generic_params> ::serde::Deserialize<'__soa_de> for #vec_name<#generic_args>
+            where
+                #columns_name<#generic_args>: ::serde::Deserialize<'__soa_de>,
+                #where_predicates
+            {
+                fn deserialize<__SoaD>(deserializer: __SoaD) -> Result<Self, __SoaD::Error> where __SoaD: ::serde::Deserializer<'__soa_de> {
+                    let columns = #columns_name::deserialize(deserializer)?;
+                    #vec_name::from_columns(#(columns.#fields_names),*).map_err(|error| {
+                        <__SoaD::Error as ::serde::de::Error>::custom(format_args!("invalid {}: {}", #vec_name_str, error))
+                    })
+                }
+            }
+        };
+    }
+}
+
+fn derive_rows(input: &Input) -> TokenStream {
+    let name = &input.name;
+    let vec_name = &input.names.vec;
+    let slice_name = &input.names.slice;
+    let slice_mut_name = &input.names.slice_mut;
+    let ref_name = &input.names.ref_;
+
+    let generic_params = input.generic_params();
+    let generic_args = input.generic_args();
+    let where_predicates = input.where_predicates();
+
+    let mut generated = TokenStream::new();
+
+    if input.attrs.derive_serialize {
+        generated.extend(quote! {
+            impl<#generic_params> ::serde::Serialize for #vec_name<#generic_args>
+            where
+                for<'__soa_b> #ref_name<'__soa_b, #generic_args>: ::serde::Serialize,
+                #where_predicates
+            {
+                fn serialize<__SoaS>(&self, serializer: __SoaS) -> Result<__SoaS::Ok, __SoaS::Error> where __SoaS: ::serde::Serializer {
+                    serializer.collect_seq(self.iter())
+                }
+            }
+
+            impl<'__soa_a, #generic_params> ::serde::Serialize for #slice_name<'__soa_a, #generic_args>
+            where
+                for<'__soa_b> #ref_name<'__soa_b, #generic_args>: ::serde::Serialize,
+                #where_predicates
+            {
+                fn serialize<__SoaS>(&self, serializer: __SoaS) -> Result<__SoaS::Ok, __SoaS::Error> where __SoaS: ::serde::Serializer {
+                    serializer.collect_seq(self.iter())
+                }
+            }
+
+            impl<'__soa_a, #generic_params> ::serde::Serialize for #slice_mut_name<'__soa_a, #generic_args>
+            where
+                for<'__soa_b> #ref_name<'__soa_b, #generic_args>: ::serde::Serialize,
+                #where_predicates
+            {
+                fn serialize<__SoaS>(&self, serializer: __SoaS) -> Result<__SoaS::Ok, __SoaS::Error> where __SoaS: ::serde::Serializer {
+                    serializer.collect_seq(self.as_slice())
+                }
+            }
+        });
+    }
+
+    if input.attrs.derive_deserialize {
+        let visitor_name = Ident::new("___soa_derive_private_visitor", Span::call_site());
+        let expecting = format!("a sequence of {}", name);
+
+        generated.extend(quote! {
+            // the elements are pushed one by one to the vector while
+            // deserializing the sequence
+            const _: () = {
+                #[allow(non_camel_case_types)]
+                struct #visitor_name<#generic_params>(::core::marker::PhantomData<#vec_name<#generic_args>>) where #where_predicates;
+
+                impl<'__soa_de, #generic_params> ::serde::de::Visitor<'__soa_de> for #visitor_name<#generic_args>
+                where
+                    #name<#generic_args>: ::serde::Deserialize<'__soa_de>,
+                    #where_predicates
+                {
+                    type Value = #vec_name<#generic_args>;
+
+                    fn expecting(&self, formatter: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                        formatter.write_str(#expecting)
+                    }
+
+                    fn visit_seq<__SoaA>(self, mut seq: __SoaA) -> Result<Self::Value, __SoaA::Error> where __SoaA: ::serde::de::SeqAccess<'__soa_de> {
+                        // do not trust the size hint too much, like serde
+                        // does for `Vec<T>`
+                        let capacity = ::core::cmp::min(seq.size_hint().unwrap_or(0), 4096);
+                        let mut vec = #vec_name::with_capacity(capacity);
+                        while let Some(value) = seq.next_element()? {
+                            vec.push(value);
+                        }
+                        Ok(vec)
+                    }
+                }
+
+                impl<'__soa_de, #generic_params> ::serde::Deserialize<'__soa_de> for #vec_name<#generic_args>
+                where
+                    #name<#generic_args>: ::serde::Deserialize<'__soa_de>,
+                    #where_predicates
+                {
+                    fn deserialize<__SoaD>(deserializer: __SoaD) -> Result<Self, __SoaD::Error> where __SoaD: ::serde::Deserializer<'__soa_de> {
+                        deserializer.deserialize_seq(#visitor_name(::core::marker::PhantomData))
+                    }
+                }
+            };
+        });
+    }
+
+    return generated;
+}
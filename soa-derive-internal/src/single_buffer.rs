@@ -0,0 +1,775 @@
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::TokenStreamExt;
+use quote::quote;
+
+use crate::input::Input;
+
+/// Generate the `Vec` type for `#[soa(storage = "single_buffer")]`. Instead of
+/// one `Vec` per field, this type manages a single allocation, split in one
+/// region per field. The region of each field starts at an offset computed
+/// with `soa_derive::single_buffer::layout`, and we keep a pointer to the start
+/// of each region in a `PtrMut`. The regions move every time the capacity
+/// changes, so the whole buffer is re-allocated and the data copied over.
+pub fn derive(input: &Input) -> TokenStream {
+    let name = &input.name;
+    let vec_name_str = format!("Vec<{}>", name);
+    let attrs = &input.attrs.vec;
+    let visibility = &input.visibility;
+    let vec_name = &input.names.vec;
+    let slice_name = &input.names.slice;
+    let slice_mut_name = &input.names.slice_mut;
+    let ref_name = &input.names.ref_;
+    let ref_mut_name = &input.names.ref_mut;
+    let ptr_name = &input.names.ptr;
+    let ptr_mut_name = &input.names.ptr_mut;
+    let into_iter_name = &input.names.into_iter;
+
+    let generic_params = input.generic_params();
+    let generic_args = input.generic_args();
+    let where_predicates = input.where_predicates();
+
+    let doc_url = format!("[`{0}`](struct.{0}.html)", name);
+    let vec_doc_url = format!("[`{0}`](struct.{0}.html)", vec_name);
+
+    let fields_names = &input.fields_names();
+    let fields_types = &input.fields.iter().map(|field| &field.ty).collect::<Vec<_>>();
+    let fields_count = input.fields.len();
+    let fields_index = (0..fields_count).collect::<Vec<_>>();
+    let first_field = &fields_names[0];
+
+    let skipped_fields_init = input.skipped_fields_init();
+    let drop_skipped_value = input.drop_skipped_fields(&quote! { value });
+    let drop_skipped_element = input.drop_skipped_fields(&quote! { element });
+
+    let fields_names_hygienic = input.fields.iter()
+        .enumerate()
+        .map(|(i, _)| Ident::new(&format!("___soa_derive_private_{}", i), Span::call_site()))
+        .collect::<Vec<_>>();
+
+    let mut generated = quote! {
+        /// An analog to `
+        #[doc = #vec_name_str]
+        /// ` with Struct of Array (SoA) layout, storing all the fields in a
+        /// single allocation.
+        #[allow(dead_code)]
+        #(#[#attrs])*
+        #visibility struct #vec_name<#generic_params> where #where_predicates {
+            // pointers to the start of each field region in the allocation
+            data: ::soa_derive::single_buffer::Pointers<#ptr_mut_name<#generic_args>>,
+            len: usize,
+            capacity: usize,
+            marker: ::core::marker::PhantomData<(#(#fields_types,)*)>,
+        }
+
+        impl<#generic_params> Default for #vec_name<#generic_args> where #where_predicates {
+            fn default() -> Self {
+                #vec_name {
+                    data: ::soa_derive::single_buffer::Pointers(#ptr_mut_name {
+                        #(#fields_names: ::core::ptr::NonNull::dangling().as_ptr(),)*
+                    }),
+                    len: 0,
+                    // if all the fields are zero-sized, we never need to allocate
+                    capacity: if Self::is_zero_sized() { usize::MAX } else { 0 },
+                    marker: ::core::marker::PhantomData,
+                }
+            }
+        }
+
+        #[allow(dead_code)]
+        #[allow(clippy::forget_non_drop)]
+        impl<#generic_params> #vec_name<#generic_args> where #where_predicates {
+            /// Are all the fields zero-sized types?
+            fn is_zero_sized() -> bool {
+                #(::core::mem::size_of::<#fields_types>() == 0)&&*
+            }
+
+            /// Get the layout of the allocation for the given `capacity`,
+            /// and the offset of each field in this allocation.
+            fn buffer_layout(capacity: usize) -> (::core::alloc::Layout, [usize; #fields_count]) {
+                let fields = [#(::core::alloc::Layout::new::<#fields_types>(),)*];
+                let mut offsets = [0; #fields_count];
+                let layout = ::soa_derive::single_buffer::layout(&fields, capacity, &mut offsets)
+                    .expect("capacity overflow");
+                (layout, offsets)
+            }
+
+            /// Move the data to a new allocation with the given `capacity`,
+            /// which must be larger than the current length.
+            fn reallocate(&mut self, capacity: usize) {
+                debug_assert!(capacity >= self.len);
+                let (layout, offsets) = Self::buffer_layout(capacity);
+                let data = if layout.size() == 0 {
+                    #ptr_mut_name {
+                        #(#fields_names: ::core::ptr::NonNull::dangling().as_ptr(),)*
+                    }
+                } else {
+                    unsafe {
+                        let buffer = ::soa_derive::alloc::alloc::alloc(layout);
+                        if buffer.is_null() {
+                            ::soa_derive::alloc::alloc::handle_alloc_error(layout);
+                        }
+                        #ptr_mut_name {
+                            #(#fields_names: buffer.add(offsets[#fields_index]).cast::<#fields_types>(),)*
+                        }
+                    }
+                };
+
+                unsafe {
+                    #(::core::ptr::copy_nonoverlapping(self.data.0.#fields_names, data.#fields_names, self.len);)*
+                    self.deallocate();
+                }
+                self.data.0 = data;
+                self.capacity = capacity;
+            }
+
+            /// Release the allocation, without dropping any element
+            unsafe fn deallocate(&mut self) {
+                let (layout, _) = Self::buffer_layout(self.capacity);
+                if layout.size() != 0 {
+                    // the first field is always at the start of the allocation
+                    ::soa_derive::alloc::alloc::dealloc(self.data.0.#first_field.cast::<u8>(), layout);
+                }
+            }
+
+            /// Similar to [`
+            #[doc = #vec_name_str]
+            /// ::new()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.new)
+            pub fn new() -> #vec_name<#generic_args> {
+                Default::default()
+            }
+
+            /// Similar to [`
+            #[doc = #vec_name_str]
+            /// ::with_capacity()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.with_capacity),
+            /// allocating space for `capacity` elements of all fields at once.
+            pub fn with_capacity(capacity: usize) -> #vec_name<#generic_args> {
+                let mut vec = Self::new();
+                vec.reserve_exact(capacity);
+                vec
+            }
+
+            /// Similar to [`
+            #[doc = #vec_name_str]
+            /// ::capacity()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.capacity),
+            /// all fields share the same capacity.
+            pub fn capacity(&self) -> usize {
+                self.capacity
+            }
+
+            /// Similar to [`
+            #[doc = #vec_name_str]
+            /// ::reserve()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.reserve),
+            /// reserving the same `additional` space for all fields with a
+            /// single allocation.
+            pub fn reserve(&mut self, additional: usize) {
+                let required = self.len.checked_add(additional).expect("capacity overflow");
+                if required > self.capacity {
+                    let capacity = required.max(2 * self.capacity).max(4);
+                    self.reallocate(capacity);
+                }
+            }
+
+            /// Similar to [`
+            #[doc = #vec_name_str]
+            /// ::reserve_exact()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.reserve_exact)
+            /// reserving the same `additional` space for all fields with a
+            /// single allocation.
+            pub fn reserve_exact(&mut self, additional: usize) {
+                let required = self.len.checked_add(additional).expect("capacity overflow");
+                if required > self.capacity {
+                    self.reallocate(required);
+                }
+            }
+
+            /// Similar to [`
+            #[doc = #vec_name_str]
+            /// ::shrink_to_fit()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.shrink_to_fit)
+            /// shrinking all fields.
+            pub fn shrink_to_fit(&mut self) {
+                if self.capacity > self.len && !Self::is_zero_sized() {
+                    self.reallocate(self.len);
+                }
+            }
+
+            /// Similar to [`
+            #[doc = #vec_name_str]
+            /// ::truncate()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.truncate)
+            /// truncating all fields.
+            pub fn truncate(&mut self, len: usize) {
+                if len >= self.len {
+                    return;
+                }
+                let removed = self.len - len;
+                self.len = len;
+                unsafe {
+                    #(::core::ptr::drop_in_place(::core::ptr::slice_from_raw_parts_mut(self.data.0.#fields_names.add(len), removed));)*
+                }
+            }
+
+            /// Similar to [`
+            #[doc = #vec_name_str]
+            /// ::push()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.push).
+            #[allow(clippy::forget_non_drop)]
+            pub fn push(&mut self, value: #name<#generic_args>) {
+                if self.len == self.capacity {
+                    self.reserve(1);
+                }
+                // We need to use ptr read/write instead of moving out of the
+                // fields in case the value struct implements Drop.
+                unsafe {
+                    #(self.data.0.#fields_names.add(self.len).write(::core::ptr::read(&value.#fields_names));)*
+                }
+                self.len += 1;
+                #drop_skipped_value
+                // if value implements Drop, we don't want to run it here, only
+                // when the vec itself will be dropped.
+                ::core::mem::forget(value);
+            }
+
+            /// Similar to [`
+            #[doc = #vec_name_str]
+            /// ::len()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.len).
+            pub fn len(&self) -> usize {
+                self.len
+            }
+
+            /// Similar to [`
+            #[doc = #vec_name_str]
+            /// ::is_empty()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.is_empty).
+            pub fn is_empty(&self) -> bool {
+                self.len == 0
+            }
+
+            /// Similar to [`
+            #[doc = #vec_name_str]
+            /// ::swap_remove()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.swap_remove).
+            pub fn swap_remove(&mut self, index: usize) -> #name<#generic_args> {
+                if index >= self.len {
+                    panic!("swap_remove index (is {}) should be < len (is {})", index, self.len);
+                }
+
+                self.len -= 1;
+                unsafe {
+                    #(
+                        let #fields_names_hygienic = ::core::ptr::read(self.data.0.#fields_names.add(index));
+                        ::core::ptr::copy(self.data.0.#fields_names.add(self.len), self.data.0.#fields_names.add(index), 1);
+                    )*
+                    #name{#(#fields_names: #fields_names_hygienic,)* #skipped_fields_init}
+                }
+            }
+
+            /// Similar to [`
+            #[doc = #vec_name_str]
+            /// ::insert()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.insert).
+            #[allow(clippy::forget_non_drop)]
+            pub fn insert(&mut self, index: usize, element: #name<#generic_args>) {
+                if index > self.len {
+                    panic!("index out of bounds: the len is {} but the index is {}", self.len, index);
+                }
+
+                if self.len == self.capacity {
+                    self.reserve(1);
+                }
+
+                // similar to push, we can not use move and have to rely on ptr
+                // read/write
+                unsafe {
+                    #(
+                        ::core::ptr::copy(self.data.0.#fields_names.add(index), self.data.0.#fields_names.add(index + 1), self.len - index);
+                        self.data.0.#fields_names.add(index).write(::core::ptr::read(&element.#fields_names));
+                    )*
+                }
+                self.len += 1;
+                #drop_skipped_element
+                // if value implements Drop, we don't want to run it here, only
+                // when the vec itself will be dropped.
+                ::core::mem::forget(element);
+            }
+
+            /// Similar to [`std::mem::replace()`](https://doc.rust-lang.org/std/mem/fn.replace.html).
+            #[allow(clippy::forget_non_drop)]
+            pub fn replace(&mut self, index: usize, element: #name<#generic_args>) -> #name<#generic_args> {
+                if index >= self.len {
+                    panic!("index out of bounds: the len is {} but the index is {}", self.len, index);
+                }
+
+                // similar to push, we can not use move and have to rely on ptr
+                // read/write
+                #(
+                    let #fields_names_hygienic = unsafe {
+                        ::core::ptr::replace(self.data.0.#fields_names.add(index), ::core::ptr::read(&element.#fields_names))
+                    };
+                )*
+                #drop_skipped_element
+                // if value implements Drop, we don't want to run it here, only
+                // when the vec itself will be dropped.
+                ::core::mem::forget(element);
+
+                #name{#(#fields_names: #fields_names_hygienic,)* #skipped_fields_init}
+            }
+
+            /// Similar to [`
+            #[doc = #vec_name_str]
+            /// ::remove()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.remove).
+            pub fn remove(&mut self, index: usize) -> #name<#generic_args> {
+                if index >= self.len {
+                    panic!("removal index (is {}) should be < len (is {})", index, self.len);
+                }
+
+                self.len -= 1;
+                unsafe {
+                    #(
+                        let #fields_names_hygienic = ::core::ptr::read(self.data.0.#fields_names.add(index));
+                        ::core::ptr::copy(self.data.0.#fields_names.add(index + 1), self.data.0.#fields_names.add(index), self.len - index);
+                    )*
+                    #name{#(#fields_names: #fields_names_hygienic,)* #skipped_fields_init}
+                }
+            }
+
+            /// Similar to [`
+            #[doc = #vec_name_str]
+            /// ::pop()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.pop).
+            pub fn pop(&mut self) -> Option<#name<#generic_args>> {
+                if self.is_empty() {
+                    None
+                } else {
+                    self.len -= 1;
+                    unsafe {
+                        #(
+                            let #fields_names_hygienic = ::core::ptr::read(self.data.0.#fields_names.add(self.len));
+                        )*
+                        Some(#name{#(#fields_names: #fields_names_hygienic,)* #skipped_fields_init})
+                    }
+                }
+            }
+
+            /// Similar to [`
+            #[doc = #vec_name_str]
+            /// ::append()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.append).
+            pub fn append(&mut self, other: &mut #vec_name<#generic_args>) {
+                self.reserve(other.len);
+                unsafe {
+                    #(::core::ptr::copy_nonoverlapping(other.data.0.#fields_names, self.data.0.#fields_names.add(self.len), other.len);)*
+                }
+                self.len += other.len;
+                other.len = 0;
+            }
+
+            /// Similar to [`
+            #[doc = #vec_name_str]
+            /// ::clear()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.clear).
+            pub fn clear(&mut self) {
+                self.truncate(0);
+            }
+
+            /// Similar to [`
+            #[doc = #vec_name_str]
+            /// ::split_off()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.split_off).
+            pub fn split_off(&mut self, at: usize) -> #vec_name<#generic_args> {
+                if at > self.len {
+                    panic!("`at` split index (is {}) should be <= len (is {})", at, self.len);
+                }
+
+                let mut other = Self::with_capacity(self.len - at);
+                unsafe {
+                    #(::core::ptr::copy_nonoverlapping(self.data.0.#fields_names.add(at), other.data.0.#fields_names, self.len - at);)*
+                }
+                other.len = self.len - at;
+                self.len = at;
+                other
+            }
+
+            /// Similar to [`
+            #[doc = #vec_name_str]
+            /// ::as_slice()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.as_slice).
+            pub fn as_slice(&self) -> #slice_name<'_, #generic_args> {
+                unsafe {
+                    #slice_name {
+                        #(#fields_names: ::core::slice::from_raw_parts(self.data.0.#fields_names, self.len), )*
+                    }
+                }
+            }
+
+            /// Similar to [`
+            #[doc = #vec_name_str]
+            /// ::as_mut_slice()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.as_mut_slice).
+            pub fn as_mut_slice(&mut self) -> #slice_mut_name<'_, #generic_args> {
+                unsafe {
+                    #slice_mut_name {
+                        #(#fields_names: ::core::slice::from_raw_parts_mut(self.data.0.#fields_names, self.len), )*
+                    }
+                }
+            }
+
+            /// Create a slice of this vector matching the given `range`. This
+            /// is analogous to `Index<Range<usize>>`.
+            pub fn slice(&self, range: ::core::ops::Range<usize>) -> #slice_name<'_, #generic_args> {
+                let slice = self.as_slice();
+                #slice_name {
+                    #( #fields_names: &slice.#fields_names[range.clone()], )*
+                }
+            }
+
+            /// Create a mutable slice of this vector matching the given
+            /// `range`. This is analogous to `IndexMut<Range<usize>>`.
+            pub fn slice_mut(&mut self, range: ::core::ops::Range<usize>) -> #slice_mut_name<'_, #generic_args> {
+                let slice = self.as_mut_slice();
+                #slice_mut_name {
+                    #( #fields_names: &mut slice.#fields_names[range.clone()], )*
+                }
+            }
+
+            /// Similar to [`
+            #[doc = #vec_name_str]
+            /// ::retain()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.retain).
+            pub fn retain<__SoaF>(&mut self, mut f: __SoaF) where __SoaF: FnMut(#ref_name<'_, #generic_args>) -> bool {
+                let len = self.len();
+                let mut del = 0;
+
+                {
+                    let mut slice = self.as_mut_slice();
+                    for i in 0..len {
+                        if !f(slice.get(i).unwrap()) {
+                            del += 1;
+                        } else if del > 0 {
+                            slice.swap(i - del, i);
+                        }
+                    }
+                }
+                if del > 0 {
+                    self.truncate(len - del);
+                }
+            }
+
+            /// Similar to [`
+            #[doc = #vec_name_str]
+            /// ::retain_mut()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.retain_mut).
+            pub fn retain_mut<__SoaF>(&mut self, mut f: __SoaF) where __SoaF: FnMut(#ref_mut_name<'_, #generic_args>) -> bool {
+                let len = self.len();
+                let mut del = 0;
+
+                {
+                    let mut slice = self.as_mut_slice();
+                    for i in 0..len {
+                        if !f(slice.get_mut(i).unwrap()) {
+                            del += 1;
+                        } else if del > 0 {
+                            slice.swap(i - del, i);
+                        }
+                    }
+                }
+                if del > 0 {
+                    self.truncate(len - del);
+                }
+            }
+
+            /// Similar to [`
+            #[doc = #vec_name_str]
+            /// ::dedup_by()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.dedup_by).
+            ///
+            /// The first argument to `same_bucket` is the element being
+            /// considered, and the second argument is the last element kept.
+            pub fn dedup_by<__SoaF>(&mut self, mut same_bucket: __SoaF)
+            where
+                __SoaF: FnMut(#ref_mut_name<'_, #generic_args>, #ref_mut_name<'_, #generic_args>) -> bool
+            {
+                let len = self.len();
+                if len <= 1 {
+                    return;
+                }
+
+                let mut kept = 1;
+                {
+                    let mut slice = self.as_mut_slice();
+                    for i in 1..len {
+                        let duplicate = {
+                            let (mut previous, mut current) = slice.reborrow().split_at_mut(i);
+                            same_bucket(current.get_mut(0).unwrap(), previous.get_mut(kept - 1).unwrap())
+                        };
+                        if !duplicate {
+                            if i != kept {
+                                slice.swap(kept, i);
+                            }
+                            kept += 1;
+                        }
+                    }
+                }
+                self.truncate(kept);
+            }
+
+            /// Similar to [`
+            #[doc = #vec_name_str]
+            /// ::dedup_by_key()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.dedup_by_key).
+            pub fn dedup_by_key<__SoaF, __SoaK>(&mut self, mut key: __SoaF)
+            where
+                __SoaF: FnMut(#ref_mut_name<'_, #generic_args>) -> __SoaK,
+                __SoaK: PartialEq,
+            {
+                self.dedup_by(|a, b| key(a) == key(b));
+            }
+
+            /// Similar to [`
+            #[doc = #vec_name_str]
+            /// ::get<I>()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.get).
+            pub fn get<'__soa_a, __SoaI>(&'__soa_a self, index: __SoaI) -> Option<__SoaI::RefOutput>
+            where
+                __SoaI: ::soa_derive::SoAIndex<&'__soa_a #vec_name<#generic_args>>
+            {
+                index.get(self)
+            }
+
+            /// Similar to [`
+            #[doc = #vec_name_str]
+            /// ::get_unchecked<I>()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.get_unchecked).
+            pub unsafe fn get_unchecked<'__soa_a, __SoaI>(&'__soa_a self, index: __SoaI) -> __SoaI::RefOutput
+            where
+                __SoaI: ::soa_derive::SoAIndex<&'__soa_a #vec_name<#generic_args>>
+            {
+                index.get_unchecked(self)
+            }
+
+            /// Similar to [`
+            #[doc = #vec_name_str]
+            /// ::index<I>()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.index).
+            pub fn index<'__soa_a, __SoaI>(&'__soa_a self, index: __SoaI) -> __SoaI::RefOutput
+            where
+                __SoaI: ::soa_derive::SoAIndex<&'__soa_a #vec_name<#generic_args>>
+            {
+                index.index(self)
+            }
+
+            /// Similar to [`
+            #[doc = #vec_name_str]
+            /// ::get_mut<I>()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.get_mut).
+            pub fn get_mut<'__soa_a, __SoaI>(&'__soa_a mut self, index: __SoaI) -> Option<__SoaI::MutOutput>
+            where
+                __SoaI: ::soa_derive::SoAIndexMut<&'__soa_a mut #vec_name<#generic_args>>
+            {
+                index.get_mut(self)
+            }
+
+            /// Similar to [`
+            #[doc = #vec_name_str]
+            /// ::get_unchecked_mut<I>()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.get_unchecked_mut).
+            pub unsafe fn get_unchecked_mut<'__soa_a, __SoaI>(&'__soa_a mut self, index: __SoaI) -> __SoaI::MutOutput
+            where
+                __SoaI: ::soa_derive::SoAIndexMut<&'__soa_a mut #vec_name<#generic_args>>
+            {
+                index.get_unchecked_mut(self)
+            }
+
+            /// Similar to [`
+            #[doc = #vec_name_str]
+            /// ::index_mut<I>()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.index_mut).
+            pub fn index_mut<'__soa_a, __SoaI>(&'__soa_a mut self, index: __SoaI) -> __SoaI::MutOutput
+            where
+                __SoaI: ::soa_derive::SoAIndexMut<&'__soa_a mut #vec_name<#generic_args>>
+            {
+                index.index_mut(self)
+            }
+
+            /// Similar to [`
+            #[doc = #vec_name_str]
+            /// ::as_ptr()`](https://doc.rust-lang.org/std/struct.Vec.html#method.as_ptr).
+            pub fn as_ptr(&self) -> #ptr_name<#generic_args> {
+                #ptr_name {
+                    #(#fields_names: self.data.0.#fields_names,)*
+                }
+            }
+
+            /// Similar to [`
+            #[doc = #vec_name_str]
+            /// ::as_mut_ptr()`](https://doc.rust-lang.org/std/struct.Vec.html#method.as_mut_ptr).
+            pub fn as_mut_ptr(&mut self) -> #ptr_mut_name<#generic_args> {
+                self.data.0
+            }
+
+            /// Similar to [`
+            #[doc = #vec_name_str]
+            /// ::from_raw_parts()`](https://doc.rust-lang.org/std/struct.Vec.html#method.from_raw_parts).
+            ///
+            /// `data` must have been obtained from the `as_mut_ptr()` function
+            /// of another vector of the same type, with the given `capacity`.
+            pub unsafe fn from_raw_parts(data: #ptr_mut_name<#generic_args>, len: usize, capacity: usize) -> #vec_name<#generic_args> {
+                #vec_name {
+                    data: ::soa_derive::single_buffer::Pointers(data),
+                    len,
+                    capacity,
+                    marker: ::core::marker::PhantomData,
+                }
+            }
+
+            /// Similar to [`
+            #[doc = #vec_name_str]
+            /// ::set_len()`](https://doc.rust-lang.org/std/struct.Vec.html#method.set_len).
+            pub unsafe fn set_len(&mut self, new_len: usize) {
+                debug_assert!(new_len <= self.capacity);
+                self.len = new_len;
+            }
+        }
+
+        impl<#generic_params> Drop for #vec_name<#generic_args> where #where_predicates {
+            fn drop(&mut self) {
+                self.clear();
+                unsafe {
+                    self.deallocate();
+                }
+            }
+        }
+
+        /// Owning iterator over
+        #[doc = #vec_doc_url]
+        /// , yielding
+        #[doc = #doc_url]
+        /// . This is similar to [`std::vec::IntoIter`](https://doc.rust-lang.org/std/vec/struct.IntoIter.html).
+        #[allow(missing_debug_implementations)]
+        #visibility struct #into_iter_name<#generic_params> where #where_predicates {
+            // the length of this vector is set to 0, and the elements which
+            // have not been yielded yet are in `start..end`
+            vec: #vec_name<#generic_args>,
+            start: usize,
+            end: usize,
+        }
+
+        impl<#generic_params> Iterator for #into_iter_name<#generic_args> where #where_predicates {
+            type Item = #name<#generic_args>;
+
+            #[inline]
+            fn next(&mut self) -> Option<#name<#generic_args>> {
+                if self.start == self.end {
+                    None
+                } else {
+                    let value = unsafe { self.vec.as_mut_ptr().add(self.start).read() };
+                    self.start += 1;
+                    Some(value)
+                }
+            }
+
+            #[inline]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                let len = self.end - self.start;
+                (len, Some(len))
+            }
+        }
+
+        impl<#generic_params> DoubleEndedIterator for #into_iter_name<#generic_args> where #where_predicates {
+            #[inline]
+            fn next_back(&mut self) -> Option<#name<#generic_args>> {
+                if self.start == self.end {
+                    None
+                } else {
+                    self.end -= 1;
+                    Some(unsafe { self.vec.as_mut_ptr().add(self.end).read() })
+                }
+            }
+        }
+
+        impl<#generic_params> ExactSizeIterator for #into_iter_name<#generic_args> where #where_predicates {}
+
+        impl<#generic_params> Drop for #into_iter_name<#generic_args> where #where_predicates {
+            fn drop(&mut self) {
+                self.for_each(::core::mem::drop);
+                // the buffer is released when dropping `self.vec`
+            }
+        }
+
+        impl<#generic_params> IntoIterator for #vec_name<#generic_args> where #where_predicates {
+            type Item = #name<#generic_args>;
+            type IntoIter = #into_iter_name<#generic_args>;
+
+            fn into_iter(mut self) -> Self::IntoIter {
+                let end = self.len;
+                self.len = 0;
+                #into_iter_name {
+                    vec: self,
+                    start: 0,
+                    end: end,
+                }
+            }
+        }
+    };
+
+    if input.attrs.derive_clone {
+        generated.append_all(quote!{
+            #[allow(dead_code)]
+            impl<#generic_params> #vec_name<#generic_args> where #(#fields_types: Clone,)* #where_predicates {
+                /// Similar to [`
+                #[doc = #vec_name_str]
+                /// ::resize()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.resize).
+                pub fn resize(&mut self, new_len: usize, value: #name<#generic_args>) {
+                    if new_len > self.len {
+                        self.reserve(new_len - self.len);
+                        while self.len + 1 < new_len {
+                            unsafe {
+                                #(self.data.0.#fields_names.add(self.len).write(value.#fields_names.clone());)*
+                            }
+                            self.len += 1;
+                        }
+                        self.push(value);
+                    } else {
+                        self.truncate(new_len);
+                    }
+                }
+            }
+
+            impl<#generic_params> ::soa_derive::SoAAppendVec<#name<#generic_args>> for #vec_name<#generic_args> where #(#fields_types: Clone,)* #where_predicates {
+                fn extend_from_slice(&mut self, other: Self::Slice<'_>) {
+                    self.reserve(other.len());
+                    for i in 0..other.len() {
+                        unsafe {
+                            #(self.data.0.#fields_names.add(self.len).write(other.#fields_names[i].clone());)*
+                        }
+                        self.len += 1;
+                    }
+                }
+            }
+
+            impl<#generic_params> Clone for #vec_name<#generic_args> where #(#fields_types: Clone,)* #where_predicates {
+                fn clone(&self) -> Self {
+                    let mut vec = Self::with_capacity(self.len);
+                    ::soa_derive::SoAAppendVec::extend_from_slice(&mut vec, self.as_slice());
+                    vec
+                }
+            }
+        });
+    }
+
+    if input.attrs.derive_debug {
+        let debug = if input.is_tuple {
+            quote! {
+                f.debug_tuple(stringify!(#vec_name))
+                    #(.field(&slice.#fields_names))*
+                    .finish()
+            }
+        } else {
+            quote! {
+                f.debug_struct(stringify!(#vec_name))
+                    #(.field(stringify!(#fields_names), &slice.#fields_names))*
+                    .finish()
+            }
+        };
+
+        generated.append_all(quote!{
+            impl<#generic_params> ::core::fmt::Debug for #vec_name<#generic_args> where #(#fields_types: ::core::fmt::Debug,)* #where_predicates {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    let slice = self.as_slice();
+                    #debug
+                }
+            }
+        });
+    }
+
+    if input.attrs.derive_partial_eq {
+        generated.append_all(quote!{
+            impl<#generic_params> PartialEq for #vec_name<#generic_args> where #(#fields_types: PartialEq,)* #where_predicates {
+                fn eq(&self, other: &Self) -> bool {
+                    let slice = self.as_slice();
+                    let other = other.as_slice();
+                    #(slice.#fields_names == other.#fields_names)&&*
+                }
+            }
+        });
+    }
+
+    return generated;
+}
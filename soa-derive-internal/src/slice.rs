@@ -3,25 +3,26 @@ use syn::Ident;
 use quote::TokenStreamExt;
 use quote::quote;
 
-use crate::input::Input;
-use crate::names;
+use crate::input::{Input, Storage};
 
 pub fn derive(input: &Input) -> TokenStream {
     let name = &input.name;
     let visibility = &input.visibility;
-    let slice_name = names::slice_name(&input.name);
+    let slice_name = &input.names.slice;
     let attrs = &input.attrs.slice;
-    let vec_name = names::vec_name(&input.name);
-    let ref_name = names::ref_name(&input.name);
-    let ptr_name = names::ptr_name(&input.name);
+    let vec_name = &input.names.vec;
+    let ref_name = &input.names.ref_;
+    let ptr_name = &input.names.ptr;
+
+    let generic_params = input.generic_params();
+    let generic_args = input.generic_args();
+    let where_predicates = input.where_predicates();
 
     let slice_name_str = format!("[{}]", input.name);
     let doc_url = format!("[`{0}`](struct.{0}.html)", input.name);
     let vec_doc_url = format!("[`{0}`](struct.{0}.html)", vec_name);
 
-    let fields_names = &input.fields.iter()
-        .map(|field| field.ident.as_ref().unwrap())
-        .collect::<Vec<_>>();
+    let fields_names = &input.fields_names();
 
     let first_field = &fields_names[0];
 
@@ -35,11 +36,8 @@ pub fn derive(input: &Input) -> TokenStream {
         .collect::<Vec<_>>();
 
     let slice_fields_types = input.map_fields_nested_or(
-        |_, field_type| {
-            let slice_type = names::slice_name(field_type);
-            quote! { #slice_type<'a> }
-        },
-        |_, field_type| quote! { &'a [#field_type] },
+        |_, field_type| quote! { <#field_type as ::soa_derive::SoASlices<'__soa_a>>::Slice },
+        |_, field_type| quote! { &'__soa_a [#field_type] },
     ).collect::<Vec<_>>();
 
     let slice_reborrow = input.map_fields_nested_or(
@@ -48,13 +46,20 @@ pub fn derive(input: &Input) -> TokenStream {
     ).collect::<Vec<_>>();
 
     let slice_from_raw_parts = input.map_fields_nested_or(
-        |ident, field_type| {
-            let slice_type = names::slice_name(field_type);
-            quote! { #slice_type::from_raw_parts(data.#ident, len) }
+        |ident, field_type| quote! {
+            <#field_type as ::soa_derive::SoASlices<'__soa_b>>::Slice::from_raw_parts(data.#ident, len)
         },
-        |ident, _| quote! { ::std::slice::from_raw_parts(data.#ident, len) },
+        |ident, _| quote! { ::core::slice::from_raw_parts(data.#ident, len) },
     ).collect::<Vec<_>>();
 
+    let fields_docs = fields_names.iter().map(|field| quote! {
+        /// slice of `
+        #[doc = stringify!(#field)]
+        ///` inside a
+        #[doc = #vec_doc_url]
+    }).collect::<Vec<_>>();
+    let slice_body = input.struct_body(Some(&quote! { '__soa_a }), &fields_docs, &slice_fields_types);
+
     let mut generated = quote! {
         /// A slice of
         #[doc = #doc_url]
@@ -62,21 +67,26 @@ pub fn derive(input: &Input) -> TokenStream {
         #[doc = #vec_doc_url]
         /// .
         #[allow(dead_code)]
-        #[derive(Copy, Clone)]
         #(#[#attrs])*
-        #[derive(Default)]
-        #visibility struct #slice_name<'a> {
-            #(
-                /// slice of `
-                #[doc = stringify!(#fields_names)]
-                ///` inside a
-                #[doc = #vec_doc_url]
-                pub #fields_names: #slice_fields_types,
-            )*
+        #visibility struct #slice_name<'__soa_a, #generic_params> #slice_body
+
+        impl<'__soa_a, #generic_params> Copy for #slice_name<'__soa_a, #generic_args> where #where_predicates {}
+        impl<'__soa_a, #generic_params> Clone for #slice_name<'__soa_a, #generic_args> where #where_predicates {
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+
+        impl<'__soa_a, #generic_params> Default for #slice_name<'__soa_a, #generic_args> where #where_predicates {
+            fn default() -> Self {
+                #slice_name {
+                    #( #fields_names: Default::default(), )*
+                }
+            }
         }
 
         #[allow(dead_code)]
-        impl<'a> #slice_name<'a> {
+        impl<'__soa_a, #generic_params> #slice_name<'__soa_a, #generic_args> where #where_predicates {
             /// Similar to [`&
             #[doc = #slice_name_str]
             /// ::len()`](https://doc.rust-lang.org/std/primitive.slice.html#method.len),
@@ -100,7 +110,7 @@ pub fn derive(input: &Input) -> TokenStream {
             /// Similar to [`&
             #[doc = #slice_name_str]
             /// ::first()`](https://doc.rust-lang.org/std/primitive.slice.html#method.first).
-            pub fn first(&self) -> Option<#ref_name<'a>> {
+            pub fn first(&self) -> Option<#ref_name<'__soa_a, #generic_args>> {
                 if self.is_empty() {
                     None
                 } else {
@@ -114,7 +124,7 @@ pub fn derive(input: &Input) -> TokenStream {
             /// Similar to [`&
             #[doc = #slice_name_str]
             /// ::split_first()`](https://doc.rust-lang.org/std/primitive.slice.html#method.split_first).
-            pub fn split_first(&self) -> Option<(#ref_name<'a>, #slice_name<'a>)> {
+            pub fn split_first(&self) -> Option<(#ref_name<'__soa_a, #generic_args>, #slice_name<'__soa_a, #generic_args>)> {
                 if self.is_empty() {
                     None
                 } else {
@@ -130,7 +140,7 @@ pub fn derive(input: &Input) -> TokenStream {
             /// Similar to [`&
             #[doc = #slice_name_str]
             /// ::last()`](https://doc.rust-lang.org/std/primitive.slice.html#method.last).
-            pub fn last(&self) -> Option<#ref_name<'a>> {
+            pub fn last(&self) -> Option<#ref_name<'__soa_a, #generic_args>> {
                 if self.is_empty() {
                     None
                 } else {
@@ -144,7 +154,7 @@ pub fn derive(input: &Input) -> TokenStream {
             /// Similar to [`&
             #[doc = #slice_name_str]
             /// ::split_last()`](https://doc.rust-lang.org/std/primitive.slice.html#method.split_last).
-            pub fn split_last(&self) -> Option<(#ref_name<'a>, #slice_name<'a>)> {
+            pub fn split_last(&self) -> Option<(#ref_name<'__soa_a, #generic_args>, #slice_name<'__soa_a, #generic_args>)> {
                 if self.is_empty() {
                     None
                 } else {
@@ -160,7 +170,7 @@ pub fn derive(input: &Input) -> TokenStream {
             /// Similar to [`&
             #[doc = #slice_name_str]
             /// ::split_at()`](https://doc.rust-lang.org/std/primitive.slice.html#method.split_at).
-            pub fn split_at(&self, mid: usize) -> (#slice_name<'a>, #slice_name<'a>) {
+            pub fn split_at(&self, mid: usize) -> (#slice_name<'__soa_a, #generic_args>, #slice_name<'__soa_a, #generic_args>) {
                 #(
                     let (#fields_names_hygienic_1, #fields_names_hygienic_2) = self.#fields_names.split_at(mid);
                 )*
@@ -172,24 +182,24 @@ pub fn derive(input: &Input) -> TokenStream {
             /// Similar to [`&
             #[doc = #slice_name_str]
             /// ::get()`](https://doc.rust-lang.org/std/primitive.slice.html#method.get).
-            pub fn get<'b, I>(&'b self, index: I) -> Option<I::RefOutput>
+            pub fn get<'__soa_b, __SoaI>(&'__soa_b self, index: __SoaI) -> Option<__SoaI::RefOutput>
             where
-                I: ::soa_derive::SoAIndex<#slice_name<'b>>,
-                'a: 'b
+                __SoaI: ::soa_derive::SoAIndex<#slice_name<'__soa_b, #generic_args>>,
+                '__soa_a: '__soa_b
             {
-                let slice: #slice_name<'b> = self.reborrow();
+                let slice: #slice_name<'__soa_b, #generic_args> = self.reborrow();
                 index.get(slice)
             }
 
             /// Similar to [`&
             #[doc = #slice_name_str]
             /// ::get_unchecked()`](https://doc.rust-lang.org/std/primitive.slice.html#method.get_unchecked).
-            pub unsafe fn get_unchecked<'b, I>(&'b self, index: I) -> I::RefOutput
+            pub unsafe fn get_unchecked<'__soa_b, __SoaI>(&'__soa_b self, index: __SoaI) -> __SoaI::RefOutput
             where
-                I: ::soa_derive::SoAIndex<#slice_name<'b>>,
-                'a: 'b
+                __SoaI: ::soa_derive::SoAIndex<#slice_name<'__soa_b, #generic_args>>,
+                '__soa_a: '__soa_b
             {
-                let slice: #slice_name<'b> = self.reborrow();
+                let slice: #slice_name<'__soa_b, #generic_args> = self.reborrow();
                 index.get_unchecked(slice)
             }
 
@@ -199,19 +209,19 @@ pub fn derive(input: &Input) -> TokenStream {
             #[doc = #slice_name_str]
             ///` .
             /// This is required because we cannot implement `std::ops::Index` directly since it requires returning a reference.
-            pub fn index<'b, I>(&'b self, index: I) -> I::RefOutput
+            pub fn index<'__soa_b, __SoaI>(&'__soa_b self, index: __SoaI) -> __SoaI::RefOutput
             where
-                I: ::soa_derive::SoAIndex<#slice_name<'b>>,
-                'a: 'b
+                __SoaI: ::soa_derive::SoAIndex<#slice_name<'__soa_b, #generic_args>>,
+                '__soa_a: '__soa_b
             {
-                let slice: #slice_name<'b> = self.reborrow();
+                let slice: #slice_name<'__soa_b, #generic_args> = self.reborrow();
                 index.index(slice)
             }
 
             /// Reborrows the slices in a narrower lifetime
-            pub fn reborrow<'b>(&'b self) -> #slice_name<'b>
+            pub fn reborrow<'__soa_b>(&'__soa_b self) -> #slice_name<'__soa_b, #generic_args>
             where
-                'a: 'b
+                '__soa_a: '__soa_b
             {
                 #slice_name {
                     #( #fields_names: #slice_reborrow, )*
@@ -221,41 +231,139 @@ pub fn derive(input: &Input) -> TokenStream {
             /// Similar to [`&
             #[doc = #slice_name_str]
             /// ::as_ptr()`](https://doc.rust-lang.org/std/primitive.slice.html#method.as_ptr).
-            pub fn as_ptr(&self) -> #ptr_name {
+            pub fn as_ptr(&self) -> #ptr_name<#generic_args> {
                 #ptr_name {
                     #(#fields_names: self.#fields_names.as_ptr(),)*
                 }
             }
 
             /// Similar to [`std::slice::from_raw_parts()`](https://doc.rust-lang.org/std/slice/fn.from_raw_parts.html).
-            pub unsafe fn from_raw_parts<'b>(data: #ptr_name, len: usize) -> #slice_name<'b> {
+            pub unsafe fn from_raw_parts<'__soa_b>(data: #ptr_name<#generic_args>, len: usize) -> #slice_name<'__soa_b, #generic_args> {
                 #slice_name {
                     #( #fields_names: #slice_from_raw_parts, )*
                 }
             }
         }
 
+
+        #[allow(dead_code)]
+        impl<'__soa_a, #generic_params> #slice_name<'__soa_a, #generic_args> where #where_predicates {
+            /// Similar to [`&
+            #[doc = #slice_name_str]
+            /// ::binary_search_by()`](https://doc.rust-lang.org/std/primitive.slice.html#method.binary_search_by).
+            pub fn binary_search_by<__SoaF>(&self, mut f: __SoaF) -> Result<usize, usize>
+            where
+                __SoaF: FnMut(#ref_name<'__soa_a, #generic_args>) -> ::core::cmp::Ordering,
+            {
+                let mut left = 0;
+                let mut right = self.len();
+                while left < right {
+                    let mid = left + (right - left) / 2;
+                    match f(::soa_derive::SoAIndex::index(mid, *self)) {
+                        ::core::cmp::Ordering::Less => left = mid + 1,
+                        ::core::cmp::Ordering::Greater => right = mid,
+                        ::core::cmp::Ordering::Equal => return Ok(mid),
+                    }
+                }
+                return Err(left);
+            }
+
+            /// Similar to [`&
+            #[doc = #slice_name_str]
+            /// ::binary_search_by_key()`](https://doc.rust-lang.org/std/primitive.slice.html#method.binary_search_by_key).
+            pub fn binary_search_by_key<__SoaB, __SoaF>(&self, b: &__SoaB, mut f: __SoaF) -> Result<usize, usize>
+            where
+                __SoaF: FnMut(#ref_name<'__soa_a, #generic_args>) -> __SoaB,
+                __SoaB: Ord,
+            {
+                self.binary_search_by(|item| f(item).cmp(b))
+            }
+
+            /// Similar to [`&
+            #[doc = #slice_name_str]
+            /// ::partition_point()`](https://doc.rust-lang.org/std/primitive.slice.html#method.partition_point).
+            pub fn partition_point<__SoaP>(&self, mut pred: __SoaP) -> usize
+            where
+                __SoaP: FnMut(#ref_name<'__soa_a, #generic_args>) -> bool,
+            {
+                self.binary_search_by(|item| {
+                    if pred(item) {
+                        ::core::cmp::Ordering::Less
+                    } else {
+                        ::core::cmp::Ordering::Greater
+                    }
+                }).unwrap_or_else(|i| i)
+            }
+        }
+
+        #[allow(dead_code)]
+        impl<'__soa_a, #generic_params> #slice_name<'__soa_a, #generic_args>
+        where
+            for<'__soa_b> #ref_name<'__soa_b, #generic_args>: PartialEq,
+            #where_predicates
+        {
+            /// Similar to [`&
+            #[doc = #slice_name_str]
+            /// ::contains()`](https://doc.rust-lang.org/std/primitive.slice.html#method.contains).
+            pub fn contains<'__soa_b>(&'__soa_b self, x: #ref_name<'__soa_b, #generic_args>) -> bool {
+                self.iter().any(|item| item == x)
+            }
+
+            /// Similar to [`&
+            #[doc = #slice_name_str]
+            /// ::starts_with()`](https://doc.rust-lang.org/std/primitive.slice.html#method.starts_with).
+            pub fn starts_with<'__soa_b>(&'__soa_b self, needle: #slice_name<'__soa_b, #generic_args>) -> bool {
+                needle.len() <= self.len() && self.iter().zip(needle.into_iter()).all(|(a, b)| a == b)
+            }
+
+            /// Similar to [`&
+            #[doc = #slice_name_str]
+            /// ::ends_with()`](https://doc.rust-lang.org/std/primitive.slice.html#method.ends_with).
+            pub fn ends_with<'__soa_b>(&'__soa_b self, needle: #slice_name<'__soa_b, #generic_args>) -> bool {
+                needle.len() <= self.len() && self.iter().rev().zip(needle.into_iter().rev()).all(|(a, b)| a == b)
+            }
+        }
+    };
+
+    // the single buffer vector does not have public fields, so we go through
+    // SoAAppendVec instead, which requires all fields to be Clone
+    let to_vec_bounds = if input.storage == Storage::SingleBuffer {
+        let fields_types = input.fields.iter().map(|field| &field.ty);
+        quote! { #(#fields_types: Clone,)* }
+    } else {
+        quote! {}
+    };
+    let to_vec = if input.storage == Storage::SingleBuffer {
+        quote! {
+            let mut vec = #vec_name::with_capacity(self.len());
+            ::soa_derive::SoAAppendVec::extend_from_slice(&mut vec, *self);
+            vec
+        }
+    } else {
+        quote! {
+            #vec_name {
+                #(#fields_names: self.#fields_names.to_vec(),)*
+            }
+        }
     };
 
     if input.attrs.derive_clone {
         generated.append_all(quote!{
             #[allow(dead_code)]
-            impl<'a> #slice_name<'a> {
+            impl<'__soa_a, #generic_params> #slice_name<'__soa_a, #generic_args> where #to_vec_bounds #where_predicates {
                 /// Similar to [`&
                 #[doc = #slice_name_str]
                 /// ::to_vec()`](https://doc.rust-lang.org/std/primitive.slice.html#method.to_vec).
-                pub fn to_vec(&self) -> #vec_name {
-                    #vec_name {
-                        #(#fields_names: self.#fields_names.to_vec(),)*
-                    }
+                pub fn to_vec(&self) -> #vec_name<#generic_args> {
+                    #to_vec
                 }
             }
         });
 
         {
             generated.append_all(quote! {
-                impl<'a> ::soa_derive::ToSoAVec<#name> for #slice_name<'a> {
-                    type SoAVecType = #vec_name;
+                impl<'__soa_a, #generic_params> ::soa_derive::ToSoAVec<#name<#generic_args>> for #slice_name<'__soa_a, #generic_args> where #to_vec_bounds #where_predicates {
+                    type SoAVecType = #vec_name<#generic_args>;
 
                     fn to_vec(&self) -> Self::SoAVecType {
                         self.to_vec()
@@ -271,14 +379,18 @@ pub fn derive(input: &Input) -> TokenStream {
 pub fn derive_mut(input: &Input) -> TokenStream {
     let name = &input.name;
     let visibility = &input.visibility;
-    let slice_name = names::slice_name(&input.name);
-    let slice_mut_name = names::slice_mut_name(&input.name);
-    let vec_name = names::vec_name(&input.name);
+    let slice_name = &input.names.slice;
+    let slice_mut_name = &input.names.slice_mut;
+    let vec_name = &input.names.vec;
     let attrs = &input.attrs.slice_mut;
-    let ref_name = names::ref_name(&input.name);
-    let ref_mut_name = names::ref_mut_name(&input.name);
-    let ptr_name = names::ptr_name(&input.name);
-    let ptr_mut_name = names::ptr_mut_name(&input.name);
+    let ref_name = &input.names.ref_;
+    let ref_mut_name = &input.names.ref_mut;
+    let ptr_name = &input.names.ptr;
+    let ptr_mut_name = &input.names.ptr_mut;
+
+    let generic_params = input.generic_params();
+    let generic_args = input.generic_args();
+    let where_predicates = input.where_predicates();
 
     let slice_name_str = format!("[{}]", input.name);
     let doc_url = format!("[`{0}`](struct.{0}.html)", input.name);
@@ -286,9 +398,7 @@ pub fn derive_mut(input: &Input) -> TokenStream {
     let slice_mut_doc_url = format!("[`{0}`](struct.{0}.html)", slice_mut_name);
     let vec_doc_url = format!("[`{0}`](struct.{0}.html)", vec_name);
 
-    let fields_names = &input.fields.iter()
-        .map(|field| field.ident.clone().unwrap())
-        .collect::<Vec<_>>();
+    let fields_names = &input.fields_names();
 
     let first_field = &fields_names[0];
     let fields_names_hygienic_1 = &input.fields.iter()
@@ -301,11 +411,8 @@ pub fn derive_mut(input: &Input) -> TokenStream {
         .collect::<Vec<_>>();
 
     let slice_mut_fields_types = input.map_fields_nested_or(
-        |_, field_type| {
-            let slice_type = names::slice_mut_name(field_type);
-            quote! { #slice_type<'a> }
-        },
-        |_, field_type| quote! { &'a mut [#field_type] },
+        |_, field_type| quote! { <#field_type as ::soa_derive::SoASlices<'__soa_a>>::SliceMut },
+        |_, field_type| quote! { &'__soa_a mut [#field_type] },
     ).collect::<Vec<_>>();
 
     let slice_as_ref = input.map_fields_nested_or(
@@ -324,27 +431,25 @@ pub fn derive_mut(input: &Input) -> TokenStream {
     ).collect::<Vec<_>>();
 
     let slice_from_raw_parts_mut = input.map_fields_nested_or(
-        |ident, field_type| {
-            let slice_type = names::slice_mut_name(field_type);
-            quote! { #slice_type::from_raw_parts_mut(data.#ident, len) }
+        |ident, field_type| quote! {
+            <#field_type as ::soa_derive::SoASlices<'__soa_b>>::SliceMut::from_raw_parts_mut(data.#ident, len)
         },
-        |ident, _| quote! {::std::slice::from_raw_parts_mut(data.#ident, len) },
+        |ident, _| quote! {::core::slice::from_raw_parts_mut(data.#ident, len) },
     ).collect::<Vec<_>>();
 
-    let mut nested_ord = input.map_fields_nested_or(
-        |_, field_type| {
-            let field_ref_type = names::ref_name(field_type);
-            quote! { for<'b> #field_ref_type<'b>: Ord }
-        },
-        |_, _| quote! {},
-    ).filter(|stream| !stream.is_empty()).collect::<Vec<_>>();
-    nested_ord.push(quote! { for<'b> #ref_name<'b>: Ord });
-
     let apply_permutation = input.map_fields_nested_or(
         |ident, _| quote! { self.#ident.__private_apply_permutation(permutation) },
         |ident, _| quote! { permutation.apply_slice_in_place(&mut self.#ident) },
     ).collect::<Vec<_>>();
 
+    let fields_docs = fields_names.iter().map(|field| quote! {
+        /// slice of `
+        #[doc = stringify!(#field)]
+        ///` inside a
+        #[doc = #vec_doc_url]
+    }).collect::<Vec<_>>();
+    let slice_mut_body = input.struct_body(Some(&quote! { '__soa_a }), &fields_docs, &slice_mut_fields_types);
+
     let mut generated = quote! {
         /// A mutable slice of
         #[doc = #doc_url]
@@ -353,26 +458,25 @@ pub fn derive_mut(input: &Input) -> TokenStream {
         /// .
         #[allow(dead_code)]
         #(#[#attrs])*
-        #[derive(Default)]
-        #visibility struct #slice_mut_name<'a> {
-            #(
-                /// slice of `
-                #[doc = stringify!(#fields_names)]
-                ///` inside a
-                #[doc = #vec_doc_url]
-                pub #fields_names: #slice_mut_fields_types,
-            )*
+        #visibility struct #slice_mut_name<'__soa_a, #generic_params> #slice_mut_body
+
+        impl<'__soa_a, #generic_params> Default for #slice_mut_name<'__soa_a, #generic_args> where #where_predicates {
+            fn default() -> Self {
+                #slice_mut_name {
+                    #( #fields_names: Default::default(), )*
+                }
+            }
         }
 
         #[allow(dead_code)]
-        impl<'a> #slice_mut_name<'a> {
+        impl<'__soa_a, #generic_params> #slice_mut_name<'__soa_a, #generic_args> where #where_predicates {
             /// Convert a
             #[doc = #slice_mut_doc_url]
             /// to a
             #[doc = #slice_doc_url]
             /// in order to be able to use the methods on the non mutable
             /// version of the slices.
-            pub fn as_ref(&self) -> #slice_name {
+            pub fn as_ref(&self) -> #slice_name<'_, #generic_args> {
                 #slice_name {
                     #( #fields_names: #slice_as_ref, )*
                 }
@@ -401,14 +505,14 @@ pub fn derive_mut(input: &Input) -> TokenStream {
             /// Similar to [`&mut
             #[doc = #slice_name_str]
             /// ::first_mut()`](https://doc.rust-lang.org/std/primitive.slice.html#method.first_mut).
-            pub fn first_mut(&mut self) -> Option<#ref_mut_name> {
+            pub fn first_mut(&mut self) -> Option<#ref_mut_name<'_, #generic_args>> {
                 if self.is_empty() {
                     None
                 } else {
                     #(
-                        let #fields_names = self.#fields_names.first_mut().unwrap();
+                        let #fields_names_hygienic_1 = self.#fields_names.first_mut().unwrap();
                     )*
-                    Some(#ref_mut_name{#(#fields_names: #fields_names),*})
+                    Some(#ref_mut_name{#(#fields_names: #fields_names_hygienic_1),*})
                 }
             }
 
@@ -419,15 +523,15 @@ pub fn derive_mut(input: &Input) -> TokenStream {
             /// The main difference is that this function consumes the slice.
             /// You should use [`Self::reborrow()`] first if you want the
             /// returned values to have a shorter lifetime.
-            pub fn split_first_mut(mut self) -> Option<(#ref_mut_name<'a>, #slice_mut_name<'a>)> {
+            pub fn split_first_mut(mut self) -> Option<(#ref_mut_name<'__soa_a, #generic_args>, #slice_mut_name<'__soa_a, #generic_args>)> {
                 if self.is_empty() {
                     None
                 } else {
                     #(
-                        let (#fields_names, #fields_names_hygienic_1) = self.#fields_names.split_first_mut().unwrap();
+                        let (#fields_names_hygienic_1, #fields_names_hygienic_2) = self.#fields_names.split_first_mut().unwrap();
                     )*
-                    let ref_ = #ref_mut_name{#(#fields_names: #fields_names),*};
-                    let slice = #slice_mut_name{#(#fields_names: #fields_names_hygienic_1),*};
+                    let ref_ = #ref_mut_name{#(#fields_names: #fields_names_hygienic_1),*};
+                    let slice = #slice_mut_name{#(#fields_names: #fields_names_hygienic_2),*};
                     Some((ref_, slice))
                 }
             }
@@ -435,14 +539,14 @@ pub fn derive_mut(input: &Input) -> TokenStream {
             /// Similar to [`&mut
             #[doc = #slice_name_str]
             /// ::last_mut()`](https://doc.rust-lang.org/std/primitive.slice.html#method.last_mut).
-            pub fn last_mut(&mut self) -> Option<#ref_mut_name> {
+            pub fn last_mut(&mut self) -> Option<#ref_mut_name<'_, #generic_args>> {
                 if self.is_empty() {
                     None
                 } else {
                     #(
-                        let #fields_names = self.#fields_names.last_mut().unwrap();
+                        let #fields_names_hygienic_1 = self.#fields_names.last_mut().unwrap();
                     )*
-                    Some(#ref_mut_name{#(#fields_names: #fields_names),*})
+                    Some(#ref_mut_name{#(#fields_names: #fields_names_hygienic_1),*})
                 }
             }
 
@@ -453,15 +557,15 @@ pub fn derive_mut(input: &Input) -> TokenStream {
             /// The main difference is that this function consumes the slice.
             /// You should use [`Self::reborrow()`] first if you want the
             /// returned values to have a shorter lifetime.
-            pub fn split_last_mut(mut self) -> Option<(#ref_mut_name<'a>, #slice_mut_name<'a>)> {
+            pub fn split_last_mut(mut self) -> Option<(#ref_mut_name<'__soa_a, #generic_args>, #slice_mut_name<'__soa_a, #generic_args>)> {
                 if self.is_empty() {
                     None
                 } else {
                     #(
-                        let (#fields_names, #fields_names_hygienic_1) = self.#fields_names.split_last_mut().unwrap();
+                        let (#fields_names_hygienic_1, #fields_names_hygienic_2) = self.#fields_names.split_last_mut().unwrap();
                     )*
-                    let ref_ = #ref_mut_name{#(#fields_names: #fields_names),*};
-                    let slice = #slice_mut_name{#(#fields_names: #fields_names_hygienic_1),*};
+                    let ref_ = #ref_mut_name{#(#fields_names: #fields_names_hygienic_1),*};
+                    let slice = #slice_mut_name{#(#fields_names: #fields_names_hygienic_2),*};
                     Some((ref_, slice))
                 }
             }
@@ -473,7 +577,7 @@ pub fn derive_mut(input: &Input) -> TokenStream {
             /// The main difference is that this function consumes the slice.
             /// You should use [`Self::reborrow()`] first if you want the
             /// returned values to have a shorter lifetime.
-            pub fn split_at_mut(mut self, mid: usize) -> (#slice_mut_name<'a>, #slice_mut_name<'a>) {
+            pub fn split_at_mut(mut self, mid: usize) -> (#slice_mut_name<'__soa_a, #generic_args>, #slice_mut_name<'__soa_a, #generic_args>) {
                 #(
                     let (#fields_names_hygienic_1, #fields_names_hygienic_2) = self.#fields_names.split_at_mut(mid);
                 )*
@@ -491,27 +595,112 @@ pub fn derive_mut(input: &Input) -> TokenStream {
                 )*
             }
 
+            /// Similar to [`&mut
+            #[doc = #slice_name_str]
+            /// ::reverse()`](https://doc.rust-lang.org/std/primitive.slice.html#method.reverse).
+            pub fn reverse(&mut self) {
+                #(
+                    self.#fields_names.reverse();
+                )*
+            }
+
+            /// Similar to [`&mut
+            #[doc = #slice_name_str]
+            /// ::rotate_left()`](https://doc.rust-lang.org/std/primitive.slice.html#method.rotate_left).
+            pub fn rotate_left(&mut self, mid: usize) {
+                #(
+                    self.#fields_names.rotate_left(mid);
+                )*
+            }
+
+            /// Similar to [`&mut
+            #[doc = #slice_name_str]
+            /// ::rotate_right()`](https://doc.rust-lang.org/std/primitive.slice.html#method.rotate_right).
+            pub fn rotate_right(&mut self, k: usize) {
+                #(
+                    self.#fields_names.rotate_right(k);
+                )*
+            }
+
+            /// Similar to [`&mut
+            #[doc = #slice_name_str]
+            /// ::swap_with_slice()`](https://doc.rust-lang.org/std/primitive.slice.html#method.swap_with_slice).
+            ///
+            /// # Panics
+            ///
+            /// If the two slices have different lengths.
+            pub fn swap_with_slice(&mut self, other: #slice_mut_name<'_, #generic_args>) {
+                assert_eq!(self.len(), other.len(), "destination and source slices have different lengths");
+                #(
+                    self.#fields_names.swap_with_slice(other.#fields_names);
+                )*
+            }
+
+            /// Similar to [`&mut
+            #[doc = #slice_name_str]
+            /// ::fill()`](https://doc.rust-lang.org/std/primitive.slice.html#method.fill).
+            pub fn fill(&mut self, value: #name<#generic_args>) where for<'__soa_b> #name<#generic_args>: Clone {
+                let mut iter = self.iter_mut();
+                if let Some(mut last) = iter.next_back() {
+                    for mut item in iter {
+                        item.replace(value.clone());
+                    }
+                    last.replace(value);
+                }
+            }
+
+            /// Similar to [`&
+            #[doc = #slice_name_str]
+            /// ::binary_search_by()`](https://doc.rust-lang.org/std/primitive.slice.html#method.binary_search_by).
+            pub fn binary_search_by<'__soa_b, __SoaF>(&'__soa_b self, f: __SoaF) -> Result<usize, usize>
+            where
+                __SoaF: FnMut(#ref_name<'__soa_b, #generic_args>) -> ::core::cmp::Ordering,
+            {
+                self.as_slice().binary_search_by(f)
+            }
+
+            /// Similar to [`&
+            #[doc = #slice_name_str]
+            /// ::binary_search_by_key()`](https://doc.rust-lang.org/std/primitive.slice.html#method.binary_search_by_key).
+            pub fn binary_search_by_key<'__soa_b, __SoaB, __SoaF>(&'__soa_b self, b: &__SoaB, f: __SoaF) -> Result<usize, usize>
+            where
+                __SoaF: FnMut(#ref_name<'__soa_b, #generic_args>) -> __SoaB,
+                __SoaB: Ord,
+            {
+                self.as_slice().binary_search_by_key(b, f)
+            }
+
+            /// Similar to [`&
+            #[doc = #slice_name_str]
+            /// ::partition_point()`](https://doc.rust-lang.org/std/primitive.slice.html#method.partition_point).
+            pub fn partition_point<'__soa_b, __SoaP>(&'__soa_b self, pred: __SoaP) -> usize
+            where
+                __SoaP: FnMut(#ref_name<'__soa_b, #generic_args>) -> bool,
+            {
+                self.as_slice().partition_point(pred)
+            }
+
             /// Similar to [`&
             #[doc = #slice_name_str]
             /// ::get()`](https://doc.rust-lang.org/std/primitive.slice.html#method.get).
-            pub fn get<'b, I>(&'b self, index: I) -> Option<I::RefOutput>
+            pub fn get<'__soa_b, __SoaI>(&'__soa_b self, index: __SoaI) -> Option<__SoaI::RefOutput>
             where
-                I: ::soa_derive::SoAIndex<#slice_name<'b>>,
-                'a: 'b
+                __SoaI: ::soa_derive::SoAIndex<#slice_name<'__soa_b, #generic_args>>,
+                '__soa_a: '__soa_b
             {
-                let slice: #slice_name<'b> = self.as_slice();
+                let slice: #slice_name<'__soa_b, #generic_args> = self.as_slice();
                 index.get(slice)
             }
 
             /// Similar to [`&
             #[doc = #slice_name_str]
             /// ::get_unchecked()`](https://doc.rust-lang.org/std/primitive.slice.html#method.get_unchecked).
-            pub unsafe fn get_unchecked<'b, I>(&'b self, index: I) -> I::RefOutput
+            pub unsafe fn get_unchecked<'__soa_b, __SoaI>(&'__soa_b self, index: __SoaI) -> __SoaI::RefOutput
             where
-                I: ::soa_derive::SoAIndex<#slice_name<'b>>,
-                'a: 'b
+                __SoaI: ::soa_derive::SoAIndex<#slice_name<'__soa_b, #generic_args>>,
+                '__soa_a: '__soa_b
             {
-                let slice: #slice_name<'b> = self.as_slice();
+                let slice: #slice_name<'__soa_b, #generic_args> = self.as_slice();
                 index.get_unchecked(slice)
             }
 
@@ -522,36 +711,36 @@ pub fn derive_mut(input: &Input) -> TokenStream {
             #[doc = #slice_name_str]
             ///` .
             /// This is required because we cannot implement that trait.
-            pub fn index<'b, I>(&'b self, index: I) -> I::RefOutput
+            pub fn index<'__soa_b, __SoaI>(&'__soa_b self, index: __SoaI) -> __SoaI::RefOutput
             where
-                I: ::soa_derive::SoAIndex<#slice_name<'b>>,
-                'a: 'b
+                __SoaI: ::soa_derive::SoAIndex<#slice_name<'__soa_b, #generic_args>>,
+                '__soa_a: '__soa_b
             {
-                let slice: #slice_name<'b> = self.as_slice();
+                let slice: #slice_name<'__soa_b, #generic_args> = self.as_slice();
                 index.index(slice)
             }
 
             /// Similar to [`&mut
             #[doc = #slice_name_str]
             /// ::get_mut()`](https://doc.rust-lang.org/std/primitive.slice.html#method.get_mut).
-            pub fn get_mut<'b, I>(&'b mut self, index: I) -> Option<I::MutOutput>
+            pub fn get_mut<'__soa_b, __SoaI>(&'__soa_b mut self, index: __SoaI) -> Option<__SoaI::MutOutput>
             where
-                I: ::soa_derive::SoAIndexMut<#slice_mut_name<'b>>,
-                'a: 'b
+                __SoaI: ::soa_derive::SoAIndexMut<#slice_mut_name<'__soa_b, #generic_args>>,
+                '__soa_a: '__soa_b
             {
-                let slice: #slice_mut_name<'b> = self.reborrow();
+                let slice: #slice_mut_name<'__soa_b, #generic_args> = self.reborrow();
                 index.get_mut(slice)
             }
 
             /// Similar to [`&mut
             #[doc = #slice_name_str]
             /// ::get_unchecked_mut()`](https://doc.rust-lang.org/std/primitive.slice.html#method.get_unchecked_mut).
-            pub unsafe fn get_unchecked_mut<'b, I>(&'b mut self, index: I) -> I::MutOutput
+            pub unsafe fn get_unchecked_mut<'__soa_b, __SoaI>(&'__soa_b mut self, index: __SoaI) -> __SoaI::MutOutput
             where
-                I: ::soa_derive::SoAIndexMut<#slice_mut_name<'b>>,
-                'a: 'b
+                __SoaI: ::soa_derive::SoAIndexMut<#slice_mut_name<'__soa_b, #generic_args>>,
+                '__soa_a: '__soa_b
             {
-                let slice: #slice_mut_name<'b> = self.reborrow();
+                let slice: #slice_mut_name<'__soa_b, #generic_args> = self.reborrow();
                 index.get_unchecked_mut(slice)
             }
 
@@ -561,19 +750,19 @@ pub fn derive_mut(input: &Input) -> TokenStream {
             #[doc = #slice_name_str]
             ///` .
             /// This is required because we cannot implement `std::ops::IndexMut` directly since it requires returning a mutable reference.
-            pub fn index_mut<'b, I>(&'b mut self, index: I) -> I::MutOutput
+            pub fn index_mut<'__soa_b, __SoaI>(&'__soa_b mut self, index: __SoaI) -> __SoaI::MutOutput
             where
-                I: ::soa_derive::SoAIndexMut<#slice_mut_name<'b>>,
-                'a: 'b
+                __SoaI: ::soa_derive::SoAIndexMut<#slice_mut_name<'__soa_b, #generic_args>>,
+                '__soa_a: '__soa_b
             {
-                let slice: #slice_mut_name<'b> = self.reborrow();
+                let slice: #slice_mut_name<'__soa_b, #generic_args> = self.reborrow();
                 index.index_mut(slice)
             }
 
             /// Returns a non-mutable slice from this mutable slice.
-            pub fn as_slice<'b>(&'b self) -> #slice_name<'b>
+            pub fn as_slice<'__soa_b>(&'__soa_b self) -> #slice_name<'__soa_b, #generic_args>
             where
-                'a: 'b
+                '__soa_a: '__soa_b
             {
                 #slice_name {
                     #( #fields_names: #slice_as_slice, )*
@@ -581,9 +770,9 @@ pub fn derive_mut(input: &Input) -> TokenStream {
             }
 
             /// Reborrows the slices in a narrower lifetime
-            pub fn reborrow<'b>(&'b mut self) -> #slice_mut_name<'b>
+            pub fn reborrow<'__soa_b>(&'__soa_b mut self) -> #slice_mut_name<'__soa_b, #generic_args>
             where
-                'a: 'b
+                '__soa_a: '__soa_b
             {
                 #slice_mut_name {
                     #( #fields_names: #slice_reborrow, )*
@@ -593,7 +782,7 @@ pub fn derive_mut(input: &Input) -> TokenStream {
             /// Similar to [`&
             #[doc = #slice_name_str]
             /// ::as_ptr()`](https://doc.rust-lang.org/std/primitive.slice.html#method.as_ptr).
-            pub fn as_ptr(&self) -> #ptr_name {
+            pub fn as_ptr(&self) -> #ptr_name<#generic_args> {
                 #ptr_name {
                     #(#fields_names: self.#fields_names.as_ptr(),)*
                 }
@@ -602,14 +791,14 @@ pub fn derive_mut(input: &Input) -> TokenStream {
             /// Similar to [`&mut
             #[doc = #slice_name_str]
             /// ::as_mut_ptr()`](https://doc.rust-lang.org/std/primitive.slice.html#method.as_mut_ptr).
-            pub fn as_mut_ptr(&mut self) -> #ptr_mut_name {
+            pub fn as_mut_ptr(&mut self) -> #ptr_mut_name<#generic_args> {
                 #ptr_mut_name {
                     #(#fields_names: self.#fields_names.as_mut_ptr(),)*
                 }
             }
 
             /// Similar to [`std::slice::from_raw_parts_mut()`](https://doc.rust-lang.org/std/slice/fn.from_raw_parts_mut.html).
-            pub unsafe fn from_raw_parts_mut<'b>(data: #ptr_mut_name, len: usize) -> #slice_mut_name<'b> {
+            pub unsafe fn from_raw_parts_mut<'__soa_b>(data: #ptr_mut_name<#generic_args>, len: usize) -> #slice_mut_name<'__soa_b, #generic_args> {
                 #slice_mut_name {
                     #( #fields_names: #slice_from_raw_parts_mut, )*
                 }
@@ -618,83 +807,235 @@ pub fn derive_mut(input: &Input) -> TokenStream {
             #[doc(hidden)]
             /// This is `pub` due to there will be compile-error if `#[nested_soa]` is used.
             /// Do not use this method directly.
-            pub fn __private_apply_permutation(&mut self, permutation: &mut soa_derive::Permutation) {
+            pub fn __private_apply_permutation(&mut self, permutation: &mut ::soa_derive::Permutation) {
                 #( #apply_permutation; )*
             }
 
             /// Similar to [`&mut
             #[doc = #slice_name_str]
             /// ::sort_by()`](https://doc.rust-lang.org/std/primitive.slice.html#method.sort_by).
-            pub fn sort_by<F>(&mut self, mut f: F)
+            pub fn sort_by<__SoaF>(&mut self, mut f: __SoaF)
             where
-                F: FnMut(#ref_name, #ref_name) -> std::cmp::Ordering,
+                __SoaF: FnMut(#ref_name<'_, #generic_args>, #ref_name<'_, #generic_args>) -> ::core::cmp::Ordering,
             {
-                use soa_derive::Permutation;
+                use ::soa_derive::Permutation;
 
-                let mut permutation: Vec<usize> = (0..self.len()).collect();
+                let mut permutation: ::soa_derive::alloc::vec::Vec<usize> = (0..self.len()).collect();
                 permutation.sort_by(|j, k| f(self.index(*j), self.index(*k)));
 
-                let mut permutation = Permutation::oneline(permutation).inverse();
+                let mut permutation = Permutation::from_sorted_indices(&permutation);
                 self.__private_apply_permutation(&mut permutation);
             }
 
             /// Similar to [`&mut
             #[doc = #slice_name_str]
             /// ::sort_by_key()`](https://doc.rust-lang.org/std/primitive.slice.html#method.sort_by_key).
-            pub fn sort_by_key<F, K>(&mut self, mut f: F)
+            pub fn sort_by_key<__SoaF, __SoaK>(&mut self, mut f: __SoaF)
             where
-                F: FnMut(#ref_name) -> K,
-                K: Ord,
+                __SoaF: FnMut(#ref_name<'_, #generic_args>) -> __SoaK,
+                __SoaK: Ord,
             {
-                use soa_derive::Permutation;
+                use ::soa_derive::Permutation;
 
-                let mut permutation: Vec<usize> = (0..self.len()).collect();
+                let mut permutation: ::soa_derive::alloc::vec::Vec<usize> = (0..self.len()).collect();
                 permutation.sort_by_key(|i| f(self.index(*i)));
 
-                let mut permutation = Permutation::oneline(permutation).inverse();
+                let mut permutation = Permutation::from_sorted_indices(&permutation);
+                self.__private_apply_permutation(&mut permutation);
+            }
+
+            /// Similar to [`&mut
+            #[doc = #slice_name_str]
+            /// ::sort_unstable_by()`](https://doc.rust-lang.org/std/primitive.slice.html#method.sort_unstable_by).
+            pub fn sort_unstable_by<__SoaF>(&mut self, mut f: __SoaF)
+            where
+                __SoaF: FnMut(#ref_name<'_, #generic_args>, #ref_name<'_, #generic_args>) -> ::core::cmp::Ordering,
+            {
+                use ::soa_derive::Permutation;
+
+                let mut permutation: ::soa_derive::alloc::vec::Vec<usize> = (0..self.len()).collect();
+                permutation.sort_unstable_by(|j, k| f(self.index(*j), self.index(*k)));
+
+                let mut permutation = Permutation::from_sorted_indices(&permutation);
+                self.__private_apply_permutation(&mut permutation);
+            }
+
+            /// Similar to [`&mut
+            #[doc = #slice_name_str]
+            /// ::sort_unstable_by_key()`](https://doc.rust-lang.org/std/primitive.slice.html#method.sort_unstable_by_key).
+            pub fn sort_unstable_by_key<__SoaF, __SoaK>(&mut self, mut f: __SoaF)
+            where
+                __SoaF: FnMut(#ref_name<'_, #generic_args>) -> __SoaK,
+                __SoaK: Ord,
+            {
+                use ::soa_derive::Permutation;
+
+                let mut permutation: ::soa_derive::alloc::vec::Vec<usize> = (0..self.len()).collect();
+                permutation.sort_unstable_by_key(|i| f(self.index(*i)));
+
+                let mut permutation = Permutation::from_sorted_indices(&permutation);
+                self.__private_apply_permutation(&mut permutation);
+            }
+
+            /// Similar to [`&mut
+            #[doc = #slice_name_str]
+            /// ::sort_by_cached_key()`](https://doc.rust-lang.org/std/primitive.slice.html#method.sort_by_cached_key).
+            /// The key function is called exactly once per element.
+            pub fn sort_by_cached_key<__SoaF, __SoaK>(&mut self, f: __SoaF)
+            where
+                __SoaF: FnMut(#ref_name<'_, #generic_args>) -> __SoaK,
+                __SoaK: Ord,
+            {
+                use ::soa_derive::Permutation;
+
+                let mut keys: ::soa_derive::alloc::vec::Vec<(__SoaK, usize)> = self.as_slice().iter()
+                    .map(f)
+                    .enumerate()
+                    .map(|(i, key)| (key, i))
+                    .collect();
+                // indices are unique, so an unstable sort gives the same
+                // result as a stable sort on the keys alone
+                keys.sort_unstable();
+
+                let permutation = keys.into_iter().map(|(_, i)| i).collect::<::soa_derive::alloc::vec::Vec<_>>();
+                let mut permutation = Permutation::from_sorted_indices(&permutation);
                 self.__private_apply_permutation(&mut permutation);
             }
+
+            /// Sort this slice according to the values in a single column,
+            /// returned by `column`. This is a stable sort, which only reads
+            /// the key column while sorting, and then applies the resulting
+            /// permutation to all the other columns.
+            ///
+            /// ```ignore
+            /// particles.sort_by_column(|slice| &slice.cell[..]);
+            /// ```
+            ///
+            /// # Panics
+            ///
+            /// If the column does not have the same length as this slice.
+            pub fn sort_by_column<__SoaF, __SoaK>(&mut self, column: __SoaF)
+            where
+                __SoaF: for<'__soa_b> FnOnce(&'__soa_b Self) -> &'__soa_b [__SoaK],
+                __SoaK: Ord,
+            {
+                use ::soa_derive::Permutation;
+
+                let column = column(self);
+                assert_eq!(column.len(), self.len(), "the column used to sort should have the same length as the slice");
+
+                let mut keys: ::soa_derive::alloc::vec::Vec<(&__SoaK, usize)> = column.iter().zip(0..).collect();
+                keys.sort_by(|a, b| a.0.cmp(b.0));
+
+                let permutation = keys.into_iter().map(|(_, i)| i).collect::<::soa_derive::alloc::vec::Vec<_>>();
+                let mut permutation = Permutation::from_sorted_indices(&permutation);
+                self.__private_apply_permutation(&mut permutation);
+            }
+        }
+
+        #[allow(dead_code)]
+        impl<'__soa_a, #generic_params> #slice_mut_name<'__soa_a, #generic_args>
+        where
+            for<'__soa_b> #ref_name<'__soa_b, #generic_args>: PartialEq,
+            #where_predicates
+        {
+            /// Similar to [`&
+            #[doc = #slice_name_str]
+            /// ::contains()`](https://doc.rust-lang.org/std/primitive.slice.html#method.contains).
+            pub fn contains<'__soa_b>(&'__soa_b self, x: #ref_name<'__soa_b, #generic_args>) -> bool {
+                self.as_slice().into_iter().any(|item| item == x)
+            }
+
+            /// Similar to [`&
+            #[doc = #slice_name_str]
+            /// ::starts_with()`](https://doc.rust-lang.org/std/primitive.slice.html#method.starts_with).
+            pub fn starts_with<'__soa_b>(&'__soa_b self, needle: #slice_name<'__soa_b, #generic_args>) -> bool {
+                let slice = self.as_slice();
+                needle.len() <= slice.len() && slice.into_iter().zip(needle).all(|(a, b)| a == b)
+            }
+
+            /// Similar to [`&
+            #[doc = #slice_name_str]
+            /// ::ends_with()`](https://doc.rust-lang.org/std/primitive.slice.html#method.ends_with).
+            pub fn ends_with<'__soa_b>(&'__soa_b self, needle: #slice_name<'__soa_b, #generic_args>) -> bool {
+                let slice = self.as_slice();
+                needle.len() <= slice.len() && slice.into_iter().rev().zip(needle.into_iter().rev()).all(|(a, b)| a == b)
+            }
         }
 
         #[allow(dead_code)]
-        impl<'a> #slice_mut_name<'a>
+        impl<'__soa_a, #generic_params> #slice_mut_name<'__soa_a, #generic_args>
         where
-            #( #nested_ord, )*
+            for<'__soa_b> #ref_name<'__soa_b, #generic_args>: Ord,
+            #where_predicates
         {
             /// Similar to [`&mut
             #[doc = #slice_name_str]
             /// ::sort()`](https://doc.rust-lang.org/std/primitive.slice.html#method.sort).
             pub fn sort(&mut self) {
-                use soa_derive::Permutation;
+                use ::soa_derive::Permutation;
 
-                let mut permutation: Vec<usize> = (0..self.len()).collect();
+                let mut permutation: ::soa_derive::alloc::vec::Vec<usize> = (0..self.len()).collect();
                 permutation.sort_by_key(|i| self.index(*i));
 
-                let mut permutation = Permutation::oneline(permutation).inverse();
+                let mut permutation = Permutation::from_sorted_indices(&permutation);
+                self.__private_apply_permutation(&mut permutation);
+            }
+
+            /// Similar to [`&mut
+            #[doc = #slice_name_str]
+            /// ::sort_unstable()`](https://doc.rust-lang.org/std/primitive.slice.html#method.sort_unstable).
+            pub fn sort_unstable(&mut self) {
+                use ::soa_derive::Permutation;
+
+                let mut permutation: ::soa_derive::alloc::vec::Vec<usize> = (0..self.len()).collect();
+                permutation.sort_unstable_by_key(|i| self.index(*i));
+
+                let mut permutation = Permutation::from_sorted_indices(&permutation);
                 self.__private_apply_permutation(&mut permutation);
             }
         }
     };
 
+    // the single buffer vector does not have public fields, so we go through
+    // SoAAppendVec instead, which requires all fields to be Clone
+    let to_vec_bounds = if input.storage == Storage::SingleBuffer {
+        let fields_types = input.fields.iter().map(|field| &field.ty);
+        quote! { #(#fields_types: Clone,)* }
+    } else {
+        quote! {}
+    };
+    let to_vec = if input.storage == Storage::SingleBuffer {
+        quote! {
+            let mut vec = #vec_name::with_capacity(self.len());
+            ::soa_derive::SoAAppendVec::extend_from_slice(&mut vec, self.as_ref());
+            vec
+        }
+    } else {
+        quote! {
+            #vec_name {
+                #(#fields_names: self.#fields_names.to_vec(),)*
+            }
+        }
+    };
+
     if input.attrs.derive_clone {
         generated.append_all(quote!{
             #[allow(dead_code)]
-            impl<'a> #slice_mut_name<'a> {
+            impl<'__soa_a, #generic_params> #slice_mut_name<'__soa_a, #generic_args> where #to_vec_bounds #where_predicates {
                 /// Similar to [`&
                 #[doc = #slice_name_str]
                 /// ::to_vec()`](https://doc.rust-lang.org/std/primitive.slice.html#method.to_vec).
-                pub fn to_vec(&self) -> #vec_name {
-                    #vec_name {
-                        #(#fields_names: self.#fields_names.to_vec(),)*
-                    }
+                pub fn to_vec(&self) -> #vec_name<#generic_args> {
+                    #to_vec
                 }
             }
         });
 
         {
             generated.append_all(quote! {
-                impl<'a> ::soa_derive::ToSoAVec<#name> for #slice_mut_name<'a> {
-                    type SoAVecType = #vec_name;
+                impl<'__soa_a, #generic_params> ::soa_derive::ToSoAVec<#name<#generic_args>> for #slice_mut_name<'__soa_a, #generic_args> where #to_vec_bounds #where_predicates {
+                    type SoAVecType = #vec_name<#generic_args>;
 
                     fn to_vec(&self) -> Self::SoAVecType {
                         self.to_vec()
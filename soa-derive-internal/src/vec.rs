@@ -1,28 +1,34 @@
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::TokenStreamExt;
-use quote::quote;
+use quote::{format_ident, quote};
 
-use crate::input::Input;
-use crate::names;
+use crate::input::{Input, TokenStreamIterator};
 
 pub fn derive(input: &Input) -> TokenStream {
     let name = &input.name;
     let vec_name_str = format!("Vec<{}>", name);
     let attrs = &input.attrs.vec;
     let visibility = &input.visibility;
-    let vec_name = names::vec_name(&input.name);
-    let slice_name = names::slice_name(name);
-    let slice_mut_name = names::slice_mut_name(&input.name);
-    let ref_name = names::ref_name(&input.name);
-    let ref_mut_name = names::ref_mut_name(&input.name);
-    let ptr_name = names::ptr_name(&input.name);
-    let ptr_mut_name = names::ptr_mut_name(&input.name);
+    let vec_name = &input.names.vec;
+    let slice_name = &input.names.slice;
+    let slice_mut_name = &input.names.slice_mut;
+    let ref_name = &input.names.ref_;
+    let ref_mut_name = &input.names.ref_mut;
+    let ptr_name = &input.names.ptr;
+    let ptr_mut_name = &input.names.ptr_mut;
+    let into_iter_name = &input.names.into_iter;
+
+    let generic_params = input.generic_params();
+    let generic_args = input.generic_args();
+    let where_predicates = input.where_predicates();
 
     let doc_url = format!("[`{0}`](struct.{0}.html)", input.name);
+    let vec_doc_url = format!("[`{0}`](struct.{0}.html)", vec_name);
 
-    let fields_names = &input.fields.iter()
-        .map(|field| field.ident.as_ref().unwrap())
-        .collect::<Vec<_>>();
+    let fields_names = &input.fields_names();
+    let skipped_fields_init = input.skipped_fields_init();
+    let drop_skipped_value = input.drop_skipped_fields(&quote! { value });
+    let drop_skipped_element = input.drop_skipped_fields(&quote! { element });
 
     let fields_names_hygienic = input.fields.iter()
         .enumerate()
@@ -31,17 +37,20 @@ pub fn derive(input: &Input) -> TokenStream {
 
     let first_field = &fields_names[0];
 
+    // names of the arguments of `from_columns()`
+    let columns_names = fields_names.iter().map(|member| match member {
+        syn::Member::Named(ident) => ident.clone(),
+        syn::Member::Unnamed(index) => Ident::new(&format!("column_{}", index.index), Span::call_site()),
+    }).collect::<Vec<_>>();
+
     let vec_fields_types = input.map_fields_nested_or(
-        |_, field_type| {
-            let vec_type = names::vec_name(field_type);
-            quote! { #vec_type }
-        },
-        |_, field_type| quote! { Vec<#field_type> },
+        |_, field_type| quote! { <#field_type as ::soa_derive::StructOfArray>::Type },
+        |_, field_type| quote! { ::soa_derive::alloc::vec::Vec<#field_type> },
     ).collect::<Vec<_>>();
 
     let vec_with_capacity = input.map_fields_nested_or(
-        |_, field_type| quote! { <#field_type as StructOfArray>::Type::with_capacity(capacity) },
-        |_, _| quote! { Vec::with_capacity(capacity) },
+        |_, field_type| quote! { <#field_type as ::soa_derive::StructOfArray>::Type::with_capacity(capacity) },
+        |_, _| quote! { ::soa_derive::alloc::vec::Vec::with_capacity(capacity) },
     ).collect::<Vec<_>>();
 
     let vec_slice = input.map_fields_nested_or(
@@ -55,42 +64,82 @@ pub fn derive(input: &Input) -> TokenStream {
     ).collect::<Vec<_>>();
 
     let vec_from_raw_parts = input.map_fields_nested_or(
-        |ident, field_type| {
-            let vec_type = names::vec_name(field_type);
-            quote! { #vec_type::from_raw_parts(data.#ident, len, capacity) }
+        |ident, field_type| quote! {
+            <#field_type as ::soa_derive::StructOfArray>::Type::from_raw_parts(data.#ident, len, capacity)
         },
-        |ident, _| quote! { Vec::from_raw_parts(data.#ident, len, capacity) },
+        |ident, _| quote! { ::soa_derive::alloc::vec::Vec::from_raw_parts(data.#ident, len, capacity) },
     ).collect::<Vec<_>>();
 
     let vec_replace = input.map_fields_nested_or(
         |ident, _| quote! { self.#ident.replace(index, field) },
-        |ident, _| quote! { ::std::mem::replace(&mut self.#ident[index], field) },
+        |ident, _| quote! { ::core::mem::replace(&mut self.#ident[index], field) },
     ).collect::<Vec<_>>();
 
+    let into_iter_type = input.map_fields_nested_or(
+        |_, field_type| quote! { <<#field_type as ::soa_derive::StructOfArray>::Type as IntoIterator>::IntoIter },
+        |_, field_type| quote! { ::soa_derive::alloc::vec::IntoIter<#field_type> },
+    ).concat_by(
+        |seq, next| { quote! { ::core::iter::Zip<#seq, #next> } }
+    );
+
+    let create_into_iter = fields_names_hygienic.iter().fold(None, |seq, ident| {
+        if let Some(seq) = seq {
+            Some(quote! { #seq.zip(#ident.into_iter()) })
+        } else {
+            Some(quote! { #ident.into_iter() })
+        }
+    }).expect("should be Some");
+
+    let iter_pat = fields_names_hygienic.iter().fold(None, |seq, ident| {
+        if let Some(seq) = seq {
+            Some(quote! { (#seq, #ident) })
+        } else {
+            Some(quote!{ #ident })
+        }
+    }).expect("should be Some");
+
+    let validate_nested = input.map_fields_nested_or(
+        |ident, _| quote! {
+            self.#ident.validate().map_err(|error| error.nested(stringify!(#ident)))?;
+        },
+        |_, _| quote! {},
+    ).collect::<Vec<_>>();
+
+    let fields_docs = fields_names.iter().map(|field| quote! {
+        /// a vector of `
+        #[doc = stringify!(#field)]
+        ///` from a
+        #[doc = #doc_url]
+    }).collect::<Vec<_>>();
+    let vec_body = if input.private_columns {
+        input.struct_body_with_visibility(None, &fields_docs, &vec_fields_types, &quote! {})
+    } else {
+        input.struct_body(None, &fields_docs, &vec_fields_types)
+    };
+
     let mut generated = quote! {
         /// An analog to `
         #[doc = #vec_name_str]
         /// ` with Struct of Array (SoA) layout
         #[allow(dead_code)]
         #(#[#attrs])*
-        #[derive(Default)]
-        #visibility struct #vec_name {
-            #(
-                /// a vector of `
-                #[doc = stringify!(#fields_names)]
-                ///` from a
-                #[doc = #doc_url]
-                pub #fields_names: #vec_fields_types,
-            )*
+        #visibility struct #vec_name<#generic_params> #vec_body
+
+        impl<#generic_params> Default for #vec_name<#generic_args> where #where_predicates {
+            fn default() -> Self {
+                #vec_name {
+                    #( #fields_names: Default::default(), )*
+                }
+            }
         }
 
         #[allow(dead_code)]
         #[allow(clippy::forget_non_drop)]
-        impl #vec_name {
+        impl<#generic_params> #vec_name<#generic_args> where #where_predicates {
             /// Similar to [`
             #[doc = #vec_name_str]
             /// ::new()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.new)
-            pub fn new() -> #vec_name {
+            pub fn new() -> #vec_name<#generic_args> {
                 Default::default()
             }
 
@@ -98,7 +147,7 @@ pub fn derive(input: &Input) -> TokenStream {
             #[doc = #vec_name_str]
             /// ::with_capacity()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.with_capacity),
             /// initializing all fields with the given `capacity`.
-            pub fn with_capacity(capacity: usize) -> #vec_name {
+            pub fn with_capacity(capacity: usize) -> #vec_name<#generic_args> {
                 #vec_name {
                     #( #fields_names: #vec_with_capacity, )*
                 }
@@ -150,15 +199,16 @@ pub fn derive(input: &Input) -> TokenStream {
             #[doc = #vec_name_str]
             /// ::push()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.push).
             #[allow(clippy::forget_non_drop)]
-            pub fn push(&mut self, value: #name) {
+            pub fn push(&mut self, value: #name<#generic_args>) {
                 // We need to use ptr read/write instead of moving out of the
                 // fields in case the value struct implements Drop.
                 unsafe {
-                    #(self.#fields_names.push(::std::ptr::read(&value.#fields_names));)*
+                    #(self.#fields_names.push(::core::ptr::read(&value.#fields_names));)*
                 }
+                #drop_skipped_value
                 // if value implements Drop, we don't want to run it here, only
                 // when the vec itself will be dropped.
-                ::std::mem::forget(value);
+                ::core::mem::forget(value);
             }
 
             /// Similar to [`
@@ -166,9 +216,49 @@ pub fn derive(input: &Input) -> TokenStream {
             /// ::len()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.len),
             /// all the fields should have the same length.
             pub fn len(&self) -> usize {
-                let len = self.#first_field.len();
-                #(debug_assert_eq!(self.#fields_names.len(), len);)*
-                len
+                debug_assert_eq!(self.validate(), Ok(()));
+                self.#first_field.len()
+            }
+
+            /// Check that all the fields, including the columns of nested
+            /// fields, have the same length. This is checked with
+            /// `debug_assert!` in `len()`, and can be used to check vectors
+            /// built by setting the public fields directly.
+            pub fn validate(&self) -> Result<(), ::soa_derive::LengthMismatch> {
+                // nested fields are checked first, so we can then use their
+                // length
+                #(#validate_nested)*
+
+                let expected = self.#first_field.len();
+                #(
+                    let actual = self.#fields_names.len();
+                    if actual != expected {
+                        return Err(::soa_derive::LengthMismatch::new(stringify!(#fields_names), expected, actual));
+                    }
+                )*
+                Ok(())
+            }
+
+            /// Create a vector from existing columns, one for each field,
+            /// checking that all the columns have the same length.
+            #[allow(clippy::too_many_arguments)]
+            pub fn from_columns(#(#columns_names: #vec_fields_types),*) -> Result<#vec_name<#generic_args>, ::soa_derive::LengthMismatch> {
+                let vec = #vec_name {
+                    #(#fields_names: #columns_names,)*
+                };
+                vec.validate()?;
+                Ok(vec)
+            }
+
+            /// Convert this vector to its columns, one for each field, in
+            /// the same order as the fields.
+            pub fn into_columns(self) -> (#(#vec_fields_types,)*) {
+                // the vector implements `Drop`, so we need to read the fields
+                // instead of moving them out
+                let vec = ::core::mem::ManuallyDrop::new(self);
+                unsafe {
+                    (#(::core::ptr::read(&vec.#fields_names),)*)
+                }
             }
 
             /// Similar to [`
@@ -184,18 +274,18 @@ pub fn derive(input: &Input) -> TokenStream {
             /// Similar to [`
             #[doc = #vec_name_str]
             /// ::swap_remove()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.swap_remove).
-            pub fn swap_remove(&mut self, index: usize) -> #name {
+            pub fn swap_remove(&mut self, index: usize) -> #name<#generic_args> {
                 #(
                     let #fields_names_hygienic = self.#fields_names.swap_remove(index);
                 )*
-                #name{#(#fields_names: #fields_names_hygienic),*}
+                #name{#(#fields_names: #fields_names_hygienic,)* #skipped_fields_init}
             }
 
             /// Similar to [`
             #[doc = #vec_name_str]
             /// ::insert()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.insert).
             #[allow(clippy::forget_non_drop)]
-            pub fn insert(&mut self, index: usize, element: #name) {
+            pub fn insert(&mut self, index: usize, element: #name<#generic_args>) {
                 if index > self.len() {
                     panic!("index out of bounds: the len is {} but the index is {}", self.len(), index);
                 }
@@ -203,16 +293,17 @@ pub fn derive(input: &Input) -> TokenStream {
                 // similar to push, we can not use move and have to rely on ptr
                 // read/write
                 unsafe {
-                    #(self.#fields_names.insert(index, ::std::ptr::read(&element.#fields_names));)*
+                    #(self.#fields_names.insert(index, ::core::ptr::read(&element.#fields_names));)*
                 }
+                #drop_skipped_element
                 // if value implements Drop, we don't want to run it here, only
                 // when the vec itself will be dropped.
-                ::std::mem::forget(element);
+                ::core::mem::forget(element);
             }
 
             /// Similar to [`std::mem::replace()`](https://doc.rust-lang.org/std/mem/fn.replace.html).
             #[allow(clippy::forget_non_drop)]
-            pub fn replace(&mut self, index: usize, element: #name) -> #name {
+            pub fn replace(&mut self, index: usize, element: #name<#generic_args>) -> #name<#generic_args> {
                 if index > self.len() {
                     panic!("index out of bounds: the len is {} but the index is {}", self.len(), index);
                 }
@@ -220,44 +311,45 @@ pub fn derive(input: &Input) -> TokenStream {
                 // similar to push, we can not use move and have to rely on ptr
                 // read/write
                 #(
-                    let field = unsafe { ::std::ptr::read(&element.#fields_names) };
+                    let field = unsafe { ::core::ptr::read(&element.#fields_names) };
                     let #fields_names_hygienic = #vec_replace;
                 )*
+                #drop_skipped_element
                 // if value implements Drop, we don't want to run it here, only
                 // when the vec itself will be dropped.
-                ::std::mem::forget(element);
+                ::core::mem::forget(element);
 
-                #name{#(#fields_names: #fields_names_hygienic),*}
+                #name{#(#fields_names: #fields_names_hygienic,)* #skipped_fields_init}
             }
 
             /// Similar to [`
             #[doc = #vec_name_str]
             /// ::remove()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.remove).
-            pub fn remove(&mut self, index: usize) -> #name {
+            pub fn remove(&mut self, index: usize) -> #name<#generic_args> {
                 #(
                     let #fields_names_hygienic = self.#fields_names.remove(index);
                 )*
-                #name{#(#fields_names: #fields_names_hygienic),*}
+                #name{#(#fields_names: #fields_names_hygienic,)* #skipped_fields_init}
             }
 
             /// Similar to [`
             #[doc = #vec_name_str]
             /// ::pop()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.pop).
-            pub fn pop(&mut self) -> Option<#name> {
+            pub fn pop(&mut self) -> Option<#name<#generic_args>> {
                 if self.is_empty() {
                     None
                 } else {
                     #(
                         let #fields_names_hygienic = self.#fields_names.pop().unwrap();
                     )*
-                    Some(#name{#(#fields_names: #fields_names_hygienic),*})
+                    Some(#name{#(#fields_names: #fields_names_hygienic,)* #skipped_fields_init})
                 }
             }
 
             /// Similar to [`
             #[doc = #vec_name_str]
             /// ::append()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.append).
-            pub fn append(&mut self, other: &mut #vec_name) {
+            pub fn append(&mut self, other: &mut #vec_name<#generic_args>) {
                 #(
                     self.#fields_names.append(&mut other.#fields_names);
                 )*
@@ -273,7 +365,7 @@ pub fn derive(input: &Input) -> TokenStream {
             /// Similar to [`
             #[doc = #vec_name_str]
             /// ::split_off()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.split_off).
-            pub fn split_off(&mut self, at: usize) -> #vec_name {
+            pub fn split_off(&mut self, at: usize) -> #vec_name<#generic_args> {
                 #vec_name {
                     #(#fields_names: self.#fields_names.split_off(at), )*
                 }
@@ -282,7 +374,7 @@ pub fn derive(input: &Input) -> TokenStream {
             /// Similar to [`
             #[doc = #vec_name_str]
             /// ::as_slice()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.as_slice).
-            pub fn as_slice(&self) -> #slice_name {
+            pub fn as_slice(&self) -> #slice_name<'_, #generic_args> {
                 #slice_name {
                     #(#fields_names: self.#fields_names.as_slice(), )*
                 }
@@ -291,7 +383,7 @@ pub fn derive(input: &Input) -> TokenStream {
             /// Similar to [`
             #[doc = #vec_name_str]
             /// ::as_mut_slice()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.as_mut_slice).
-            pub fn as_mut_slice(&mut self) -> #slice_mut_name {
+            pub fn as_mut_slice(&mut self) -> #slice_mut_name<'_, #generic_args> {
                 #slice_mut_name {
                     #(#fields_names: self.#fields_names.as_mut_slice(), )*
                 }
@@ -299,7 +391,7 @@ pub fn derive(input: &Input) -> TokenStream {
 
             /// Create a slice of this vector matching the given `range`. This
             /// is analogous to `Index<Range<usize>>`.
-            pub fn slice(&self, range: ::std::ops::Range<usize>) -> #slice_name {
+            pub fn slice(&self, range: ::core::ops::Range<usize>) -> #slice_name<'_, #generic_args> {
                 #slice_name {
                     #( #fields_names: #vec_slice, )*
                 }
@@ -307,7 +399,7 @@ pub fn derive(input: &Input) -> TokenStream {
 
             /// Create a mutable slice of this vector matching the given
             /// `range`. This is analogous to `IndexMut<Range<usize>>`.
-            pub fn slice_mut(&mut self, range: ::std::ops::Range<usize>) -> #slice_mut_name {
+            pub fn slice_mut(&mut self, range: ::core::ops::Range<usize>) -> #slice_mut_name<'_, #generic_args> {
                 #slice_mut_name {
                     #( #fields_names: #vec_slice_mut, )*
                 }
@@ -316,7 +408,7 @@ pub fn derive(input: &Input) -> TokenStream {
             /// Similar to [`
             #[doc = #vec_name_str]
             /// ::retain()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.retain).
-            pub fn retain<F>(&mut self, mut f: F) where F: FnMut(#ref_name) -> bool {
+            pub fn retain<__SoaF>(&mut self, mut f: __SoaF) where __SoaF: FnMut(#ref_name<'_, #generic_args>) -> bool {
                 let len = self.len();
                 let mut del = 0;
 
@@ -338,7 +430,7 @@ pub fn derive(input: &Input) -> TokenStream {
             /// Similar to [`
             #[doc = #vec_name_str]
             /// ::retain_mut()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.retain_mut).
-            pub fn retain_mut<F>(&mut self, mut f: F) where F: FnMut(#ref_mut_name) -> bool {
+            pub fn retain_mut<__SoaF>(&mut self, mut f: __SoaF) where __SoaF: FnMut(#ref_mut_name<'_, #generic_args>) -> bool {
                 let len = self.len();
                 let mut del = 0;
 
@@ -357,12 +449,57 @@ pub fn derive(input: &Input) -> TokenStream {
                 }
             }
 
+            /// Similar to [`
+            #[doc = #vec_name_str]
+            /// ::dedup_by()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.dedup_by).
+            ///
+            /// The first argument to `same_bucket` is the element being
+            /// considered, and the second argument is the last element kept.
+            pub fn dedup_by<__SoaF>(&mut self, mut same_bucket: __SoaF)
+            where
+                __SoaF: FnMut(#ref_mut_name<'_, #generic_args>, #ref_mut_name<'_, #generic_args>) -> bool
+            {
+                let len = self.len();
+                if len <= 1 {
+                    return;
+                }
+
+                let mut kept = 1;
+                {
+                    let mut slice = self.as_mut_slice();
+                    for i in 1..len {
+                        let duplicate = {
+                            let (mut previous, mut current) = slice.reborrow().split_at_mut(i);
+                            same_bucket(current.get_mut(0).unwrap(), previous.get_mut(kept - 1).unwrap())
+                        };
+                        if !duplicate {
+                            if i != kept {
+                                slice.swap(kept, i);
+                            }
+                            kept += 1;
+                        }
+                    }
+                }
+                self.truncate(kept);
+            }
+
+            /// Similar to [`
+            #[doc = #vec_name_str]
+            /// ::dedup_by_key()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.dedup_by_key).
+            pub fn dedup_by_key<__SoaF, __SoaK>(&mut self, mut key: __SoaF)
+            where
+                __SoaF: FnMut(#ref_mut_name<'_, #generic_args>) -> __SoaK,
+                __SoaK: PartialEq,
+            {
+                self.dedup_by(|a, b| key(a) == key(b));
+            }
+
             /// Similar to [`
             #[doc = #vec_name_str]
             /// ::get<I>()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.get).
-            pub fn get<'a, I>(&'a self, index: I) -> Option<I::RefOutput>
+            pub fn get<'__soa_a, __SoaI>(&'__soa_a self, index: __SoaI) -> Option<__SoaI::RefOutput>
             where
-                I: ::soa_derive::SoAIndex<&'a #vec_name>
+                __SoaI: ::soa_derive::SoAIndex<&'__soa_a #vec_name<#generic_args>>
             {
                 index.get(self)
             }
@@ -370,9 +507,9 @@ pub fn derive(input: &Input) -> TokenStream {
             /// Similar to [`
             #[doc = #vec_name_str]
             /// ::get_unchecked<I>()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.get_unchecked).
-            pub unsafe fn get_unchecked<'a, I>(&'a self, index: I) -> I::RefOutput
+            pub unsafe fn get_unchecked<'__soa_a, __SoaI>(&'__soa_a self, index: __SoaI) -> __SoaI::RefOutput
             where
-                I: ::soa_derive::SoAIndex<&'a #vec_name>
+                __SoaI: ::soa_derive::SoAIndex<&'__soa_a #vec_name<#generic_args>>
             {
                 index.get_unchecked(self)
             }
@@ -380,9 +517,9 @@ pub fn derive(input: &Input) -> TokenStream {
             /// Similar to [`
             #[doc = #vec_name_str]
             /// ::index<I>()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.index).
-            pub fn index<'a, I>(&'a self, index: I) -> I::RefOutput
+            pub fn index<'__soa_a, __SoaI>(&'__soa_a self, index: __SoaI) -> __SoaI::RefOutput
             where
-                I: ::soa_derive::SoAIndex<&'a #vec_name>
+                __SoaI: ::soa_derive::SoAIndex<&'__soa_a #vec_name<#generic_args>>
             {
                 index.index(self)
             }
@@ -390,9 +527,9 @@ pub fn derive(input: &Input) -> TokenStream {
             /// Similar to [`
             #[doc = #vec_name_str]
             /// ::get_mut<I>()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.get_mut).
-            pub fn get_mut<'a, I>(&'a mut self, index: I) -> Option<I::MutOutput>
+            pub fn get_mut<'__soa_a, __SoaI>(&'__soa_a mut self, index: __SoaI) -> Option<__SoaI::MutOutput>
             where
-                I: ::soa_derive::SoAIndexMut<&'a mut #vec_name>
+                __SoaI: ::soa_derive::SoAIndexMut<&'__soa_a mut #vec_name<#generic_args>>
             {
                 index.get_mut(self)
             }
@@ -400,9 +537,9 @@ pub fn derive(input: &Input) -> TokenStream {
             /// Similar to [`
             #[doc = #vec_name_str]
             /// ::get_unchecked_mut<I>()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.get_unchecked_mut).
-            pub unsafe fn get_unchecked_mut<'a, I>(&'a mut self, index: I) -> I::MutOutput
+            pub unsafe fn get_unchecked_mut<'__soa_a, __SoaI>(&'__soa_a mut self, index: __SoaI) -> __SoaI::MutOutput
             where
-                I: ::soa_derive::SoAIndexMut<&'a mut #vec_name>
+                __SoaI: ::soa_derive::SoAIndexMut<&'__soa_a mut #vec_name<#generic_args>>
             {
                 index.get_unchecked_mut(self)
             }
@@ -410,9 +547,9 @@ pub fn derive(input: &Input) -> TokenStream {
             /// Similar to [`
             #[doc = #vec_name_str]
             /// ::index_mut<I>()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.index_mut).
-            pub fn index_mut<'a, I>(&'a mut self, index: I) -> I::MutOutput
+            pub fn index_mut<'__soa_a, __SoaI>(&'__soa_a mut self, index: __SoaI) -> __SoaI::MutOutput
             where
-                I: ::soa_derive::SoAIndexMut<&'a mut #vec_name>
+                __SoaI: ::soa_derive::SoAIndexMut<&'__soa_a mut #vec_name<#generic_args>>
             {
                 index.index_mut(self)
             }
@@ -420,7 +557,7 @@ pub fn derive(input: &Input) -> TokenStream {
             /// Similar to [`
             #[doc = #vec_name_str]
             /// ::as_ptr()`](https://doc.rust-lang.org/std/struct.Vec.html#method.as_ptr).
-            pub fn as_ptr(&self) -> #ptr_name {
+            pub fn as_ptr(&self) -> #ptr_name<#generic_args> {
                 #ptr_name {
                     #(#fields_names: self.#fields_names.as_ptr(),)*
                 }
@@ -429,7 +566,7 @@ pub fn derive(input: &Input) -> TokenStream {
             /// Similar to [`
             #[doc = #vec_name_str]
             /// ::as_mut_ptr()`](https://doc.rust-lang.org/std/struct.Vec.html#method.as_mut_ptr).
-            pub fn as_mut_ptr(&mut self) -> #ptr_mut_name {
+            pub fn as_mut_ptr(&mut self) -> #ptr_mut_name<#generic_args> {
                 #ptr_mut_name {
                     #(#fields_names: self.#fields_names.as_mut_ptr(),)*
                 }
@@ -438,38 +575,158 @@ pub fn derive(input: &Input) -> TokenStream {
             /// Similar to [`
             #[doc = #vec_name_str]
             /// ::from_raw_parts()`](https://doc.rust-lang.org/std/struct.Vec.html#method.from_raw_parts).
-            pub unsafe fn from_raw_parts(data: #ptr_mut_name, len: usize, capacity: usize) -> #vec_name {
+            pub unsafe fn from_raw_parts(data: #ptr_mut_name<#generic_args>, len: usize, capacity: usize) -> #vec_name<#generic_args> {
                 #vec_name {
                     #( #fields_names: #vec_from_raw_parts, )*
                 }
             }
+
+            /// Similar to [`
+            #[doc = #vec_name_str]
+            /// ::set_len()`](https://doc.rust-lang.org/std/struct.Vec.html#method.set_len),
+            /// setting the length of all fields.
+            pub unsafe fn set_len(&mut self, new_len: usize) {
+                #(self.#fields_names.set_len(new_len);)*
+            }
         }
 
         #[allow(clippy::drop_non_drop)]
-        impl Drop for #vec_name {
+        impl<#generic_params> Drop for #vec_name<#generic_args> where #where_predicates {
             fn drop(&mut self) {
+                if self.validate().is_err() {
+                    // values can not be re-created from columns with
+                    // different lengths, let the columns drop their elements
+                    return;
+                }
                 while let Some(value) = self.pop() {
-                    ::std::mem::drop(value);
+                    ::core::mem::drop(value);
                 }
             }
         }
+
+        /// Owning iterator over
+        #[doc = #vec_doc_url]
+        /// , yielding
+        #[doc = #doc_url]
+        /// . This is built on the `IntoIter` of each field's vector, and
+        /// similar to [`std::vec::IntoIter`](https://doc.rust-lang.org/std/vec/struct.IntoIter.html).
+        #[allow(missing_debug_implementations)]
+        #visibility struct #into_iter_name<#generic_params>(#into_iter_type) where #where_predicates;
+
+        impl<#generic_params> Iterator for #into_iter_name<#generic_args> where #where_predicates {
+            type Item = #name<#generic_args>;
+
+            #[inline]
+            fn next(&mut self) -> Option<#name<#generic_args>> {
+                self.0.next().map(|#iter_pat| {
+                    #name{#(#fields_names: #fields_names_hygienic,)* #skipped_fields_init}
+                })
+            }
+
+            #[inline]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.0.size_hint()
+            }
+        }
+
+        impl<#generic_params> DoubleEndedIterator for #into_iter_name<#generic_args> where #where_predicates {
+            #[inline]
+            fn next_back(&mut self) -> Option<#name<#generic_args>> {
+                self.0.next_back().map(|#iter_pat| {
+                    #name{#(#fields_names: #fields_names_hygienic,)* #skipped_fields_init}
+                })
+            }
+        }
+
+        impl<#generic_params> ExactSizeIterator for #into_iter_name<#generic_args> where #where_predicates {
+            fn len(&self) -> usize {
+                self.0.len()
+            }
+        }
+
+        impl<#generic_params> Drop for #into_iter_name<#generic_args> where #where_predicates {
+            fn drop(&mut self) {
+                // re-create the remaining values to run their `Drop`
+                // implementation, if any
+                self.for_each(::core::mem::drop);
+            }
+        }
+
+        impl<#generic_params> IntoIterator for #vec_name<#generic_args> where #where_predicates {
+            type Item = #name<#generic_args>;
+            type IntoIter = #into_iter_name<#generic_args>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                // the vector implements `Drop`, so we need to read the fields
+                // instead of moving them out
+                let vec = ::core::mem::ManuallyDrop::new(self);
+                #(
+                    let #fields_names_hygienic = unsafe { ::core::ptr::read(&vec.#fields_names) };
+                )*
+                #into_iter_name(#create_into_iter)
+            }
+        }
     };
 
+    if input.private_columns {
+        // the accessors are prefixed to not clash with the other methods of
+        // the vector, e.g. for a field named `len`
+        let accessors = fields_names.iter().map(|member| match member {
+            syn::Member::Named(ident) => format_ident!("column_{}", ident),
+            syn::Member::Unnamed(index) => format_ident!("column_{}", index.index),
+        }).collect::<Vec<_>>();
+        let accessors_mut = accessors.iter()
+            .map(|name| format_ident!("{}_mut", name))
+            .collect::<Vec<_>>();
+
+        let column_types = input.map_fields_nested_or(
+            |_, field_type| quote! { <#field_type as ::soa_derive::SoASlices<'_>>::Slice },
+            |_, field_type| quote! { &[#field_type] },
+        ).collect::<Vec<_>>();
+
+        let column_mut_types = input.map_fields_nested_or(
+            |_, field_type| quote! { <#field_type as ::soa_derive::SoASlices<'_>>::SliceMut },
+            |_, field_type| quote! { &mut [#field_type] },
+        ).collect::<Vec<_>>();
+
+        generated.append_all(quote! {
+            #[allow(dead_code)]
+            impl<#generic_params> #vec_name<#generic_args> where #where_predicates {
+                #(
+                    /// Get the column containing the `
+                    #[doc = stringify!(#fields_names)]
+                    /// ` field of all the elements in this vector
+                    pub fn #accessors(&self) -> #column_types {
+                        self.#fields_names.as_slice()
+                    }
+
+                    /// Get the column containing the `
+                    #[doc = stringify!(#fields_names)]
+                    /// ` field of all the elements in this vector, as a
+                    /// mutable slice
+                    pub fn #accessors_mut(&mut self) -> #column_mut_types {
+                        self.#fields_names.as_mut_slice()
+                    }
+                )*
+            }
+        });
+    }
+
     if input.attrs.derive_clone {
         generated.append_all(quote!{
             #[allow(dead_code)]
-            impl #vec_name {
+            impl<#generic_params> #vec_name<#generic_args> where #where_predicates {
                 /// Similar to [`
                 #[doc = #vec_name_str]
                 /// ::resize()`](https://doc.rust-lang.org/std/vec/struct.Vec.html#method.resize).
-                pub fn resize(&mut self, new_len: usize, value: #name) {
+                pub fn resize(&mut self, new_len: usize, value: #name<#generic_args>) {
                     #(
                         self.#fields_names.resize(new_len, value.#fields_names);
                     )*
                 }
             }
 
-            impl ::soa_derive::SoAAppendVec<#name> for #vec_name {
+            impl<#generic_params> ::soa_derive::SoAAppendVec<#name<#generic_args>> for #vec_name<#generic_args> where #where_predicates {
                 fn extend_from_slice(&mut self, other: Self::Slice<'_>) {
                     #(
                         self.#fields_names.extend_from_slice(other.#fields_names);
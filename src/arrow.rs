@@ -0,0 +1,183 @@
+//! Conversion of struct of arrays vectors to and from [Apache Arrow] arrays.
+//!
+//! This module is only available with the `arrow` feature. The
+//! `arrow_schema()`, `to_record_batch()`, `into_record_batch()` and
+//! `try_from_record_batch()` methods generated for `CheeseVec` use the
+//! [`ArrowColumn`] trait to convert each column.
+//!
+//! [Apache Arrow]: https://arrow.apache.org/
+
+use std::sync::Arc;
+
+use arrow_array::types::{
+    Float32Type, Float64Type, Int16Type, Int32Type, Int64Type, Int8Type,
+    UInt16Type, UInt32Type, UInt64Type, UInt8Type,
+};
+use arrow_array::{Array, ArrayRef, BooleanArray, PrimitiveArray, StringArray, StructArray};
+use arrow_buffer::ScalarBuffer;
+use arrow_schema::{ArrowError, DataType};
+
+/// A column of a struct of arrays vector, which can be converted to and from
+/// an Arrow array.
+///
+/// This trait is implemented for `Vec<T>` where `T` is a primitive integer or
+/// floating point type, `bool` or `String`; and for the `CheeseVec` types
+/// generated by [`StructOfArray`](crate::StructOfArray), which are converted
+/// to struct arrays. Arrays containing null values are not supported.
+pub trait ArrowColumn: Sized {
+    /// Get the Arrow data type of this column
+    fn arrow_data_type() -> DataType;
+
+    /// Convert this column to an Arrow array, copying the data
+    fn to_arrow_array(&self) -> Result<ArrayRef, ArrowError>;
+
+    /// Convert this column to an Arrow array. The data is moved to the array
+    /// without copying it when the memory layout is the same, i.e. for
+    /// primitive types.
+    fn into_arrow_array(self) -> Result<ArrayRef, ArrowError>;
+
+    /// Create a column from an Arrow array, copying the data
+    fn try_from_arrow_array(array: &dyn Array) -> Result<Self, ArrowError>;
+}
+
+/// Downcast `array` to the concrete array type `T`, checking that it has the
+/// `expected` data type and does not contain null values.
+#[doc(hidden)]
+pub fn downcast<'a, T: Array + 'static>(array: &'a dyn Array, expected: &DataType) -> Result<&'a T, ArrowError> {
+    if array.data_type() != expected {
+        return Err(ArrowError::SchemaError(format!(
+            "expected an array of {}, got an array of {}", expected, array.data_type()
+        )));
+    }
+
+    if array.null_count() != 0 {
+        return Err(ArrowError::InvalidArgumentError(format!(
+            "array contains {} null values, which are not supported", array.null_count()
+        )));
+    }
+
+    array.as_any().downcast_ref::<T>().ok_or_else(|| ArrowError::SchemaError(format!(
+        "expected an array of {}, got an array of {}", expected, array.data_type()
+    )))
+}
+
+/// Downcast `array` to a struct array without null values. The fields of the
+/// struct are checked separately, allowing additional fields.
+#[doc(hidden)]
+pub fn downcast_struct(array: &dyn Array) -> Result<&StructArray, ArrowError> {
+    let data_type = array.data_type();
+    let array = array.as_any().downcast_ref::<StructArray>().ok_or_else(|| ArrowError::SchemaError(format!(
+        "expected a struct array, got an array of {}", data_type
+    )))?;
+
+    if array.null_count() != 0 {
+        return Err(ArrowError::InvalidArgumentError(format!(
+            "array contains {} null values, which are not supported", array.null_count()
+        )));
+    }
+
+    Ok(array)
+}
+
+/// Get the column named `name` in a struct `array`
+#[doc(hidden)]
+pub fn struct_column<'a>(array: &'a StructArray, name: &str) -> Result<&'a ArrayRef, ArrowError> {
+    array.column_by_name(name).ok_or_else(|| ArrowError::SchemaError(format!("missing column `{}`", name)))
+}
+
+/// Add the name of the column to an error created while converting it
+#[doc(hidden)]
+pub fn column_error(name: &str, error: ArrowError) -> ArrowError {
+    match error {
+        ArrowError::SchemaError(message) => ArrowError::SchemaError(format!("invalid column `{}`: {}", name, message)),
+        ArrowError::InvalidArgumentError(message) => ArrowError::InvalidArgumentError(format!("invalid column `{}`: {}", name, message)),
+        error => error,
+    }
+}
+
+/// Create a struct array from its columns, checking that they all have the
+/// same length
+#[doc(hidden)]
+pub fn struct_array(data_type: DataType, columns: Vec<ArrayRef>) -> Result<ArrayRef, ArrowError> {
+    let fields = match data_type {
+        DataType::Struct(fields) => fields,
+        _ => unreachable!("the data type of a vector should be a struct"),
+    };
+    let array = StructArray::try_new(fields, columns, None)?;
+    Ok(Arc::new(array))
+}
+
+macro_rules! impl_primitive_column {
+    ($($native: ty => $arrow: ty,)*) => {$(
+        impl ArrowColumn for Vec<$native> {
+            fn arrow_data_type() -> DataType {
+                <$arrow as arrow_array::ArrowPrimitiveType>::DATA_TYPE
+            }
+
+            fn to_arrow_array(&self) -> Result<ArrayRef, ArrowError> {
+                self.clone().into_arrow_array()
+            }
+
+            fn into_arrow_array(self) -> Result<ArrayRef, ArrowError> {
+                let array = PrimitiveArray::<$arrow>::new(ScalarBuffer::from(self), None);
+                Ok(Arc::new(array))
+            }
+
+            fn try_from_arrow_array(array: &dyn Array) -> Result<Self, ArrowError> {
+                let array = downcast::<PrimitiveArray<$arrow>>(array, &Self::arrow_data_type())?;
+                Ok(array.values().to_vec())
+            }
+        }
+    )*};
+}
+
+impl_primitive_column!(
+    i8 => Int8Type,
+    i16 => Int16Type,
+    i32 => Int32Type,
+    i64 => Int64Type,
+    u8 => UInt8Type,
+    u16 => UInt16Type,
+    u32 => UInt32Type,
+    u64 => UInt64Type,
+    f32 => Float32Type,
+    f64 => Float64Type,
+);
+
+impl ArrowColumn for Vec<bool> {
+    fn arrow_data_type() -> DataType {
+        DataType::Boolean
+    }
+
+    fn to_arrow_array(&self) -> Result<ArrayRef, ArrowError> {
+        Ok(Arc::new(BooleanArray::from(self.clone())))
+    }
+
+    fn into_arrow_array(self) -> Result<ArrayRef, ArrowError> {
+        Ok(Arc::new(BooleanArray::from(self)))
+    }
+
+    fn try_from_arrow_array(array: &dyn Array) -> Result<Self, ArrowError> {
+        let array = downcast::<BooleanArray>(array, &Self::arrow_data_type())?;
+        Ok(array.values().iter().collect())
+    }
+}
+
+impl ArrowColumn for Vec<String> {
+    fn arrow_data_type() -> DataType {
+        DataType::Utf8
+    }
+
+    fn to_arrow_array(&self) -> Result<ArrayRef, ArrowError> {
+        Ok(Arc::new(StringArray::from_iter_values(self)))
+    }
+
+    fn into_arrow_array(self) -> Result<ArrayRef, ArrowError> {
+        self.to_arrow_array()
+    }
+
+    fn try_from_arrow_array(array: &dyn Array) -> Result<Self, ArrowError> {
+        let array = downcast::<StringArray>(array, &Self::arrow_data_type())?;
+        Ok((0..array.len()).map(|i| array.value(i).to_owned()).collect())
+    }
+}
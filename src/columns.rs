@@ -0,0 +1,46 @@
+use alloc::string::String;
+
+/// Error returned when the columns of a struct of arrays vector do not all
+/// have the same length, for example by `from_columns()` or `validate()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LengthMismatch {
+    /// Name of the column with an unexpected length. Columns inside nested
+    /// fields are named using their full path, e.g. `color.r` for the `r`
+    /// column of a `#[nested_soa]` field named `color`, or `data.2` for the
+    /// third column of a `#[soa(explode)]` field named `data`.
+    pub column: String,
+    /// The expected length, i.e. the length of the first column
+    pub expected: usize,
+    /// The actual length of the column
+    pub actual: usize,
+}
+
+impl LengthMismatch {
+    /// Create a new error for the column named `column`
+    pub fn new(column: &str, expected: usize, actual: usize) -> LengthMismatch {
+        LengthMismatch {
+            column: column.into(),
+            expected,
+            actual,
+        }
+    }
+
+    /// Prefix the name of the column in this error with the name of the
+    /// nested field containing it.
+    #[doc(hidden)]
+    #[must_use]
+    pub fn nested(mut self, field: &str) -> LengthMismatch {
+        self.column.insert(0, '.');
+        self.column.insert_str(0, field);
+        self
+    }
+}
+
+impl core::fmt::Display for LengthMismatch {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "column `{}` has length {}, expected {}", self.column, self.actual, self.expected)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LengthMismatch {}
@@ -0,0 +1,1310 @@
+use core::convert::TryFrom;
+use core::mem::MaybeUninit;
+use core::ops::{Index, IndexMut};
+
+use alloc::vec::Vec;
+
+use crate::{LengthMismatch, Permutation, SoAArrayChunks, SoAArrayVec, SoAAsRef, SoAIndex, SoAIndexMut, SoAIter, SoAPointers, SoASlices, StructOfArray};
+
+/// Create an array from the first `N` items of `iter`
+fn from_iter<T, const N: usize>(mut iter: impl Iterator<Item = T>) -> [T; N] {
+    core::array::from_fn(|_| iter.next().expect("iterator is too short"))
+}
+
+/// Split an array of pairs into a pair of arrays
+fn unzip<A, B, const N: usize>(pairs: [(A, B); N]) -> ([A; N], [B; N]) {
+    let mut second: [Option<B>; N] = core::array::from_fn(|_| None);
+    let mut pairs = IntoIterator::into_iter(pairs).enumerate();
+    let first = core::array::from_fn(|_| {
+        let (i, (a, b)) = pairs.next().expect("missing pair");
+        second[i] = Some(b);
+        a
+    });
+    (first, second.map(|b| b.expect("missing pair")))
+}
+
+fn check_length<const N: usize>() {
+    assert!(N > 0, "#[soa(explode)] can not be used with empty arrays");
+}
+
+/// The columns of a `[T; N]` field marked with `#[soa(explode)]` inside a
+/// struct of arrays vector, with one `Vec<T>` for each element of the arrays.
+/// `vec.field[i]` is the column containing the `i`-th elements.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ArrayColumns<T, const N: usize>(pub [Vec<T>; N]);
+
+/// A slice of [`ArrayColumns`], with one `&[T]` for each element of the
+/// arrays.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ArrayColumnsSlice<'a, T, const N: usize>(pub [&'a [T]; N]);
+
+/// A mutable slice of [`ArrayColumns`], with one `&mut [T]` for each element
+/// of the arrays.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ArrayColumnsSliceMut<'a, T, const N: usize>(pub [&'a mut [T]; N]);
+
+/// A reference to an exploded `[T; N]` array, with one `&T` for each element.
+/// `reference[i]` is the `i`-th element.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ArrayColumnsRef<'a, T, const N: usize>(pub [&'a T; N]);
+
+/// A mutable reference to an exploded `[T; N]` array, with one `&mut T` for
+/// each element. `reference[i]` is the `i`-th element.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ArrayColumnsRefMut<'a, T, const N: usize>(pub [&'a mut T; N]);
+
+/// A pointer to an exploded `[T; N]` array, with one `*const T` for each
+/// element.
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct ArrayColumnsPtr<T, const N: usize>(pub [*const T; N]);
+
+/// A mutable pointer to an exploded `[T; N]` array, with one `*mut T` for
+/// each element.
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct ArrayColumnsPtrMut<T, const N: usize>(pub [*mut T; N]);
+
+/// References to `CHUNK` consecutive exploded `[T; N]` arrays, with one
+/// `&[T; CHUNK]` for each element of the arrays.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ArrayColumnsArrayChunk<'a, T, const N: usize, const CHUNK: usize>(pub [&'a [T; CHUNK]; N]);
+
+/// Mutable references to `CHUNK` consecutive exploded `[T; N]` arrays, with
+/// one `&mut [T; CHUNK]` for each element of the arrays.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ArrayColumnsArrayChunkMut<'a, T, const N: usize, const CHUNK: usize>(pub [&'a mut [T; CHUNK]; N]);
+
+/// Iterator over [`ArrayColumnsRef`]
+#[derive(Debug)]
+pub struct ArrayColumnsIter<'a, T, const N: usize>(ArrayColumnsSlice<'a, T, N>);
+
+/// Iterator over [`ArrayColumnsRefMut`]
+#[derive(Debug)]
+pub struct ArrayColumnsIterMut<'a, T, const N: usize>(ArrayColumnsSliceMut<'a, T, N>);
+
+/// Owning iterator over the arrays in [`ArrayColumns`]
+#[derive(Debug)]
+pub struct ArrayColumnsIntoIter<T, const N: usize>([alloc::vec::IntoIter<T>; N]);
+
+/// The columns of a `[T; N]` field marked with `#[soa(explode)]` inside a
+/// fixed-capacity vector, storing up to `CAPACITY` elements inline.
+pub struct ArrayColumnsArrayVec<T, const N: usize, const CAPACITY: usize> {
+    columns: [[MaybeUninit<T>; CAPACITY]; N],
+    len: usize,
+}
+
+impl<T, const N: usize> StructOfArray for [T; N] {
+    type Type = ArrayColumns<T, N>;
+}
+
+impl<'a, T: 'a, const N: usize> SoAIter<'a> for [T; N] {
+    type Ref = ArrayColumnsRef<'a, T, N>;
+    type RefMut = ArrayColumnsRefMut<'a, T, N>;
+    type Iter = ArrayColumnsIter<'a, T, N>;
+    type IterMut = ArrayColumnsIterMut<'a, T, N>;
+}
+
+impl<'a, T: 'a, const N: usize> SoASlices<'a> for [T; N] {
+    type Slice = ArrayColumnsSlice<'a, T, N>;
+    type SliceMut = ArrayColumnsSliceMut<'a, T, N>;
+}
+
+impl<T, const N: usize> SoAPointers for [T; N] {
+    type Ptr = ArrayColumnsPtr<T, N>;
+    type MutPtr = ArrayColumnsPtrMut<T, N>;
+}
+
+impl<T, const N: usize, const CAPACITY: usize> SoAArrayVec<CAPACITY> for [T; N] {
+    type ArrayVec = ArrayColumnsArrayVec<T, N, CAPACITY>;
+}
+
+impl<'a, T: 'a, const N: usize, const CHUNK: usize> SoAArrayChunks<'a, CHUNK> for [T; N] {
+    type Chunk = ArrayColumnsArrayChunk<'a, T, N, CHUNK>;
+    type ChunkMut = ArrayColumnsArrayChunkMut<'a, T, N, CHUNK>;
+}
+
+impl<'a, T: 'a, const N: usize> SoAAsRef<'a> for [T; N] {
+    fn as_soa_ref(&'a self) -> ArrayColumnsRef<'a, T, N> {
+        ArrayColumnsRef(core::array::from_fn(|i| &self[i]))
+    }
+
+    fn as_soa_mut(&'a mut self) -> ArrayColumnsRefMut<'a, T, N> {
+        ArrayColumnsRefMut(from_iter(self.iter_mut()))
+    }
+}
+
+/******************************************************************************/
+
+impl<T, const N: usize> Default for ArrayColumns<T, N> {
+    fn default() -> Self {
+        check_length::<N>();
+        ArrayColumns(core::array::from_fn(|_| Vec::new()))
+    }
+}
+
+impl<T, const N: usize> Index<usize> for ArrayColumns<T, N> {
+    type Output = Vec<T>;
+
+    fn index(&self, index: usize) -> &Vec<T> {
+        &self.0[index]
+    }
+}
+
+impl<T, const N: usize> IndexMut<usize> for ArrayColumns<T, N> {
+    fn index_mut(&mut self, index: usize) -> &mut Vec<T> {
+        &mut self.0[index]
+    }
+}
+
+#[allow(clippy::len_without_is_empty, clippy::new_without_default)]
+impl<T, const N: usize> ArrayColumns<T, N> {
+    /// Similar to `Vec::new()`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Similar to `Vec::with_capacity()`
+    pub fn with_capacity(capacity: usize) -> Self {
+        check_length::<N>();
+        ArrayColumns(core::array::from_fn(|_| Vec::with_capacity(capacity)))
+    }
+
+    /// Similar to `Vec::capacity()`
+    pub fn capacity(&self) -> usize {
+        self.0[0].capacity()
+    }
+
+    /// Similar to `Vec::reserve()`
+    pub fn reserve(&mut self, additional: usize) {
+        self.0.iter_mut().for_each(|column| column.reserve(additional));
+    }
+
+    /// Similar to `Vec::reserve_exact()`
+    pub fn reserve_exact(&mut self, additional: usize) {
+        self.0.iter_mut().for_each(|column| column.reserve_exact(additional));
+    }
+
+    /// Similar to `Vec::shrink_to_fit()`
+    pub fn shrink_to_fit(&mut self) {
+        self.0.iter_mut().for_each(Vec::shrink_to_fit);
+    }
+
+    /// Similar to `Vec::truncate()`
+    pub fn truncate(&mut self, len: usize) {
+        self.0.iter_mut().for_each(|column| column.truncate(len));
+    }
+
+    /// Similar to `Vec::push()`
+    pub fn push(&mut self, value: [T; N]) {
+        for (column, value) in self.0.iter_mut().zip(IntoIterator::into_iter(value)) {
+            column.push(value);
+        }
+    }
+
+    /// Similar to `Vec::len()`
+    pub fn len(&self) -> usize {
+        self.0[0].len()
+    }
+
+    /// Similar to `Vec::is_empty()`
+    pub fn is_empty(&self) -> bool {
+        self.0[0].is_empty()
+    }
+
+    /// Check that all the columns have the same length. The columns are
+    /// named after their index in the arrays.
+    pub fn validate(&self) -> Result<(), LengthMismatch> {
+        let expected = self.0[0].len();
+        for (i, column) in self.0.iter().enumerate() {
+            if column.len() != expected {
+                return Err(LengthMismatch::new(&alloc::format!("{}", i), expected, column.len()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Similar to `Vec::swap_remove()`
+    pub fn swap_remove(&mut self, index: usize) -> [T; N] {
+        core::array::from_fn(|i| self.0[i].swap_remove(index))
+    }
+
+    /// Similar to `Vec::insert()`
+    pub fn insert(&mut self, index: usize, element: [T; N]) {
+        for (column, value) in self.0.iter_mut().zip(IntoIterator::into_iter(element)) {
+            column.insert(index, value);
+        }
+    }
+
+    /// Similar to `std::mem::replace()`
+    pub fn replace(&mut self, index: usize, element: [T; N]) -> [T; N] {
+        let mut element = IntoIterator::into_iter(element);
+        core::array::from_fn(|i| core::mem::replace(&mut self.0[i][index], element.next().expect("missing element")))
+    }
+
+    /// Similar to `Vec::remove()`
+    pub fn remove(&mut self, index: usize) -> [T; N] {
+        core::array::from_fn(|i| self.0[i].remove(index))
+    }
+
+    /// Similar to `Vec::pop()`
+    pub fn pop(&mut self) -> Option<[T; N]> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(core::array::from_fn(|i| self.0[i].pop().expect("columns have different lengths")))
+        }
+    }
+
+    /// Similar to `Vec::append()`
+    pub fn append(&mut self, other: &mut Self) {
+        for (column, other) in self.0.iter_mut().zip(other.0.iter_mut()) {
+            column.append(other);
+        }
+    }
+
+    /// Similar to `Vec::clear()`
+    pub fn clear(&mut self) {
+        self.0.iter_mut().for_each(Vec::clear);
+    }
+
+    /// Similar to `Vec::split_off()`
+    pub fn split_off(&mut self, at: usize) -> Self {
+        ArrayColumns(core::array::from_fn(|i| self.0[i].split_off(at)))
+    }
+
+    /// Similar to `Vec::as_slice()`
+    pub fn as_slice(&self) -> ArrayColumnsSlice<'_, T, N> {
+        ArrayColumnsSlice(core::array::from_fn(|i| self.0[i].as_slice()))
+    }
+
+    /// Similar to `Vec::as_mut_slice()`
+    pub fn as_mut_slice(&mut self) -> ArrayColumnsSliceMut<'_, T, N> {
+        ArrayColumnsSliceMut(from_iter(self.0.iter_mut().map(Vec::as_mut_slice)))
+    }
+
+    /// Create a slice of these columns matching the given `range`
+    pub fn slice(&self, range: core::ops::Range<usize>) -> ArrayColumnsSlice<'_, T, N> {
+        ArrayColumnsSlice(core::array::from_fn(|i| &self.0[i][range.clone()]))
+    }
+
+    /// Create a mutable slice of these columns matching the given `range`
+    pub fn slice_mut(&mut self, range: core::ops::Range<usize>) -> ArrayColumnsSliceMut<'_, T, N> {
+        ArrayColumnsSliceMut(from_iter(self.0.iter_mut().map(|column| &mut column[range.clone()])))
+    }
+
+    /// Similar to `Vec::as_ptr()`
+    pub fn as_ptr(&self) -> ArrayColumnsPtr<T, N> {
+        ArrayColumnsPtr(core::array::from_fn(|i| self.0[i].as_ptr()))
+    }
+
+    /// Similar to `Vec::as_mut_ptr()`
+    pub fn as_mut_ptr(&mut self) -> ArrayColumnsPtrMut<T, N> {
+        ArrayColumnsPtrMut(core::array::from_fn(|i| self.0[i].as_mut_ptr()))
+    }
+
+    /// Similar to `Vec::from_raw_parts()`
+    ///
+    /// # Safety
+    ///
+    /// See `Vec::from_raw_parts()`, the same requirements apply to all the
+    /// columns.
+    pub unsafe fn from_raw_parts(data: ArrayColumnsPtrMut<T, N>, len: usize, capacity: usize) -> Self {
+        ArrayColumns(data.0.map(|ptr| Vec::from_raw_parts(ptr, len, capacity)))
+    }
+
+    /// Similar to `Vec::set_len()`
+    ///
+    /// # Safety
+    ///
+    /// See `Vec::set_len()`, the same requirements apply to all the columns.
+    pub unsafe fn set_len(&mut self, new_len: usize) {
+        self.0.iter_mut().for_each(|column| column.set_len(new_len));
+    }
+
+    /// Get an iterator over references to the arrays in these columns
+    pub fn iter(&self) -> ArrayColumnsIter<'_, T, N> {
+        self.as_slice().into_iter()
+    }
+
+    /// Get an iterator over mutable references to the arrays in these
+    /// columns
+    pub fn iter_mut(&mut self) -> ArrayColumnsIterMut<'_, T, N> {
+        self.as_mut_slice().into_iter()
+    }
+}
+
+impl<T, const N: usize> IntoIterator for ArrayColumns<T, N> {
+    type Item = [T; N];
+    type IntoIter = ArrayColumnsIntoIter<T, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        ArrayColumnsIntoIter(self.0.map(IntoIterator::into_iter))
+    }
+}
+
+impl<T: Clone, const N: usize> ArrayColumns<T, N> {
+    /// Similar to `Vec::resize()`
+    pub fn resize(&mut self, new_len: usize, value: [T; N]) {
+        for (column, value) in self.0.iter_mut().zip(IntoIterator::into_iter(value)) {
+            column.resize(new_len, value);
+        }
+    }
+
+    /// Similar to `Vec::extend_from_slice()`
+    pub fn extend_from_slice(&mut self, other: ArrayColumnsSlice<'_, T, N>) {
+        for (column, other) in self.0.iter_mut().zip(other.0.iter()) {
+            column.extend_from_slice(other);
+        }
+    }
+}
+
+/******************************************************************************/
+
+impl<'a, T, const N: usize> Copy for ArrayColumnsSlice<'a, T, N> {}
+impl<'a, T, const N: usize> Clone for ArrayColumnsSlice<'a, T, N> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T, const N: usize> Default for ArrayColumnsSlice<'a, T, N> {
+    fn default() -> Self {
+        check_length::<N>();
+        ArrayColumnsSlice([&[]; N])
+    }
+}
+
+impl<'a, T, const N: usize> Index<usize> for ArrayColumnsSlice<'a, T, N> {
+    type Output = [T];
+
+    fn index(&self, index: usize) -> &[T] {
+        self.0[index]
+    }
+}
+
+#[allow(clippy::len_without_is_empty)]
+impl<'a, T, const N: usize> ArrayColumnsSlice<'a, T, N> {
+    /// Similar to `<[T]>::len()`
+    pub fn len(&self) -> usize {
+        self.0[0].len()
+    }
+
+    /// Similar to `<[T]>::is_empty()`
+    pub fn is_empty(&self) -> bool {
+        self.0[0].is_empty()
+    }
+
+    /// Similar to `<[T]>::first()`
+    pub fn first(&self) -> Option<ArrayColumnsRef<'a, T, N>> {
+        self.get(0)
+    }
+
+    /// Similar to `<[T]>::split_first()`
+    pub fn split_first(&self) -> Option<(ArrayColumnsRef<'a, T, N>, ArrayColumnsSlice<'a, T, N>)> {
+        if self.is_empty() {
+            None
+        } else {
+            let (first, rest) = unzip(self.0.map(|column| column.split_first().expect("columns have different lengths")));
+            Some((ArrayColumnsRef(first), ArrayColumnsSlice(rest)))
+        }
+    }
+
+    /// Similar to `<[T]>::last()`
+    pub fn last(&self) -> Option<ArrayColumnsRef<'a, T, N>> {
+        self.len().checked_sub(1).and_then(|last| self.get(last))
+    }
+
+    /// Similar to `<[T]>::split_last()`
+    pub fn split_last(&self) -> Option<(ArrayColumnsRef<'a, T, N>, ArrayColumnsSlice<'a, T, N>)> {
+        if self.is_empty() {
+            None
+        } else {
+            let (last, rest) = unzip(self.0.map(|column| column.split_last().expect("columns have different lengths")));
+            Some((ArrayColumnsRef(last), ArrayColumnsSlice(rest)))
+        }
+    }
+
+    /// Similar to `<[T]>::split_at()`
+    pub fn split_at(&self, mid: usize) -> (ArrayColumnsSlice<'a, T, N>, ArrayColumnsSlice<'a, T, N>) {
+        let (left, right) = unzip(self.0.map(|column| column.split_at(mid)));
+        (ArrayColumnsSlice(left), ArrayColumnsSlice(right))
+    }
+
+    /// Similar to `<[T]>::get()`
+    pub fn get<I: SoAIndex<Self>>(&self, index: I) -> Option<I::RefOutput> {
+        index.get(*self)
+    }
+
+    /// Similar to `<[T]>::index()`
+    pub fn index<I: SoAIndex<Self>>(&self, index: I) -> I::RefOutput {
+        index.index(*self)
+    }
+
+    /// Reborrows the slices in a narrower lifetime
+    pub fn reborrow<'b>(&'b self) -> ArrayColumnsSlice<'b, T, N> where 'a: 'b {
+        *self
+    }
+
+    /// Similar to `<[T]>::as_ptr()`
+    pub fn as_ptr(&self) -> ArrayColumnsPtr<T, N> {
+        ArrayColumnsPtr(self.0.map(<[T]>::as_ptr))
+    }
+
+    /// Similar to `std::slice::from_raw_parts()`
+    ///
+    /// # Safety
+    ///
+    /// See `std::slice::from_raw_parts()`, the same requirements apply to all
+    /// the columns.
+    pub unsafe fn from_raw_parts<'b>(data: ArrayColumnsPtr<T, N>, len: usize) -> ArrayColumnsSlice<'b, T, N> {
+        ArrayColumnsSlice(data.0.map(|ptr| core::slice::from_raw_parts(ptr, len)))
+    }
+
+    /// Get an iterator over references to the arrays in this slice
+    pub fn iter(&self) -> ArrayColumnsIter<'a, T, N> {
+        self.into_iter()
+    }
+
+    #[doc(hidden)]
+    pub fn __private_array_chunk<const CHUNK: usize>(self) -> ArrayColumnsArrayChunk<'a, T, N, CHUNK> {
+        ArrayColumnsArrayChunk(self.0.map(|column| {
+            <&[T; CHUNK]>::try_from(column).expect("all columns should have CHUNK elements")
+        }))
+    }
+}
+
+impl<'a, T: Clone, const N: usize> ArrayColumnsSlice<'a, T, N> {
+    /// Similar to `<[T]>::to_vec()`
+    pub fn to_vec(&self) -> ArrayColumns<T, N> {
+        ArrayColumns(self.0.map(<[T]>::to_vec))
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for ArrayColumnsSlice<'a, T, N> {
+    type Item = ArrayColumnsRef<'a, T, N>;
+    type IntoIter = ArrayColumnsIter<'a, T, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        ArrayColumnsIter(self)
+    }
+}
+
+/******************************************************************************/
+
+impl<'a, T, const N: usize> Default for ArrayColumnsSliceMut<'a, T, N> {
+    fn default() -> Self {
+        check_length::<N>();
+        ArrayColumnsSliceMut(core::array::from_fn(|_| Default::default()))
+    }
+}
+
+impl<'a, T, const N: usize> Index<usize> for ArrayColumnsSliceMut<'a, T, N> {
+    type Output = [T];
+
+    fn index(&self, index: usize) -> &[T] {
+        self.0[index]
+    }
+}
+
+impl<'a, T, const N: usize> IndexMut<usize> for ArrayColumnsSliceMut<'a, T, N> {
+    fn index_mut(&mut self, index: usize) -> &mut [T] {
+        self.0[index]
+    }
+}
+
+#[allow(clippy::len_without_is_empty)]
+impl<'a, T, const N: usize> ArrayColumnsSliceMut<'a, T, N> {
+    /// Convert this mutable slice to an immutable one
+    pub fn as_ref(&self) -> ArrayColumnsSlice<'_, T, N> {
+        self.as_slice()
+    }
+
+    /// Similar to `<[T]>::len()`
+    pub fn len(&self) -> usize {
+        self.0[0].len()
+    }
+
+    /// Similar to `<[T]>::is_empty()`
+    pub fn is_empty(&self) -> bool {
+        self.0[0].is_empty()
+    }
+
+    /// Similar to `<[T]>::first_mut()`
+    pub fn first_mut(&mut self) -> Option<ArrayColumnsRefMut<'_, T, N>> {
+        self.reborrow().split_first_mut().map(|(first, _)| first)
+    }
+
+    /// Similar to `<[T]>::split_first_mut()`, consuming the slice
+    pub fn split_first_mut(self) -> Option<(ArrayColumnsRefMut<'a, T, N>, ArrayColumnsSliceMut<'a, T, N>)> {
+        if self.is_empty() {
+            None
+        } else {
+            let (first, rest) = unzip(self.0.map(|column| column.split_first_mut().expect("columns have different lengths")));
+            Some((ArrayColumnsRefMut(first), ArrayColumnsSliceMut(rest)))
+        }
+    }
+
+    /// Similar to `<[T]>::last_mut()`
+    pub fn last_mut(&mut self) -> Option<ArrayColumnsRefMut<'_, T, N>> {
+        self.reborrow().split_last_mut().map(|(last, _)| last)
+    }
+
+    /// Similar to `<[T]>::split_last_mut()`, consuming the slice
+    pub fn split_last_mut(self) -> Option<(ArrayColumnsRefMut<'a, T, N>, ArrayColumnsSliceMut<'a, T, N>)> {
+        if self.is_empty() {
+            None
+        } else {
+            let (last, rest) = unzip(self.0.map(|column| column.split_last_mut().expect("columns have different lengths")));
+            Some((ArrayColumnsRefMut(last), ArrayColumnsSliceMut(rest)))
+        }
+    }
+
+    /// Similar to `<[T]>::split_at_mut()`, consuming the slice
+    pub fn split_at_mut(self, mid: usize) -> (ArrayColumnsSliceMut<'a, T, N>, ArrayColumnsSliceMut<'a, T, N>) {
+        let (left, right) = unzip(self.0.map(|column| column.split_at_mut(mid)));
+        (ArrayColumnsSliceMut(left), ArrayColumnsSliceMut(right))
+    }
+
+    /// Similar to `<[T]>::swap()`
+    pub fn swap(&mut self, a: usize, b: usize) {
+        self.0.iter_mut().for_each(|column| column.swap(a, b));
+    }
+
+    /// Similar to `<[T]>::reverse()`
+    pub fn reverse(&mut self) {
+        self.0.iter_mut().for_each(|column| column.reverse());
+    }
+
+    /// Similar to `<[T]>::rotate_left()`
+    pub fn rotate_left(&mut self, mid: usize) {
+        self.0.iter_mut().for_each(|column| column.rotate_left(mid));
+    }
+
+    /// Similar to `<[T]>::rotate_right()`
+    pub fn rotate_right(&mut self, k: usize) {
+        self.0.iter_mut().for_each(|column| column.rotate_right(k));
+    }
+
+    /// Similar to `<[T]>::swap_with_slice()`
+    pub fn swap_with_slice(&mut self, other: ArrayColumnsSliceMut<'_, T, N>) {
+        self.0.iter_mut().zip(other.0).for_each(|(column, other)| column.swap_with_slice(other));
+    }
+
+    /// Similar to `<[T]>::get()`
+    pub fn get<'b, I: SoAIndex<ArrayColumnsSlice<'b, T, N>>>(&'b self, index: I) -> Option<I::RefOutput> {
+        index.get(self.as_slice())
+    }
+
+    /// Similar to `<[T]>::index()`
+    pub fn index<'b, I: SoAIndex<ArrayColumnsSlice<'b, T, N>>>(&'b self, index: I) -> I::RefOutput {
+        index.index(self.as_slice())
+    }
+
+    /// Similar to `<[T]>::get_mut()`
+    pub fn get_mut<'b, I: SoAIndexMut<ArrayColumnsSliceMut<'b, T, N>>>(&'b mut self, index: I) -> Option<I::MutOutput> {
+        index.get_mut(self.reborrow())
+    }
+
+    /// Similar to `<[T]>::index_mut()`
+    pub fn index_mut<'b, I: SoAIndexMut<ArrayColumnsSliceMut<'b, T, N>>>(&'b mut self, index: I) -> I::MutOutput {
+        index.index_mut(self.reborrow())
+    }
+
+    /// Returns a non-mutable slice from this mutable slice
+    pub fn as_slice<'b>(&'b self) -> ArrayColumnsSlice<'b, T, N> where 'a: 'b {
+        ArrayColumnsSlice(core::array::from_fn(|i| &*self.0[i]))
+    }
+
+    /// Reborrows the slices in a narrower lifetime
+    pub fn reborrow<'b>(&'b mut self) -> ArrayColumnsSliceMut<'b, T, N> where 'a: 'b {
+        ArrayColumnsSliceMut(from_iter(self.0.iter_mut().map(|column| &mut **column)))
+    }
+
+    /// Similar to `<[T]>::as_ptr()`
+    pub fn as_ptr(&self) -> ArrayColumnsPtr<T, N> {
+        ArrayColumnsPtr(core::array::from_fn(|i| self.0[i].as_ptr()))
+    }
+
+    /// Similar to `<[T]>::as_mut_ptr()`
+    pub fn as_mut_ptr(&mut self) -> ArrayColumnsPtrMut<T, N> {
+        ArrayColumnsPtrMut(core::array::from_fn(|i| self.0[i].as_mut_ptr()))
+    }
+
+    /// Similar to `std::slice::from_raw_parts_mut()`
+    ///
+    /// # Safety
+    ///
+    /// See `std::slice::from_raw_parts_mut()`, the same requirements apply to
+    /// all the columns.
+    pub unsafe fn from_raw_parts_mut<'b>(data: ArrayColumnsPtrMut<T, N>, len: usize) -> ArrayColumnsSliceMut<'b, T, N> {
+        ArrayColumnsSliceMut(data.0.map(|ptr| core::slice::from_raw_parts_mut(ptr, len)))
+    }
+
+    /// Get an iterator over references to the arrays in this slice
+    pub fn iter(&mut self) -> ArrayColumnsIter<'_, T, N> {
+        self.as_ref().into_iter()
+    }
+
+    /// Get an iterator over mutable references to the arrays in this slice
+    pub fn iter_mut(&mut self) -> ArrayColumnsIterMut<'_, T, N> {
+        self.reborrow().into_iter()
+    }
+
+    #[doc(hidden)]
+    pub fn __private_apply_permutation(&mut self, permutation: &mut Permutation) {
+        for column in &mut self.0 {
+            permutation.apply_slice_in_place(column);
+        }
+    }
+
+    #[doc(hidden)]
+    pub fn __private_array_chunk_mut<const CHUNK: usize>(self) -> ArrayColumnsArrayChunkMut<'a, T, N, CHUNK> {
+        ArrayColumnsArrayChunkMut(self.0.map(|column| {
+            <&mut [T; CHUNK]>::try_from(column).expect("all columns should have CHUNK elements")
+        }))
+    }
+}
+
+impl<'a, T: Clone, const N: usize> ArrayColumnsSliceMut<'a, T, N> {
+    /// Similar to `<[T]>::to_vec()`
+    pub fn to_vec(&self) -> ArrayColumns<T, N> {
+        self.as_slice().to_vec()
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for ArrayColumnsSliceMut<'a, T, N> {
+    type Item = ArrayColumnsRefMut<'a, T, N>;
+    type IntoIter = ArrayColumnsIterMut<'a, T, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        ArrayColumnsIterMut(self)
+    }
+}
+
+/******************************************************************************/
+
+impl<'a, T, const N: usize> Copy for ArrayColumnsRef<'a, T, N> {}
+impl<'a, T, const N: usize> Clone for ArrayColumnsRef<'a, T, N> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T, const N: usize> Index<usize> for ArrayColumnsRef<'a, T, N> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        self.0[index]
+    }
+}
+
+impl<'a, T, const N: usize> ArrayColumnsRef<'a, T, N> {
+    /// Convert this reference into an owned array
+    pub fn to_owned(&self) -> [T; N] where T: Clone {
+        self.0.map(T::clone)
+    }
+
+    /// Get a pointer to the referenced values
+    pub fn as_ptr(&self) -> ArrayColumnsPtr<T, N> {
+        ArrayColumnsPtr(self.0.map(|value| value as *const T))
+    }
+}
+
+impl<'a, T, const N: usize> Index<usize> for ArrayColumnsRefMut<'a, T, N> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        self.0[index]
+    }
+}
+
+impl<'a, T, const N: usize> IndexMut<usize> for ArrayColumnsRefMut<'a, T, N> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        self.0[index]
+    }
+}
+
+impl<'a, T, const N: usize> ArrayColumnsRefMut<'a, T, N> {
+    /// Convert this reference into an owned array
+    pub fn to_owned(&self) -> [T; N] where T: Clone {
+        core::array::from_fn(|i| self.0[i].clone())
+    }
+
+    /// Similar to `std::mem::replace()`
+    pub fn replace(&mut self, value: [T; N]) -> [T; N] {
+        let mut value = IntoIterator::into_iter(value);
+        core::array::from_fn(|i| core::mem::replace(&mut *self.0[i], value.next().expect("missing value")))
+    }
+
+    /// Get a pointer to the referenced values
+    pub fn as_ptr(&self) -> ArrayColumnsPtr<T, N> {
+        ArrayColumnsPtr(core::array::from_fn(|i| &*self.0[i] as *const T))
+    }
+
+    /// Get a mutable pointer to the referenced values
+    pub fn as_mut_ptr(&mut self) -> ArrayColumnsPtrMut<T, N> {
+        ArrayColumnsPtrMut(core::array::from_fn(|i| &mut *self.0[i] as *mut T))
+    }
+}
+
+/******************************************************************************/
+
+impl<'a, T, const N: usize, const CHUNK: usize> Copy for ArrayColumnsArrayChunk<'a, T, N, CHUNK> {}
+impl<'a, T, const N: usize, const CHUNK: usize> Clone for ArrayColumnsArrayChunk<'a, T, N, CHUNK> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T, const N: usize, const CHUNK: usize> Index<usize> for ArrayColumnsArrayChunk<'a, T, N, CHUNK> {
+    type Output = [T; CHUNK];
+
+    fn index(&self, index: usize) -> &[T; CHUNK] {
+        self.0[index]
+    }
+}
+
+impl<'a, T, const N: usize, const CHUNK: usize> Index<usize> for ArrayColumnsArrayChunkMut<'a, T, N, CHUNK> {
+    type Output = [T; CHUNK];
+
+    fn index(&self, index: usize) -> &[T; CHUNK] {
+        self.0[index]
+    }
+}
+
+impl<'a, T, const N: usize, const CHUNK: usize> IndexMut<usize> for ArrayColumnsArrayChunkMut<'a, T, N, CHUNK> {
+    fn index_mut(&mut self, index: usize) -> &mut [T; CHUNK] {
+        self.0[index]
+    }
+}
+
+/******************************************************************************/
+
+impl<T, const N: usize> Copy for ArrayColumnsPtr<T, N> {}
+impl<T, const N: usize> Clone for ArrayColumnsPtr<T, N> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, const N: usize> Copy for ArrayColumnsPtrMut<T, N> {}
+impl<T, const N: usize> Clone for ArrayColumnsPtrMut<T, N> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+#[allow(clippy::missing_safety_doc)]
+impl<T, const N: usize> ArrayColumnsPtr<T, N> {
+    /// Convert to a mutable pointer
+    pub fn as_mut_ptr(&self) -> ArrayColumnsPtrMut<T, N> {
+        ArrayColumnsPtrMut(self.0.map(|ptr| ptr as *mut T))
+    }
+
+    /// Similar to `pointer::is_null()`
+    pub fn is_null(self) -> bool {
+        self.0.iter().any(|ptr| ptr.is_null())
+    }
+
+    /// Similar to `pointer::as_ref()`
+    pub unsafe fn as_ref<'a>(self) -> Option<ArrayColumnsRef<'a, T, N>> {
+        if self.is_null() {
+            None
+        } else {
+            Some(self.as_ref_unchecked())
+        }
+    }
+
+    /// Similar to `pointer::as_ref_unchecked()`
+    pub unsafe fn as_ref_unchecked<'a>(self) -> ArrayColumnsRef<'a, T, N> {
+        ArrayColumnsRef(self.0.map(|ptr| &*ptr))
+    }
+
+    /// Similar to `pointer::offset()`
+    pub unsafe fn offset(self, count: isize) -> Self {
+        ArrayColumnsPtr(self.0.map(|ptr| ptr.offset(count)))
+    }
+
+    /// Similar to `pointer::wrapping_offset()`
+    pub fn wrapping_offset(self, count: isize) -> Self {
+        ArrayColumnsPtr(self.0.map(|ptr| ptr.wrapping_offset(count)))
+    }
+
+    /// Similar to `pointer::add()`
+    pub unsafe fn add(self, count: usize) -> Self {
+        ArrayColumnsPtr(self.0.map(|ptr| ptr.add(count)))
+    }
+
+    /// Similar to `pointer::sub()`
+    pub unsafe fn sub(self, count: usize) -> Self {
+        ArrayColumnsPtr(self.0.map(|ptr| ptr.sub(count)))
+    }
+
+    /// Similar to `pointer::wrapping_add()`
+    pub fn wrapping_add(self, count: usize) -> Self {
+        ArrayColumnsPtr(self.0.map(|ptr| ptr.wrapping_add(count)))
+    }
+
+    /// Similar to `pointer::wrapping_sub()`
+    pub fn wrapping_sub(self, count: usize) -> Self {
+        ArrayColumnsPtr(self.0.map(|ptr| ptr.wrapping_sub(count)))
+    }
+
+    /// Similar to `pointer::read()`
+    pub unsafe fn read(self) -> [T; N] {
+        self.0.map(|ptr| ptr.read())
+    }
+
+    /// Similar to `pointer::read_volatile()`
+    pub unsafe fn read_volatile(self) -> [T; N] {
+        self.0.map(|ptr| ptr.read_volatile())
+    }
+
+    /// Similar to `pointer::read_unaligned()`
+    pub unsafe fn read_unaligned(self) -> [T; N] {
+        self.0.map(|ptr| ptr.read_unaligned())
+    }
+
+    /// Similar to `pointer::copy_to()`
+    pub unsafe fn copy_to(self, dest: ArrayColumnsPtrMut<T, N>, count: usize) {
+        for (ptr, dest) in self.0.iter().zip(dest.0.iter()) {
+            ptr.copy_to(*dest, count);
+        }
+    }
+}
+
+#[allow(clippy::missing_safety_doc)]
+impl<T, const N: usize> ArrayColumnsPtrMut<T, N> {
+    /// Convert to an immutable pointer
+    pub fn as_ptr(&self) -> ArrayColumnsPtr<T, N> {
+        ArrayColumnsPtr(self.0.map(|ptr| ptr as *const T))
+    }
+
+    /// Similar to `pointer::is_null()`
+    pub fn is_null(self) -> bool {
+        self.0.iter().any(|ptr| ptr.is_null())
+    }
+
+    /// Similar to `pointer::as_ref()`
+    pub unsafe fn as_ref<'a>(self) -> Option<ArrayColumnsRef<'a, T, N>> {
+        self.as_ptr().as_ref()
+    }
+
+    /// Similar to `pointer::as_ref_unchecked()`
+    pub unsafe fn as_ref_unchecked<'a>(self) -> ArrayColumnsRef<'a, T, N> {
+        self.as_ptr().as_ref_unchecked()
+    }
+
+    /// Similar to `pointer::as_mut()`
+    pub unsafe fn as_mut<'a>(self) -> Option<ArrayColumnsRefMut<'a, T, N>> {
+        if self.is_null() {
+            None
+        } else {
+            Some(self.as_mut_unchecked())
+        }
+    }
+
+    /// Similar to `pointer::as_mut_unchecked()`
+    pub unsafe fn as_mut_unchecked<'a>(self) -> ArrayColumnsRefMut<'a, T, N> {
+        ArrayColumnsRefMut(self.0.map(|ptr| &mut *ptr))
+    }
+
+    /// Similar to `pointer::offset()`
+    pub unsafe fn offset(self, count: isize) -> Self {
+        ArrayColumnsPtrMut(self.0.map(|ptr| ptr.offset(count)))
+    }
+
+    /// Similar to `pointer::wrapping_offset()`
+    pub fn wrapping_offset(self, count: isize) -> Self {
+        ArrayColumnsPtrMut(self.0.map(|ptr| ptr.wrapping_offset(count)))
+    }
+
+    /// Similar to `pointer::add()`
+    pub unsafe fn add(self, count: usize) -> Self {
+        ArrayColumnsPtrMut(self.0.map(|ptr| ptr.add(count)))
+    }
+
+    /// Similar to `pointer::sub()`
+    pub unsafe fn sub(self, count: usize) -> Self {
+        ArrayColumnsPtrMut(self.0.map(|ptr| ptr.sub(count)))
+    }
+
+    /// Similar to `pointer::wrapping_add()`
+    pub fn wrapping_add(self, count: usize) -> Self {
+        ArrayColumnsPtrMut(self.0.map(|ptr| ptr.wrapping_add(count)))
+    }
+
+    /// Similar to `pointer::wrapping_sub()`
+    pub fn wrapping_sub(self, count: usize) -> Self {
+        ArrayColumnsPtrMut(self.0.map(|ptr| ptr.wrapping_sub(count)))
+    }
+
+    /// Similar to `pointer::read()`
+    pub unsafe fn read(self) -> [T; N] {
+        self.0.map(|ptr| ptr.read())
+    }
+
+    /// Similar to `pointer::read_volatile()`
+    pub unsafe fn read_volatile(self) -> [T; N] {
+        self.0.map(|ptr| ptr.read_volatile())
+    }
+
+    /// Similar to `pointer::read_unaligned()`
+    pub unsafe fn read_unaligned(self) -> [T; N] {
+        self.0.map(|ptr| ptr.read_unaligned())
+    }
+
+    /// Similar to `pointer::copy_to()`
+    pub unsafe fn copy_to(self, dest: ArrayColumnsPtrMut<T, N>, count: usize) {
+        self.as_ptr().copy_to(dest, count);
+    }
+
+    /// Similar to `pointer::write()`
+    pub unsafe fn write(self, value: [T; N]) {
+        for (ptr, value) in self.0.iter().zip(IntoIterator::into_iter(value)) {
+            ptr.write(value);
+        }
+    }
+
+    /// Similar to `pointer::write_volatile()`
+    pub unsafe fn write_volatile(self, value: [T; N]) {
+        for (ptr, value) in self.0.iter().zip(IntoIterator::into_iter(value)) {
+            ptr.write_volatile(value);
+        }
+    }
+
+    /// Similar to `pointer::write_unaligned()`
+    pub unsafe fn write_unaligned(self, value: [T; N]) {
+        for (ptr, value) in self.0.iter().zip(IntoIterator::into_iter(value)) {
+            ptr.write_unaligned(value);
+        }
+    }
+}
+
+/******************************************************************************/
+
+impl<'a, T, const N: usize> Iterator for ArrayColumnsIter<'a, T, N> {
+    type Item = ArrayColumnsRef<'a, T, N>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (first, rest) = self.0.split_first()?;
+        self.0 = rest;
+        Some(first)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.len(), Some(self.0.len()))
+    }
+}
+
+impl<'a, T, const N: usize> DoubleEndedIterator for ArrayColumnsIter<'a, T, N> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let (last, rest) = self.0.split_last()?;
+        self.0 = rest;
+        Some(last)
+    }
+}
+
+impl<'a, T, const N: usize> ExactSizeIterator for ArrayColumnsIter<'a, T, N> {}
+
+impl<'a, T, const N: usize> Iterator for ArrayColumnsIterMut<'a, T, N> {
+    type Item = ArrayColumnsRefMut<'a, T, N>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (first, rest) = core::mem::take(&mut self.0).split_first_mut()?;
+        self.0 = rest;
+        Some(first)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.len(), Some(self.0.len()))
+    }
+}
+
+impl<'a, T, const N: usize> DoubleEndedIterator for ArrayColumnsIterMut<'a, T, N> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let (last, rest) = core::mem::take(&mut self.0).split_last_mut()?;
+        self.0 = rest;
+        Some(last)
+    }
+}
+
+impl<'a, T, const N: usize> ExactSizeIterator for ArrayColumnsIterMut<'a, T, N> {}
+
+impl<T, const N: usize> Iterator for ArrayColumnsIntoIter<T, N> {
+    type Item = [T; N];
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.0[0].len() == 0 {
+            None
+        } else {
+            Some(core::array::from_fn(|i| self.0[i].next().expect("columns have different lengths")))
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0[0].len(), Some(self.0[0].len()))
+    }
+}
+
+impl<T, const N: usize> DoubleEndedIterator for ArrayColumnsIntoIter<T, N> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.0[0].len() == 0 {
+            None
+        } else {
+            Some(core::array::from_fn(|i| self.0[i].next_back().expect("columns have different lengths")))
+        }
+    }
+}
+
+impl<T, const N: usize> ExactSizeIterator for ArrayColumnsIntoIter<T, N> {}
+
+/******************************************************************************/
+
+impl<'a, T, const N: usize> SoAIndex<ArrayColumnsSlice<'a, T, N>> for usize {
+    type RefOutput = ArrayColumnsRef<'a, T, N>;
+
+    #[inline]
+    fn get(self, slice: ArrayColumnsSlice<'a, T, N>) -> Option<Self::RefOutput> {
+        if self < slice.len() {
+            Some(unsafe { self.get_unchecked(slice) })
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    unsafe fn get_unchecked(self, slice: ArrayColumnsSlice<'a, T, N>) -> Self::RefOutput {
+        ArrayColumnsRef(slice.0.map(|column| column.get_unchecked(self)))
+    }
+
+    #[inline]
+    fn index(self, slice: ArrayColumnsSlice<'a, T, N>) -> Self::RefOutput {
+        ArrayColumnsRef(slice.0.map(|column| &column[self]))
+    }
+}
+
+impl<'a, T, const N: usize> SoAIndexMut<ArrayColumnsSliceMut<'a, T, N>> for usize {
+    type MutOutput = ArrayColumnsRefMut<'a, T, N>;
+
+    #[inline]
+    fn get_mut(self, slice: ArrayColumnsSliceMut<'a, T, N>) -> Option<Self::MutOutput> {
+        if self < slice.len() {
+            Some(unsafe { self.get_unchecked_mut(slice) })
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    unsafe fn get_unchecked_mut(self, slice: ArrayColumnsSliceMut<'a, T, N>) -> Self::MutOutput {
+        ArrayColumnsRefMut(slice.0.map(|column| column.get_unchecked_mut(self)))
+    }
+
+    #[inline]
+    fn index_mut(self, slice: ArrayColumnsSliceMut<'a, T, N>) -> Self::MutOutput {
+        ArrayColumnsRefMut(slice.0.map(|column| &mut column[self]))
+    }
+}
+
+macro_rules! impl_range_index {
+    ($($range: ty),*) => {$(
+        impl<'a, T, const N: usize> SoAIndex<ArrayColumnsSlice<'a, T, N>> for $range {
+            type RefOutput = ArrayColumnsSlice<'a, T, N>;
+
+            #[inline]
+            fn get(self, slice: ArrayColumnsSlice<'a, T, N>) -> Option<Self::RefOutput> {
+                if slice.0.iter().all(|column| column.get(self.clone()).is_some()) {
+                    Some(unsafe { self.get_unchecked(slice) })
+                } else {
+                    None
+                }
+            }
+
+            #[inline]
+            unsafe fn get_unchecked(self, slice: ArrayColumnsSlice<'a, T, N>) -> Self::RefOutput {
+                ArrayColumnsSlice(slice.0.map(|column| column.get_unchecked(self.clone())))
+            }
+
+            #[inline]
+            fn index(self, slice: ArrayColumnsSlice<'a, T, N>) -> Self::RefOutput {
+                ArrayColumnsSlice(slice.0.map(|column| &column[self.clone()]))
+            }
+        }
+
+        impl<'a, T, const N: usize> SoAIndexMut<ArrayColumnsSliceMut<'a, T, N>> for $range {
+            type MutOutput = ArrayColumnsSliceMut<'a, T, N>;
+
+            #[inline]
+            fn get_mut(self, slice: ArrayColumnsSliceMut<'a, T, N>) -> Option<Self::MutOutput> {
+                if slice.0.iter().all(|column| column.get(self.clone()).is_some()) {
+                    Some(unsafe { self.get_unchecked_mut(slice) })
+                } else {
+                    None
+                }
+            }
+
+            #[inline]
+            unsafe fn get_unchecked_mut(self, slice: ArrayColumnsSliceMut<'a, T, N>) -> Self::MutOutput {
+                ArrayColumnsSliceMut(slice.0.map(|column| column.get_unchecked_mut(self.clone())))
+            }
+
+            #[inline]
+            fn index_mut(self, slice: ArrayColumnsSliceMut<'a, T, N>) -> Self::MutOutput {
+                ArrayColumnsSliceMut(slice.0.map(|column| &mut column[self.clone()]))
+            }
+        }
+    )*};
+}
+
+impl_range_index!(
+    core::ops::Range<usize>,
+    core::ops::RangeTo<usize>,
+    core::ops::RangeFrom<usize>,
+    core::ops::RangeFull,
+    core::ops::RangeInclusive<usize>,
+    core::ops::RangeToInclusive<usize>
+);
+
+/******************************************************************************/
+
+impl<T, const N: usize, const CAPACITY: usize> Default for ArrayColumnsArrayVec<T, N, CAPACITY> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[allow(clippy::len_without_is_empty)]
+impl<T, const N: usize, const CAPACITY: usize> ArrayColumnsArrayVec<T, N, CAPACITY> {
+    /// Create new empty columns, with space for `CAPACITY` arrays
+    pub const fn new() -> Self {
+        assert!(N > 0, "#[soa(explode)] can not be used with empty arrays");
+        ArrayColumnsArrayVec {
+            // an array of `MaybeUninit` does not require initialization
+            columns: unsafe { MaybeUninit::<[[MaybeUninit<T>; CAPACITY]; N]>::uninit().assume_init() },
+            len: 0,
+        }
+    }
+
+    /// Get the number of arrays in these columns
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Similar to `FooArrayVec::push()`, giving back the value if the
+    /// columns are full
+    pub fn push(&mut self, value: [T; N]) -> Result<(), [T; N]> {
+        if self.len == CAPACITY {
+            return Err(value);
+        }
+
+        for (column, value) in self.columns.iter_mut().zip(IntoIterator::into_iter(value)) {
+            column[self.len] = MaybeUninit::new(value);
+        }
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Similar to `FooArrayVec::insert()`, giving back the value if the
+    /// columns are full
+    pub fn insert(&mut self, index: usize, element: [T; N]) -> Result<(), [T; N]> {
+        assert!(index <= self.len, "index out of bounds: the len is {} but the index is {}", self.len, index);
+        if self.len == CAPACITY {
+            return Err(element);
+        }
+
+        for (column, value) in self.columns.iter_mut().zip(IntoIterator::into_iter(element)) {
+            unsafe {
+                let column = column.as_mut_ptr();
+                core::ptr::copy(column.add(index), column.add(index + 1), self.len - index);
+                column.add(index).write(MaybeUninit::new(value));
+            }
+        }
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Similar to `Vec::pop()`
+    pub fn pop(&mut self) -> Option<[T; N]> {
+        if self.len == 0 {
+            return None;
+        }
+
+        self.len -= 1;
+        let len = self.len;
+        Some(core::array::from_fn(|i| unsafe { self.columns[i][len].as_ptr().read() }))
+    }
+
+    /// Similar to `Vec::remove()`
+    pub fn remove(&mut self, index: usize) -> [T; N] {
+        assert!(index < self.len, "index out of bounds: the len is {} but the index is {}", self.len, index);
+        self.len -= 1;
+        let len = self.len;
+        core::array::from_fn(|i| unsafe {
+            let column = self.columns[i].as_mut_ptr();
+            let value = column.add(index).read().assume_init();
+            core::ptr::copy(column.add(index + 1), column.add(index), len - index);
+            value
+        })
+    }
+
+    /// Similar to `Vec::swap_remove()`
+    pub fn swap_remove(&mut self, index: usize) -> [T; N] {
+        assert!(index < self.len, "index out of bounds: the len is {} but the index is {}", self.len, index);
+        self.len -= 1;
+        let len = self.len;
+        core::array::from_fn(|i| unsafe {
+            let column = self.columns[i].as_mut_ptr();
+            let value = column.add(index).read().assume_init();
+            core::ptr::copy(column.add(len), column.add(index), 1);
+            value
+        })
+    }
+
+    /// Similar to `Vec::truncate()`
+    pub fn truncate(&mut self, len: usize) {
+        if len >= self.len {
+            return;
+        }
+
+        let removed = self.len - len;
+        self.len = len;
+        for column in &mut self.columns {
+            unsafe {
+                core::ptr::drop_in_place(core::ptr::slice_from_raw_parts_mut(
+                    column.as_mut_ptr().add(len).cast::<T>(),
+                    removed,
+                ));
+            }
+        }
+    }
+
+    /// Get a slice containing all the arrays in these columns
+    pub fn as_slice(&self) -> ArrayColumnsSlice<'_, T, N> {
+        ArrayColumnsSlice(core::array::from_fn(|i| unsafe {
+            core::slice::from_raw_parts(self.columns[i].as_ptr().cast::<T>(), self.len)
+        }))
+    }
+
+    /// Get a mutable slice containing all the arrays in these columns
+    pub fn as_mut_slice(&mut self) -> ArrayColumnsSliceMut<'_, T, N> {
+        let len = self.len;
+        ArrayColumnsSliceMut(from_iter(self.columns.iter_mut().map(|column| unsafe {
+            core::slice::from_raw_parts_mut(column.as_mut_ptr().cast::<T>(), len)
+        })))
+    }
+}
+
+impl<T, const N: usize, const CAPACITY: usize> Drop for ArrayColumnsArrayVec<T, N, CAPACITY> {
+    fn drop(&mut self) {
+        self.truncate(0);
+    }
+}
@@ -0,0 +1,20 @@
+//! Column types used by fields marked with `#[soa(explode)]`.
+//!
+//! An exploded `[T; N]` field is stored in [`ArrayColumns<T, N>`], with one
+//! `Vec<T>` for each element of the array; and an exploded `(A, B, ...)`
+//! field is stored in [`Tuple2Columns<A, B>`] (and the other `TupleNColumns`
+//! types), with one `Vec` for each element of the tuple. The corresponding
+//! slices, references and pointers follow the same layout, so
+//! `vec.field[1]`, `slice.field[1]` and `reference.field[1]` all give access
+//! to the second element of the arrays, and `vec.field.1`, `slice.field.1`
+//! and `reference.field.1` to the second element of the tuples.
+//!
+//! These types are not meant to be created directly: they are the nested
+//! struct of arrays types for `[T; N]` and tuples, used by the code generated
+//! by `#[derive(StructOfArray)]`.
+
+mod array;
+pub use self::array::*;
+
+mod tuple;
+pub use self::tuple::*;
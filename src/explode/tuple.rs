@@ -0,0 +1,1240 @@
+use core::convert::TryFrom;
+use core::mem::MaybeUninit;
+
+use alloc::vec::Vec;
+
+use crate::{LengthMismatch, Permutation, SoAArrayChunks, SoAArrayVec, SoAAsRef, SoAIndex, SoAIndexMut, SoAIter, SoAPointers, SoASlices, StructOfArray};
+
+macro_rules! tuple_columns {
+    (
+        $vec: ident, $slice: ident, $slice_mut: ident, $ref: ident, $ref_mut: ident,
+        $ptr: ident, $ptr_mut: ident, $iter: ident, $iter_mut: ident, $array_vec: ident,
+        $array_chunk: ident, $array_chunk_mut: ident, $into_iter: ident;
+        $(($T: ident, $idx: tt)),+
+    ) => {
+        /// The columns of a tuple field marked with `#[soa(explode)]` inside a
+        /// struct of arrays vector, with one `Vec` for each element of the
+        /// tuples. `vec.field.0` is the column containing the first elements.
+        #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $vec<$($T),+>($(pub Vec<$T>),+);
+
+        #[doc = concat!("A slice of [`", stringify!($vec), "`], with one slice for each element of the tuples.")]
+        #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $slice<'a, $($T),+>($(pub &'a [$T]),+);
+
+        #[doc = concat!("A mutable slice of [`", stringify!($vec), "`], with one slice for each element of the tuples.")]
+        #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $slice_mut<'a, $($T),+>($(pub &'a mut [$T]),+);
+
+        /// A reference to an exploded tuple, with one reference for each
+        /// element.
+        #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $ref<'a, $($T),+>($(pub &'a $T),+);
+
+        /// A mutable reference to an exploded tuple, with one reference for
+        /// each element.
+        #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $ref_mut<'a, $($T),+>($(pub &'a mut $T),+);
+
+        /// A pointer to an exploded tuple, with one pointer for each element.
+        #[derive(Debug, PartialEq, Eq, Hash)]
+        pub struct $ptr<$($T),+>($(pub *const $T),+);
+
+        /// A mutable pointer to an exploded tuple, with one pointer for each
+        /// element.
+        #[derive(Debug, PartialEq, Eq, Hash)]
+        pub struct $ptr_mut<$($T),+>($(pub *mut $T),+);
+
+        /// References to `CHUNK` consecutive exploded tuples, with one
+        /// `&[T; CHUNK]` for each element of the tuples.
+        #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $array_chunk<'a, $($T,)+ const CHUNK: usize>($(pub &'a [$T; CHUNK]),+);
+
+        /// Mutable references to `CHUNK` consecutive exploded tuples, with
+        /// one `&mut [T; CHUNK]` for each element of the tuples.
+        #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $array_chunk_mut<'a, $($T,)+ const CHUNK: usize>($(pub &'a mut [$T; CHUNK]),+);
+
+        #[doc = concat!("Iterator over [`", stringify!($ref), "`]")]
+        #[derive(Debug)]
+        pub struct $iter<'a, $($T),+>($slice<'a, $($T),+>);
+
+        #[doc = concat!("Iterator over [`", stringify!($ref_mut), "`]")]
+        #[derive(Debug)]
+        pub struct $iter_mut<'a, $($T),+>($slice_mut<'a, $($T),+>);
+
+        #[doc = concat!("Owning iterator over the tuples in [`", stringify!($vec), "`]")]
+        #[derive(Debug)]
+        pub struct $into_iter<$($T),+>($(alloc::vec::IntoIter<$T>),+);
+
+        /// The columns of a tuple field marked with `#[soa(explode)]` inside
+        /// a fixed-capacity vector, storing up to `CAPACITY` elements inline.
+        pub struct $array_vec<$($T,)+ const CAPACITY: usize> {
+            columns: ($([MaybeUninit<$T>; CAPACITY],)+),
+            len: usize,
+        }
+
+        impl<$($T),+> StructOfArray for ($($T,)+) {
+            type Type = $vec<$($T),+>;
+        }
+
+        impl<'a, $($T: 'a),+> SoAIter<'a> for ($($T,)+) {
+            type Ref = $ref<'a, $($T),+>;
+            type RefMut = $ref_mut<'a, $($T),+>;
+            type Iter = $iter<'a, $($T),+>;
+            type IterMut = $iter_mut<'a, $($T),+>;
+        }
+
+        impl<'a, $($T: 'a),+> SoASlices<'a> for ($($T,)+) {
+            type Slice = $slice<'a, $($T),+>;
+            type SliceMut = $slice_mut<'a, $($T),+>;
+        }
+
+        impl<$($T),+> SoAPointers for ($($T,)+) {
+            type Ptr = $ptr<$($T),+>;
+            type MutPtr = $ptr_mut<$($T),+>;
+        }
+
+        impl<$($T,)+ const CAPACITY: usize> SoAArrayVec<CAPACITY> for ($($T,)+) {
+            type ArrayVec = $array_vec<$($T,)+ CAPACITY>;
+        }
+
+        impl<'a, $($T: 'a,)+ const CHUNK: usize> SoAArrayChunks<'a, CHUNK> for ($($T,)+) {
+            type Chunk = $array_chunk<'a, $($T,)+ CHUNK>;
+            type ChunkMut = $array_chunk_mut<'a, $($T,)+ CHUNK>;
+        }
+
+        impl<'a, $($T: 'a),+> SoAAsRef<'a> for ($($T,)+) {
+            fn as_soa_ref(&'a self) -> $ref<'a, $($T),+> {
+                $ref($(&self.$idx),+)
+            }
+
+            fn as_soa_mut(&'a mut self) -> $ref_mut<'a, $($T),+> {
+                $ref_mut($(&mut self.$idx),+)
+            }
+        }
+
+        /**********************************************************************/
+
+        impl<$($T),+> Default for $vec<$($T),+> {
+            fn default() -> Self {
+                $vec($(Vec::<$T>::new()),+)
+            }
+        }
+
+        #[allow(clippy::len_without_is_empty, clippy::new_without_default)]
+        impl<$($T),+> $vec<$($T),+> {
+            /// Similar to `Vec::new()`
+            pub fn new() -> Self {
+                Self::default()
+            }
+
+            /// Similar to `Vec::with_capacity()`
+            pub fn with_capacity(capacity: usize) -> Self {
+                $vec($(Vec::<$T>::with_capacity(capacity)),+)
+            }
+
+            /// Similar to `Vec::capacity()`
+            pub fn capacity(&self) -> usize {
+                self.0.capacity()
+            }
+
+            /// Similar to `Vec::reserve()`
+            pub fn reserve(&mut self, additional: usize) {
+                $(self.$idx.reserve(additional);)+
+            }
+
+            /// Similar to `Vec::reserve_exact()`
+            pub fn reserve_exact(&mut self, additional: usize) {
+                $(self.$idx.reserve_exact(additional);)+
+            }
+
+            /// Similar to `Vec::shrink_to_fit()`
+            pub fn shrink_to_fit(&mut self) {
+                $(self.$idx.shrink_to_fit();)+
+            }
+
+            /// Similar to `Vec::truncate()`
+            pub fn truncate(&mut self, len: usize) {
+                $(self.$idx.truncate(len);)+
+            }
+
+            /// Similar to `Vec::push()`
+            pub fn push(&mut self, value: ($($T,)+)) {
+                $(self.$idx.push(value.$idx);)+
+            }
+
+            /// Similar to `Vec::len()`
+            pub fn len(&self) -> usize {
+                self.0.len()
+            }
+
+            /// Similar to `Vec::is_empty()`
+            pub fn is_empty(&self) -> bool {
+                self.0.is_empty()
+            }
+
+            /// Check that all the columns have the same length. The columns
+            /// are named after their index in the tuples.
+            pub fn validate(&self) -> Result<(), LengthMismatch> {
+                let expected = self.0.len();
+                $(
+                    if self.$idx.len() != expected {
+                        return Err(LengthMismatch::new(stringify!($idx), expected, self.$idx.len()));
+                    }
+                )+
+                Ok(())
+            }
+
+            /// Similar to `Vec::swap_remove()`
+            pub fn swap_remove(&mut self, index: usize) -> ($($T,)+) {
+                ($(self.$idx.swap_remove(index),)+)
+            }
+
+            /// Similar to `Vec::insert()`
+            pub fn insert(&mut self, index: usize, element: ($($T,)+)) {
+                $(self.$idx.insert(index, element.$idx);)+
+            }
+
+            /// Similar to `std::mem::replace()`
+            pub fn replace(&mut self, index: usize, element: ($($T,)+)) -> ($($T,)+) {
+                ($(core::mem::replace(&mut self.$idx[index], element.$idx),)+)
+            }
+
+            /// Similar to `Vec::remove()`
+            pub fn remove(&mut self, index: usize) -> ($($T,)+) {
+                ($(self.$idx.remove(index),)+)
+            }
+
+            /// Similar to `Vec::pop()`
+            pub fn pop(&mut self) -> Option<($($T,)+)> {
+                if self.is_empty() {
+                    None
+                } else {
+                    Some(($(self.$idx.pop().expect("columns have different lengths"),)+))
+                }
+            }
+
+            /// Similar to `Vec::append()`
+            pub fn append(&mut self, other: &mut Self) {
+                $(self.$idx.append(&mut other.$idx);)+
+            }
+
+            /// Similar to `Vec::clear()`
+            pub fn clear(&mut self) {
+                $(self.$idx.clear();)+
+            }
+
+            /// Similar to `Vec::split_off()`
+            pub fn split_off(&mut self, at: usize) -> Self {
+                $vec($(self.$idx.split_off(at)),+)
+            }
+
+            /// Similar to `Vec::as_slice()`
+            pub fn as_slice(&self) -> $slice<'_, $($T),+> {
+                $slice($(self.$idx.as_slice()),+)
+            }
+
+            /// Similar to `Vec::as_mut_slice()`
+            pub fn as_mut_slice(&mut self) -> $slice_mut<'_, $($T),+> {
+                $slice_mut($(self.$idx.as_mut_slice()),+)
+            }
+
+            /// Create a slice of these columns matching the given `range`
+            pub fn slice(&self, range: core::ops::Range<usize>) -> $slice<'_, $($T),+> {
+                $slice($(&self.$idx[range.clone()]),+)
+            }
+
+            /// Create a mutable slice of these columns matching the given
+            /// `range`
+            pub fn slice_mut(&mut self, range: core::ops::Range<usize>) -> $slice_mut<'_, $($T),+> {
+                $slice_mut($(&mut self.$idx[range.clone()]),+)
+            }
+
+            /// Similar to `Vec::as_ptr()`
+            pub fn as_ptr(&self) -> $ptr<$($T),+> {
+                $ptr($(self.$idx.as_ptr()),+)
+            }
+
+            /// Similar to `Vec::as_mut_ptr()`
+            pub fn as_mut_ptr(&mut self) -> $ptr_mut<$($T),+> {
+                $ptr_mut($(self.$idx.as_mut_ptr()),+)
+            }
+
+            /// Similar to `Vec::from_raw_parts()`
+            ///
+            /// # Safety
+            ///
+            /// See `Vec::from_raw_parts()`, the same requirements apply to
+            /// all the columns.
+            pub unsafe fn from_raw_parts(data: $ptr_mut<$($T),+>, len: usize, capacity: usize) -> Self {
+                $vec($(Vec::from_raw_parts(data.$idx, len, capacity)),+)
+            }
+
+            /// Similar to `Vec::set_len()`
+            ///
+            /// # Safety
+            ///
+            /// See `Vec::set_len()`, the same requirements apply to all the
+            /// columns.
+            pub unsafe fn set_len(&mut self, new_len: usize) {
+                $(self.$idx.set_len(new_len);)+
+            }
+
+            /// Get an iterator over references to the tuples in these
+            /// columns
+            pub fn iter(&self) -> $iter<'_, $($T),+> {
+                self.as_slice().into_iter()
+            }
+
+            /// Get an iterator over mutable references to the tuples in
+            /// these columns
+            pub fn iter_mut(&mut self) -> $iter_mut<'_, $($T),+> {
+                self.as_mut_slice().into_iter()
+            }
+        }
+
+        impl<$($T),+> IntoIterator for $vec<$($T),+> {
+            type Item = ($($T,)+);
+            type IntoIter = $into_iter<$($T),+>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                $into_iter($(self.$idx.into_iter()),+)
+            }
+        }
+
+        impl<$($T: Clone),+> $vec<$($T),+> {
+            /// Similar to `Vec::resize()`
+            pub fn resize(&mut self, new_len: usize, value: ($($T,)+)) {
+                $(self.$idx.resize(new_len, value.$idx);)+
+            }
+
+            /// Similar to `Vec::extend_from_slice()`
+            pub fn extend_from_slice(&mut self, other: $slice<'_, $($T),+>) {
+                $(self.$idx.extend_from_slice(other.$idx);)+
+            }
+        }
+
+        /**********************************************************************/
+
+        impl<'a, $($T),+> Copy for $slice<'a, $($T),+> {}
+        impl<'a, $($T),+> Clone for $slice<'a, $($T),+> {
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+
+        impl<'a, $($T),+> Default for $slice<'a, $($T),+> {
+            fn default() -> Self {
+                $slice($(<&[$T]>::default()),+)
+            }
+        }
+
+        #[allow(clippy::len_without_is_empty)]
+        impl<'a, $($T),+> $slice<'a, $($T),+> {
+            /// Similar to `<[T]>::len()`
+            pub fn len(&self) -> usize {
+                self.0.len()
+            }
+
+            /// Similar to `<[T]>::is_empty()`
+            pub fn is_empty(&self) -> bool {
+                self.0.is_empty()
+            }
+
+            /// Similar to `<[T]>::first()`
+            pub fn first(&self) -> Option<$ref<'a, $($T),+>> {
+                self.get(0)
+            }
+
+            /// Similar to `<[T]>::split_first()`
+            pub fn split_first(&self) -> Option<($ref<'a, $($T),+>, $slice<'a, $($T),+>)> {
+                let split = ($(self.$idx.split_first()?,)+);
+                Some(($ref($(split.$idx.0),+), $slice($(split.$idx.1),+)))
+            }
+
+            /// Similar to `<[T]>::last()`
+            pub fn last(&self) -> Option<$ref<'a, $($T),+>> {
+                self.len().checked_sub(1).and_then(|last| self.get(last))
+            }
+
+            /// Similar to `<[T]>::split_last()`
+            pub fn split_last(&self) -> Option<($ref<'a, $($T),+>, $slice<'a, $($T),+>)> {
+                let split = ($(self.$idx.split_last()?,)+);
+                Some(($ref($(split.$idx.0),+), $slice($(split.$idx.1),+)))
+            }
+
+            /// Similar to `<[T]>::split_at()`
+            pub fn split_at(&self, mid: usize) -> ($slice<'a, $($T),+>, $slice<'a, $($T),+>) {
+                let split = ($(self.$idx.split_at(mid),)+);
+                ($slice($(split.$idx.0),+), $slice($(split.$idx.1),+))
+            }
+
+            /// Similar to `<[T]>::get()`
+            pub fn get<I: SoAIndex<Self>>(&self, index: I) -> Option<I::RefOutput> {
+                index.get(*self)
+            }
+
+            /// Similar to `<[T]>::index()`
+            pub fn index<I: SoAIndex<Self>>(&self, index: I) -> I::RefOutput {
+                index.index(*self)
+            }
+
+            /// Reborrows the slices in a narrower lifetime
+            pub fn reborrow<'b>(&'b self) -> $slice<'b, $($T),+> where 'a: 'b {
+                *self
+            }
+
+            /// Similar to `<[T]>::as_ptr()`
+            pub fn as_ptr(&self) -> $ptr<$($T),+> {
+                $ptr($(self.$idx.as_ptr()),+)
+            }
+
+            /// Similar to `std::slice::from_raw_parts()`
+            ///
+            /// # Safety
+            ///
+            /// See `std::slice::from_raw_parts()`, the same requirements
+            /// apply to all the columns.
+            pub unsafe fn from_raw_parts<'b>(data: $ptr<$($T),+>, len: usize) -> $slice<'b, $($T),+> {
+                $slice($(core::slice::from_raw_parts(data.$idx, len)),+)
+            }
+
+            /// Get an iterator over references to the tuples in this slice
+            pub fn iter(&self) -> $iter<'a, $($T),+> {
+                self.into_iter()
+            }
+
+            #[doc(hidden)]
+            pub fn __private_array_chunk<const CHUNK: usize>(self) -> $array_chunk<'a, $($T,)+ CHUNK> {
+                $array_chunk($(
+                    <&[$T; CHUNK]>::try_from(self.$idx).expect("all columns should have CHUNK elements")
+                ),+)
+            }
+        }
+
+        impl<'a, $($T: Clone),+> $slice<'a, $($T),+> {
+            /// Similar to `<[T]>::to_vec()`
+            pub fn to_vec(&self) -> $vec<$($T),+> {
+                $vec($(self.$idx.to_vec()),+)
+            }
+        }
+
+        impl<'a, $($T),+> IntoIterator for $slice<'a, $($T),+> {
+            type Item = $ref<'a, $($T),+>;
+            type IntoIter = $iter<'a, $($T),+>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                $iter(self)
+            }
+        }
+
+        /**********************************************************************/
+
+        impl<'a, $($T),+> Default for $slice_mut<'a, $($T),+> {
+            fn default() -> Self {
+                $slice_mut($(<&mut [$T]>::default()),+)
+            }
+        }
+
+        #[allow(clippy::len_without_is_empty)]
+        impl<'a, $($T),+> $slice_mut<'a, $($T),+> {
+            /// Convert this mutable slice to an immutable one
+            pub fn as_ref(&self) -> $slice<'_, $($T),+> {
+                self.as_slice()
+            }
+
+            /// Similar to `<[T]>::len()`
+            pub fn len(&self) -> usize {
+                self.0.len()
+            }
+
+            /// Similar to `<[T]>::is_empty()`
+            pub fn is_empty(&self) -> bool {
+                self.0.is_empty()
+            }
+
+            /// Similar to `<[T]>::first_mut()`
+            pub fn first_mut(&mut self) -> Option<$ref_mut<'_, $($T),+>> {
+                self.reborrow().split_first_mut().map(|(first, _)| first)
+            }
+
+            /// Similar to `<[T]>::split_first_mut()`, consuming the slice
+            pub fn split_first_mut(self) -> Option<($ref_mut<'a, $($T),+>, $slice_mut<'a, $($T),+>)> {
+                let split = ($(self.$idx.split_first_mut()?,)+);
+                Some(($ref_mut($(split.$idx.0),+), $slice_mut($(split.$idx.1),+)))
+            }
+
+            /// Similar to `<[T]>::last_mut()`
+            pub fn last_mut(&mut self) -> Option<$ref_mut<'_, $($T),+>> {
+                self.reborrow().split_last_mut().map(|(last, _)| last)
+            }
+
+            /// Similar to `<[T]>::split_last_mut()`, consuming the slice
+            pub fn split_last_mut(self) -> Option<($ref_mut<'a, $($T),+>, $slice_mut<'a, $($T),+>)> {
+                let split = ($(self.$idx.split_last_mut()?,)+);
+                Some(($ref_mut($(split.$idx.0),+), $slice_mut($(split.$idx.1),+)))
+            }
+
+            /// Similar to `<[T]>::split_at_mut()`, consuming the slice
+            pub fn split_at_mut(self, mid: usize) -> ($slice_mut<'a, $($T),+>, $slice_mut<'a, $($T),+>) {
+                let split = ($(self.$idx.split_at_mut(mid),)+);
+                ($slice_mut($(split.$idx.0),+), $slice_mut($(split.$idx.1),+))
+            }
+
+            /// Similar to `<[T]>::swap()`
+            pub fn swap(&mut self, a: usize, b: usize) {
+                $(self.$idx.swap(a, b);)+
+            }
+
+            /// Similar to `<[T]>::reverse()`
+            pub fn reverse(&mut self) {
+                $(self.$idx.reverse();)+
+            }
+
+            /// Similar to `<[T]>::rotate_left()`
+            pub fn rotate_left(&mut self, mid: usize) {
+                $(self.$idx.rotate_left(mid);)+
+            }
+
+            /// Similar to `<[T]>::rotate_right()`
+            pub fn rotate_right(&mut self, k: usize) {
+                $(self.$idx.rotate_right(k);)+
+            }
+
+            /// Similar to `<[T]>::swap_with_slice()`
+            pub fn swap_with_slice(&mut self, other: $slice_mut<'_, $($T),+>) {
+                $(self.$idx.swap_with_slice(other.$idx);)+
+            }
+
+            /// Similar to `<[T]>::get()`
+            pub fn get<'b, I: SoAIndex<$slice<'b, $($T),+>>>(&'b self, index: I) -> Option<I::RefOutput> {
+                index.get(self.as_slice())
+            }
+
+            /// Similar to `<[T]>::index()`
+            pub fn index<'b, I: SoAIndex<$slice<'b, $($T),+>>>(&'b self, index: I) -> I::RefOutput {
+                index.index(self.as_slice())
+            }
+
+            /// Similar to `<[T]>::get_mut()`
+            pub fn get_mut<'b, I: SoAIndexMut<$slice_mut<'b, $($T),+>>>(&'b mut self, index: I) -> Option<I::MutOutput> {
+                index.get_mut(self.reborrow())
+            }
+
+            /// Similar to `<[T]>::index_mut()`
+            pub fn index_mut<'b, I: SoAIndexMut<$slice_mut<'b, $($T),+>>>(&'b mut self, index: I) -> I::MutOutput {
+                index.index_mut(self.reborrow())
+            }
+
+            /// Returns a non-mutable slice from this mutable slice
+            pub fn as_slice<'b>(&'b self) -> $slice<'b, $($T),+> where 'a: 'b {
+                $slice($(&*self.$idx),+)
+            }
+
+            /// Reborrows the slices in a narrower lifetime
+            pub fn reborrow<'b>(&'b mut self) -> $slice_mut<'b, $($T),+> where 'a: 'b {
+                $slice_mut($(&mut *self.$idx),+)
+            }
+
+            /// Similar to `<[T]>::as_ptr()`
+            pub fn as_ptr(&self) -> $ptr<$($T),+> {
+                $ptr($(self.$idx.as_ptr()),+)
+            }
+
+            /// Similar to `<[T]>::as_mut_ptr()`
+            pub fn as_mut_ptr(&mut self) -> $ptr_mut<$($T),+> {
+                $ptr_mut($(self.$idx.as_mut_ptr()),+)
+            }
+
+            /// Similar to `std::slice::from_raw_parts_mut()`
+            ///
+            /// # Safety
+            ///
+            /// See `std::slice::from_raw_parts_mut()`, the same requirements
+            /// apply to all the columns.
+            pub unsafe fn from_raw_parts_mut<'b>(data: $ptr_mut<$($T),+>, len: usize) -> $slice_mut<'b, $($T),+> {
+                $slice_mut($(core::slice::from_raw_parts_mut(data.$idx, len)),+)
+            }
+
+            /// Get an iterator over references to the tuples in this slice
+            pub fn iter(&mut self) -> $iter<'_, $($T),+> {
+                self.as_ref().into_iter()
+            }
+
+            /// Get an iterator over mutable references to the tuples in this
+            /// slice
+            pub fn iter_mut(&mut self) -> $iter_mut<'_, $($T),+> {
+                self.reborrow().into_iter()
+            }
+
+            #[doc(hidden)]
+            pub fn __private_apply_permutation(&mut self, permutation: &mut Permutation) {
+                $(permutation.apply_slice_in_place(self.$idx);)+
+            }
+
+            #[doc(hidden)]
+            pub fn __private_array_chunk_mut<const CHUNK: usize>(self) -> $array_chunk_mut<'a, $($T,)+ CHUNK> {
+                $array_chunk_mut($(
+                    <&mut [$T; CHUNK]>::try_from(self.$idx).expect("all columns should have CHUNK elements")
+                ),+)
+            }
+        }
+
+        impl<'a, $($T: Clone),+> $slice_mut<'a, $($T),+> {
+            /// Similar to `<[T]>::to_vec()`
+            pub fn to_vec(&self) -> $vec<$($T),+> {
+                self.as_slice().to_vec()
+            }
+        }
+
+        impl<'a, $($T),+> IntoIterator for $slice_mut<'a, $($T),+> {
+            type Item = $ref_mut<'a, $($T),+>;
+            type IntoIter = $iter_mut<'a, $($T),+>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                $iter_mut(self)
+            }
+        }
+
+        /**********************************************************************/
+
+        impl<'a, $($T),+> Copy for $ref<'a, $($T),+> {}
+        impl<'a, $($T),+> Clone for $ref<'a, $($T),+> {
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+
+        impl<'a, $($T),+> $ref<'a, $($T),+> {
+            /// Convert this reference into an owned tuple
+            pub fn to_owned(&self) -> ($($T,)+) where $($T: Clone),+ {
+                ($(self.$idx.clone(),)+)
+            }
+
+            /// Get a pointer to the referenced values
+            pub fn as_ptr(&self) -> $ptr<$($T),+> {
+                $ptr($(self.$idx as *const $T),+)
+            }
+        }
+
+        impl<'a, $($T),+> $ref_mut<'a, $($T),+> {
+            /// Convert this reference into an owned tuple
+            pub fn to_owned(&self) -> ($($T,)+) where $($T: Clone),+ {
+                ($(self.$idx.clone(),)+)
+            }
+
+            /// Similar to `std::mem::replace()`
+            pub fn replace(&mut self, value: ($($T,)+)) -> ($($T,)+) {
+                ($(core::mem::replace(&mut *self.$idx, value.$idx),)+)
+            }
+
+            /// Get a pointer to the referenced values
+            pub fn as_ptr(&self) -> $ptr<$($T),+> {
+                $ptr($(&*self.$idx as *const $T),+)
+            }
+
+            /// Get a mutable pointer to the referenced values
+            pub fn as_mut_ptr(&mut self) -> $ptr_mut<$($T),+> {
+                $ptr_mut($(&mut *self.$idx as *mut $T),+)
+            }
+        }
+
+        /**********************************************************************/
+
+        impl<'a, $($T,)+ const CHUNK: usize> Copy for $array_chunk<'a, $($T,)+ CHUNK> {}
+        impl<'a, $($T,)+ const CHUNK: usize> Clone for $array_chunk<'a, $($T,)+ CHUNK> {
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+
+        /**********************************************************************/
+
+        impl<$($T),+> Copy for $ptr<$($T),+> {}
+        impl<$($T),+> Clone for $ptr<$($T),+> {
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+
+        impl<$($T),+> Copy for $ptr_mut<$($T),+> {}
+        impl<$($T),+> Clone for $ptr_mut<$($T),+> {
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+
+        #[allow(clippy::missing_safety_doc)]
+        impl<$($T),+> $ptr<$($T),+> {
+            /// Convert to a mutable pointer
+            pub fn as_mut_ptr(&self) -> $ptr_mut<$($T),+> {
+                $ptr_mut($(self.$idx as *mut $T),+)
+            }
+
+            /// Similar to `pointer::is_null()`
+            pub fn is_null(self) -> bool {
+                false $(|| self.$idx.is_null())+
+            }
+
+            /// Similar to `pointer::as_ref()`
+            pub unsafe fn as_ref<'a>(self) -> Option<$ref<'a, $($T),+>> {
+                if self.is_null() {
+                    None
+                } else {
+                    Some(self.as_ref_unchecked())
+                }
+            }
+
+            /// Similar to `pointer::as_ref_unchecked()`
+            pub unsafe fn as_ref_unchecked<'a>(self) -> $ref<'a, $($T),+> {
+                $ref($(&*self.$idx),+)
+            }
+
+            /// Similar to `pointer::offset()`
+            pub unsafe fn offset(self, count: isize) -> Self {
+                $ptr($(self.$idx.offset(count)),+)
+            }
+
+            /// Similar to `pointer::wrapping_offset()`
+            pub fn wrapping_offset(self, count: isize) -> Self {
+                $ptr($(self.$idx.wrapping_offset(count)),+)
+            }
+
+            /// Similar to `pointer::add()`
+            pub unsafe fn add(self, count: usize) -> Self {
+                $ptr($(self.$idx.add(count)),+)
+            }
+
+            /// Similar to `pointer::sub()`
+            pub unsafe fn sub(self, count: usize) -> Self {
+                $ptr($(self.$idx.sub(count)),+)
+            }
+
+            /// Similar to `pointer::wrapping_add()`
+            pub fn wrapping_add(self, count: usize) -> Self {
+                $ptr($(self.$idx.wrapping_add(count)),+)
+            }
+
+            /// Similar to `pointer::wrapping_sub()`
+            pub fn wrapping_sub(self, count: usize) -> Self {
+                $ptr($(self.$idx.wrapping_sub(count)),+)
+            }
+
+            /// Similar to `pointer::read()`
+            pub unsafe fn read(self) -> ($($T,)+) {
+                ($(self.$idx.read(),)+)
+            }
+
+            /// Similar to `pointer::read_volatile()`
+            pub unsafe fn read_volatile(self) -> ($($T,)+) {
+                ($(self.$idx.read_volatile(),)+)
+            }
+
+            /// Similar to `pointer::read_unaligned()`
+            pub unsafe fn read_unaligned(self) -> ($($T,)+) {
+                ($(self.$idx.read_unaligned(),)+)
+            }
+
+            /// Similar to `pointer::copy_to()`
+            pub unsafe fn copy_to(self, dest: $ptr_mut<$($T),+>, count: usize) {
+                $(self.$idx.copy_to(dest.$idx, count);)+
+            }
+        }
+
+        #[allow(clippy::missing_safety_doc)]
+        impl<$($T),+> $ptr_mut<$($T),+> {
+            /// Convert to an immutable pointer
+            pub fn as_ptr(&self) -> $ptr<$($T),+> {
+                $ptr($(self.$idx as *const $T),+)
+            }
+
+            /// Similar to `pointer::is_null()`
+            pub fn is_null(self) -> bool {
+                false $(|| self.$idx.is_null())+
+            }
+
+            /// Similar to `pointer::as_ref()`
+            pub unsafe fn as_ref<'a>(self) -> Option<$ref<'a, $($T),+>> {
+                self.as_ptr().as_ref()
+            }
+
+            /// Similar to `pointer::as_ref_unchecked()`
+            pub unsafe fn as_ref_unchecked<'a>(self) -> $ref<'a, $($T),+> {
+                self.as_ptr().as_ref_unchecked()
+            }
+
+            /// Similar to `pointer::as_mut()`
+            pub unsafe fn as_mut<'a>(self) -> Option<$ref_mut<'a, $($T),+>> {
+                if self.is_null() {
+                    None
+                } else {
+                    Some(self.as_mut_unchecked())
+                }
+            }
+
+            /// Similar to `pointer::as_mut_unchecked()`
+            pub unsafe fn as_mut_unchecked<'a>(self) -> $ref_mut<'a, $($T),+> {
+                $ref_mut($(&mut *self.$idx),+)
+            }
+
+            /// Similar to `pointer::offset()`
+            pub unsafe fn offset(self, count: isize) -> Self {
+                $ptr_mut($(self.$idx.offset(count)),+)
+            }
+
+            /// Similar to `pointer::wrapping_offset()`
+            pub fn wrapping_offset(self, count: isize) -> Self {
+                $ptr_mut($(self.$idx.wrapping_offset(count)),+)
+            }
+
+            /// Similar to `pointer::add()`
+            pub unsafe fn add(self, count: usize) -> Self {
+                $ptr_mut($(self.$idx.add(count)),+)
+            }
+
+            /// Similar to `pointer::sub()`
+            pub unsafe fn sub(self, count: usize) -> Self {
+                $ptr_mut($(self.$idx.sub(count)),+)
+            }
+
+            /// Similar to `pointer::wrapping_add()`
+            pub fn wrapping_add(self, count: usize) -> Self {
+                $ptr_mut($(self.$idx.wrapping_add(count)),+)
+            }
+
+            /// Similar to `pointer::wrapping_sub()`
+            pub fn wrapping_sub(self, count: usize) -> Self {
+                $ptr_mut($(self.$idx.wrapping_sub(count)),+)
+            }
+
+            /// Similar to `pointer::read()`
+            pub unsafe fn read(self) -> ($($T,)+) {
+                ($(self.$idx.read(),)+)
+            }
+
+            /// Similar to `pointer::read_volatile()`
+            pub unsafe fn read_volatile(self) -> ($($T,)+) {
+                ($(self.$idx.read_volatile(),)+)
+            }
+
+            /// Similar to `pointer::read_unaligned()`
+            pub unsafe fn read_unaligned(self) -> ($($T,)+) {
+                ($(self.$idx.read_unaligned(),)+)
+            }
+
+            /// Similar to `pointer::copy_to()`
+            pub unsafe fn copy_to(self, dest: $ptr_mut<$($T),+>, count: usize) {
+                self.as_ptr().copy_to(dest, count);
+            }
+
+            /// Similar to `pointer::write()`
+            pub unsafe fn write(self, value: ($($T,)+)) {
+                $(self.$idx.write(value.$idx);)+
+            }
+
+            /// Similar to `pointer::write_volatile()`
+            pub unsafe fn write_volatile(self, value: ($($T,)+)) {
+                $(self.$idx.write_volatile(value.$idx);)+
+            }
+
+            /// Similar to `pointer::write_unaligned()`
+            pub unsafe fn write_unaligned(self, value: ($($T,)+)) {
+                $(self.$idx.write_unaligned(value.$idx);)+
+            }
+        }
+
+        /**********************************************************************/
+
+        impl<'a, $($T),+> Iterator for $iter<'a, $($T),+> {
+            type Item = $ref<'a, $($T),+>;
+
+            #[inline]
+            fn next(&mut self) -> Option<Self::Item> {
+                let (first, rest) = self.0.split_first()?;
+                self.0 = rest;
+                Some(first)
+            }
+
+            #[inline]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                (self.0.len(), Some(self.0.len()))
+            }
+        }
+
+        impl<'a, $($T),+> DoubleEndedIterator for $iter<'a, $($T),+> {
+            #[inline]
+            fn next_back(&mut self) -> Option<Self::Item> {
+                let (last, rest) = self.0.split_last()?;
+                self.0 = rest;
+                Some(last)
+            }
+        }
+
+        impl<'a, $($T),+> ExactSizeIterator for $iter<'a, $($T),+> {}
+
+        impl<'a, $($T),+> Iterator for $iter_mut<'a, $($T),+> {
+            type Item = $ref_mut<'a, $($T),+>;
+
+            #[inline]
+            fn next(&mut self) -> Option<Self::Item> {
+                let (first, rest) = core::mem::take(&mut self.0).split_first_mut()?;
+                self.0 = rest;
+                Some(first)
+            }
+
+            #[inline]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                (self.0.len(), Some(self.0.len()))
+            }
+        }
+
+        impl<'a, $($T),+> DoubleEndedIterator for $iter_mut<'a, $($T),+> {
+            #[inline]
+            fn next_back(&mut self) -> Option<Self::Item> {
+                let (last, rest) = core::mem::take(&mut self.0).split_last_mut()?;
+                self.0 = rest;
+                Some(last)
+            }
+        }
+
+        impl<'a, $($T),+> ExactSizeIterator for $iter_mut<'a, $($T),+> {}
+
+        impl<$($T),+> Iterator for $into_iter<$($T),+> {
+            type Item = ($($T,)+);
+
+            #[inline]
+            fn next(&mut self) -> Option<Self::Item> {
+                // all the columns have the same length, so either all or none
+                // of them return `None`
+                Some(($(self.$idx.next()?,)+))
+            }
+
+            #[inline]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.0.size_hint()
+            }
+        }
+
+        impl<$($T),+> DoubleEndedIterator for $into_iter<$($T),+> {
+            #[inline]
+            fn next_back(&mut self) -> Option<Self::Item> {
+                Some(($(self.$idx.next_back()?,)+))
+            }
+        }
+
+        impl<$($T),+> ExactSizeIterator for $into_iter<$($T),+> {}
+
+        /**********************************************************************/
+
+        impl<'a, $($T),+> SoAIndex<$slice<'a, $($T),+>> for usize {
+            type RefOutput = $ref<'a, $($T),+>;
+
+            #[inline]
+            fn get(self, slice: $slice<'a, $($T),+>) -> Option<Self::RefOutput> {
+                if self < slice.len() {
+                    Some(unsafe { self.get_unchecked(slice) })
+                } else {
+                    None
+                }
+            }
+
+            #[inline]
+            unsafe fn get_unchecked(self, slice: $slice<'a, $($T),+>) -> Self::RefOutput {
+                $ref($(slice.$idx.get_unchecked(self)),+)
+            }
+
+            #[inline]
+            fn index(self, slice: $slice<'a, $($T),+>) -> Self::RefOutput {
+                $ref($(&slice.$idx[self]),+)
+            }
+        }
+
+        impl<'a, $($T),+> SoAIndexMut<$slice_mut<'a, $($T),+>> for usize {
+            type MutOutput = $ref_mut<'a, $($T),+>;
+
+            #[inline]
+            fn get_mut(self, slice: $slice_mut<'a, $($T),+>) -> Option<Self::MutOutput> {
+                if self < slice.len() {
+                    Some(unsafe { self.get_unchecked_mut(slice) })
+                } else {
+                    None
+                }
+            }
+
+            #[inline]
+            unsafe fn get_unchecked_mut(self, slice: $slice_mut<'a, $($T),+>) -> Self::MutOutput {
+                $ref_mut($(slice.$idx.get_unchecked_mut(self)),+)
+            }
+
+            #[inline]
+            fn index_mut(self, slice: $slice_mut<'a, $($T),+>) -> Self::MutOutput {
+                $ref_mut($(&mut slice.$idx[self]),+)
+            }
+        }
+
+        tuple_columns!(@range $slice, $slice_mut, core::ops::Range<usize>; $(($T, $idx)),+);
+        tuple_columns!(@range $slice, $slice_mut, core::ops::RangeTo<usize>; $(($T, $idx)),+);
+        tuple_columns!(@range $slice, $slice_mut, core::ops::RangeFrom<usize>; $(($T, $idx)),+);
+        tuple_columns!(@range $slice, $slice_mut, core::ops::RangeFull; $(($T, $idx)),+);
+        tuple_columns!(@range $slice, $slice_mut, core::ops::RangeInclusive<usize>; $(($T, $idx)),+);
+        tuple_columns!(@range $slice, $slice_mut, core::ops::RangeToInclusive<usize>; $(($T, $idx)),+);
+
+        /**********************************************************************/
+
+        impl<$($T,)+ const CAPACITY: usize> Default for $array_vec<$($T,)+ CAPACITY> {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        #[allow(clippy::len_without_is_empty)]
+        impl<$($T,)+ const CAPACITY: usize> $array_vec<$($T,)+ CAPACITY> {
+            /// Create new empty columns, with space for `CAPACITY` tuples
+            pub const fn new() -> Self {
+                $array_vec {
+                    // an array of `MaybeUninit` does not require initialization
+                    columns: unsafe { MaybeUninit::<($([MaybeUninit<$T>; CAPACITY],)+)>::uninit().assume_init() },
+                    len: 0,
+                }
+            }
+
+            /// Get the number of tuples in these columns
+            pub fn len(&self) -> usize {
+                self.len
+            }
+
+            /// Similar to `FooArrayVec::push()`, giving back the value if the
+            /// columns are full
+            pub fn push(&mut self, value: ($($T,)+)) -> Result<(), ($($T,)+)> {
+                if self.len == CAPACITY {
+                    return Err(value);
+                }
+
+                $(self.columns.$idx[self.len] = MaybeUninit::new(value.$idx);)+
+                self.len += 1;
+                Ok(())
+            }
+
+            /// Similar to `FooArrayVec::insert()`, giving back the value if
+            /// the columns are full
+            pub fn insert(&mut self, index: usize, element: ($($T,)+)) -> Result<(), ($($T,)+)> {
+                assert!(index <= self.len, "index out of bounds: the len is {} but the index is {}", self.len, index);
+                if self.len == CAPACITY {
+                    return Err(element);
+                }
+
+                $(unsafe {
+                    let column = self.columns.$idx.as_mut_ptr();
+                    core::ptr::copy(column.add(index), column.add(index + 1), self.len - index);
+                    column.add(index).write(MaybeUninit::new(element.$idx));
+                })+
+                self.len += 1;
+                Ok(())
+            }
+
+            /// Similar to `Vec::pop()`
+            pub fn pop(&mut self) -> Option<($($T,)+)> {
+                if self.len == 0 {
+                    return None;
+                }
+
+                self.len -= 1;
+                unsafe {
+                    Some(($(self.columns.$idx[self.len].as_ptr().read(),)+))
+                }
+            }
+
+            /// Similar to `Vec::remove()`
+            pub fn remove(&mut self, index: usize) -> ($($T,)+) {
+                assert!(index < self.len, "index out of bounds: the len is {} but the index is {}", self.len, index);
+                self.len -= 1;
+                ($(unsafe {
+                    let column = self.columns.$idx.as_mut_ptr();
+                    let value = column.add(index).read().assume_init();
+                    core::ptr::copy(column.add(index + 1), column.add(index), self.len - index);
+                    value
+                },)+)
+            }
+
+            /// Similar to `Vec::swap_remove()`
+            pub fn swap_remove(&mut self, index: usize) -> ($($T,)+) {
+                assert!(index < self.len, "index out of bounds: the len is {} but the index is {}", self.len, index);
+                self.len -= 1;
+                ($(unsafe {
+                    let column = self.columns.$idx.as_mut_ptr();
+                    let value = column.add(index).read().assume_init();
+                    core::ptr::copy(column.add(self.len), column.add(index), 1);
+                    value
+                },)+)
+            }
+
+            /// Similar to `Vec::truncate()`
+            pub fn truncate(&mut self, len: usize) {
+                if len >= self.len {
+                    return;
+                }
+
+                let removed = self.len - len;
+                self.len = len;
+                $(unsafe {
+                    core::ptr::drop_in_place(core::ptr::slice_from_raw_parts_mut(
+                        self.columns.$idx.as_mut_ptr().add(len).cast::<$T>(),
+                        removed,
+                    ));
+                })+
+            }
+
+            /// Get a slice containing all the tuples in these columns
+            pub fn as_slice(&self) -> $slice<'_, $($T),+> {
+                unsafe {
+                    $slice($(core::slice::from_raw_parts(self.columns.$idx.as_ptr().cast::<$T>(), self.len)),+)
+                }
+            }
+
+            /// Get a mutable slice containing all the tuples in these columns
+            pub fn as_mut_slice(&mut self) -> $slice_mut<'_, $($T),+> {
+                unsafe {
+                    $slice_mut($(core::slice::from_raw_parts_mut(self.columns.$idx.as_mut_ptr().cast::<$T>(), self.len)),+)
+                }
+            }
+        }
+
+        impl<$($T,)+ const CAPACITY: usize> Drop for $array_vec<$($T,)+ CAPACITY> {
+            fn drop(&mut self) {
+                self.truncate(0);
+            }
+        }
+    };
+    (@range $slice: ident, $slice_mut: ident, $range: ty; $(($T: ident, $idx: tt)),+) => {
+        impl<'a, $($T),+> SoAIndex<$slice<'a, $($T),+>> for $range {
+            type RefOutput = $slice<'a, $($T),+>;
+
+            #[inline]
+            fn get(self, slice: $slice<'a, $($T),+>) -> Option<Self::RefOutput> {
+                if $(slice.$idx.get(self.clone()).is_some())&&+ {
+                    Some(unsafe { self.get_unchecked(slice) })
+                } else {
+                    None
+                }
+            }
+
+            #[inline]
+            unsafe fn get_unchecked(self, slice: $slice<'a, $($T),+>) -> Self::RefOutput {
+                $slice($(slice.$idx.get_unchecked(self.clone())),+)
+            }
+
+            #[inline]
+            fn index(self, slice: $slice<'a, $($T),+>) -> Self::RefOutput {
+                $slice($(&slice.$idx[self.clone()]),+)
+            }
+        }
+
+        impl<'a, $($T),+> SoAIndexMut<$slice_mut<'a, $($T),+>> for $range {
+            type MutOutput = $slice_mut<'a, $($T),+>;
+
+            #[inline]
+            fn get_mut(self, slice: $slice_mut<'a, $($T),+>) -> Option<Self::MutOutput> {
+                if $(slice.$idx.get(self.clone()).is_some())&&+ {
+                    Some(unsafe { self.get_unchecked_mut(slice) })
+                } else {
+                    None
+                }
+            }
+
+            #[inline]
+            unsafe fn get_unchecked_mut(self, slice: $slice_mut<'a, $($T),+>) -> Self::MutOutput {
+                $slice_mut($(slice.$idx.get_unchecked_mut(self.clone())),+)
+            }
+
+            #[inline]
+            fn index_mut(self, slice: $slice_mut<'a, $($T),+>) -> Self::MutOutput {
+                $slice_mut($(&mut slice.$idx[self.clone()]),+)
+            }
+        }
+    };
+}
+
+tuple_columns!(
+    Tuple1Columns, Tuple1ColumnsSlice, Tuple1ColumnsSliceMut, Tuple1ColumnsRef, Tuple1ColumnsRefMut,
+    Tuple1ColumnsPtr, Tuple1ColumnsPtrMut, Tuple1ColumnsIter, Tuple1ColumnsIterMut, Tuple1ColumnsArrayVec,
+    Tuple1ColumnsArrayChunk, Tuple1ColumnsArrayChunkMut, Tuple1ColumnsIntoIter;
+    (T0, 0)
+);
+
+tuple_columns!(
+    Tuple2Columns, Tuple2ColumnsSlice, Tuple2ColumnsSliceMut, Tuple2ColumnsRef, Tuple2ColumnsRefMut,
+    Tuple2ColumnsPtr, Tuple2ColumnsPtrMut, Tuple2ColumnsIter, Tuple2ColumnsIterMut, Tuple2ColumnsArrayVec,
+    Tuple2ColumnsArrayChunk, Tuple2ColumnsArrayChunkMut, Tuple2ColumnsIntoIter;
+    (T0, 0), (T1, 1)
+);
+
+tuple_columns!(
+    Tuple3Columns, Tuple3ColumnsSlice, Tuple3ColumnsSliceMut, Tuple3ColumnsRef, Tuple3ColumnsRefMut,
+    Tuple3ColumnsPtr, Tuple3ColumnsPtrMut, Tuple3ColumnsIter, Tuple3ColumnsIterMut, Tuple3ColumnsArrayVec,
+    Tuple3ColumnsArrayChunk, Tuple3ColumnsArrayChunkMut, Tuple3ColumnsIntoIter;
+    (T0, 0), (T1, 1), (T2, 2)
+);
+
+tuple_columns!(
+    Tuple4Columns, Tuple4ColumnsSlice, Tuple4ColumnsSliceMut, Tuple4ColumnsRef, Tuple4ColumnsRefMut,
+    Tuple4ColumnsPtr, Tuple4ColumnsPtrMut, Tuple4ColumnsIter, Tuple4ColumnsIterMut, Tuple4ColumnsArrayVec,
+    Tuple4ColumnsArrayChunk, Tuple4ColumnsArrayChunkMut, Tuple4ColumnsIntoIter;
+    (T0, 0), (T1, 1), (T2, 2), (T3, 3)
+);
+
+tuple_columns!(
+    Tuple5Columns, Tuple5ColumnsSlice, Tuple5ColumnsSliceMut, Tuple5ColumnsRef, Tuple5ColumnsRefMut,
+    Tuple5ColumnsPtr, Tuple5ColumnsPtrMut, Tuple5ColumnsIter, Tuple5ColumnsIterMut, Tuple5ColumnsArrayVec,
+    Tuple5ColumnsArrayChunk, Tuple5ColumnsArrayChunkMut, Tuple5ColumnsIntoIter;
+    (T0, 0), (T1, 1), (T2, 2), (T3, 3), (T4, 4)
+);
+
+tuple_columns!(
+    Tuple6Columns, Tuple6ColumnsSlice, Tuple6ColumnsSliceMut, Tuple6ColumnsRef, Tuple6ColumnsRefMut,
+    Tuple6ColumnsPtr, Tuple6ColumnsPtrMut, Tuple6ColumnsIter, Tuple6ColumnsIterMut, Tuple6ColumnsArrayVec,
+    Tuple6ColumnsArrayChunk, Tuple6ColumnsArrayChunkMut, Tuple6ColumnsIntoIter;
+    (T0, 0), (T1, 1), (T2, 2), (T3, 3), (T4, 4), (T5, 5)
+);
+
+tuple_columns!(
+    Tuple7Columns, Tuple7ColumnsSlice, Tuple7ColumnsSliceMut, Tuple7ColumnsRef, Tuple7ColumnsRefMut,
+    Tuple7ColumnsPtr, Tuple7ColumnsPtrMut, Tuple7ColumnsIter, Tuple7ColumnsIterMut, Tuple7ColumnsArrayVec,
+    Tuple7ColumnsArrayChunk, Tuple7ColumnsArrayChunkMut, Tuple7ColumnsIntoIter;
+    (T0, 0), (T1, 1), (T2, 2), (T3, 3), (T4, 4), (T5, 5), (T6, 6)
+);
+
+tuple_columns!(
+    Tuple8Columns, Tuple8ColumnsSlice, Tuple8ColumnsSliceMut, Tuple8ColumnsRef, Tuple8ColumnsRefMut,
+    Tuple8ColumnsPtr, Tuple8ColumnsPtrMut, Tuple8ColumnsIter, Tuple8ColumnsIterMut, Tuple8ColumnsArrayVec,
+    Tuple8ColumnsArrayChunk, Tuple8ColumnsArrayChunkMut, Tuple8ColumnsIntoIter;
+    (T0, 0), (T1, 1), (T2, 2), (T3, 3), (T4, 4), (T5, 5), (T6, 6), (T7, 7)
+);
+
+tuple_columns!(
+    Tuple9Columns, Tuple9ColumnsSlice, Tuple9ColumnsSliceMut, Tuple9ColumnsRef, Tuple9ColumnsRefMut,
+    Tuple9ColumnsPtr, Tuple9ColumnsPtrMut, Tuple9ColumnsIter, Tuple9ColumnsIterMut, Tuple9ColumnsArrayVec,
+    Tuple9ColumnsArrayChunk, Tuple9ColumnsArrayChunkMut, Tuple9ColumnsIntoIter;
+    (T0, 0), (T1, 1), (T2, 2), (T3, 3), (T4, 4), (T5, 5), (T6, 6), (T7, 7), (T8, 8)
+);
+
+tuple_columns!(
+    Tuple10Columns, Tuple10ColumnsSlice, Tuple10ColumnsSliceMut, Tuple10ColumnsRef, Tuple10ColumnsRefMut,
+    Tuple10ColumnsPtr, Tuple10ColumnsPtrMut, Tuple10ColumnsIter, Tuple10ColumnsIterMut, Tuple10ColumnsArrayVec,
+    Tuple10ColumnsArrayChunk, Tuple10ColumnsArrayChunkMut, Tuple10ColumnsIntoIter;
+    (T0, 0), (T1, 1), (T2, 2), (T3, 3), (T4, 4), (T5, 5), (T6, 6), (T7, 7), (T8, 8), (T9, 9)
+);
+
+tuple_columns!(
+    Tuple11Columns, Tuple11ColumnsSlice, Tuple11ColumnsSliceMut, Tuple11ColumnsRef, Tuple11ColumnsRefMut,
+    Tuple11ColumnsPtr, Tuple11ColumnsPtrMut, Tuple11ColumnsIter, Tuple11ColumnsIterMut, Tuple11ColumnsArrayVec,
+    Tuple11ColumnsArrayChunk, Tuple11ColumnsArrayChunkMut, Tuple11ColumnsIntoIter;
+    (T0, 0), (T1, 1), (T2, 2), (T3, 3), (T4, 4), (T5, 5), (T6, 6), (T7, 7), (T8, 8), (T9, 9), (T10, 10)
+);
+
+tuple_columns!(
+    Tuple12Columns, Tuple12ColumnsSlice, Tuple12ColumnsSliceMut, Tuple12ColumnsRef, Tuple12ColumnsRefMut,
+    Tuple12ColumnsPtr, Tuple12ColumnsPtrMut, Tuple12ColumnsIter, Tuple12ColumnsIterMut, Tuple12ColumnsArrayVec,
+    Tuple12ColumnsArrayChunk, Tuple12ColumnsArrayChunkMut, Tuple12ColumnsIntoIter;
+    (T0, 0), (T1, 1), (T2, 2), (T3, 3), (T4, 4), (T5, 5), (T6, 6), (T7, 7), (T8, 8), (T9, 9), (T10, 10), (T11, 11)
+);
@@ -52,6 +52,16 @@
 //! # }
 //! ```
 //!
+//! `Clone` and serde's `Deserialize` are only derived for the vector. Serde's
+//! `Serialize` is derived for the vector, slices and references, which
+//! serialize exactly like the vector and the original struct respectively: a
+//! `CheeseSlice` can be deserialized as a `CheeseVec`, and a `CheeseRef` as a
+//! `Cheese`. To this end, references use the `#[serde(...)]` attributes of
+//! the struct and its fields, and slices the `#[serde(...)]` attributes given
+//! to the vector with `#[soa_attr(Vec, ...)]`. When deserializing a vector,
+//! all the columns must have the same length, otherwise deserialization fails
+//! with an error.
+//!
 //! If you want to add attribute to a specific generated struct(such as
 //! `#[cfg_attr(test, derive(PartialEq))]` on `CheeseVec`), you can add an
 //! attribute `#[soa_attr(Vec, cfg_attr(test, derive(PartialEq)))]` to the
@@ -104,6 +114,13 @@
 //! functions are duplicated, or require a call to `as_ref()/as_mut()` to change
 //! the type used.
 //!
+//! The fields of `CheeseVec` are public, and nothing prevents them from getting
+//! different lengths when modified directly. `CheeseVec::from_columns()` builds
+//! a vector from existing columns and checks their lengths, returning a
+//! [`LengthMismatch`] error if they differ; and `validate()` runs the same
+//! check on an existing vector. `into_columns()` gives back the columns of a
+//! vector.
+//!
 //! # Iteration
 //!
 //! It is possible to iterate over the values in a `CheeseVec`
@@ -189,6 +206,39 @@
 //! # }
 //! ```
 //!
+//! Vectors and slices can also be iterated in chunks, to process multiple
+//! elements at once and allow the compiler to auto-vectorize loops.
+//! `chunks_exact(n)` yields `CheeseSlice` of `n` elements, while
+//! `array_chunks::<N>()` yields `CheeseArrayChunk` containing a `&[T; N]`
+//! array for each field, which can be passed directly to SIMD code. The
+//! elements at the end which do not fit in a full chunk are available with
+//! `remainder()`.
+//!
+//! ```no_run
+//! # #[macro_use] extern crate soa_derive;
+//! # mod cheese {
+//! # #[derive(Debug, PartialEq, StructOfArray)]
+//! # pub struct Cheese {
+//! #     pub smell: f64,
+//! #     pub color: (f64, f64, f64),
+//! #     pub with_mushrooms: bool,
+//! #     pub name: String,
+//! # }
+//! # impl Cheese { fn new(name: &str) -> Cheese { unimplemented!() } }
+//! # fn main() {
+//! # let mut vec = CheeseVec::new();
+//! # vec.push(Cheese::new("stilton"));
+//! # vec.push(Cheese::new("brie"));
+//! for chunk in vec.array_chunks_mut::<4>() {
+//!     let smell: &mut [f64; 4] = chunk.smell;
+//!     for smell in smell {
+//!         *smell *= 2.0;
+//!     }
+//! }
+//! # }
+//! # }
+//! ```
+//!
 //! ## Nested Struct of Arrays
 //!
 //! In order to nest a struct of arrays inside another struct of arrays, one can use the `#[nested_soa]` attribute.
@@ -227,6 +277,358 @@
 //!
 //! All helper structs will be also nested, for example `PointSlice` will be nested in `ParticleSlice`.
 //!
+//! The nested types are found through the [`StructOfArray`], [`SoASlices`],
+//! [`SoAIter`], [`SoAPointers`], [`SoAArrayVec`] and [`SoAArrayChunks`] traits
+//! (*i.e.* `PointVec` is `<Point as StructOfArray>::Type`), so the nested field
+//! can use any path or generic type, such as `geometry::Point` or
+//! `Vector3<T>`.
+//!
+//! ## Exploded array and tuple fields
+//!
+//! Array and tuple fields are stored by default in a single `Vec<[T; N]>` or
+//! `Vec<(A, B, ...)>`. The `#[soa(explode)]` attribute stores each element of
+//! the array or tuple in a separate vector instead:
+//!
+//! ```
+//! # mod cheese {
+//! # use soa_derive::StructOfArray;
+//! #[derive(StructOfArray)]
+//! pub struct Particle {
+//!     #[soa(explode)]
+//!     position: (f64, f64, f64),
+//!     #[soa(explode)]
+//!     data: [usize; 18],
+//! }
+//! # }
+//! ```
+//!
+//! Here `ParticleVec::position` contains three `Vec<f64>`, accessible as
+//! `position.0`, `position.1` and `position.2`; and `ParticleVec::data`
+//! contains 18 `Vec<usize>`, accessible as `data[0]` to `data[17]`. The
+//! slices, references and pointers are grouped the same way, so
+//! `particle.position.0` is a `&f64` and `particle.data[3]` a `usize` in a
+//! `ParticleRef`. The corresponding types live in the [`explode`] module.
+//!
+//! `#[soa(explode)]` can be used with arrays of any non-zero length and with
+//! tuples of up to 12 elements.
+//!
+//! ## Generated types names
+//!
+//! The names of the generated types can be changed with a `#[soa(...)]`
+//! attribute on the struct. Each name can be set explicitly with the `vec`,
+//! `array_vec`, `slice`, `slice_mut`, `ref`, `ref_mut`, `ptr`, `ptr_mut`,
+//! `iter`, `iter_mut`, `into_iter`, `chunks_exact`, `chunks_exact_mut`,
+//! `array_chunk`, `array_chunk_mut`, `array_chunks`, `array_chunks_mut`,
+//! `drain`, `splice` and `extract_if` keys, and a `prefix` and `suffix` can be
+//! added to all the other default names.
+//!
+//! ```
+//! # mod cheese {
+//! # use soa_derive::StructOfArray;
+//! #[derive(StructOfArray)]
+//! #[soa(vec = "Particles", slice = "ParticlesView", suffix = "Soa")]
+//! pub struct Particle {
+//!     mass: f32,
+//! }
+//!
+//! # fn main() {
+//! let particles = Particles::new();
+//! let slice: ParticlesView<'_> = particles.as_slice();
+//! let ptr: ParticlePtrSoa = particles.as_ptr();
+//! # }
+//! # }
+//! ```
+//!
+//! ## Skipping fields
+//!
+//! Fields marked with `#[soa(skip)]` are not stored in the generated types.
+//! When a value of the original struct is re-created (for example with
+//! `pop`, `remove`, `swap_remove`, `to_owned` on references or `read` on
+//! pointers), these fields are initialized with `Default::default()`, or with
+//! the function given in `#[soa(skip, default = "function")]`. Generic
+//! parameters of the struct must still be used by at least one of the
+//! fields which are not skipped.
+//!
+//! ```
+//! # mod cheese {
+//! # use soa_derive::StructOfArray;
+//! fn no_name() -> String {
+//!     String::from("<none>")
+//! }
+//!
+//! #[derive(StructOfArray)]
+//! pub struct Particle {
+//!     mass: f32,
+//!     #[soa(skip)]
+//!     cached_energy: Option<f32>,
+//!     #[soa(skip, default = "no_name")]
+//!     name: String,
+//! }
+//!
+//! # fn main() {
+//! let mut particles = ParticleVec::new();
+//! particles.push(Particle { mass: 1.0, cached_energy: Some(3.0), name: "H".into() });
+//!
+//! let particle = particles.pop().unwrap();
+//! assert_eq!(particle.cached_energy, None);
+//! assert_eq!(particle.name, "<none>");
+//! # }
+//! # }
+//! ```
+//!
+//! ## Private columns
+//!
+//! With `#[soa(private_columns)]`, the fields of the `Vec` type are private,
+//! so that code outside of the module defining the struct can not change the
+//! length of a single column. Each column can instead be accessed as a slice
+//! with a `column_` method named after the field (`column_x()`), and modified
+//! with a `_mut` method (`column_x_mut()`). Columns of tuple structs are
+//! accessed with `column_0()`, `column_0_mut()`, *etc.* `soa_zip!` needs to be
+//! used with `as_slice()` or `as_mut_slice()` for these vectors.
+//!
+//! ```
+//! mod particles {
+//! #   use soa_derive::StructOfArray;
+//!     #[derive(StructOfArray)]
+//!     #[soa(private_columns)]
+//!     pub struct Particle {
+//!         pub mass: f32,
+//!         pub charge: f32,
+//!     }
+//! }
+//!
+//! # fn main() {
+//! use particles::{Particle, ParticleVec};
+//!
+//! let mut particles = ParticleVec::new();
+//! particles.push(Particle { mass: 1.0, charge: -1.0 });
+//! particles.column_mass_mut()[0] = 2.0;
+//! assert_eq!(particles.column_mass(), [2.0]);
+//! # }
+//! ```
+//!
+//! ## Generic structs
+//!
+//! Structs with generic type, lifetime or const parameters are supported, and
+//! all the generated types will use the same parameters and where clause.
+//!
+//! ```
+//! # mod cheese {
+//! # use soa_derive::StructOfArray;
+//! #[derive(StructOfArray)]
+//! pub struct Point<T: Copy> {
+//!     x: T,
+//!     y: T,
+//! }
+//!
+//! # fn main() {
+//! let mut points = PointVec::<f32>::new();
+//! points.push(Point { x: 1.0, y: 2.0 });
+//! let slice: PointSlice<'_, f32> = points.as_slice();
+//! # }
+//! # }
+//! ```
+//!
+//! ## Tuple structs
+//!
+//! Tuple structs are also supported. The generated types are then tuple
+//! structs as well, with one positional column per field:
+//!
+//! ```
+//! # mod cheese {
+//! # use soa_derive::StructOfArray;
+//! #[derive(StructOfArray)]
+//! pub struct Vec3(f32, f32, f32);
+//!
+//! # fn main() {
+//! let mut vectors = Vec3Vec::new();
+//! vectors.push(Vec3(1.0, 2.0, 3.0));
+//! assert_eq!(vectors.1, [2.0]);
+//! assert_eq!(*vectors.index(0).2, 3.0);
+//! # }
+//! # }
+//! ```
+//!
+//! ## Enums
+//!
+//! `#[derive(StructOfArray)]` can also be used on enums without generic
+//! parameters. The data of each variant with fields is stored in a separate
+//! struct of arrays (using a generated `ShapeCircle` struct for the `Circle`
+//! variant of `Shape`, and so on), and the vector keeps track of the variant
+//! of each element. The vector provides `push`, `pop`, `get`, `get_mut`,
+//! `iter` and `iter_mut`, returning a `ShapeRef` or `ShapeRefMut` enum with
+//! one variant per variant of the original enum.
+//!
+//! ```
+//! # mod cheese {
+//! # use soa_derive::StructOfArray;
+//! #[derive(StructOfArray)]
+//! pub enum Shape {
+//!     Circle { r: f64 },
+//!     Rect { w: f64, h: f64 },
+//!     Empty,
+//! }
+//!
+//! # fn main() {
+//! let mut shapes = ShapeVec::new();
+//! shapes.push(Shape::Circle { r: 1.0 });
+//! shapes.push(Shape::Rect { w: 2.0, h: 3.0 });
+//! shapes.push(Shape::Circle { r: 4.0 });
+//!
+//! // all the circles radii are stored together
+//! assert_eq!(shapes.circle.r, [1.0, 4.0]);
+//!
+//! match shapes.get(1) {
+//!     Some(ShapeRef::Rect(rect)) => assert_eq!(*rect.w, 2.0),
+//!     _ => unreachable!(),
+//! }
+//! # }
+//! # }
+//! ```
+//!
+//! ## Fixed-capacity vectors
+//!
+//! In addition to `CheeseVec`, a `CheeseArrayVec<const CAPACITY: usize>` type
+//! is generated, storing up to `CAPACITY` elements inline without allocating
+//! any memory. It provides `push`, `pop`, `insert`, `remove`, `iter`,
+//! `as_slice` and `as_mut_slice` among others, and the slices are the same
+//! `CheeseSlice` and `CheeseSliceMut` types used by `CheeseVec`. `push` and
+//! `insert` give back the value in `Err` when the vector is full.
+//!
+//! ```
+//! # mod cheese {
+//! # use soa_derive::StructOfArray;
+//! #[derive(Debug, PartialEq, StructOfArray)]
+//! pub struct Particle {
+//!     mass: f32,
+//! }
+//!
+//! # fn main() {
+//! let mut particles = ParticleArrayVec::<2>::new();
+//! assert!(particles.push(Particle { mass: 1.0 }).is_ok());
+//! assert!(particles.push(Particle { mass: 2.0 }).is_ok());
+//! assert_eq!(particles.push(Particle { mass: 3.0 }), Err(Particle { mass: 3.0 }));
+//!
+//! let slice: ParticleSlice<'_> = particles.as_slice();
+//! assert_eq!(slice.mass, [1.0, 2.0]);
+//! # }
+//! # }
+//! ```
+//!
+//! ## Single buffer storage
+//!
+//! By default, the vector stores each field in a separate `Vec`, which means
+//! one allocation per field. With `#[soa(storage = "single_buffer")]`, the
+//! vector uses a single allocation, split in one aligned region per field,
+//! and one capacity shared by all fields. `push`, `reserve` and friends then
+//! only allocate once, regardless of the number of fields.
+//!
+//! The vector fields are private in this case, and should be accessed through
+//! `as_slice()` and `as_mut_slice()`. Nested struct of arrays are not
+//! supported, and only `Clone`, `Debug` and `PartialEq` can be derived for the
+//! vector with `#[soa_derive]` (see below for serde support).
+//!
+//! ```
+//! # mod cheese {
+//! # use soa_derive::StructOfArray;
+//! #[derive(StructOfArray)]
+//! #[soa(storage = "single_buffer")]
+//! pub struct Particle {
+//!     mass: f64,
+//!     charge: i8,
+//! }
+//!
+//! # fn main() {
+//! let mut particles = ParticleVec::with_capacity(10);
+//! particles.push(Particle { mass: 1.0, charge: -1 });
+//! particles.push(Particle { mass: 2.0, charge: 1 });
+//!
+//! assert_eq!(particles.capacity(), 10);
+//! assert_eq!(particles.as_slice().charge, [-1, 1]);
+//! # }
+//! # }
+//! ```
+//!
+//! ## Serialization layout
+//!
+//! When deriving serde's `Serialize` and `Deserialize` with `#[soa_derive]`,
+//! the vector and slices are serialized with one sequence per field by default
+//! (`{"mass":[1.0,2.0],"charge":[-1,1]}`). With `#[soa(serde = "rows")]`, they
+//! are instead serialized as a sequence of the original struct
+//! (`[{"mass":1.0,"charge":-1},{"mass":2.0,"charge":1}]`), and deserialized by
+//! pushing the elements one by one in the vector, without going through a
+//! `Vec<Particle>`. The struct itself must implement `Serialize` and
+//! `Deserialize`. This layout can also be used with single buffer storage.
+//!
+//! ```
+//! # mod cheese {
+//! # use soa_derive::StructOfArray;
+//! # use serde::{Deserialize, Serialize};
+//! #[derive(StructOfArray, Serialize, Deserialize)]
+//! #[soa_derive(Serialize, Deserialize)]
+//! #[soa(serde = "rows")]
+//! pub struct Particle {
+//!     mass: f64,
+//!     charge: i8,
+//! }
+//!
+//! # fn main() {
+//! let particles: ParticleVec = serde_json::from_str(r#"[{"mass":1.0,"charge":-1}]"#).unwrap();
+//! assert_eq!(particles.charge, [-1]);
+//! # }
+//! # }
+//! ```
+//!
+//! ## `no_std` support
+//!
+//! The generated code only uses `core` and `alloc`, so it can be used in
+//! `#![no_std]` crates as long as an allocator is available. Disable the
+//! default `std` feature to make `soa_derive` itself `no_std`:
+//!
+//! ```toml
+//! [dependencies]
+//! soa_derive = { version = "0.13", default-features = false }
+//! ```
+//!
+//! ## Parallel iteration
+//!
+//! With the optional `rayon` feature, `CheeseVec`, `CheeseSlice` and
+//! `CheeseSliceMut` get `par_iter()`, `par_iter_mut()`, `par_chunks()` and
+//! `par_chunks_mut()` methods, returning [rayon] parallel iterators over
+//! `CheeseRef`, `CheeseRefMut`, `CheeseSlice` and `CheeseSliceMut`
+//! respectively. They also implement `IntoParallelIterator`. The
+//! `soa_par_zip!` macro is the parallel equivalent of
+//! [soa_zip!](macro.soa_zip.html).
+//! `CheeseSliceMut` also gets `par_sort_by()`, `par_sort_by_key()`,
+//! `par_sort_unstable_by()` and `par_sort_unstable_by_key()`, which sort
+//! the elements using multiple threads.
+//!
+//! ```toml
+//! [dependencies]
+//! soa_derive = { version = "0.13", features = ["rayon"] }
+//! ```
+//!
+//! [rayon]: https://docs.rs/rayon/
+//!
+//! ## Apache Arrow conversion
+//!
+//! With the optional `arrow` feature, `CheeseVec` gets `arrow_schema()`,
+//! `to_record_batch()`, `into_record_batch()` and `try_from_record_batch()`
+//! methods, converting the vector to and from an [Arrow] `RecordBatch` with
+//! one column per field. Columns of primitive numeric types, `bool` and
+//! `String` are supported, and `#[nested_soa]` fields are stored as struct
+//! arrays. `into_record_batch()` moves the columns of primitive numeric types
+//! to the record batch without copying them. These methods are only available
+//! if all the columns can be converted (see the `arrow::ArrowColumn`
+//! trait), and null values are not supported.
+//!
+//! ```toml
+//! [dependencies]
+//! soa_derive = { version = "0.13", features = ["arrow"] }
+//! ```
+//!
+//! [Arrow]: https://arrow.apache.org/
+//!
 //! # Use in a generic context
 //!
 //! `StructOfArray` does not provide a set of common operations by default. Thus if you wanted to use a `StructOfArray`
@@ -250,15 +652,57 @@
 //! # }
 //! ```
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
 // The proc macro is implemented in soa_derive_internal, and re-exported by this
 // crate. This is because a single crate can not define both a proc macro and a
 // macro_rules macro.
 pub use soa_derive_internal::StructOfArray;
 
-// External dependency necessary for implementing the sorting methods.
-// It is basically used by the macro-generated code.
+// The `alloc` crate is re-exported for the macro-generated code, which needs
+// `Vec` even when the `std` feature is disabled.
+#[doc(hidden)]
+pub extern crate alloc;
+
+// Permutation used to implement the sorting methods in the macro-generated
+// code.
+mod permutation;
 #[doc(hidden)]
-pub use permutation::permutation::*;
+pub use self::permutation::Permutation;
+
+// Runtime support for the vectors using `#[soa(storage = "single_buffer")]`,
+// used by the macro-generated code.
+#[doc(hidden)]
+pub mod single_buffer;
+
+// Range handling shared by the `drain()`, `splice()` and `extract_if()`
+// methods of the macro-generated vectors.
+#[doc(hidden)]
+pub mod drain;
+
+pub mod explode;
+
+mod columns;
+pub use self::columns::LengthMismatch;
+
+// rayon is re-exported for the `soa_par_zip!` macro.
+#[cfg(feature = "rayon")]
+#[doc(hidden)]
+pub extern crate rayon;
+
+#[cfg(feature = "rayon")]
+pub mod parallel;
+
+// arrow crates are re-exported for the macro-generated code.
+#[cfg(feature = "arrow")]
+#[doc(hidden)]
+pub extern crate arrow_array;
+#[cfg(feature = "arrow")]
+#[doc(hidden)]
+pub extern crate arrow_schema;
+
+#[cfg(feature = "arrow")]
+pub mod arrow;
 
 /// Any struct derived by StructOfArray will auto impl this trait You can use
 /// `<Cheese as StructOfArray>::Type` instead of explicit named type
@@ -282,12 +726,59 @@ pub trait SoAIter<'a> {
     type IterMut: 'a + Iterator<Item=Self::RefMut>;
 }
 
+/// Any struct derived by StructOfArray will auto impl this trait.
+///
+/// Useful for generic programming and implementation of attribute `nested_soa`.
+///
+/// `CheeseVec::as_slice(&'a self)` returns a slice which has a type `<Cheese as SoASlices<'a>>::Slice`
+///
+/// `CheeseVec::as_mut_slice(&'a mut self)` returns a slice which has a type `<Cheese as SoASlices<'a>>::SliceMut`
+pub trait SoASlices<'a> {
+    type Slice;
+    type SliceMut;
+}
+
+/// Any struct derived by StructOfArray will auto impl this trait.
+///
+/// Useful for generic programming and implementation of attribute `nested_soa`.
+///
+/// `<Cheese as SoAArrayVec<CAPACITY>>::ArrayVec` is the same type as
+/// `CheeseArrayVec<CAPACITY>`.
+pub trait SoAArrayVec<const CAPACITY: usize> {
+    type ArrayVec;
+}
+
+/// Any struct derived by StructOfArray will auto impl this trait.
+///
+/// Useful for generic programming and implementation of attribute `nested_soa`.
+///
+/// `CheeseSlice::array_chunks::<CHUNK>()` returns an iterator over items of
+/// type `<Cheese as SoAArrayChunks<'a, CHUNK>>::Chunk`, which is the same as
+/// `CheeseArrayChunk<'a, CHUNK>`.
+pub trait SoAArrayChunks<'a, const CHUNK: usize> {
+    type Chunk;
+    type ChunkMut;
+}
+
+/// Any struct derived by StructOfArray will auto impl this trait.
+///
+/// Useful for generic programming and implementation of attribute `nested_soa`.
+///
+/// `<Cheese as SoAAsRef<'a>>::as_soa_ref(&'a cheese)` is the same as
+/// `cheese.as_ref()`, and returns a `CheeseRef<'a>`.
+pub trait SoAAsRef<'a>: SoAIter<'a> {
+    /// Get a reference of the SoA type to this value
+    fn as_soa_ref(&'a self) -> Self::Ref;
+    /// Get a mutable reference of the SoA type to this value
+    fn as_soa_mut(&'a mut self) -> Self::RefMut;
+}
+
 mod private_soa_indexes {
     // From [`std::slice::SliceIndex`](https://doc.rust-lang.org/std/slice/trait.SliceIndex.html) code.
     // Limits the types that may implement the SoA index traits.
     // It's also helpful to have the exaustive list of all accepted types.
 
-    use ::std::ops;
+    use ::core::ops;
 
     pub trait Sealed {}
 
@@ -409,6 +900,45 @@ macro_rules! soa_zip {
     }};
 }
 
+/// Create a parallel iterator over multiple fields in a Struct of array style
+/// vector, using rayon.
+///
+/// This macro is only available with the `rayon` feature, and takes the same
+/// arguments as [soa_zip!](macro.soa_zip.html). External iterators must
+/// implement [`IndexedParallelIterator`](rayon::iter::IndexedParallelIterator)
+/// once converted with `into_par_iter()`.
+///
+/// ```
+/// # #[macro_use] extern crate soa_derive;
+/// # mod cheese {
+/// use rayon::prelude::*;
+///
+/// #[derive(StructOfArray)]
+/// struct Cheese {
+///     size: f64,
+///     mass: f64,
+///     name: String,
+/// }
+///
+/// # fn main() {
+/// let mut vec = CheeseVec::new();
+/// // fill the vector
+///
+/// soa_par_zip!(&mut vec, [mut mass, size]).for_each(|(mass, size)| {
+///     *mass = 2.0 * size;
+/// });
+/// # }
+/// # }
+/// ```
+#[cfg(feature = "rayon")]
+#[macro_export]
+macro_rules! soa_par_zip {
+    ($self: expr, [$($fields: tt)*] $(, $external: expr)* $(,)*) => {{
+        let this = $self;
+        $crate::soa_zip_impl!(@par_munch this, {$($fields)*} -> [] $($external ,)*)
+    }};
+}
+
 
 /// This trait is automatically implemented by the relevant generated by [`StructOfArray`].
 ///
@@ -473,6 +1003,55 @@ mod generics {
             self.get(self.len().saturating_sub(1))
         }
 
+        /// Analogous to [`slice::binary_search_by()`](https://doc.rust-lang.org/std/primitive.slice.html#method.binary_search_by)
+        fn binary_search_by<F>(&self, mut f: F) -> Result<usize, usize> where F: FnMut(Self::Ref<'_>) -> core::cmp::Ordering {
+            let mut left = 0;
+            let mut right = self.len();
+            while left < right {
+                let mid = left + (right - left) / 2;
+                match f(self.index(mid)) {
+                    core::cmp::Ordering::Less => left = mid + 1,
+                    core::cmp::Ordering::Greater => right = mid,
+                    core::cmp::Ordering::Equal => return Ok(mid),
+                }
+            }
+            Err(left)
+        }
+
+        /// Analogous to [`slice::binary_search_by_key()`](https://doc.rust-lang.org/std/primitive.slice.html#method.binary_search_by_key)
+        fn binary_search_by_key<B, F>(&self, b: &B, mut f: F) -> Result<usize, usize> where
+            F: FnMut(Self::Ref<'_>) -> B,
+            B: Ord,
+        {
+            self.binary_search_by(|item| f(item).cmp(b))
+        }
+
+        /// Analogous to [`slice::partition_point()`](https://doc.rust-lang.org/std/primitive.slice.html#method.partition_point)
+        fn partition_point<P>(&self, mut pred: P) -> usize where P: FnMut(Self::Ref<'_>) -> bool {
+            self.binary_search_by(|item| {
+                if pred(item) {
+                    core::cmp::Ordering::Less
+                } else {
+                    core::cmp::Ordering::Greater
+                }
+            }).unwrap_or_else(|i| i)
+        }
+
+        /// Analogous to [`slice::contains()`](https://doc.rust-lang.org/std/primitive.slice.html#method.contains)
+        fn contains<'c>(&'c self, x: Self::Ref<'c>) -> bool where Self::Ref<'c>: PartialEq {
+            self.iter().any(|item| item == x)
+        }
+
+        /// Analogous to [`slice::starts_with()`](https://doc.rust-lang.org/std/primitive.slice.html#method.starts_with)
+        fn starts_with<'c>(&'c self, needle: Self::Slice<'c>) -> bool where Self::Ref<'c>: PartialEq {
+            needle.len() <= self.len() && self.iter().zip(needle).all(|(a, b)| a == b)
+        }
+
+        /// Analogous to [`slice::ends_with()`](https://doc.rust-lang.org/std/primitive.slice.html#method.ends_with)
+        fn ends_with<'c>(&'c self, needle: Self::Slice<'c>) -> bool where Self::Ref<'c>: PartialEq {
+            needle.len() <= self.len() && self.iter().skip(self.len() - needle.len()).zip(needle).all(|(a, b)| a == b)
+        }
+
         /// Obtain a `const` pointer type for this data
         fn as_ptr(&self) -> Self::Ptr;
     }
@@ -538,6 +1117,55 @@ mod generics {
             self.get(self.len().saturating_sub(1))
         }
 
+        /// Analogous to [`slice::binary_search_by()`](https://doc.rust-lang.org/std/primitive.slice.html#method.binary_search_by)
+        fn binary_search_by<F>(&self, mut f: F) -> Result<usize, usize> where F: FnMut(Self::Ref<'_>) -> core::cmp::Ordering {
+            let mut left = 0;
+            let mut right = self.len();
+            while left < right {
+                let mid = left + (right - left) / 2;
+                match f(self.index(mid)) {
+                    core::cmp::Ordering::Less => left = mid + 1,
+                    core::cmp::Ordering::Greater => right = mid,
+                    core::cmp::Ordering::Equal => return Ok(mid),
+                }
+            }
+            Err(left)
+        }
+
+        /// Analogous to [`slice::binary_search_by_key()`](https://doc.rust-lang.org/std/primitive.slice.html#method.binary_search_by_key)
+        fn binary_search_by_key<B, F>(&self, b: &B, mut f: F) -> Result<usize, usize> where
+            F: FnMut(Self::Ref<'_>) -> B,
+            B: Ord,
+        {
+            self.binary_search_by(|item| f(item).cmp(b))
+        }
+
+        /// Analogous to [`slice::partition_point()`](https://doc.rust-lang.org/std/primitive.slice.html#method.partition_point)
+        fn partition_point<P>(&self, mut pred: P) -> usize where P: FnMut(Self::Ref<'_>) -> bool {
+            self.binary_search_by(|item| {
+                if pred(item) {
+                    core::cmp::Ordering::Less
+                } else {
+                    core::cmp::Ordering::Greater
+                }
+            }).unwrap_or_else(|i| i)
+        }
+
+        /// Analogous to [`slice::contains()`](https://doc.rust-lang.org/std/primitive.slice.html#method.contains)
+        fn contains<'c>(&'c self, x: Self::Ref<'c>) -> bool where Self::Ref<'c>: PartialEq {
+            self.iter().any(|item| item == x)
+        }
+
+        /// Analogous to [`slice::starts_with()`](https://doc.rust-lang.org/std/primitive.slice.html#method.starts_with)
+        fn starts_with<'c>(&'c self, needle: Self::Slice<'c>) -> bool where Self::Ref<'c>: PartialEq {
+            needle.len() <= self.len() && self.iter().zip(needle).all(|(a, b)| a == b)
+        }
+
+        /// Analogous to [`slice::ends_with()`](https://doc.rust-lang.org/std/primitive.slice.html#method.ends_with)
+        fn ends_with<'c>(&'c self, needle: Self::Slice<'c>) -> bool where Self::Ref<'c>: PartialEq {
+            needle.len() <= self.len() && self.iter().skip(self.len() - needle.len()).zip(needle).all(|(a, b)| a == b)
+        }
+
         /// Obtain a `const` pointer type for this data
         fn as_ptr(&self) -> Self::Ptr;
 
@@ -563,8 +1191,8 @@ mod generics {
         fn apply_index(&mut self, indices: &[usize]);
 
         /// `[slice::sort_by()`](<https://doc.rust-lang.org/std/primitive.slice.html#method.sort_by>).
-        fn sort_by<F>(&mut self, mut f: F) where F: FnMut(Self::Ref<'_>, Self::Ref<'_>) -> std::cmp::Ordering {
-            let mut permutation: Vec<usize> = (0..self.len()).collect();
+        fn sort_by<F>(&mut self, mut f: F) where F: FnMut(Self::Ref<'_>, Self::Ref<'_>) -> core::cmp::Ordering {
+            let mut permutation: alloc::vec::Vec<usize> = (0..self.len()).collect();
             permutation.sort_by(|j, k| f(self.index(*j), self.index(*k)));
 
             self.apply_index(&permutation);
@@ -575,12 +1203,139 @@ mod generics {
             F: FnMut(Self::Ref<'_>) -> K,
             K: Ord,
         {
-            let mut permutation: Vec<usize> = (0..self.len()).collect();
+            let mut permutation: alloc::vec::Vec<usize> = (0..self.len()).collect();
             permutation.sort_by_key(|j| f(self.index(*j)));
 
             self.apply_index(&permutation);
         }
 
+        /// `[slice::sort_unstable_by()`](<https://doc.rust-lang.org/std/primitive.slice.html#method.sort_unstable_by>).
+        fn sort_unstable_by<F>(&mut self, mut f: F) where F: FnMut(Self::Ref<'_>, Self::Ref<'_>) -> core::cmp::Ordering {
+            let mut permutation: alloc::vec::Vec<usize> = (0..self.len()).collect();
+            permutation.sort_unstable_by(|j, k| f(self.index(*j), self.index(*k)));
+
+            self.apply_index(&permutation);
+        }
+
+        /// `[slice::sort_unstable_by_key()`](<https://doc.rust-lang.org/std/primitive.slice.html#method.sort_unstable_by_key>).
+        fn sort_unstable_by_key<F, K>(&mut self, mut f: F) where
+            F: FnMut(Self::Ref<'_>) -> K,
+            K: Ord,
+        {
+            let mut permutation: alloc::vec::Vec<usize> = (0..self.len()).collect();
+            permutation.sort_unstable_by_key(|j| f(self.index(*j)));
+
+            self.apply_index(&permutation);
+        }
+
+        /// `[slice::sort_by_cached_key()`](<https://doc.rust-lang.org/std/primitive.slice.html#method.sort_by_cached_key>).
+        fn sort_by_cached_key<F, K>(&mut self, mut f: F) where
+            F: FnMut(Self::Ref<'_>) -> K,
+            K: Ord,
+        {
+            let mut keys: alloc::vec::Vec<(K, usize)> = (0..self.len()).map(|i| (f(self.index(i)), i)).collect();
+            keys.sort_unstable();
+
+            let permutation = keys.into_iter().map(|(_, i)| i).collect::<alloc::vec::Vec<_>>();
+            self.apply_index(&permutation);
+        }
+
+        /// Sort according to the values in the single column returned by
+        /// `column`, only reading this column while sorting. This is a
+        /// stable sort.
+        fn sort_by_column<F, K>(&mut self, column: F) where
+            F: for<'b> FnOnce(&'b Self) -> &'b [K],
+            K: Ord,
+        {
+            let permutation = {
+                let column = column(self);
+                assert_eq!(column.len(), self.len(), "the column used to sort should have the same length as the slice");
+
+                let mut keys: alloc::vec::Vec<(&K, usize)> = column.iter().zip(0..).collect();
+                keys.sort_by(|a, b| a.0.cmp(b.0));
+                keys.into_iter().map(|(_, i)| i).collect::<alloc::vec::Vec<_>>()
+            };
+
+            self.apply_index(&permutation);
+        }
+
+        /// Parallel version of [`sort_by()`](Self::sort_by), using rayon to
+        /// sort the elements
+        #[cfg(feature = "rayon")]
+        fn par_sort_by<F>(&mut self, f: F) where
+            Self: Sync,
+            F: Fn(Self::Ref<'_>, Self::Ref<'_>) -> core::cmp::Ordering + Sync,
+        {
+            use rayon::slice::ParallelSliceMut;
+
+            let mut permutation: alloc::vec::Vec<usize> = (0..self.len()).collect();
+            permutation.par_sort_by(|j, k| f(self.index(*j), self.index(*k)));
+
+            self.apply_index(&permutation);
+        }
+
+        /// Parallel version of [`sort_by_key()`](Self::sort_by_key), using
+        /// rayon to sort the elements
+        #[cfg(feature = "rayon")]
+        fn par_sort_by_key<F, K>(&mut self, f: F) where
+            Self: Sync,
+            F: Fn(Self::Ref<'_>) -> K + Sync,
+            K: Ord,
+        {
+            use rayon::slice::ParallelSliceMut;
+
+            let mut permutation: alloc::vec::Vec<usize> = (0..self.len()).collect();
+            permutation.par_sort_by(|j, k| f(self.index(*j)).cmp(&f(self.index(*k))));
+
+            self.apply_index(&permutation);
+        }
+
+        /// Parallel version of [`sort_unstable_by()`](Self::sort_unstable_by),
+        /// using rayon to sort the elements
+        #[cfg(feature = "rayon")]
+        fn par_sort_unstable_by<F>(&mut self, f: F) where
+            Self: Sync,
+            F: Fn(Self::Ref<'_>, Self::Ref<'_>) -> core::cmp::Ordering + Sync,
+        {
+            use rayon::slice::ParallelSliceMut;
+
+            let mut permutation: alloc::vec::Vec<usize> = (0..self.len()).collect();
+            permutation.par_sort_unstable_by(|j, k| f(self.index(*j), self.index(*k)));
+
+            self.apply_index(&permutation);
+        }
+
+        /// Parallel version of [`sort_unstable_by_key()`](Self::sort_unstable_by_key),
+        /// using rayon to sort the elements
+        #[cfg(feature = "rayon")]
+        fn par_sort_unstable_by_key<F, K>(&mut self, f: F) where
+            Self: Sync,
+            F: Fn(Self::Ref<'_>) -> K + Sync,
+            K: Ord,
+        {
+            use rayon::slice::ParallelSliceMut;
+
+            let mut permutation: alloc::vec::Vec<usize> = (0..self.len()).collect();
+            permutation.par_sort_unstable_by(|j, k| f(self.index(*j)).cmp(&f(self.index(*k))));
+
+            self.apply_index(&permutation);
+        }
+
+        /// Analogous to [`slice::reverse()`](<https://doc.rust-lang.org/std/primitive.slice.html#method.reverse>).
+        fn reverse(&mut self);
+
+        /// Analogous to [`slice::rotate_left()`](<https://doc.rust-lang.org/std/primitive.slice.html#method.rotate_left>).
+        fn rotate_left(&mut self, mid: usize);
+
+        /// Analogous to [`slice::rotate_right()`](<https://doc.rust-lang.org/std/primitive.slice.html#method.rotate_right>).
+        fn rotate_right(&mut self, k: usize);
+
+        /// Analogous to [`slice::fill()`](<https://doc.rust-lang.org/std/primitive.slice.html#method.fill>).
+        fn fill(&mut self, value: T) where T: Clone;
+
+        /// Analogous to [`slice::swap_with_slice()`](<https://doc.rust-lang.org/std/primitive.slice.html#method.swap_with_slice>).
+        fn swap_with_slice<'c>(&'c mut self, other: Self::SliceMut<'c>);
+
         /// Analogous to [`slice::first_mut()`](<https://doc.rust-lang.org/std/primitive.slice.html#method.first_mut>).
         fn first_mut(&mut self) -> Option<Self::RefMut<'_>> {
             self.get_mut(0)
@@ -606,7 +1361,7 @@ mod generics {
         type Ref<'t> where Self: 't;
 
         /// The type representing immutable slices of elements
-        type Slice<'t>: SoASlice<T> + IntoSoAIter<'t, T> where Self: 't;
+        type Slice<'t>: SoASlice<T> + IntoSoAIter<'t, T, Ref<'t> = Self::Ref<'t>> where Self: 't;
 
         /// The type used for iteration over [`Self::Ref`]
         type Iter<'t>: Iterator<Item=Self::Ref<'t>> where Self: 't;
@@ -626,6 +1381,15 @@ mod generics {
         /// The mut pointer type interface
         type PtrMut;
 
+        /// The iterator returned by [`SoAVec::drain()`]
+        type Drain<'t>: Iterator<Item=T> + DoubleEndedIterator + ExactSizeIterator where Self: 't;
+
+        /// The iterator returned by [`SoAVec::splice()`]
+        type Splice<'t, I>: Iterator<Item=T> where Self: 't, I: Iterator<Item=T>;
+
+        /// The iterator returned by [`SoAVec::extract_if()`]
+        type ExtractIf<'t, F>: Iterator<Item=T> where Self: 't;
+
         /// Returns the number of elements in the arrays
         fn len(&self) -> usize;
 
@@ -658,6 +1422,55 @@ mod generics {
             self.get(self.len().saturating_sub(1))
         }
 
+        /// Analogous to [`slice::binary_search_by()`](https://doc.rust-lang.org/std/primitive.slice.html#method.binary_search_by)
+        fn binary_search_by<F>(&self, mut f: F) -> Result<usize, usize> where F: FnMut(Self::Ref<'_>) -> core::cmp::Ordering {
+            let mut left = 0;
+            let mut right = self.len();
+            while left < right {
+                let mid = left + (right - left) / 2;
+                match f(self.index(mid)) {
+                    core::cmp::Ordering::Less => left = mid + 1,
+                    core::cmp::Ordering::Greater => right = mid,
+                    core::cmp::Ordering::Equal => return Ok(mid),
+                }
+            }
+            Err(left)
+        }
+
+        /// Analogous to [`slice::binary_search_by_key()`](https://doc.rust-lang.org/std/primitive.slice.html#method.binary_search_by_key)
+        fn binary_search_by_key<B, F>(&self, b: &B, mut f: F) -> Result<usize, usize> where
+            F: FnMut(Self::Ref<'_>) -> B,
+            B: Ord,
+        {
+            self.binary_search_by(|item| f(item).cmp(b))
+        }
+
+        /// Analogous to [`slice::partition_point()`](https://doc.rust-lang.org/std/primitive.slice.html#method.partition_point)
+        fn partition_point<P>(&self, mut pred: P) -> usize where P: FnMut(Self::Ref<'_>) -> bool {
+            self.binary_search_by(|item| {
+                if pred(item) {
+                    core::cmp::Ordering::Less
+                } else {
+                    core::cmp::Ordering::Greater
+                }
+            }).unwrap_or_else(|i| i)
+        }
+
+        /// Analogous to [`slice::contains()`](https://doc.rust-lang.org/std/primitive.slice.html#method.contains)
+        fn contains<'c>(&'c self, x: Self::Ref<'c>) -> bool where Self::Ref<'c>: PartialEq {
+            self.iter().any(|item| item == x)
+        }
+
+        /// Analogous to [`slice::starts_with()`](https://doc.rust-lang.org/std/primitive.slice.html#method.starts_with)
+        fn starts_with<'c>(&'c self, needle: Self::Slice<'c>) -> bool where Self::Ref<'c>: PartialEq {
+            needle.len() <= self.len() && self.iter().zip(needle).all(|(a, b)| a == b)
+        }
+
+        /// Analogous to [`slice::ends_with()`](https://doc.rust-lang.org/std/primitive.slice.html#method.ends_with)
+        fn ends_with<'c>(&'c self, needle: Self::Slice<'c>) -> bool where Self::Ref<'c>: PartialEq {
+            needle.len() <= self.len() && self.iter().skip(self.len() - needle.len()).zip(needle).all(|(a, b)| a == b)
+        }
+
         /// Obtain a `const` pointer type for this data
         fn as_ptr(&self) -> Self::Ptr;
 
@@ -683,8 +1496,8 @@ mod generics {
         fn apply_index(&mut self, indices: &[usize]);
 
         /// `[slice::sort_by()`](<https://doc.rust-lang.org/std/primitive.slice.html#method.sort_by>).
-        fn sort_by<F>(&mut self, mut f: F) where F: FnMut(Self::Ref<'_>, Self::Ref<'_>) -> std::cmp::Ordering {
-            let mut permutation: Vec<usize> = (0..self.len()).collect();
+        fn sort_by<F>(&mut self, mut f: F) where F: FnMut(Self::Ref<'_>, Self::Ref<'_>) -> core::cmp::Ordering {
+            let mut permutation: alloc::vec::Vec<usize> = (0..self.len()).collect();
             permutation.sort_by(|j, k| f(self.index(*j), self.index(*k)));
 
             self.apply_index(&permutation);
@@ -695,12 +1508,139 @@ mod generics {
             F: FnMut(Self::Ref<'_>) -> K,
             K: Ord,
         {
-            let mut permutation: Vec<usize> = (0..self.len()).collect();
+            let mut permutation: alloc::vec::Vec<usize> = (0..self.len()).collect();
             permutation.sort_by_key(|j| f(self.index(*j)));
 
             self.apply_index(&permutation);
         }
 
+        /// `[slice::sort_unstable_by()`](<https://doc.rust-lang.org/std/primitive.slice.html#method.sort_unstable_by>).
+        fn sort_unstable_by<F>(&mut self, mut f: F) where F: FnMut(Self::Ref<'_>, Self::Ref<'_>) -> core::cmp::Ordering {
+            let mut permutation: alloc::vec::Vec<usize> = (0..self.len()).collect();
+            permutation.sort_unstable_by(|j, k| f(self.index(*j), self.index(*k)));
+
+            self.apply_index(&permutation);
+        }
+
+        /// `[slice::sort_unstable_by_key()`](<https://doc.rust-lang.org/std/primitive.slice.html#method.sort_unstable_by_key>).
+        fn sort_unstable_by_key<F, K>(&mut self, mut f: F) where
+            F: FnMut(Self::Ref<'_>) -> K,
+            K: Ord,
+        {
+            let mut permutation: alloc::vec::Vec<usize> = (0..self.len()).collect();
+            permutation.sort_unstable_by_key(|j| f(self.index(*j)));
+
+            self.apply_index(&permutation);
+        }
+
+        /// `[slice::sort_by_cached_key()`](<https://doc.rust-lang.org/std/primitive.slice.html#method.sort_by_cached_key>).
+        fn sort_by_cached_key<F, K>(&mut self, mut f: F) where
+            F: FnMut(Self::Ref<'_>) -> K,
+            K: Ord,
+        {
+            let mut keys: alloc::vec::Vec<(K, usize)> = (0..self.len()).map(|i| (f(self.index(i)), i)).collect();
+            keys.sort_unstable();
+
+            let permutation = keys.into_iter().map(|(_, i)| i).collect::<alloc::vec::Vec<_>>();
+            self.apply_index(&permutation);
+        }
+
+        /// Sort according to the values in the single column returned by
+        /// `column`, only reading this column while sorting. This is a
+        /// stable sort.
+        fn sort_by_column<F, K>(&mut self, column: F) where
+            F: for<'b> FnOnce(&'b Self) -> &'b [K],
+            K: Ord,
+        {
+            let permutation = {
+                let column = column(self);
+                assert_eq!(column.len(), self.len(), "the column used to sort should have the same length as the slice");
+
+                let mut keys: alloc::vec::Vec<(&K, usize)> = column.iter().zip(0..).collect();
+                keys.sort_by(|a, b| a.0.cmp(b.0));
+                keys.into_iter().map(|(_, i)| i).collect::<alloc::vec::Vec<_>>()
+            };
+
+            self.apply_index(&permutation);
+        }
+
+        /// Parallel version of [`sort_by()`](Self::sort_by), using rayon to
+        /// sort the elements
+        #[cfg(feature = "rayon")]
+        fn par_sort_by<F>(&mut self, f: F) where
+            Self: Sync,
+            F: Fn(Self::Ref<'_>, Self::Ref<'_>) -> core::cmp::Ordering + Sync,
+        {
+            use rayon::slice::ParallelSliceMut;
+
+            let mut permutation: alloc::vec::Vec<usize> = (0..self.len()).collect();
+            permutation.par_sort_by(|j, k| f(self.index(*j), self.index(*k)));
+
+            self.apply_index(&permutation);
+        }
+
+        /// Parallel version of [`sort_by_key()`](Self::sort_by_key), using
+        /// rayon to sort the elements
+        #[cfg(feature = "rayon")]
+        fn par_sort_by_key<F, K>(&mut self, f: F) where
+            Self: Sync,
+            F: Fn(Self::Ref<'_>) -> K + Sync,
+            K: Ord,
+        {
+            use rayon::slice::ParallelSliceMut;
+
+            let mut permutation: alloc::vec::Vec<usize> = (0..self.len()).collect();
+            permutation.par_sort_by(|j, k| f(self.index(*j)).cmp(&f(self.index(*k))));
+
+            self.apply_index(&permutation);
+        }
+
+        /// Parallel version of [`sort_unstable_by()`](Self::sort_unstable_by),
+        /// using rayon to sort the elements
+        #[cfg(feature = "rayon")]
+        fn par_sort_unstable_by<F>(&mut self, f: F) where
+            Self: Sync,
+            F: Fn(Self::Ref<'_>, Self::Ref<'_>) -> core::cmp::Ordering + Sync,
+        {
+            use rayon::slice::ParallelSliceMut;
+
+            let mut permutation: alloc::vec::Vec<usize> = (0..self.len()).collect();
+            permutation.par_sort_unstable_by(|j, k| f(self.index(*j), self.index(*k)));
+
+            self.apply_index(&permutation);
+        }
+
+        /// Parallel version of [`sort_unstable_by_key()`](Self::sort_unstable_by_key),
+        /// using rayon to sort the elements
+        #[cfg(feature = "rayon")]
+        fn par_sort_unstable_by_key<F, K>(&mut self, f: F) where
+            Self: Sync,
+            F: Fn(Self::Ref<'_>) -> K + Sync,
+            K: Ord,
+        {
+            use rayon::slice::ParallelSliceMut;
+
+            let mut permutation: alloc::vec::Vec<usize> = (0..self.len()).collect();
+            permutation.par_sort_unstable_by(|j, k| f(self.index(*j)).cmp(&f(self.index(*k))));
+
+            self.apply_index(&permutation);
+        }
+
+        /// Analogous to [`slice::reverse()`](<https://doc.rust-lang.org/std/primitive.slice.html#method.reverse>).
+        fn reverse(&mut self);
+
+        /// Analogous to [`slice::rotate_left()`](<https://doc.rust-lang.org/std/primitive.slice.html#method.rotate_left>).
+        fn rotate_left(&mut self, mid: usize);
+
+        /// Analogous to [`slice::rotate_right()`](<https://doc.rust-lang.org/std/primitive.slice.html#method.rotate_right>).
+        fn rotate_right(&mut self, k: usize);
+
+        /// Analogous to [`slice::fill()`](<https://doc.rust-lang.org/std/primitive.slice.html#method.fill>).
+        fn fill(&mut self, value: T) where T: Clone;
+
+        /// Analogous to [`slice::swap_with_slice()`](<https://doc.rust-lang.org/std/primitive.slice.html#method.swap_with_slice>).
+        fn swap_with_slice<'c>(&'c mut self, other: Self::SliceMut<'c>);
+
         /// Analogous to [`slice::first_mut()`](<https://doc.rust-lang.org/std/primitive.slice.html#method.first_mut>)
         fn first_mut(&mut self) -> Option<Self::RefMut<'_>> {
             self.get_mut(0)
@@ -761,6 +1701,30 @@ mod generics {
 
         /// Analogous to [`Vec::split_off`]
         fn split_off(&mut self, at: usize) -> Self;
+
+        /// Analogous to [`Vec::drain`]
+        fn drain<R>(&mut self, range: R) -> Self::Drain<'_> where R: core::ops::RangeBounds<usize>;
+
+        /// Analogous to [`Vec::splice`]
+        fn splice<R, I>(&mut self, range: R, replace_with: I) -> Self::Splice<'_, I::IntoIter> where
+            R: core::ops::RangeBounds<usize>,
+            I: IntoIterator<Item=T>;
+
+        /// Analogous to `Vec::extract_if`
+        fn extract_if<F, R>(&mut self, range: R, filter: F) -> Self::ExtractIf<'_, F> where
+            F: FnMut(Self::RefMut<'_>) -> bool,
+            R: core::ops::RangeBounds<usize>;
+
+        /// Analogous to [`Vec::dedup_by`]
+        fn dedup_by<F>(&mut self, same_bucket: F) where F: FnMut(Self::RefMut<'_>, Self::RefMut<'_>) -> bool;
+
+        /// Analogous to [`Vec::dedup_by_key`]
+        fn dedup_by_key<F, K>(&mut self, mut key: F) where
+            F: FnMut(Self::RefMut<'_>) -> K,
+            K: PartialEq,
+        {
+            self.dedup_by(|a, b| key(a) == key(b));
+        }
     }
 
     /// A trait to implement `Clone`-dependent behavior to convert a non-owning SoA type into an
@@ -802,7 +1766,7 @@ macro_rules! soa_zip_impl {
     // The main code is emmited here: we create an iterator, zip it and then
     // map the zipped iterator to flatten it
     (@last , $first: expr, $($tail: expr,)*) => {
-        ::std::iter::IntoIterator::into_iter($first)
+        ::core::iter::IntoIterator::into_iter($first)
             $(
                 .zip($tail)
             )*
@@ -811,6 +1775,21 @@ macro_rules! soa_zip_impl {
             )
     };
 
+    // Same as @last, creating a parallel iterator instead
+    (@par_last , $first: expr, $($tail: expr,)*) => {
+        $crate::rayon::iter::ParallelIterator::map(
+            $crate::soa_zip_impl!(@par_zip $crate::rayon::iter::IntoParallelIterator::into_par_iter($first); $($tail,)*),
+            $crate::soa_zip_impl!(@flatten a => (a) $( , $tail )*)
+        )
+    };
+    // Zip parallel iterators without requiring the rayon traits to be in scope
+    (@par_zip $iter: expr; ) => {
+        $iter
+    };
+    (@par_zip $iter: expr; $next: expr, $($tail: expr,)*) => {
+        $crate::soa_zip_impl!(@par_zip $crate::rayon::iter::IndexedParallelIterator::zip($iter, $next); $($tail,)*)
+    };
+
     // Eat the last `mut $field` and then emit code
     (@munch $self: expr, {mut $field: ident} -> [$($output: tt)*] $($ext: expr ,)*) => {
         $crate::soa_zip_impl!(@last $($output)*, $self.$field.iter_mut(), $($ext, )*)
@@ -828,4 +1807,18 @@ macro_rules! soa_zip_impl {
     (@munch $self: expr, {$field: ident, $($tail: tt)*} -> [$($output: tt)*] $($ext: expr ,)*) => {
         $crate::soa_zip_impl!(@munch $self, {$($tail)*} -> [$($output)*, $self.$field.iter()] $($ext, )*)
     };
+
+    // Same as @munch, using the parallel iterators over the fields
+    (@par_munch $self: expr, {mut $field: ident} -> [$($output: tt)*] $($ext: expr ,)*) => {
+        $crate::soa_zip_impl!(@par_last $($output)*, &mut $self.$field[..], $($ext, )*)
+    };
+    (@par_munch $self: expr, {$field: ident} -> [$($output: tt)*] $($ext: expr ,)*) => {
+        $crate::soa_zip_impl!(@par_last $($output)*, &$self.$field[..], $($ext, )*)
+    };
+    (@par_munch $self: expr, {mut $field: ident, $($tail: tt)*} -> [$($output: tt)*] $($ext: expr ,)*) => {
+        $crate::soa_zip_impl!(@par_munch $self, {$($tail)*} -> [$($output)*, &mut $self.$field[..]] $($ext, )*)
+    };
+    (@par_munch $self: expr, {$field: ident, $($tail: tt)*} -> [$($output: tt)*] $($ext: expr ,)*) => {
+        $crate::soa_zip_impl!(@par_munch $self, {$($tail)*} -> [$($output)*, &$self.$field[..]] $($ext, )*)
+    };
 }
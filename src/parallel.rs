@@ -0,0 +1,230 @@
+//! Parallel iteration over struct of arrays, using [rayon].
+//!
+//! This module is only available with the `rayon` feature. The
+//! `par_iter()`, `par_iter_mut()`, `par_chunks()` and `par_chunks_mut()`
+//! methods generated for `CheeseVec`, `CheeseSlice` and `CheeseSliceMut`
+//! return the iterators defined here.
+//!
+//! [rayon]: https://docs.rs/rayon/
+
+use rayon::iter::plumbing::{bridge, Consumer, Producer, ProducerCallback, UnindexedConsumer};
+use rayon::iter::{IndexedParallelIterator, ParallelIterator};
+
+/// Slices of struct of arrays which can be split to be processed in
+/// parallel.
+///
+/// This trait is automatically implemented for the `CheeseSlice` and
+/// `CheeseSliceMut` types generated by [`StructOfArray`](crate::StructOfArray).
+pub trait SoAParallelSlice: Sized {
+    /// The type of the elements in this slice, i.e. `CheeseRef` or
+    /// `CheeseRefMut`
+    type Item;
+    /// The sequential iterator over the elements of this slice
+    type Iter: Iterator<Item = Self::Item> + DoubleEndedIterator + ExactSizeIterator;
+
+    /// Get the number of elements in this slice
+    fn len(&self) -> usize;
+
+    /// Check if this slice is empty
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Divide this slice in two at `mid`, with the same semantics as
+    /// [`slice::split_at()`]
+    fn split_at(self, mid: usize) -> (Self, Self);
+
+    /// Get a sequential iterator over the elements of this slice
+    fn into_iter(self) -> Self::Iter;
+}
+
+/// Parallel iterator over the elements of a struct of arrays slice, yielding
+/// `CheeseRef` or `CheeseRefMut`.
+#[derive(Debug, Clone)]
+pub struct ParIter<S> {
+    slice: S,
+}
+
+impl<S: SoAParallelSlice> ParIter<S> {
+    /// Create a parallel iterator over the elements of `slice`
+    pub fn new(slice: S) -> ParIter<S> {
+        ParIter { slice }
+    }
+}
+
+impl<S> ParallelIterator for ParIter<S> where S: SoAParallelSlice + Send, S::Item: Send {
+    type Item = S::Item;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result where C: UnindexedConsumer<Self::Item> {
+        bridge(self, consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        Some(self.slice.len())
+    }
+}
+
+impl<S> IndexedParallelIterator for ParIter<S> where S: SoAParallelSlice + Send, S::Item: Send {
+    fn drive<C>(self, consumer: C) -> C::Result where C: Consumer<Self::Item> {
+        bridge(self, consumer)
+    }
+
+    fn len(&self) -> usize {
+        self.slice.len()
+    }
+
+    fn with_producer<CB>(self, callback: CB) -> CB::Output where CB: ProducerCallback<Self::Item> {
+        callback.callback(IterProducer { slice: self.slice })
+    }
+}
+
+struct IterProducer<S> {
+    slice: S,
+}
+
+impl<S> Producer for IterProducer<S> where S: SoAParallelSlice + Send {
+    type Item = S::Item;
+    type IntoIter = S::Iter;
+
+    fn into_iter(self) -> S::Iter {
+        self.slice.into_iter()
+    }
+
+    fn split_at(self, index: usize) -> (Self, Self) {
+        let (left, right) = self.slice.split_at(index);
+        (IterProducer { slice: left }, IterProducer { slice: right })
+    }
+}
+
+/// Parallel iterator over chunks of a struct of arrays slice, yielding
+/// `CheeseSlice` or `CheeseSliceMut`. The last chunk contains less than
+/// `chunk_size` elements if `chunk_size` does not divide the length of the
+/// slice.
+#[derive(Debug, Clone)]
+pub struct ParChunks<S> {
+    slice: S,
+    chunk_size: usize,
+}
+
+impl<S: SoAParallelSlice> ParChunks<S> {
+    /// Create a parallel iterator over chunks of `slice` with `chunk_size`
+    /// elements.
+    ///
+    /// # Panics
+    ///
+    /// If `chunk_size` is 0.
+    pub fn new(slice: S, chunk_size: usize) -> ParChunks<S> {
+        assert!(chunk_size != 0, "chunk size must be non-zero");
+        ParChunks { slice, chunk_size }
+    }
+}
+
+impl<S> ParallelIterator for ParChunks<S> where S: SoAParallelSlice + Send {
+    type Item = S;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result where C: UnindexedConsumer<Self::Item> {
+        bridge(self, consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        Some(IndexedParallelIterator::len(self))
+    }
+}
+
+impl<S> IndexedParallelIterator for ParChunks<S> where S: SoAParallelSlice + Send {
+    fn drive<C>(self, consumer: C) -> C::Result where C: Consumer<Self::Item> {
+        bridge(self, consumer)
+    }
+
+    fn len(&self) -> usize {
+        chunks_count(self.slice.len(), self.chunk_size)
+    }
+
+    fn with_producer<CB>(self, callback: CB) -> CB::Output where CB: ProducerCallback<Self::Item> {
+        callback.callback(ChunksProducer { slice: Some(self.slice), chunk_size: self.chunk_size })
+    }
+}
+
+fn chunks_count(len: usize, chunk_size: usize) -> usize {
+    if len == 0 {
+        0
+    } else {
+        (len - 1) / chunk_size + 1
+    }
+}
+
+/// Producer for `ParChunks`, also used as the corresponding sequential
+/// iterator. The slice is only `None` while splitting it.
+struct ChunksProducer<S> {
+    slice: Option<S>,
+    chunk_size: usize,
+}
+
+impl<S: SoAParallelSlice> ChunksProducer<S> {
+    /// Get the number of elements (not chunks) remaining in this producer
+    fn elements(&self) -> usize {
+        self.slice.as_ref().map_or(0, SoAParallelSlice::len)
+    }
+
+    /// Split the slice at `mid`, keeping the first part in `self` and
+    /// returning the second part
+    fn split_off(&mut self, mid: usize) -> Option<S> {
+        let slice = self.slice.take()?;
+        let (left, right) = slice.split_at(mid);
+        self.slice = Some(left);
+        Some(right)
+    }
+}
+
+impl<S> Producer for ChunksProducer<S> where S: SoAParallelSlice + Send {
+    type Item = S;
+    type IntoIter = Self;
+
+    fn into_iter(self) -> Self {
+        self
+    }
+
+    fn split_at(mut self, index: usize) -> (Self, Self) {
+        let mid = usize::min(index * self.chunk_size, self.elements());
+        let right = self.split_off(mid);
+        let chunk_size = self.chunk_size;
+        (self, ChunksProducer { slice: right, chunk_size })
+    }
+}
+
+impl<S: SoAParallelSlice> Iterator for ChunksProducer<S> {
+    type Item = S;
+
+    fn next(&mut self) -> Option<S> {
+        let len = self.elements();
+        if len == 0 {
+            return None;
+        }
+
+        let mid = usize::min(self.chunk_size, len);
+        let rest = self.split_off(mid);
+        core::mem::replace(&mut self.slice, rest)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = chunks_count(self.elements(), self.chunk_size);
+        (len, Some(len))
+    }
+}
+
+impl<S: SoAParallelSlice> DoubleEndedIterator for ChunksProducer<S> {
+    fn next_back(&mut self) -> Option<S> {
+        let len = self.elements();
+        if len == 0 {
+            return None;
+        }
+
+        let last = match len % self.chunk_size {
+            0 => self.chunk_size,
+            remainder => remainder,
+        };
+        self.split_off(len - last)
+    }
+}
+
+impl<S: SoAParallelSlice> ExactSizeIterator for ChunksProducer<S> {}
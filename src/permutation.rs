@@ -0,0 +1,50 @@
+use alloc::vec::Vec;
+
+/// A permutation of the elements of a slice, used to apply the same
+/// reordering to all the fields of a struct of arrays when sorting.
+pub struct Permutation {
+    indices: Vec<usize>,
+    visited: Vec<bool>,
+}
+
+impl Permutation {
+    /// Create a permutation from the result of sorting the indices of a
+    /// slice: applying this permutation moves the element at `indices[i]` to
+    /// position `i`.
+    pub fn from_sorted_indices(indices: &[usize]) -> Permutation {
+        debug_assert!({
+            let mut seen = alloc::vec![false; indices.len()];
+            indices.iter().all(|&i| i < seen.len() && !core::mem::replace(&mut seen[i], true))
+        });
+
+        Permutation {
+            indices: indices.to_vec(),
+            visited: alloc::vec![false; indices.len()],
+        }
+    }
+
+    /// Reorder `slice` in place according to this permutation.
+    ///
+    /// # Panics
+    ///
+    /// If the slice and the permutation don't have the same length.
+    pub fn apply_slice_in_place<T>(&mut self, slice: &mut [T]) {
+        assert_eq!(slice.len(), self.indices.len(), "permutation and slice have different lengths");
+        self.visited.iter_mut().for_each(|visited| *visited = false);
+
+        // follow each cycle of the permutation, pulling the element that
+        // should end up at position `current` from position `next`
+        for start in 0..self.indices.len() {
+            let mut current = start;
+            while !self.visited[current] {
+                self.visited[current] = true;
+                let next = self.indices[current];
+                if next == start {
+                    break;
+                }
+                slice.swap(current, next);
+                current = next;
+            }
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use core::alloc::Layout;
+
+/// Compute the layout of a single allocation containing `capacity` elements
+/// for each of the `fields`, stored one after the other. The offset of each
+/// field inside the allocation is written in `offsets`, the first field being
+/// always at offset 0. This returns `None` if the allocation would be too
+/// large.
+pub fn layout(fields: &[Layout], capacity: usize, offsets: &mut [usize]) -> Option<Layout> {
+    debug_assert_eq!(fields.len(), offsets.len());
+    let mut layout = Layout::from_size_align(0, 1).ok()?;
+    for (field, offset) in fields.iter().zip(offsets.iter_mut()) {
+        let size = field.size().checked_mul(capacity)?;
+        let array = Layout::from_size_align(size, field.align()).ok()?;
+        let (extended, field_offset) = layout.extend(array).ok()?;
+        layout = extended;
+        *offset = field_offset;
+    }
+    Some(layout)
+}
+
+/// Pointers to the start of each field in the allocation of a single buffer
+/// vector.
+///
+/// Raw pointers are neither `Send` nor `Sync`, but the vector owns the data
+/// they point to, like `Vec<T>`. This wrapper is always `Send` and `Sync`,
+/// and the vector uses a `PhantomData` of the fields types to only implement
+/// these traits when all the fields do.
+#[derive(Clone, Copy)]
+pub struct Pointers<T>(pub T);
+
+unsafe impl<T> Send for Pointers<T> {}
+unsafe impl<T> Sync for Pointers<T> {}
@@ -0,0 +1,167 @@
+#![allow(clippy::float_cmp)]
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use soa_derive::{SoASlice, StructOfArray};
+
+#[derive(Debug, Clone, PartialEq, StructOfArray)]
+#[soa_derive(Debug, Clone, PartialEq)]
+pub struct Particle {
+    pub name: String,
+    pub mass: f64,
+}
+
+impl Particle {
+    fn new(name: &str, mass: f64) -> Particle {
+        Particle { name: name.into(), mass }
+    }
+}
+
+#[test]
+fn push_pop() {
+    let mut particles = ParticleArrayVec::<3>::new();
+    assert_eq!(particles.capacity(), 3);
+    assert!(particles.is_empty());
+
+    assert!(particles.push(Particle::new("Na", 22.9)).is_ok());
+    assert!(particles.push(Particle::new("Cl", 35.4)).is_ok());
+    assert!(particles.push(Particle::new("Zn", 65.4)).is_ok());
+    assert!(particles.is_full());
+
+    let rejected = particles.push(Particle::new("H", 1.0));
+    assert_eq!(rejected, Err(Particle::new("H", 1.0)));
+    assert_eq!(particles.len(), 3);
+
+    assert_eq!(particles.pop(), Some(Particle::new("Zn", 65.4)));
+    assert_eq!(particles.len(), 2);
+    assert_eq!(particles.as_slice().name, ["Na", "Cl"]);
+
+    particles.clear();
+    assert_eq!(particles.pop(), None);
+}
+
+#[test]
+fn insert_remove() {
+    let mut particles = ParticleArrayVec::<4>::new();
+    particles.push(Particle::new("A", 1.0)).unwrap();
+    particles.push(Particle::new("B", 2.0)).unwrap();
+    particles.insert(0, Particle::new("C", 3.0)).unwrap();
+    particles.insert(3, Particle::new("D", 4.0)).unwrap();
+    assert_eq!(particles.as_slice().name, ["C", "A", "B", "D"]);
+
+    let rejected = particles.insert(1, Particle::new("E", 5.0));
+    assert_eq!(rejected.unwrap_err().name, "E");
+
+    assert_eq!(particles.remove(1), Particle::new("A", 1.0));
+    assert_eq!(particles.as_slice().mass, [3.0, 2.0, 4.0]);
+
+    assert_eq!(particles.swap_remove(0), Particle::new("C", 3.0));
+    assert_eq!(particles.as_slice().name, ["D", "B"]);
+
+    particles.truncate(1);
+    assert_eq!(particles.as_slice().name, ["D"]);
+}
+
+#[test]
+fn slices_and_iter() {
+    let mut particles = ParticleArrayVec::<8>::default();
+    for i in 0..5 {
+        particles.push(Particle::new(&i.to_string(), f64::from(i))).unwrap();
+    }
+
+    for particle in particles.iter_mut() {
+        *particle.mass *= 2.0;
+    }
+    assert_eq!(particles.iter().map(|p| *p.mass).sum::<f64>(), 20.0);
+    assert_eq!(particles.index(2).name, "2");
+    assert_eq!(*particles.get(4).unwrap().mass, 8.0);
+    assert!(particles.get(5).is_none());
+    assert_eq!(particles.index(1..3).mass, [2.0, 4.0]);
+
+    *particles.index_mut(0).mass = -1.0;
+    let mut slice = particles.as_mut_slice();
+    *slice.get_mut(1).unwrap().mass = -2.0;
+    assert_eq!(particles.as_slice().mass, [-1.0, -2.0, 4.0, 6.0, 8.0]);
+
+    let names = (&particles).into_iter().map(|p| p.name.as_str()).collect::<Vec<_>>();
+    assert_eq!(names, ["0", "1", "2", "3", "4"]);
+
+    assert_eq!(particles.as_slice().to_vec().len(), 5);
+}
+
+fn count_elements<T: StructOfArray, S: SoASlice<T>>(slice: &S) -> usize {
+    assert_eq!(slice.len(), slice.iter().count());
+    slice.len()
+}
+
+#[test]
+fn soa_slice_interop() {
+    let mut particles = ParticleArrayVec::<2>::new();
+    particles.push(Particle::new("Na", 1.0)).unwrap();
+    particles.push(Particle::new("Cl", 2.0)).unwrap();
+
+    let slice = particles.as_slice();
+    assert_eq!(count_elements(&slice), 2);
+    assert_eq!(SoASlice::index(&slice, 1).name, "Cl");
+}
+
+#[derive(Debug, Clone, PartialEq, StructOfArray)]
+pub struct Point {
+    pub x: f32,
+    pub y: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, StructOfArray)]
+pub struct Body {
+    pub mass: f32,
+    #[nested_soa]
+    pub position: Point,
+}
+
+#[test]
+fn nested() {
+    let mut bodies = BodyArrayVec::<2>::new();
+    bodies.push(Body { mass: 1.0, position: Point { x: 0.0, y: 1.0 } }).unwrap();
+    bodies.insert(0, Body { mass: 2.0, position: Point { x: 2.0, y: 3.0 } }).unwrap();
+    assert!(bodies.push(Body { mass: 3.0, position: Point { x: 4.0, y: 5.0 } }).is_err());
+
+    assert_eq!(bodies.as_slice().position.x, [2.0, 0.0]);
+    assert_eq!(bodies.index(1).to_owned(), Body { mass: 1.0, position: Point { x: 0.0, y: 1.0 } });
+    assert_eq!(bodies.remove(0).position, Point { x: 2.0, y: 3.0 });
+    assert_eq!(bodies.pop().unwrap().mass, 1.0);
+    assert!(bodies.is_empty());
+}
+
+#[derive(StructOfArray)]
+pub struct Tracked {
+    pub counter: Rc<Cell<usize>>,
+    pub value: u8,
+}
+
+#[test]
+fn drop_elements() {
+    let counter = Rc::new(Cell::new(0));
+    {
+        let mut vec = TrackedArrayVec::<16>::new();
+        for value in 0..8 {
+            vec.push(Tracked { counter: counter.clone(), value }).ok().unwrap();
+        }
+        assert_eq!(Rc::strong_count(&counter), 9);
+
+        vec.truncate(5);
+        assert_eq!(Rc::strong_count(&counter), 6);
+    }
+    assert_eq!(Rc::strong_count(&counter), 1);
+}
+
+#[derive(Debug, Clone, PartialEq, StructOfArray)]
+pub struct Labeled<'l, T: Copy, const N: usize>(pub &'l str, pub [T; N]);
+
+#[test]
+fn generic() {
+    const EMPTY: LabeledArrayVec<'static, u8, 2, 4> = LabeledArrayVec::new();
+    let mut vec = EMPTY;
+    vec.push(Labeled("a", [1, 2])).unwrap();
+    assert_eq!(vec.as_slice().1, [[1, 2]]);
+}
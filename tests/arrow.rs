@@ -0,0 +1,146 @@
+#![cfg(feature = "arrow")]
+
+use std::sync::Arc;
+
+use soa_derive::arrow_array::{Array, ArrayRef, Float64Array, Int32Array, RecordBatch, StringArray, StructArray};
+use soa_derive::arrow_schema::{DataType, Field, Schema};
+use soa_derive::StructOfArray;
+
+#[derive(Debug, Clone, PartialEq, StructOfArray)]
+#[soa_derive(Debug, Clone, PartialEq)]
+pub struct Particle {
+    pub name: String,
+    pub mass: f64,
+    pub charge: i32,
+    pub active: bool,
+}
+
+fn particles() -> ParticleVec {
+    let mut particles = ParticleVec::new();
+    particles.push(Particle { name: "Na".into(), mass: 23.0, charge: 1, active: true });
+    particles.push(Particle { name: "Cl".into(), mass: 35.5, charge: -1, active: false });
+    particles
+}
+
+#[test]
+fn schema() {
+    let schema = ParticleVec::arrow_schema();
+    assert_eq!(schema, Schema::new(vec![
+        Field::new("name", DataType::Utf8, false),
+        Field::new("mass", DataType::Float64, false),
+        Field::new("charge", DataType::Int32, false),
+        Field::new("active", DataType::Boolean, false),
+    ]));
+}
+
+#[test]
+fn record_batch() {
+    let particles = particles();
+    let batch = particles.to_record_batch().unwrap();
+    assert_eq!(batch.num_rows(), 2);
+    assert_eq!(batch.schema().as_ref(), &ParticleVec::arrow_schema());
+
+    let mass = batch.column(1).as_any().downcast_ref::<Float64Array>().unwrap();
+    assert_eq!(mass.values().as_ref(), [23.0, 35.5]);
+    let name = batch.column(0).as_any().downcast_ref::<StringArray>().unwrap();
+    assert_eq!(name.value(1), "Cl");
+
+    assert_eq!(ParticleVec::try_from_record_batch(&batch).unwrap(), particles);
+    assert_eq!(particles.clone().into_record_batch().unwrap(), batch);
+}
+
+#[test]
+fn zero_copy() {
+    let particles = particles();
+    let mass_ptr = particles.mass.as_ptr();
+    let batch = particles.into_record_batch().unwrap();
+
+    let mass = batch.column(1).as_any().downcast_ref::<Float64Array>().unwrap();
+    assert_eq!(mass.values().as_ptr(), mass_ptr);
+}
+
+#[test]
+fn invalid_record_batch() {
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("name", DataType::Utf8, false),
+        Field::new("mass", DataType::Int32, false),
+    ]));
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(StringArray::from(vec!["Na"])),
+        Arc::new(Int32Array::from(vec![23])),
+    ];
+    let batch = RecordBatch::try_new(schema, columns).unwrap();
+    let error = ParticleVec::try_from_record_batch(&batch).unwrap_err();
+    assert_eq!(error.to_string(), "Schema error: invalid column `mass`: expected an array of Float64, got an array of Int32");
+
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("name", DataType::Utf8, false),
+        Field::new("mass", DataType::Float64, true),
+        Field::new("charge", DataType::Int32, false),
+    ]));
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(StringArray::from(vec!["Na"])),
+        Arc::new(Float64Array::from(vec![None])),
+        Arc::new(Int32Array::from(vec![1])),
+    ];
+    let batch = RecordBatch::try_new(schema, columns).unwrap();
+    let error = ParticleVec::try_from_record_batch(&batch).unwrap_err();
+    assert_eq!(error.to_string(), "Invalid argument error: invalid column `mass`: array contains 1 null values, which are not supported");
+}
+
+#[derive(Debug, Clone, PartialEq, StructOfArray)]
+#[soa_derive(Debug, PartialEq)]
+pub struct Point {
+    pub x: f32,
+    pub y: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, StructOfArray)]
+#[soa_derive(Debug, PartialEq)]
+pub struct Body<T> {
+    pub id: T,
+    #[nested_soa]
+    pub position: Point,
+}
+
+#[test]
+fn nested_and_generic() {
+    let mut bodies = BodyVec::<u64>::new();
+    bodies.push(Body { id: 3, position: Point { x: 1.0, y: 2.0 } });
+    bodies.push(Body { id: 4, position: Point { x: 3.0, y: 4.0 } });
+
+    let schema = BodyVec::<u64>::arrow_schema();
+    assert_eq!(schema.field(0).data_type(), &DataType::UInt64);
+    assert_eq!(schema.field(1).data_type(), &DataType::Struct(PointVec::arrow_schema().fields().clone()));
+
+    let batch = bodies.to_record_batch().unwrap();
+    let position = batch.column(1).as_any().downcast_ref::<StructArray>().unwrap();
+    assert_eq!(position.len(), 2);
+    assert_eq!(position.column_names(), ["x", "y"]);
+
+    assert_eq!(BodyVec::<u64>::try_from_record_batch(&batch).unwrap(), bodies);
+
+    // extra columns are ignored
+    let mut columns = batch.columns().to_vec();
+    columns.push(Arc::new(Int32Array::from(vec![0, 0])));
+    let mut fields = schema.fields().to_vec();
+    fields.push(Arc::new(Field::new("extra", DataType::Int32, false)));
+    let batch = RecordBatch::try_new(Arc::new(Schema::new(fields)), columns).unwrap();
+    assert_eq!(BodyVec::<u64>::try_from_record_batch(&batch).unwrap(), bodies);
+
+    let error = ParticleVec::try_from_record_batch(&batch).unwrap_err();
+    assert_eq!(error.to_string(), "Schema error: missing column `name`");
+}
+
+#[derive(Debug, Clone, PartialEq, StructOfArray)]
+#[soa_derive(Debug, PartialEq)]
+pub struct Pair(i8, u16);
+
+#[test]
+fn tuple_struct() {
+    let mut pairs = PairVec::new();
+    pairs.push(Pair(-1, 2));
+    let batch = pairs.to_record_batch().unwrap();
+    assert_eq!(batch.schema().field(1).name(), "1");
+    assert_eq!(PairVec::try_from_record_batch(&batch).unwrap(), pairs);
+}
@@ -0,0 +1,157 @@
+#![allow(clippy::float_cmp)]
+
+mod particles;
+use self::particles::*;
+
+use soa_derive::StructOfArray;
+
+fn particles(count: usize) -> ParticleVec {
+    let mut particles = ParticleVec::new();
+    for i in 0..count {
+        particles.push(Particle::new(format!("P{}", i), i as f64));
+    }
+    particles
+}
+
+#[test]
+fn chunks_exact() {
+    let particles = particles(7);
+
+    let mut chunks = particles.chunks_exact(3);
+    assert_eq!(chunks.len(), 2);
+    assert_eq!(chunks.remainder().name, ["P6"]);
+
+    let chunk = chunks.next().unwrap();
+    assert_eq!(chunk.len(), 3);
+    assert_eq!(chunk.mass, [0.0, 1.0, 2.0]);
+
+    let chunk = chunks.next_back().unwrap();
+    assert_eq!(chunk.mass, [3.0, 4.0, 5.0]);
+    assert!(chunks.next().is_none());
+
+    let slice = particles.slice(1..5);
+    let names = slice.chunks_exact(2).map(|chunk| chunk.name.concat()).collect::<Vec<_>>();
+    assert_eq!(names, ["P1P2", "P3P4"]);
+    assert!(slice.chunks_exact(2).remainder().is_empty());
+
+    let chunks = slice.chunks_exact(5);
+    assert_eq!(chunks.len(), 0);
+    assert_eq!(chunks.remainder().len(), 4);
+}
+
+#[test]
+fn chunks_exact_mut() {
+    let mut particles = particles(5);
+
+    for chunk in particles.chunks_exact_mut(2) {
+        chunk.mass[1] = -chunk.mass[0];
+    }
+    assert_eq!(particles.mass, [0.0, -0.0, 2.0, -2.0, 4.0]);
+
+    let mut slice = particles.as_mut_slice();
+    let mut chunks = slice.chunks_exact_mut(2);
+    let last = chunks.next_back().unwrap();
+    last.name[0] = String::from("Last");
+    let remainder = chunks.into_remainder();
+    remainder.name[0] = String::from("Remainder");
+
+    assert_eq!(particles.name, ["P0", "P1", "Last", "P3", "Remainder"]);
+}
+
+#[test]
+#[should_panic(expected = "chunk size must be non-zero")]
+fn chunks_exact_zero() {
+    let particles = particles(5);
+    let _ = particles.chunks_exact(0);
+}
+
+#[test]
+fn array_chunks() {
+    let particles = particles(10);
+
+    let mut chunks = particles.array_chunks::<4>();
+    assert_eq!(chunks.len(), 2);
+    assert_eq!(chunks.remainder().mass, [8.0, 9.0]);
+
+    let chunk = chunks.next().unwrap();
+    let mass: &[f64; 4] = chunk.mass;
+    assert_eq!(mass, &[0.0, 1.0, 2.0, 3.0]);
+    assert_eq!(chunk.name[3], "P3");
+
+    let chunk = chunks.next_back().unwrap();
+    assert_eq!(chunk.mass, &[4.0, 5.0, 6.0, 7.0]);
+    assert!(chunks.next().is_none());
+
+    let total = particles.as_slice().array_chunks::<5>()
+        .map(|chunk| chunk.mass.iter().sum::<f64>())
+        .collect::<Vec<_>>();
+    assert_eq!(total, [10.0, 35.0]);
+}
+
+#[test]
+fn array_chunks_mut() {
+    let mut particles = particles(7);
+
+    for chunk in particles.array_chunks_mut::<2>() {
+        let mass: &mut [f64; 2] = chunk.mass;
+        for value in mass {
+            *value *= 10.0;
+        }
+    }
+    assert_eq!(particles.mass, [0.0, 10.0, 20.0, 30.0, 40.0, 50.0, 6.0]);
+
+    let mut slice = particles.as_mut_slice();
+    let remainder = slice.array_chunks_mut::<3>().into_remainder();
+    remainder.mass[0] = 0.0;
+    assert_eq!(particles.mass[6], 0.0);
+}
+
+#[derive(Debug, Clone, PartialEq, StructOfArray)]
+pub struct Point {
+    pub x: f32,
+    pub y: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, StructOfArray)]
+pub struct Body {
+    #[nested_soa]
+    pub position: Point,
+    #[soa(explode)]
+    pub velocity: [f32; 2],
+    #[soa(explode)]
+    pub state: (u8, bool),
+}
+
+#[test]
+fn nested() {
+    let mut bodies = BodyVec::new();
+    for i in 0..6 {
+        let i = i as f32;
+        bodies.push(Body {
+            position: Point { x: i, y: -i },
+            velocity: [1.0, 2.0],
+            state: (0, true),
+        });
+    }
+
+    for chunk in bodies.array_chunks_mut::<3>() {
+        for i in 0..3 {
+            chunk.position.x[i] += chunk.velocity.0[0][i];
+            chunk.position.y[i] += chunk.velocity.0[1][i];
+        }
+        chunk.state.0[1] = 1;
+    }
+
+    assert_eq!(bodies.position.x, [1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    assert_eq!(bodies.position.y, [2.0, 1.0, 0.0, -1.0, -2.0, -3.0]);
+    assert_eq!(bodies.state.0, [0, 1, 0, 0, 1, 0]);
+
+    let chunk = bodies.array_chunks::<2>().next_back().unwrap();
+    assert_eq!(chunk.position.x, &[5.0, 6.0]);
+    assert_eq!(chunk.velocity[1], [2.0, 2.0]);
+    assert_eq!(chunk.state.1, &[true, true]);
+
+    let chunk = bodies.chunks_exact(4).next().unwrap();
+    assert_eq!(chunk.position.x, [1.0, 2.0, 3.0, 4.0]);
+    assert_eq!(chunk.velocity[0], [1.0, 1.0, 1.0, 1.0]);
+}
@@ -0,0 +1,151 @@
+#![allow(clippy::float_cmp)]
+
+use serde::{Deserialize, Serialize};
+use soa_derive::StructOfArray;
+
+#[derive(Debug, Clone, PartialEq, StructOfArray)]
+#[soa_derive(Debug, Clone, PartialEq)]
+pub enum Shape {
+    Circle { r: f64 },
+    Rect { w: f64, h: f64 },
+    Polygon(Vec<(f64, f64)>),
+    Empty,
+}
+
+fn shapes() -> ShapeVec {
+    vec![
+        Shape::Circle { r: 1.0 },
+        Shape::Rect { w: 2.0, h: 3.0 },
+        Shape::Empty,
+        Shape::Circle { r: 4.0 },
+        Shape::Polygon(vec![(0.0, 0.0), (1.0, 0.0), (0.0, 1.0)]),
+    ].into_iter().collect()
+}
+
+#[test]
+fn push_and_get() {
+    let shapes = shapes();
+    assert_eq!(shapes.len(), 5);
+    assert!(!shapes.is_empty());
+
+    // each variant data is stored in its own struct of arrays
+    assert_eq!(shapes.circle.r, [1.0, 4.0]);
+    assert_eq!(shapes.rect.w, [2.0]);
+    assert_eq!(shapes.rect.h, [3.0]);
+    assert_eq!(shapes.polygon.0.len(), 1);
+
+    match shapes.get(3) {
+        Some(ShapeRef::Circle(circle)) => assert_eq!(*circle.r, 4.0),
+        other => panic!("expected a circle, got {:?}", other),
+    }
+    assert!(matches!(shapes.get(2), Some(ShapeRef::Empty)));
+    assert!(shapes.get(5).is_none());
+
+    assert_eq!(shapes.get(1).unwrap().to_owned(), Shape::Rect { w: 2.0, h: 3.0 });
+}
+
+#[test]
+fn get_mut_and_pop() {
+    let mut shapes = shapes();
+    if let Some(ShapeRefMut::Rect(rect)) = shapes.get_mut(1) {
+        *rect.h = 10.0;
+    }
+    assert_eq!(shapes.rect.h, [10.0]);
+
+    let polygon = shapes.pop().unwrap();
+    assert_eq!(polygon, Shape::Polygon(vec![(0.0, 0.0), (1.0, 0.0), (0.0, 1.0)]));
+    assert_eq!(shapes.pop(), Some(Shape::Circle { r: 4.0 }));
+    assert_eq!(shapes.pop(), Some(Shape::Empty));
+    assert_eq!(shapes.len(), 2);
+    assert_eq!(shapes.circle.r, [1.0]);
+
+    shapes.push(Shape::Circle { r: 5.0 });
+    assert_eq!(shapes.get(2).unwrap().to_owned(), Shape::Circle { r: 5.0 });
+
+    shapes.clear();
+    assert!(shapes.is_empty());
+    assert!(shapes.circle.is_empty());
+    assert_eq!(shapes.pop(), None);
+}
+
+#[test]
+fn iter() {
+    let mut shapes = shapes();
+    let owned = shapes.iter().map(|shape| shape.to_owned()).collect::<Vec<_>>();
+    assert_eq!(owned, [
+        Shape::Circle { r: 1.0 },
+        Shape::Rect { w: 2.0, h: 3.0 },
+        Shape::Empty,
+        Shape::Circle { r: 4.0 },
+        Shape::Polygon(vec![(0.0, 0.0), (1.0, 0.0), (0.0, 1.0)]),
+    ]);
+    assert_eq!(shapes.iter().len(), 5);
+
+    for shape in &mut shapes {
+        match shape {
+            ShapeRefMut::Circle(circle) => *circle.r *= 2.0,
+            ShapeRefMut::Rect(rect) => *rect.w *= 2.0,
+            ShapeRefMut::Polygon(polygon) => polygon.0.clear(),
+            ShapeRefMut::Empty => {}
+        }
+    }
+    assert_eq!(shapes.circle.r, [2.0, 8.0]);
+    assert_eq!(shapes.rect.w, [4.0]);
+    assert_eq!(shapes.polygon.0, [vec![]]);
+}
+
+#[derive(Debug, Clone, PartialEq, StructOfArray, Serialize, Deserialize)]
+#[soa_derive(Debug, PartialEq, Serialize, Deserialize)]
+pub enum Message {
+    Type { id: u32 },
+    HTTPRequest(String),
+    Quit,
+}
+
+#[test]
+fn variant_names() {
+    let mut messages = MessageVec::new();
+    messages.push(Message::Type { id: 3 });
+    messages.push(Message::HTTPRequest(String::from("GET")));
+    messages.push(Message::Quit);
+
+    assert_eq!(messages.r#type.id, [3]);
+    assert_eq!(messages.http_request.0, ["GET"]);
+    assert!(matches!(messages.get(2), Some(MessageRef::Quit)));
+}
+
+#[test]
+fn deserialize() -> Result<(), serde_json::Error> {
+    let mut messages = MessageVec::new();
+    messages.push(Message::Type { id: 3 });
+    messages.push(Message::Quit);
+    messages.push(Message::Type { id: 4 });
+
+    let json = serde_json::to_string(&messages)?;
+    let messages2: MessageVec = serde_json::from_str(&json)?;
+    assert_eq!(messages2, messages);
+
+    let invalid = [
+        (
+            r#"{"tags":[0,5],"indices":[0,0],"type":{"id":[3]},"http_request":[]}"#,
+            "invalid MessageVec: unknown tag 5",
+        ),
+        (
+            r#"{"tags":[0,0],"indices":[0,2],"type":{"id":[3,4]},"http_request":[]}"#,
+            "invalid MessageVec: index 2 for tag 0, expected 1",
+        ),
+        (
+            r#"{"tags":[0],"indices":[0,0],"type":{"id":[3]},"http_request":[]}"#,
+            "invalid MessageVec: column `indices` has length 2, expected 1",
+        ),
+        (
+            r#"{"tags":[0,2],"indices":[0,0],"type":{"id":[3,4]},"http_request":[]}"#,
+            "invalid MessageVec: column `type` has length 2, expected 1",
+        ),
+    ];
+    for (json, message) in invalid {
+        let error = serde_json::from_str::<MessageVec>(json).unwrap_err();
+        assert!(error.to_string().starts_with(message), "unexpected error: {}", error);
+    }
+    Ok(())
+}
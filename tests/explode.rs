@@ -0,0 +1,308 @@
+use soa_derive::{LengthMismatch, SoAAppendVec, StructOfArray};
+
+#[derive(Debug, Clone, PartialEq, StructOfArray)]
+#[soa_derive(Debug, Clone, PartialEq)]
+pub struct Particle {
+    pub name: String,
+    #[soa(explode)]
+    pub position: (f64, f64, f64),
+    #[soa(explode)]
+    pub data: [usize; 4],
+}
+
+impl Particle {
+    fn new(name: &str, x: f64, data: usize) -> Particle {
+        Particle {
+            name: name.into(),
+            position: (x, 2.0 * x, 3.0 * x),
+            data: [data, data + 1, data + 2, data + 3],
+        }
+    }
+}
+
+#[test]
+fn columns() {
+    let mut particles = ParticleVec::new();
+    particles.push(Particle::new("Na", 1.0, 0));
+    particles.push(Particle::new("Cl", 2.0, 10));
+    particles.push(Particle::new("Zn", 3.0, 20));
+
+    assert_eq!(particles.len(), 3);
+    assert_eq!(particles.position.0, [1.0, 2.0, 3.0]);
+    assert_eq!(particles.position.1, [2.0, 4.0, 6.0]);
+    assert_eq!(particles.position.2, [3.0, 6.0, 9.0]);
+    assert_eq!(particles.data[0], [0, 10, 20]);
+    assert_eq!(particles.data[3], [3, 13, 23]);
+
+    for x in &mut particles.position.0 {
+        *x *= 10.0;
+    }
+    for value in &mut particles.data[1] {
+        *value = 0;
+    }
+
+    assert_eq!(particles.get(1).unwrap().to_owned(), Particle {
+        name: "Cl".into(),
+        position: (20.0, 4.0, 6.0),
+        data: [10, 0, 12, 13],
+    });
+
+    let slice = particles.slice(1..3);
+    assert_eq!(slice.position.0, [20.0, 30.0]);
+    assert_eq!(slice.data[2], [12, 22]);
+}
+
+#[test]
+fn references() {
+    let mut particles = ParticleVec::new();
+    particles.push(Particle::new("Na", 1.0, 0));
+    particles.push(Particle::new("Cl", 2.0, 10));
+
+    let particle = particles.index(1);
+    assert_eq!(*particle.position.0, 2.0);
+    assert_eq!(*particle.position.2, 6.0);
+    assert_eq!(particle.data[0], 10);
+    assert_eq!(particle.data[3], 13);
+
+    let mut particle = particles.index_mut(0);
+    *particle.position.1 = -1.0;
+    particle.data[2] = 42;
+    assert_eq!(particles.position.1, [-1.0, 4.0]);
+    assert_eq!(particles.data[2], [42, 12]);
+
+    let mut particle = Particle::new("Fe", 5.0, 50);
+    let reference = particle.as_ref();
+    assert_eq!(*reference.position.0, 5.0);
+    assert_eq!(reference.data[1], 51);
+
+    let mut reference = particle.as_mut();
+    reference.data[1] = 0;
+    assert_eq!(particle.data, [50, 0, 52, 53]);
+
+    let old = particles.index_mut(1).replace(Particle::new("Fe", 5.0, 50));
+    assert_eq!(old, Particle::new("Cl", 2.0, 10));
+    assert_eq!(particles.index(1).to_owned(), Particle::new("Fe", 5.0, 50));
+}
+
+#[test]
+fn iter() {
+    let mut particles = ParticleVec::new();
+    particles.push(Particle::new("Na", 1.0, 0));
+    particles.push(Particle::new("Cl", 2.0, 10));
+    particles.push(Particle::new("Zn", 3.0, 20));
+
+    for mut particle in particles.iter_mut() {
+        *particle.position.0 += 1.0;
+        particle.data[0] *= 2;
+    }
+
+    let names = particles.iter().map(|particle| particle.name.clone()).collect::<Vec<_>>();
+    assert_eq!(names, ["Na", "Cl", "Zn"]);
+
+    let x = particles.iter().rev().map(|particle| *particle.position.0).collect::<Vec<_>>();
+    assert_eq!(x, [4.0, 3.0, 2.0]);
+    assert_eq!(particles.data[0], [0, 20, 40]);
+
+    let sum = particles.position.iter().map(|position| position.0 + position.1 + position.2).sum::<f64>();
+    assert_eq!(sum, 2.0 + 3.0 + 4.0 + 2.0 * (1.0 + 2.0 + 3.0) + 3.0 * (1.0 + 2.0 + 3.0));
+}
+
+#[test]
+fn iter_nth() {
+    let mut particles = ParticleVec::new();
+    particles.push(Particle::new("Na", 1.0, 0));
+    particles.push(Particle::new("Cl", 2.0, 10));
+    particles.push(Particle::new("Zn", 3.0, 20));
+
+    let mut iter = particles.iter();
+    assert_eq!(*iter.nth(1).unwrap().position.1, 4.0);
+    assert_eq!(iter.as_slice().data[3], [23]);
+    assert_eq!(iter.len(), 1);
+
+    let mut iter = particles.iter_mut();
+    iter.nth_back(1).unwrap().data[2] = 0;
+    let slice = iter.into_slice();
+    assert_eq!(slice.name, ["Na"]);
+    assert_eq!(particles.data[2], [2, 0, 22]);
+}
+
+#[test]
+fn vec_methods() {
+    let mut particles = ParticleVec::with_capacity(4);
+    particles.push(Particle::new("Na", 1.0, 0));
+    particles.push(Particle::new("Cl", 2.0, 10));
+    particles.insert(1, Particle::new("Zn", 3.0, 20));
+    assert_eq!(particles.data[0], [0, 20, 10]);
+
+    assert_eq!(particles.remove(0), Particle::new("Na", 1.0, 0));
+    assert_eq!(particles.swap_remove(0), Particle::new("Zn", 3.0, 20));
+    assert_eq!(particles.len(), 1);
+
+    particles.resize(3, Particle::new("Fe", 5.0, 50));
+    assert_eq!(particles.position.1, [4.0, 10.0, 10.0]);
+
+    let mut other = particles.split_off(1);
+    assert_eq!(particles.len(), 1);
+    assert_eq!(other.len(), 2);
+    particles.append(&mut other);
+    assert_eq!(particles.data[3], [13, 53, 53]);
+
+    assert_eq!(particles.pop(), Some(Particle::new("Fe", 5.0, 50)));
+    particles.truncate(1);
+    assert_eq!(particles.position.2, [6.0]);
+
+    let copy = particles.clone();
+    particles.extend_from_slice(copy.as_slice());
+    assert_eq!(particles.data[0], [10, 10]);
+
+    particles.clear();
+    assert!(particles.is_empty());
+    assert!(particles.position.0.is_empty());
+    assert!(particles.data[3].is_empty());
+}
+
+#[test]
+fn drain() {
+    let mut particles = ParticleVec::new();
+    particles.push(Particle::new("Na", 1.0, 0));
+    particles.push(Particle::new("Cl", 2.0, 10));
+    particles.push(Particle::new("Zn", 3.0, 20));
+
+    let drained = particles.drain(..2).collect::<Vec<_>>();
+    assert_eq!(drained, [Particle::new("Na", 1.0, 0), Particle::new("Cl", 2.0, 10)]);
+    assert_eq!(particles.data[1], [21]);
+
+    drop(particles.splice(.., vec![Particle::new("Fe", 5.0, 50)]));
+    assert_eq!(particles.position.0, [5.0]);
+    assert_eq!(particles.data[3], [53]);
+}
+
+#[test]
+fn into_iter() {
+    let particles = vec![Particle::new("Na", 1.0, 0), Particle::new("Cl", 2.0, 10)];
+    let vec = ParticleVec::from(particles.clone());
+    assert_eq!(vec.data[1], [1, 11]);
+    assert_eq!(Vec::from(vec.clone()), particles);
+
+    let mut iter = vec.into_iter();
+    assert_eq!(iter.next_back(), Some(Particle::new("Cl", 2.0, 10)));
+    assert_eq!(iter.len(), 1);
+    assert_eq!(iter.next(), Some(Particle::new("Na", 1.0, 0)));
+    assert_eq!(iter.next(), None);
+}
+
+#[test]
+fn from_columns() {
+    let mut particles = ParticleVec::new();
+    particles.push(Particle::new("Na", 1.0, 0));
+    particles.push(Particle::new("Cl", 2.0, 10));
+    assert_eq!(particles.validate(), Ok(()));
+
+    let (name, position, mut data) = particles.into_columns();
+    assert_eq!(position.1, [2.0, 4.0]);
+    data.0[2].pop();
+    assert_eq!(data.validate(), Err(LengthMismatch::new("2", 2, 1)));
+
+    let error = ParticleVec::from_columns(name, position, data).unwrap_err();
+    assert_eq!(error, LengthMismatch::new("data.2", 2, 1));
+    assert_eq!(error.to_string(), "column `data.2` has length 1, expected 2");
+}
+
+#[test]
+fn sort() {
+    let mut particles = ParticleVec::new();
+    particles.push(Particle::new("Na", 3.0, 0));
+    particles.push(Particle::new("Cl", 1.0, 10));
+    particles.push(Particle::new("Zn", 2.0, 20));
+
+    particles.as_mut_slice().sort_by(|a, b| a.position.0.partial_cmp(b.position.0).unwrap());
+    assert_eq!(particles.name, ["Cl", "Zn", "Na"]);
+    assert_eq!(particles.position.0, [1.0, 2.0, 3.0]);
+    assert_eq!(particles.position.2, [3.0, 6.0, 9.0]);
+    assert_eq!(particles.data[0], [10, 20, 0]);
+    assert_eq!(particles.data[3], [13, 23, 3]);
+}
+
+#[test]
+fn reorder() {
+    let mut particles = ParticleVec::new();
+    particles.push(Particle::new("Na", 1.0, 0));
+    particles.push(Particle::new("Cl", 2.0, 10));
+    particles.push(Particle::new("Zn", 3.0, 20));
+    particles.push(Particle::new("C", 4.0, 30));
+
+    particles.as_mut_slice().reverse();
+    assert_eq!(particles.name, ["C", "Zn", "Cl", "Na"]);
+    assert_eq!(particles.position.1, [8.0, 6.0, 4.0, 2.0]);
+    assert_eq!(particles.data[2], [32, 22, 12, 2]);
+
+    particles.as_mut_slice().rotate_left(1);
+    assert_eq!(particles.name, ["Zn", "Cl", "Na", "C"]);
+    assert_eq!(particles.position.0, [3.0, 2.0, 1.0, 4.0]);
+    assert_eq!(particles.data[0], [20, 10, 0, 30]);
+
+    let (mut left, right) = particles.as_mut_slice().split_at_mut(2);
+    left.swap_with_slice(right);
+    assert_eq!(particles.name, ["Na", "C", "Zn", "Cl"]);
+    assert_eq!(particles.position.2, [3.0, 12.0, 9.0, 6.0]);
+    assert_eq!(particles.data[1], [1, 31, 21, 11]);
+
+    particles.as_mut_slice().fill(Particle::new("H", 0.0, 0));
+    assert_eq!(particles.index(3), Particle::new("H", 0.0, 0).as_ref());
+}
+
+#[test]
+fn ptr() {
+    let mut particles = ParticleVec::new();
+    particles.push(Particle::new("Na", 1.0, 0));
+    particles.push(Particle::new("Cl", 2.0, 10));
+
+    let ptr = particles.as_mut_ptr();
+    unsafe {
+        let mut particle = ptr.add(1).as_mut().unwrap();
+        *particle.position.0 = 12.0;
+        particle.data[1] = 0;
+
+        let particle = ptr.as_ptr().add(1).read();
+        assert_eq!(particle.position, (12.0, 4.0, 6.0));
+        assert_eq!(particle.data, [10, 0, 12, 13]);
+        std::mem::forget(particle);
+    }
+}
+
+#[test]
+fn array_vec() {
+    let mut particles = ParticleArrayVec::<2>::new();
+    particles.push(Particle::new("Na", 1.0, 0)).unwrap();
+    particles.push(Particle::new("Cl", 2.0, 10)).unwrap();
+    assert!(particles.push(Particle::new("Zn", 3.0, 20)).is_err());
+
+    assert_eq!(particles.as_slice().position.0, [1.0, 2.0]);
+    assert_eq!(particles.as_slice().data[2], [2, 12]);
+    assert_eq!(particles.pop(), Some(Particle::new("Cl", 2.0, 10)));
+    assert_eq!(particles.len(), 1);
+}
+
+#[derive(Debug, Clone, PartialEq, StructOfArray)]
+#[soa_derive(Debug, Clone, PartialEq)]
+pub struct Pair<T: Copy> {
+    #[soa(explode)]
+    pub values: [T; 2],
+    #[soa(explode)]
+    pub tagged: (T, u8),
+}
+
+#[test]
+fn generic() {
+    let mut pairs = PairVec::<f32>::new();
+    pairs.push(Pair { values: [1.0, 2.0], tagged: (3.0, 4) });
+    pairs.push(Pair { values: [5.0, 6.0], tagged: (7.0, 8) });
+
+    assert_eq!(pairs.values[1], [2.0, 6.0]);
+    assert_eq!(pairs.tagged.1, [4, 8]);
+    assert_eq!(pairs.index(1).to_owned(), Pair { values: [5.0, 6.0], tagged: (7.0, 8) });
+
+    pairs.as_mut_slice().sort_by(|a, b| b.tagged.1.cmp(a.tagged.1));
+    assert_eq!(pairs.values[0], [5.0, 1.0]);
+    assert_eq!(pairs.tagged.0, [7.0, 3.0]);
+}
@@ -5,8 +5,8 @@ mod particles;
 use std::marker::PhantomData;
 use std::fmt::Debug;
 
-use particles::ParticleVec;
-use soa_derive::{SoAVec, SoASlice, StructOfArray};
+use particles::{ParticleSliceMut, ParticleVec};
+use soa_derive::{SoAVec, SoASlice, SoASliceMut, StructOfArray};
 
 use self::particles::Particle;
 
@@ -191,3 +191,96 @@ fn test_ops() {
 
     assert_eq!(vec.first().unwrap().name, "baz");
 }
+
+fn sort_generic<V: SoAVec<Particle>, F>(vec: &mut V, f: F) where F: FnMut(V::Ref<'_>, V::Ref<'_>) -> std::cmp::Ordering {
+    vec.sort_unstable_by(f);
+}
+
+#[test]
+fn test_generic_sort() {
+    let mut vec = ParticleVec::new();
+    vec.push(Particle::new("foo".into(), 100.0));
+    vec.push(Particle::new("bar".into(), 1000.0));
+    vec.push(Particle::new("baz".into(), 50.0));
+
+    sort_generic(&mut vec, |a, b| a.name.cmp(b.name));
+    assert_eq!(vec.name, ["bar", "baz", "foo"]);
+
+    SoAVec::sort_by_cached_key(&mut vec, |particle| particle.mass.to_string());
+    assert_eq!(vec.name, ["foo", "bar", "baz"]);
+
+    SoAVec::sort_unstable_by_key(&mut vec, |particle| particle.name.len() + *particle.mass as usize);
+    assert_eq!(vec.name, ["baz", "foo", "bar"]);
+
+    SoAVec::sort_by_column(&mut vec, |vec: &ParticleVec| &vec.name[..]);
+    assert_eq!(vec.name, ["bar", "baz", "foo"]);
+
+    SoAVec::sort_by(&mut vec, |a, b| b.mass.total_cmp(a.mass));
+    assert_eq!(vec.mass, [1000.0, 100.0, 50.0]);
+
+    SoASliceMut::sort_by_column(&mut vec.slice_mut(1..3), |slice: &ParticleSliceMut| &slice.name[..]);
+    assert_eq!(vec.name, ["bar", "baz", "foo"]);
+}
+
+fn dedup_generic<V: SoAVec<Particle>, F>(vec: &mut V, f: F) where F: FnMut(V::RefMut<'_>, V::RefMut<'_>) -> bool {
+    vec.dedup_by(f);
+}
+
+#[test]
+fn test_generic_search_reorder() {
+    let mut vec = ParticleVec::new();
+    vec.push(Particle::new("foo".into(), 100.0));
+    vec.push(Particle::new("bar".into(), 1000.0));
+    vec.push(Particle::new("baz".into(), 50.0));
+    vec.push(Particle::new("baz".into(), 50.0));
+
+    dedup_generic(&mut vec, |a, b| a == b);
+    assert_eq!(vec.name, ["foo", "bar", "baz"]);
+
+    SoAVec::sort_by_column(&mut vec, |vec: &ParticleVec| &vec.name[..]);
+    assert_eq!(SoAVec::binary_search_by_key(&vec, &String::from("baz"), |particle| particle.name.clone()), Ok(1));
+    assert_eq!(SoAVec::partition_point(&vec, |particle| particle.name.as_str() < "c"), 2);
+    assert!(SoAVec::contains(&vec, vec.index(2)));
+    assert!(SoASlice::ends_with(&vec.as_slice(), vec.slice(1..3)));
+
+    SoAVec::reverse(&mut vec);
+    assert_eq!(vec.name, ["foo", "baz", "bar"]);
+    SoASliceMut::rotate_left(&mut vec.as_mut_slice(), 1);
+    assert_eq!(vec.name, ["baz", "bar", "foo"]);
+    SoAVec::rotate_right(&mut vec, 2);
+    assert_eq!(vec.name, ["bar", "foo", "baz"]);
+
+    SoASliceMut::fill(&mut vec.slice_mut(0..2), Particle::new("qux".into(), 1.0));
+    assert_eq!(vec.name, ["qux", "qux", "baz"]);
+    assert_eq!(vec.mass, [1.0, 1.0, 50.0]);
+
+    let mut other = vec.split_off(2);
+    SoAVec::swap_with_slice(&mut other, vec.slice_mut(0..1));
+    assert_eq!(vec.name, ["baz", "qux"]);
+    assert_eq!(other.name, ["qux"]);
+}
+
+fn drain_generic<V: SoAVec<Particle>>(vec: &mut V) -> Vec<Particle> {
+    vec.drain(1..2).collect()
+}
+
+#[test]
+fn test_generic_drain() {
+    let mut vec = ParticleVec::new();
+    vec.push(Particle::new("foo".into(), 100.0));
+    vec.push(Particle::new("bar".into(), 1000.0));
+    vec.push(Particle::new("baz".into(), 50.0));
+
+    let drained = drain_generic(&mut vec);
+    assert_eq!(drained.len(), 1);
+    assert_eq!(drained[0].name, "bar");
+    assert_eq!(vec.name, ["foo", "baz"]);
+
+    let removed = SoAVec::splice(&mut vec, ..1, vec![Particle::new("qux".into(), 1.0), Particle::new("quux".into(), 2.0)]).count();
+    assert_eq!(removed, 1);
+    assert_eq!(vec.name, ["qux", "quux", "baz"]);
+
+    let light = SoAVec::extract_if(&mut vec, .., |particle| *particle.mass < 10.0).collect::<ParticleVec>();
+    assert_eq!(light.name, ["qux", "quux"]);
+    assert_eq!(vec.name, ["baz"]);
+}
@@ -0,0 +1,157 @@
+#![allow(clippy::float_cmp)]
+
+use std::fmt::Debug;
+
+use soa_derive::{SoAVec, StructOfArray};
+
+pub trait Float: Copy + PartialOrd + Debug + Default {}
+impl Float for f32 {}
+impl Float for f64 {}
+
+#[derive(Debug, Clone, PartialEq, StructOfArray)]
+#[soa_derive(Debug, Clone, PartialEq)]
+pub struct Particle<T: Float> {
+    pub name: String,
+    pub mass: T,
+}
+
+#[derive(Debug, Clone, PartialEq, StructOfArray)]
+#[soa_derive(Debug, PartialEq)]
+pub struct Labeled<'l, L, const N: usize> where L: Clone {
+    pub label: &'l str,
+    pub data: [L; N],
+}
+
+#[test]
+fn generic_type() {
+    let mut particles = ParticleVec::<f32>::new();
+    particles.push(Particle { name: "Na".into(), mass: 22.9 });
+    particles.push(Particle { name: "Cl".into(), mass: 35.4 });
+
+    assert_eq!(particles.len(), 2);
+    assert_eq!(particles.mass, [22.9, 35.4]);
+    assert_eq!(particles.index(1).name, "Cl");
+
+    particles.sort_by(|a, b| b.mass.partial_cmp(a.mass).unwrap());
+    assert_eq!(particles.name, ["Cl", "Na"]);
+
+    let slice = particles.as_slice();
+    let masses = slice.iter().map(|p| *p.mass).collect::<Vec<f32>>();
+    assert_eq!(masses, [35.4, 22.9]);
+
+    for particle in particles.iter_mut() {
+        *particle.mass *= 2.0;
+    }
+    assert_eq!(particles.pop(), Some(Particle { name: "Na".into(), mass: 45.8 }));
+
+    let copy: ParticleVec<f32> = particles.as_slice().to_vec();
+    assert_eq!(copy, particles);
+
+    let other: <Particle<f64> as StructOfArray>::Type = vec![
+        Particle { name: "H".into(), mass: 1.0 },
+    ].into_iter().collect();
+    assert_eq!(other.mass, [1.0]);
+}
+
+fn total_mass<T: Float + std::ops::Add<Output = T>, V: SoAVec<Particle<T>>>(vec: &V) -> T
+where
+    for<'t> V::Ref<'t>: Into<Particle<T>>
+{
+    vec.iter().map(|p| p.into().mass).fold(T::default(), |a, b| a + b)
+}
+
+#[test]
+fn generic_soa_vec() {
+    let mut particles = ParticleVec::<f64>::new();
+    SoAVec::push(&mut particles, Particle { name: "Na".into(), mass: 1.0 });
+    SoAVec::push(&mut particles, Particle { name: "Cl".into(), mass: 2.0 });
+    assert_eq!(total_mass(&particles), 3.0);
+}
+
+#[test]
+fn lifetimes_and_const_generics() {
+    let label = String::from("first");
+    let mut vec = LabeledVec::<char, 2>::new();
+    vec.push(Labeled { label: &label, data: ['a', 'b'] });
+    vec.push(Labeled { label: "second", data: ['c', 'd'] });
+
+    assert_eq!(vec.label, ["first", "second"]);
+    assert_eq!(vec.index(1).data, &['c', 'd']);
+
+    let mut slice = vec.as_mut_slice();
+    slice.index_mut(0).data[1] = 'z';
+    assert_eq!(vec.get(0).unwrap().to_owned(), Labeled { label: "first", data: ['a', 'z'] });
+
+    let ptr = vec.as_ptr();
+    assert_eq!(unsafe { ptr.add(1).read() }.label, "second");
+}
+
+#[derive(Debug, Clone, PartialEq, StructOfArray)]
+pub struct Boxed<T> {
+    pub value: Box<T>,
+}
+
+fn copy_twice<T: Copy>(value: T) -> (T, T) {
+    (value, value)
+}
+
+#[test]
+fn views_are_always_copy() {
+    let mut vec = BoxedVec::<String>::new();
+    vec.push(Boxed { value: Box::new("a".into()) });
+    vec.push(Boxed { value: Box::new("b".into()) });
+
+    let (first, second) = copy_twice(vec.as_slice());
+    assert_eq!(first.len(), second.len());
+
+    let (first, second) = copy_twice(vec.index(1));
+    assert_eq!(**first.value, **second.value);
+
+    let (first, second) = copy_twice(vec.as_ptr());
+    assert_eq!(first.value, second.value);
+
+    let slice = vec.as_slice();
+    let values = (0..2).map(|i| slice.index(i).value.as_str()).collect::<Vec<_>>();
+    assert_eq!(values, ["a", "b"]);
+}
+
+// these generic parameters use the same names as the ones commonly used in the
+// methods of the generated code
+#[derive(Debug, Clone, PartialEq, StructOfArray)]
+#[soa_derive(Debug, Clone, PartialEq)]
+pub struct Common<'a, 'b, 't, F: Float, I: Copy, K: Clone, const CAPACITY: usize, const CHUNK: usize> {
+    pub name: &'a str,
+    pub bytes: &'b [u8],
+    pub tag: Option<&'t char>,
+    pub weight: F,
+    pub ids: [I; CAPACITY],
+    pub keys: [K; CHUNK],
+}
+
+#[test]
+fn common_generic_names() {
+    let mut vec = CommonVec::<f64, u8, char, 2, 1>::new();
+    for (i, name) in ["c", "a", "d", "b"].iter().enumerate() {
+        vec.push(Common { name, bytes: b"bytes", tag: None, weight: i as f64, ids: [i as u8, 0], keys: ['k'] });
+    }
+
+    vec.as_mut_slice().sort_by(|a, b| a.name.cmp(b.name));
+    assert_eq!(vec.name, ["a", "b", "c", "d"]);
+    assert_eq!(vec.get(1).map(|item| *item.weight), Some(3.0));
+    assert_eq!(vec.get(1..3).unwrap().len(), 2);
+
+    let drained = vec.drain(0..1).collect::<Vec<_>>();
+    assert_eq!(drained[0].name, "a");
+    let removed = vec.splice(0..1, drained).collect::<Vec<_>>();
+    assert_eq!(removed[0].name, "b");
+    let extracted = vec.extract_if(.., |item| item.ids[0] == 2).collect::<Vec<_>>();
+    assert_eq!(extracted[0].name, "d");
+    assert_eq!(vec.name, ["a", "c"]);
+
+    let mut array = CommonArrayVec::<f64, u8, char, 2, 1, 4>::new();
+    assert!(array.push(vec.index(0).to_owned()).is_ok());
+    assert_eq!(array.len(), 1);
+
+    assert_eq!(vec.as_slice().array_chunks::<2>().count(), 1);
+    assert_eq!(vec.iter().map(|item| *item.weight).sum::<f64>(), 1.0);
+}
@@ -52,6 +52,73 @@ fn iter_mut() {
     assert_eq!(*particles.index(2).mass, 2.0);
 }
 
+#[test]
+fn iter_nth_and_back() {
+    let mut particles = ParticleVec::new();
+    for name in ["Na", "Cl", "Zn", "Fe", "Cu"] {
+        particles.push(Particle::new(String::from(name), 0.0));
+    }
+
+    let mut iter = particles.iter();
+    assert_eq!(iter.len(), 5);
+    assert_eq!(iter.nth(1).unwrap().name, "Cl");
+    assert_eq!(iter.len(), 3);
+    assert_eq!(iter.next_back().unwrap().name, "Cu");
+    assert_eq!(iter.nth_back(1).unwrap().name, "Zn");
+    assert_eq!(iter.len(), 0);
+    assert!(iter.next().is_none());
+    assert!(iter.next_back().is_none());
+
+    let mut iter = particles.iter();
+    assert!(iter.nth(5).is_none());
+    assert!(iter.next().is_none());
+
+    let names = particles.iter().rev().map(|particle| particle.name.as_str()).collect::<Vec<_>>();
+    assert_eq!(names, ["Cu", "Fe", "Zn", "Cl", "Na"]);
+
+    assert_eq!(particles.iter().count(), 5);
+    assert_eq!(particles.iter().last().unwrap().name, "Cu");
+    assert_eq!(particles.iter().skip(2).step_by(2).map(|particle| particle.name.as_str()).collect::<Vec<_>>(), ["Zn", "Cu"]);
+
+    let mut iter = particles.iter_mut();
+    *iter.nth(2).unwrap().mass = 1.0;
+    *iter.nth_back(0).unwrap().mass = 2.0;
+    assert_eq!(iter.len(), 1);
+    *iter.next().unwrap().mass = 3.0;
+    assert!(iter.next().is_none());
+    assert_eq!(particles.mass, [0.0, 0.0, 1.0, 3.0, 2.0]);
+}
+
+#[test]
+fn iter_as_slice() {
+    let mut particles = ParticleVec::new();
+    particles.push(Particle::new(String::from("Na"), 0.0));
+    particles.push(Particle::new(String::from("Cl"), 0.0));
+    particles.push(Particle::new(String::from("Zn"), 0.0));
+
+    let mut iter = particles.iter();
+    assert_eq!(iter.as_slice().len(), 3);
+    iter.next();
+    let slice = iter.as_slice();
+    assert_eq!(slice.name, ["Cl", "Zn"]);
+    iter.next_back();
+    assert_eq!(iter.as_slice().name, ["Cl"]);
+    assert_eq!(slice.len(), 2);
+
+    let mut iter = particles.iter_mut();
+    iter.next();
+    assert_eq!(iter.as_slice().name, ["Cl", "Zn"]);
+    let slice = iter.into_slice();
+    for mass in slice.mass {
+        *mass = 4.0;
+    }
+    assert_eq!(particles.mass, [0.0, 4.0, 4.0]);
+
+    let iter = particles.iter();
+    let cloned = iter.clone();
+    assert_eq!(iter.zip(cloned).filter(|(a, b)| a.name == b.name).count(), 3);
+}
+
 #[test]
 fn from_iter() {
     let vec_with_particles = vec![
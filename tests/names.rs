@@ -0,0 +1,98 @@
+use soa_derive::StructOfArray;
+
+#[derive(Debug, Clone, PartialEq, StructOfArray)]
+#[soa_derive(Debug, Clone, PartialEq)]
+#[soa(vec = "Particles", slice = "ParticlesView", ref = "ParticleView")]
+pub struct Particle {
+    pub name: String,
+    pub mass: f64,
+}
+
+/// This type would conflict with the default name of the generated vector
+#[allow(dead_code)]
+pub struct ParticleVec;
+
+#[derive(Debug, Clone, PartialEq, StructOfArray)]
+#[soa(prefix = "Soa", suffix = "Type", iter_mut = "PointsIterMut")]
+pub struct Point {
+    pub x: f32,
+    pub y: f32,
+}
+
+#[test]
+fn explicit_names() {
+    let mut particles = Particles::new();
+    particles.push(Particle { name: "H".into(), mass: 1.0 });
+    particles.push(Particle { name: "He".into(), mass: 4.0 });
+
+    let slice: ParticlesView<'_> = particles.as_slice();
+    let particle: ParticleView<'_> = slice.index(1);
+    assert_eq!(particle.to_owned(), Particle { name: "He".into(), mass: 4.0 });
+
+    let slice_mut: ParticleSliceMut<'_> = particles.as_mut_slice();
+    assert_eq!(slice_mut.len(), 2);
+
+    let _: ParticleIter<'_> = particles.iter();
+    let _: <Particle as StructOfArray>::Type = Particles::new();
+}
+
+#[test]
+fn prefix_and_suffix() {
+    let mut points = SoaPointVecType::new();
+    points.push(Point { x: 1.0, y: 2.0 });
+
+    let _: SoaPointSliceType<'_> = points.as_slice();
+    let _: SoaPointRefType<'_> = points.index(0);
+    let _: SoaPointPtrType = points.as_ptr();
+    let _: SoaPointIterType<'_> = points.iter();
+
+    for point in points.iter_mut() {
+        let point: SoaPointRefMutType<'_> = point;
+        *point.x += 1.0;
+    }
+    let _: PointsIterMut<'_> = points.iter_mut();
+
+    assert_eq!(points.x, [2.0]);
+}
+
+#[derive(Debug, Clone, PartialEq, StructOfArray)]
+#[soa_derive(Debug, Clone, PartialEq)]
+#[soa(vec = "Colors", suffix = "Soa")]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, StructOfArray)]
+#[soa_derive(Debug, Clone, PartialEq)]
+pub struct Labeled {
+    #[nested_soa]
+    pub particle: Particle,
+    #[nested_soa]
+    pub color: Color,
+}
+
+#[test]
+fn nested_renamed() {
+    let mut vec = LabeledVec::new();
+    vec.push(Labeled {
+        particle: Particle { name: "H".into(), mass: 1.0 },
+        color: Color { r: 255, g: 0, b: 0 },
+    });
+
+    let particles: &Particles = &vec.particle;
+    assert_eq!(particles.name, ["H"]);
+    let colors: &Colors = &vec.color;
+    assert_eq!(colors.r, [255]);
+
+    let _: ParticlesView<'_> = vec.as_slice().particle;
+    let _: ColorSliceSoa<'_> = vec.as_slice().color;
+    let labeled = vec.index(0);
+    let _: ParticleView<'_> = labeled.particle;
+    let _: ColorRefSoa<'_> = labeled.color;
+    assert_eq!(vec.iter().next().unwrap().to_owned(), Labeled {
+        particle: Particle { name: "H".into(), mass: 1.0 },
+        color: Color { r: 255, g: 0, b: 0 },
+    });
+}
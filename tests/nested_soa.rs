@@ -64,3 +64,147 @@ fn nested_soa() {
         a: vec![255, 23],
     });
 }
+
+#[derive(Debug, Clone, PartialEq, StructOfArray)]
+#[soa_derive(Debug, Clone, PartialEq)]
+pub struct Vec3<T: Copy> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+#[derive(Debug, Clone, PartialEq, StructOfArray)]
+#[soa_derive(Debug, Clone, PartialEq)]
+pub struct Body<T: Copy> {
+    #[nested_soa]
+    pub color: other_mod::Color,
+    #[nested_soa]
+    pub position: Vec3<T>,
+    #[nested_soa]
+    pub velocity: Vec3<f32>,
+}
+
+#[test]
+fn nested_path_and_generic() {
+    let mut bodies = BodyVec::<f64>::new();
+    bodies.push(Body {
+        color: other_mod::Color { r: 1, g: 2, b: 3, a: 4 },
+        position: Vec3 { x: 1.0, y: 2.0, z: 3.0 },
+        velocity: Vec3 { x: -1.0, y: -2.0, z: -3.0 },
+    });
+    bodies.push(Body {
+        color: other_mod::Color { r: 5, g: 6, b: 7, a: 8 },
+        position: Vec3 { x: 4.0, y: 5.0, z: 6.0 },
+        velocity: Vec3 { x: -4.0, y: -5.0, z: -6.0 },
+    });
+
+    assert_eq!(bodies.color.g, [2, 6]);
+    assert_eq!(bodies.position.y, [2.0, 5.0]);
+    assert_eq!(bodies.velocity, Vec3Vec { x: vec![-1.0, -4.0], y: vec![-2.0, -5.0], z: vec![-3.0, -6.0] });
+
+    let slice = bodies.slice(1..2);
+    assert_eq!(*slice.position.z.first().unwrap(), 6.0);
+    assert_eq!(*bodies.index(0).color.a, 4);
+
+    for body in bodies.iter_mut() {
+        *body.position.x *= 10.0;
+    }
+    assert_eq!(bodies.position.x, [10.0, 40.0]);
+
+    let ptr = bodies.as_ptr();
+    assert_eq!(unsafe { *ptr.velocity.x.add(1) }, -4.0);
+
+    let body = bodies.pop().unwrap();
+    assert_eq!(body.position, Vec3 { x: 40.0, y: 5.0, z: 6.0 });
+    assert_eq!(body.color, other_mod::Color { r: 5, g: 6, b: 7, a: 8 });
+
+    let mut array = BodyArrayVec::<f64, 2>::new();
+    array.push(body.clone()).unwrap();
+    assert_eq!(array.index(0).to_owned(), body);
+}
+
+#[test]
+fn nested_drain() {
+    let mut bodies = BodyVec::<f64>::new();
+    for i in 0..5 {
+        bodies.push(Body {
+            color: other_mod::Color { r: i, g: 0, b: 0, a: 0 },
+            position: Vec3 { x: i as f64, y: 0.0, z: 0.0 },
+            velocity: Vec3 { x: 0.0, y: 0.0, z: i as f32 },
+        });
+    }
+
+    let drained = bodies.drain(1..3).map(|body| body.color.r).collect::<Vec<_>>();
+    assert_eq!(drained, [1, 2]);
+    assert_eq!(bodies.color.r, [0, 3, 4]);
+    assert_eq!(bodies.position.x, [0.0, 3.0, 4.0]);
+
+    let replacement = Body {
+        color: other_mod::Color { r: 10, g: 0, b: 0, a: 0 },
+        position: Vec3 { x: 10.0, y: 0.0, z: 0.0 },
+        velocity: Vec3 { x: 0.0, y: 0.0, z: 10.0 },
+    };
+    drop(bodies.splice(2.., vec![replacement.clone(), replacement]));
+    assert_eq!(bodies.color.r, [0, 3, 10, 10]);
+    assert_eq!(bodies.velocity.z, [0.0, 3.0, 10.0, 10.0]);
+
+    let extracted = bodies.extract_if(.., |body| *body.color.r == 10).count();
+    assert_eq!(extracted, 2);
+    assert_eq!(bodies.position.x, [0.0, 3.0]);
+}
+
+#[test]
+fn nested_into_iter() {
+    let bodies = (0..3).map(|i| Body {
+        color: other_mod::Color { r: i, g: 0, b: 0, a: 0 },
+        position: Vec3 { x: i as f64, y: 0.0, z: 0.0 },
+        velocity: Vec3 { x: 0.0, y: 0.0, z: i as f32 },
+    }).collect::<Vec<_>>();
+
+    let vec = BodyVec::from(bodies.clone());
+    assert_eq!(vec.position.x, [0.0, 1.0, 2.0]);
+    assert_eq!(vec.into_iter().rev().collect::<Vec<_>>(), bodies.into_iter().rev().collect::<Vec<_>>());
+}
+
+#[test]
+fn nested_iter() {
+    let mut bodies = (0..4).map(|i| Body {
+        color: other_mod::Color { r: i, g: 0, b: 0, a: 0 },
+        position: Vec3 { x: i as f64, y: 0.0, z: 0.0 },
+        velocity: Vec3 { x: 0.0, y: 0.0, z: i as f32 },
+    }).collect::<BodyVec<_>>();
+
+    let mut iter = bodies.iter();
+    assert_eq!(*iter.nth(1).unwrap().color.r, 1);
+    assert_eq!(*iter.next_back().unwrap().position.x, 3.0);
+    assert_eq!(iter.as_slice().velocity.z, [2.0]);
+    assert_eq!(iter.len(), 1);
+
+    let mut iter = bodies.iter_mut();
+    *iter.nth(3).unwrap().color.g = 5;
+    assert!(iter.next().is_none());
+    assert_eq!(bodies.color.g, [0, 0, 0, 5]);
+}
+
+#[test]
+fn nested_columns() {
+    let color = ColorVec::from_columns(vec![1, 2], vec![3, 4], vec![5, 6], vec![7, 8]).unwrap();
+    let position = Vec3Vec::from_columns(vec![0.0, 1.0], vec![0.0, 2.0], vec![0.0, 3.0]).unwrap();
+    let velocity = Vec3Vec::from_columns(vec![0.0; 2], vec![0.0; 2], vec![1.0; 2]).unwrap();
+    let bodies = BodyVec::<f64>::from_columns(color, position, velocity).unwrap();
+    assert_eq!(bodies.len(), 2);
+    assert_eq!(bodies.index(1).position.z, &3.0);
+
+    let (mut color, position, velocity) = bodies.into_columns();
+    assert_eq!(color.a, [7, 8]);
+
+    color.g.push(4);
+    let error = BodyVec::<f64>::from_columns(color, position, velocity).unwrap_err();
+    assert_eq!(error, soa_derive::LengthMismatch::new("color.g", 2, 3));
+
+    let position = Vec3Vec::from_columns(vec![0.0], vec![0.0], vec![0.0]).unwrap();
+    let velocity = Vec3Vec::from_columns(vec![0.0; 2], vec![0.0; 2], vec![1.0; 2]).unwrap();
+    let color = ColorVec::from_columns(vec![1, 2], vec![3, 4], vec![5, 6], vec![7, 8]).unwrap();
+    let error = BodyVec::<f32>::from_columns(color, position, velocity).unwrap_err();
+    assert_eq!(error, soa_derive::LengthMismatch::new("position", 2, 1));
+}
@@ -0,0 +1,82 @@
+// Check that the generated code only uses `core` and `alloc`, and not `std`
+#![no_std]
+
+extern crate alloc;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use soa_derive::StructOfArray;
+
+#[derive(Debug, Clone, PartialEq, StructOfArray)]
+#[soa_derive(Debug, Clone, PartialEq)]
+pub struct Particle {
+    pub name: String,
+    pub mass: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, StructOfArray)]
+#[soa_derive(Debug, Clone, PartialEq)]
+pub struct Body {
+    #[nested_soa]
+    pub particle: Particle,
+    pub velocity: [f32; 3],
+}
+
+#[derive(Debug, Clone, PartialEq, StructOfArray)]
+#[soa_derive(Debug, Clone, PartialEq)]
+#[soa(storage = "single_buffer")]
+pub struct Point {
+    pub x: f32,
+    pub y: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, StructOfArray)]
+#[soa_derive(Debug, Clone, PartialEq)]
+pub enum Shape {
+    Circle { radius: f32 },
+    Square { side: f32 },
+}
+
+#[test]
+fn vec() {
+    let mut particles = ParticleVec::new();
+    particles.push(Particle { name: "Cl".into(), mass: 35.4 });
+    particles.push(Particle { name: "Na".into(), mass: 22.9 });
+    particles.as_mut_slice().sort_by(|a, b| a.mass.partial_cmp(b.mass).unwrap());
+
+    let names = particles.iter().map(|p| p.name.as_str()).collect::<Vec<_>>();
+    assert_eq!(names, ["Na", "Cl"]);
+}
+
+#[test]
+fn nested() {
+    let mut bodies = BodyVec::with_capacity(2);
+    bodies.push(Body { particle: Particle { name: "H".into(), mass: 1.0 }, velocity: [0.0; 3] });
+    assert_eq!(bodies.particle.name, ["H"]);
+    assert_eq!(bodies.pop().unwrap().particle.mass, 1.0);
+}
+
+#[test]
+fn single_buffer() {
+    let mut points = PointVec::new();
+    points.push(Point { x: 1.0, y: 2.0 });
+    points.push(Point { x: 3.0, y: 4.0 });
+    assert_eq!(points.as_slice().y, [2.0, 4.0]);
+    assert_eq!(points.clone(), points);
+}
+
+#[test]
+fn array_vec() {
+    let mut points = PointArrayVec::<2>::new();
+    points.push(Point { x: 1.0, y: 2.0 }).unwrap();
+    assert_eq!(points.as_slice().x, [1.0]);
+}
+
+#[test]
+fn enums() {
+    let mut shapes = ShapeVec::new();
+    shapes.push(Shape::Circle { radius: 1.0 });
+    shapes.push(Shape::Square { side: 2.0 });
+    assert_eq!(shapes.len(), 2);
+}
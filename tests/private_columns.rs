@@ -0,0 +1,112 @@
+#![allow(clippy::float_cmp)]
+
+mod bodies {
+    use soa_derive::StructOfArray;
+
+    #[derive(Debug, Clone, PartialEq, StructOfArray)]
+    #[soa_derive(Debug, Clone, PartialEq)]
+    #[soa(private_columns)]
+    pub struct Point {
+        pub x: f64,
+        pub y: f64,
+    }
+
+    #[derive(Debug, Clone, PartialEq, StructOfArray)]
+    #[soa_derive(Debug, Clone, PartialEq)]
+    #[soa(private_columns)]
+    pub struct Body {
+        pub name: String,
+        #[nested_soa]
+        pub position: Point,
+        #[soa(explode)]
+        pub velocity: [f64; 2],
+    }
+
+    #[derive(Debug, Clone, PartialEq, StructOfArray)]
+    #[soa(private_columns)]
+    pub struct Pair(pub u32, pub u64);
+
+    #[derive(Debug, Clone, PartialEq, StructOfArray)]
+    #[soa(private_columns)]
+    pub struct Entry {
+        pub index: usize,
+        pub len: usize,
+        pub get: bool,
+    }
+}
+
+use self::bodies::*;
+
+fn body(name: &str, x: f64) -> Body {
+    Body {
+        name: name.into(),
+        position: Point { x, y: -x },
+        velocity: [x, 2.0 * x],
+    }
+}
+
+#[test]
+fn accessors() {
+    let mut bodies = BodyVec::new();
+    bodies.push(body("a", 1.0));
+    bodies.push(body("b", 2.0));
+    bodies.push(body("c", 3.0));
+
+    assert_eq!(bodies.len(), 3);
+    assert_eq!(bodies.column_name(), ["a", "b", "c"]);
+    assert_eq!(bodies.column_position().x, [1.0, 2.0, 3.0]);
+    assert_eq!(bodies.column_velocity().0[1], [2.0, 4.0, 6.0]);
+
+    bodies.column_name_mut()[1].push('!');
+    for y in bodies.column_position_mut().y {
+        *y *= 10.0;
+    }
+    bodies.column_velocity_mut().0[0][2] = 0.0;
+
+    assert_eq!(bodies.index(1).to_owned(), Body {
+        name: "b!".into(),
+        position: Point { x: 2.0, y: -20.0 },
+        velocity: [2.0, 4.0],
+    });
+    assert_eq!(bodies.pop().unwrap().velocity, [0.0, 6.0]);
+
+    let mut pairs = PairVec::new();
+    pairs.push(Pair(1, 2));
+    pairs.column_1_mut()[0] = 4;
+    assert_eq!(pairs.column_0(), [1]);
+    assert_eq!(pairs.column_1(), [4]);
+}
+
+#[test]
+fn columns() {
+    let points = PointVec::from_columns(vec![1.0, 2.0], vec![3.0, 4.0]).unwrap();
+    assert_eq!(points.column_y(), [3.0, 4.0]);
+    assert!(PointVec::from_columns(vec![1.0, 2.0], vec![3.0]).is_err());
+
+    let (x, y) = points.into_columns();
+    assert_eq!(x, [1.0, 2.0]);
+    assert_eq!(y, [3.0, 4.0]);
+}
+
+#[test]
+fn zip() {
+    let mut points = PointVec::from_columns(vec![1.0, 2.0], vec![3.0, 4.0]).unwrap();
+    for (x, y) in soa_derive::soa_zip!(points.as_mut_slice(), [mut x, y]) {
+        *x += y;
+    }
+    assert_eq!(points.column_x(), [4.0, 6.0]);
+}
+
+#[test]
+fn fields_named_like_methods() {
+    let mut entries = EntryVec::new();
+    entries.push(Entry { index: 3, len: 5, get: true });
+    entries.push(Entry { index: 8, len: 2, get: false });
+
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries.column_index(), [3, 8]);
+    assert_eq!(entries.column_len(), [5, 2]);
+    entries.column_get_mut()[1] = true;
+    assert_eq!(entries.get(1).map(|entry| *entry.get), Some(true));
+    assert_eq!(*entries.index(0).len, 5);
+}
@@ -165,3 +165,22 @@ fn write() {
 
     assert_eq!(DROP_COUNTER.load(Ordering::SeqCst), 1);
 }
+
+#[test]
+fn copy_to() {
+    let mut particles = ParticleVec::new();
+    particles.push(Particle::new(String::from("Na"), 1.0));
+    particles.push(Particle::new(String::from("Zn"), 2.0));
+    particles.push(Particle::new(String::from("Fe"), 3.0));
+
+    unsafe {
+        // overlapping copy, moving the last two elements to the front
+        let ptr = particles.as_mut_ptr();
+        let first = ptr.read();
+        ptr.add(1).copy_to(ptr, 2);
+        ptr.add(2).write(first);
+    }
+
+    assert_eq!(particles.name, ["Zn", "Fe", "Na"]);
+    assert_eq!(particles.mass, [2.0, 3.0, 1.0]);
+}
@@ -0,0 +1,188 @@
+#![cfg(feature = "rayon")]
+#![allow(clippy::float_cmp)]
+
+mod particles;
+use self::particles::*;
+
+use rayon::prelude::*;
+use soa_derive::{soa_par_zip, StructOfArray};
+
+fn particles(count: usize) -> ParticleVec {
+    let mut particles = ParticleVec::new();
+    for i in 0..count {
+        particles.push(Particle::new(format!("P{}", i), i as f64));
+    }
+    particles
+}
+
+#[test]
+fn par_iter() {
+    let particles = particles(1000);
+
+    let total = particles.par_iter().map(|particle| *particle.mass).sum::<f64>();
+    assert_eq!(total, 499500.0);
+
+    let names = particles.slice(10..13).par_iter().map(|particle| particle.name.clone()).collect::<Vec<_>>();
+    assert_eq!(names, ["P10", "P11", "P12"]);
+
+    let heavy = (&particles).into_par_iter().filter(|particle| *particle.mass >= 990.0).count();
+    assert_eq!(heavy, 10);
+
+    let position = particles.par_iter().position_any(|particle| particle.name == "P500");
+    assert_eq!(position, Some(500));
+    assert_eq!(particles.par_iter().len(), 1000);
+}
+
+#[test]
+fn par_iter_mut() {
+    let mut particles = particles(1000);
+
+    particles.par_iter_mut().for_each(|particle| {
+        *particle.mass *= 2.0;
+    });
+    assert_eq!(particles.mass[999], 1998.0);
+
+    let mut slice = particles.slice_mut(0..10);
+    slice.par_iter_mut().enumerate().for_each(|(i, particle)| {
+        *particle.name = format!("first {}", i);
+    });
+    assert_eq!(particles.name[3], "first 3");
+    assert_eq!(particles.name[10], "P10");
+
+    particles.as_mut_slice().into_par_iter().rev().enumerate().for_each(|(i, particle)| {
+        *particle.mass = i as f64;
+    });
+    assert_eq!(particles.mass[0], 999.0);
+    assert_eq!(particles.mass[999], 0.0);
+}
+
+#[test]
+fn par_chunks() {
+    let mut particles = particles(10);
+
+    let sizes = particles.par_chunks(4).map(|chunk| chunk.len()).collect::<Vec<_>>();
+    assert_eq!(sizes, [4, 4, 2]);
+
+    let sums = particles.as_slice().par_chunks(3)
+        .map(|chunk| chunk.mass.iter().sum::<f64>())
+        .collect::<Vec<_>>();
+    assert_eq!(sums, [3.0, 12.0, 21.0, 9.0]);
+
+    particles.par_chunks_mut(4).enumerate().for_each(|(i, chunk)| {
+        for mass in chunk.mass {
+            *mass = i as f64;
+        }
+    });
+    assert_eq!(particles.mass, [0.0, 0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0, 2.0, 2.0]);
+
+    let last = particles.as_mut_slice().par_chunks_mut(3).rev().map(|chunk| chunk.len()).collect::<Vec<_>>();
+    assert_eq!(last, [1, 3, 3, 3]);
+
+    assert_eq!(ParticleVec::new().par_chunks(3).count(), 0);
+}
+
+#[test]
+#[should_panic(expected = "chunk size must be non-zero")]
+fn par_chunks_zero() {
+    let particles = particles(5);
+    let _ = particles.par_chunks(0);
+}
+
+#[test]
+fn zip() {
+    let mut particles = particles(100);
+    let factors = (0..100).map(|i| i as f64).collect::<Vec<_>>();
+
+    soa_par_zip!(&mut particles, [mut mass, name], &factors).for_each(|(mass, name, factor)| {
+        assert!(name.starts_with('P'));
+        *mass *= factor;
+    });
+    assert_eq!(particles.mass[10], 100.0);
+
+    let total = soa_par_zip!(particles.as_slice(), [mass]).sum::<f64>();
+    assert_eq!(total, (0..100).map(|i| (i * i) as f64).sum::<f64>());
+
+    let mut slice = particles.slice_mut(0..50);
+    soa_par_zip!(&mut slice, [name, mut mass]).for_each(|(_, mass)| *mass = 0.0);
+    assert_eq!(particles.mass[49], 0.0);
+    assert_eq!(particles.mass[50], 2500.0);
+}
+
+#[derive(Debug, Clone, PartialEq, StructOfArray)]
+pub struct Point {
+    pub x: f32,
+    pub y: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, StructOfArray)]
+pub struct Body {
+    #[nested_soa]
+    pub position: Point,
+    #[soa(explode)]
+    pub velocity: [f32; 2],
+}
+
+#[test]
+fn nested() {
+    let mut bodies = BodyVec::new();
+    for i in 0..100 {
+        bodies.push(Body {
+            position: Point { x: i as f32, y: 0.0 },
+            velocity: [1.0, -1.0],
+        });
+    }
+
+    bodies.par_iter_mut().for_each(|body| {
+        *body.position.x += body.velocity[0];
+        *body.position.y += body.velocity[1];
+    });
+    assert_eq!(bodies.position.x[99], 100.0);
+    assert_eq!(bodies.position.y[42], -1.0);
+
+    let total = bodies.par_chunks(8).map(|chunk| chunk.position.x.iter().sum::<f32>()).sum::<f32>();
+    assert_eq!(total, 5050.0);
+}
+
+#[test]
+fn par_sort() {
+    let mut particles = ParticleVec::new();
+    for i in 0..1000 {
+        particles.push(Particle::new(format!("P{}", i), ((i * 7919) % 1000) as f64));
+    }
+
+    particles.as_mut_slice().par_sort_by(|a, b| a.mass.total_cmp(b.mass));
+    assert!(particles.mass.windows(2).all(|w| w[0] <= w[1]));
+    assert!(particles.iter().all(|p| p.name[1..].parse::<usize>().unwrap() * 7919 % 1000 == *p.mass as usize));
+
+    particles.as_mut_slice().par_sort_unstable_by_key(|particle| std::cmp::Reverse(*particle.mass as u64));
+    assert_eq!(particles.mass[0], 999.0);
+
+    particles.as_mut_slice().par_sort_by_key(|particle| particle.name.clone());
+    assert_eq!(particles.name[..3], ["P0", "P1", "P10"]);
+
+    particles.as_mut_slice().par_sort_unstable_by(|a, b| a.mass.total_cmp(b.mass));
+    assert_eq!(particles.mass[..3], [0.0, 1.0, 2.0]);
+
+    soa_derive::SoAVec::par_sort_by_key(&mut particles, |particle| particle.name.len());
+    assert_eq!(particles.name[0].len(), 2);
+}
+
+#[derive(Debug, Clone, PartialEq, StructOfArray)]
+pub struct Generic<T> {
+    pub value: T,
+    pub weight: f64,
+}
+
+#[test]
+fn generic() {
+    let mut vec = GenericVec::<u64>::new();
+    for i in 0..100 {
+        vec.push(Generic { value: i, weight: 2.0 });
+    }
+
+    let total = vec.par_iter().map(|item| *item.value as f64 * *item.weight).sum::<f64>();
+    assert_eq!(total, 9900.0);
+
+    vec.par_iter_mut().for_each(|item| *item.value += 1);
+    assert_eq!(vec.value[99], 100);
+}
@@ -29,3 +29,145 @@ fn serde_test() -> Result<(), serde_json::Error> {
     assert_eq!(soa, soa2);
     Ok(())
 }
+
+#[derive(Debug, Clone, PartialEq, StructOfArray, Serialize, Deserialize)]
+#[soa_derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Molecule {
+    pub name: String,
+    pub mass: f64,
+}
+
+impl Molecule {
+    pub fn new(name: String, mass: f64) -> Self {
+        Molecule {
+            name,
+            mass,
+        }
+    }
+}
+
+#[test]
+fn serialize_slices_and_references() -> Result<(), serde_json::Error> {
+    let mut soa = MoleculeVec::new();
+    soa.push(Molecule::new(String::from("Na"), 56.0));
+    soa.push(Molecule::new(String::from("Cl"), 35.0));
+    soa.push(Molecule::new(String::from("Zn"), 65.0));
+
+    let slice = soa.slice(1..3);
+    let json = serde_json::to_string(&slice)?;
+    assert_eq!(json, serde_json::to_string(&slice.to_vec())?);
+    assert_eq!(json, r#"{"name":["Cl","Zn"],"mass":[35.0,65.0]}"#);
+    let soa2: MoleculeVec = serde_json::from_str(&json)?;
+    assert_eq!(soa2, slice.to_vec());
+
+    let reference = soa.index(0);
+    let json = serde_json::to_string(&reference)?;
+    assert_eq!(json, serde_json::to_string(&reference.to_owned())?);
+    let molecule: Molecule = serde_json::from_str(&json)?;
+    assert_eq!(molecule, reference.to_owned());
+
+    {
+        let slice = soa.slice_mut(0..2);
+        assert_eq!(serde_json::to_string(&slice)?, r#"{"name":["Na","Cl"],"mass":[56.0,35.0]}"#);
+    }
+
+    let reference = soa.index_mut(2);
+    assert_eq!(serde_json::to_string(&reference)?, r#"{"name":"Zn","mass":65.0}"#);
+    Ok(())
+}
+
+#[derive(Debug, Clone, PartialEq, StructOfArray, Serialize, Deserialize)]
+#[soa_derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Point(f64, f64);
+
+#[derive(Debug, Clone, PartialEq, StructOfArray, Serialize, Deserialize)]
+#[soa_derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Body {
+    pub name: String,
+    #[nested_soa]
+    pub position: Point,
+}
+
+#[test]
+fn serialize_nested() -> Result<(), serde_json::Error> {
+    let mut soa = BodyVec::new();
+    soa.push(Body { name: String::from("Earth"), position: Point(1.0, 0.0) });
+    soa.push(Body { name: String::from("Mars"), position: Point(1.5, 0.2) });
+
+    let json = serde_json::to_string(&soa.as_slice())?;
+    assert_eq!(json, serde_json::to_string(&soa)?);
+    assert_eq!(json, r#"{"name":["Earth","Mars"],"position":[[1.0,1.5],[0.0,0.2]]}"#);
+    let soa2: BodyVec = serde_json::from_str(&json)?;
+    assert_eq!(soa2, soa);
+
+    let json = serde_json::to_string(&soa.index(1))?;
+    assert_eq!(json, serde_json::to_string(&soa.index(1).to_owned())?);
+    assert_eq!(json, r#"{"name":"Mars","position":[1.5,0.2]}"#);
+    Ok(())
+}
+
+#[test]
+fn deserialize_mismatched_columns() {
+    let error = serde_json::from_str::<ParticleVec>(r#"{"name":["Na"],"mass":[56.0,35.0]}"#).unwrap_err();
+    assert_eq!(error.to_string(), "invalid ParticleVec: column `mass` has length 2, expected 1");
+
+    let error = serde_json::from_str::<BodyVec>(r#"{"name":["Earth","Mars"],"position":[[1.0,1.5],[0.0]]}"#).unwrap_err();
+    // nested vectors are checked while deserializing them
+    assert!(error.to_string().starts_with("invalid PointVec: column `1` has length 1, expected 2"));
+
+    let error = serde_json::from_str::<BodyVec>(r#"{"name":["Earth"],"position":[[1.0,1.5],[0.0,0.2]]}"#).unwrap_err();
+    assert_eq!(error.to_string(), "invalid BodyVec: column `position` has length 2, expected 1");
+}
+
+#[derive(Debug, Clone, PartialEq, StructOfArray)]
+#[soa_derive(Debug, PartialEq, Serialize, Deserialize)]
+#[soa_attr(Vec, serde(deny_unknown_fields))]
+pub struct Generic<T: Clone> {
+    pub value: T,
+    pub count: usize,
+}
+
+#[test]
+fn deserialize_generic() -> Result<(), serde_json::Error> {
+    let soa: GenericVec<String> = serde_json::from_str(r#"{"value":["a","b"],"count":[1,2]}"#)?;
+    assert_eq!(soa.value, ["a", "b"]);
+    assert_eq!(soa.count, [1, 2]);
+
+    let error = serde_json::from_str::<GenericVec<String>>(r#"{"value":["a","b"],"count":[1]}"#).unwrap_err();
+    assert!(error.to_string().starts_with("invalid GenericVec: column `count` has length 1, expected 2"));
+
+    let error = serde_json::from_str::<GenericVec<f64>>(r#"{"value":[],"count":[],"other":[]}"#).unwrap_err();
+    assert!(error.to_string().starts_with("unknown field `other`"));
+    Ok(())
+}
+
+#[derive(Debug, Clone, PartialEq, StructOfArray, Serialize, Deserialize)]
+#[soa_derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[soa_attr(Vec, serde(rename_all = "UPPERCASE"))]
+#[serde(rename_all = "camelCase")]
+pub struct Atom {
+    pub atom_name: String,
+    #[serde(rename = "m")]
+    pub atom_mass: f64,
+}
+
+#[test]
+fn serde_attributes() -> Result<(), serde_json::Error> {
+    let mut soa = AtomVec::new();
+    soa.push(Atom { atom_name: String::from("Na"), atom_mass: 56.0 });
+    soa.push(Atom { atom_name: String::from("Cl"), atom_mass: 35.0 });
+
+    let json = serde_json::to_string(&soa.index(0))?;
+    assert_eq!(json, r#"{"atomName":"Na","m":56.0}"#);
+    assert_eq!(json, serde_json::to_string(&soa.index(0).to_owned())?);
+    assert_eq!(serde_json::to_string(&soa.index_mut(0))?, json);
+
+    let json = serde_json::to_string(&soa.as_slice())?;
+    assert_eq!(json, r#"{"ATOM_NAME":["Na","Cl"],"ATOM_MASS":[56.0,35.0]}"#);
+    assert_eq!(json, serde_json::to_string(&soa)?);
+    assert_eq!(serde_json::to_string(&soa.as_mut_slice())?, json);
+
+    let soa2: AtomVec = serde_json::from_str(&json)?;
+    assert_eq!(soa2, soa);
+    Ok(())
+}
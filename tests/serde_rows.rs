@@ -0,0 +1,116 @@
+use serde::{Deserialize, Serialize};
+use soa_derive::StructOfArray;
+
+#[derive(Debug, Clone, PartialEq, StructOfArray, Serialize, Deserialize)]
+#[soa_derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[soa(serde = "rows")]
+pub struct Particle {
+    pub name: String,
+    pub mass: f64,
+}
+
+impl Particle {
+    pub fn new(name: &str, mass: f64) -> Self {
+        Particle {
+            name: name.into(),
+            mass,
+        }
+    }
+}
+
+#[test]
+fn rows() -> Result<(), serde_json::Error> {
+    let mut soa = ParticleVec::new();
+    soa.push(Particle::new("Na", 56.0));
+    soa.push(Particle::new("Cl", 35.0));
+
+    let json = serde_json::to_string(&soa)?;
+    assert_eq!(json, r#"[{"name":"Na","mass":56.0},{"name":"Cl","mass":35.0}]"#);
+    assert_eq!(json, serde_json::to_string(&Vec::from(soa.clone()))?);
+
+    let soa2: ParticleVec = serde_json::from_str(&json)?;
+    assert_eq!(soa, soa2);
+
+    assert_eq!(serde_json::to_string(&soa.slice(1..2))?, r#"[{"name":"Cl","mass":35.0}]"#);
+    assert_eq!(serde_json::to_string(&soa.slice_mut(0..1))?, r#"[{"name":"Na","mass":56.0}]"#);
+    assert_eq!(serde_json::to_string(&soa.index(1))?, r#"{"name":"Cl","mass":35.0}"#);
+
+    let empty: ParticleVec = serde_json::from_str("[]")?;
+    assert!(empty.is_empty());
+
+    let error = serde_json::from_str::<ParticleVec>(r#"{"name":["Na"],"mass":[56.0]}"#).unwrap_err();
+    assert!(error.to_string().starts_with("invalid type: map, expected a sequence of Particle"));
+    Ok(())
+}
+
+#[derive(Debug, Clone, PartialEq, StructOfArray, Serialize, Deserialize)]
+#[soa_derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, StructOfArray, Serialize, Deserialize)]
+#[soa_derive(Debug, PartialEq, Serialize, Deserialize)]
+#[soa(serde = "rows")]
+pub struct Body<T> {
+    pub value: T,
+    #[nested_soa]
+    pub position: Point,
+}
+
+#[test]
+fn nested_and_generic() -> Result<(), serde_json::Error> {
+    let json = r#"[{"value":1,"position":{"x":1.0,"y":2.0}},{"value":2,"position":{"x":3.0,"y":4.0}}]"#;
+    let soa: BodyVec<u32> = serde_json::from_str(json)?;
+    assert_eq!(soa.value, [1, 2]);
+    assert_eq!(soa.position.y, [2.0, 4.0]);
+    assert_eq!(serde_json::to_string(&soa)?, json);
+
+    // the nested vector still uses the columns layout
+    assert_eq!(serde_json::to_string(&soa.position)?, r#"{"x":[1.0,3.0],"y":[2.0,4.0]}"#);
+    Ok(())
+}
+
+#[derive(Debug, Clone, PartialEq, StructOfArray, Serialize, Deserialize)]
+#[soa_derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[soa(serde = "rows", storage = "single_buffer")]
+pub struct Atom {
+    pub name: String,
+    pub charge: i8,
+}
+
+#[test]
+fn single_buffer() -> Result<(), serde_json::Error> {
+    let json = r#"[{"name":"Na","charge":1},{"name":"Cl","charge":-1}]"#;
+    let soa: AtomVec = serde_json::from_str(json)?;
+    assert_eq!(soa.len(), 2);
+    assert_eq!(soa.as_slice().charge, [1, -1]);
+    assert_eq!(serde_json::to_string(&soa)?, json);
+    Ok(())
+}
+
+#[derive(Debug, Clone, PartialEq, StructOfArray, Serialize, Deserialize)]
+#[soa_derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[soa(serde = "rows")]
+#[serde(rename_all = "camelCase")]
+pub struct Residue {
+    pub atom_name: String,
+    #[serde(rename = "id")]
+    pub residue_id: u32,
+}
+
+#[test]
+fn renamed() -> Result<(), serde_json::Error> {
+    let mut soa = ResidueVec::new();
+    soa.push(Residue { atom_name: String::from("CA"), residue_id: 3 });
+    soa.push(Residue { atom_name: String::from("N"), residue_id: 4 });
+
+    let json = serde_json::to_string(&soa)?;
+    assert_eq!(json, r#"[{"atomName":"CA","id":3},{"atomName":"N","id":4}]"#);
+    assert_eq!(json, serde_json::to_string(&Vec::from(soa.clone()))?);
+
+    let soa2: ResidueVec = serde_json::from_str(&json)?;
+    assert_eq!(soa2, soa);
+    Ok(())
+}
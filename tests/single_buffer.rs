@@ -0,0 +1,324 @@
+#![allow(clippy::float_cmp)]
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use soa_derive::{SoAVec, StructOfArray};
+
+#[derive(Debug, Clone, PartialEq, StructOfArray)]
+#[soa_derive(Debug, Clone, PartialEq)]
+#[soa(storage = "single_buffer")]
+pub struct Particle {
+    pub name: String,
+    pub mass: f64,
+    pub charge: i8,
+    pub position: [f32; 3],
+}
+
+impl Particle {
+    fn new(name: &str, mass: f64) -> Particle {
+        Particle { name: name.into(), mass, charge: 0, position: [mass as f32; 3] }
+    }
+}
+
+#[test]
+fn push_and_access() {
+    let mut particles = ParticleVec::new();
+    assert_eq!(particles.capacity(), 0);
+    assert!(particles.is_empty());
+
+    particles.push(Particle::new("Na", 22.9));
+    particles.push(Particle::new("Cl", 35.4));
+    particles.push(Particle::new("Zn", 65.4));
+
+    assert_eq!(particles.len(), 3);
+    assert!(particles.capacity() >= 3);
+    assert_eq!(particles.index(1).name, "Cl");
+    assert_eq!(*particles.get(2).unwrap().mass, 65.4);
+    assert!(particles.get(3).is_none());
+
+    let slice = particles.as_slice();
+    assert_eq!(slice.name, ["Na", "Cl", "Zn"]);
+    assert_eq!(slice.mass, [22.9, 35.4, 65.4]);
+    assert_eq!(slice.position[2], [65.4; 3]);
+
+    for particle in particles.iter_mut() {
+        *particle.charge = 2;
+    }
+    assert_eq!(particles.as_slice().charge, [2, 2, 2]);
+
+    let names = particles.iter().map(|p| p.name.clone()).collect::<Vec<_>>();
+    assert_eq!(names, ["Na", "Cl", "Zn"]);
+
+    assert_eq!(particles.slice(1..3).mass, [35.4, 65.4]);
+    particles.slice_mut(0..1).mass[0] = 23.0;
+    assert_eq!(particles.index(0).to_owned().mass, 23.0);
+}
+
+#[test]
+fn regions_are_aligned() {
+    let mut particles = ParticleVec::with_capacity(7);
+    assert_eq!(particles.capacity(), 7);
+    for i in 0..7 {
+        particles.push(Particle::new(&i.to_string(), i as f64));
+    }
+    assert_eq!(particles.capacity(), 7);
+
+    let ptr = particles.as_ptr();
+    assert_eq!(ptr.name as usize % std::mem::align_of::<String>(), 0);
+    assert_eq!(ptr.mass as usize % std::mem::align_of::<f64>(), 0);
+    assert_eq!(ptr.position as usize % std::mem::align_of::<[f32; 3]>(), 0);
+
+    // all fields live in the same allocation, one after the other
+    assert!((ptr.name as usize) < (ptr.mass as usize));
+    assert!((ptr.mass as usize) < (ptr.charge as usize));
+    assert!((ptr.charge as usize) < (ptr.position as usize));
+    assert!((ptr.position as usize) - (ptr.name as usize) < 7 * std::mem::size_of::<Particle>());
+}
+
+#[test]
+fn capacity() {
+    let mut particles = ParticleVec::new();
+    particles.reserve(10);
+    assert!(particles.capacity() >= 10);
+
+    particles.reserve_exact(20);
+    assert_eq!(particles.capacity(), 20);
+
+    particles.push(Particle::new("H", 1.0));
+    particles.shrink_to_fit();
+    assert_eq!(particles.capacity(), 1);
+    assert_eq!(particles.index(0).name, "H");
+
+    for i in 0..100 {
+        particles.push(Particle::new("He", f64::from(i)));
+    }
+    assert_eq!(particles.len(), 101);
+    assert_eq!(particles.as_slice().mass[100], 99.0);
+
+    particles.truncate(10);
+    assert_eq!(particles.len(), 10);
+    particles.clear();
+    assert!(particles.is_empty());
+    assert!(particles.capacity() >= 101);
+}
+
+#[test]
+fn remove_and_insert() {
+    let mut particles = ParticleVec::new();
+    for name in ["A", "B", "C", "D", "E"] {
+        particles.push(Particle::new(name, 1.0));
+    }
+
+    assert_eq!(particles.remove(1).name, "B");
+    assert_eq!(particles.as_slice().name, ["A", "C", "D", "E"]);
+
+    assert_eq!(particles.swap_remove(0).name, "A");
+    assert_eq!(particles.as_slice().name, ["E", "C", "D"]);
+
+    particles.insert(1, Particle::new("F", 2.0));
+    particles.insert(4, Particle::new("G", 3.0));
+    assert_eq!(particles.as_slice().name, ["E", "F", "C", "D", "G"]);
+    assert_eq!(particles.as_slice().mass, [1.0, 2.0, 1.0, 1.0, 3.0]);
+
+    let old = particles.replace(2, Particle::new("H", 4.0));
+    assert_eq!(old.name, "C");
+    assert_eq!(particles.as_slice().name, ["E", "F", "H", "D", "G"]);
+
+    assert_eq!(particles.pop().unwrap().name, "G");
+
+    let mut other = particles.split_off(2);
+    assert_eq!(particles.as_slice().name, ["E", "F"]);
+    assert_eq!(other.as_slice().name, ["H", "D"]);
+
+    particles.append(&mut other);
+    assert!(other.is_empty());
+    assert_eq!(particles.as_slice().name, ["E", "F", "H", "D"]);
+
+    particles.retain(|p| p.name != "F");
+    assert_eq!(particles.as_slice().name, ["E", "H", "D"]);
+}
+
+#[test]
+fn derives() {
+    let mut particles = ParticleVec::new();
+    particles.push(Particle::new("Na", 22.9));
+    particles.push(Particle::new("Cl", 35.4));
+
+    let copy = particles.clone();
+    assert_eq!(copy, particles);
+    assert_eq!(copy.as_slice().to_vec(), particles);
+
+    particles.resize(4, Particle::new("H", 1.0));
+    assert_eq!(particles.as_slice().name, ["Na", "Cl", "H", "H"]);
+    assert_ne!(copy, particles);
+
+    assert_eq!(
+        format!("{:?}", copy),
+        r#"ParticleVec { name: ["Na", "Cl"], mass: [22.9, 35.4], charge: [0, 0], position: [[22.9, 22.9, 22.9], [35.4, 35.4, 35.4]] }"#
+    );
+}
+
+#[derive(Debug, Clone, PartialEq, StructOfArray)]
+#[soa_derive(Debug, Clone, PartialEq)]
+#[soa(storage = "single_buffer")]
+pub struct Tagged<T> {
+    pub tag: T,
+    pub value: f64,
+}
+
+#[test]
+fn generic_derives() {
+    let mut vec = TaggedVec::<String>::new();
+    vec.push(Tagged { tag: "a".into(), value: 1.0 });
+    vec.push(Tagged { tag: "b".into(), value: 2.0 });
+
+    let copy = vec.clone();
+    assert_eq!(copy, vec);
+    assert_eq!(copy.as_slice().to_vec(), vec);
+
+    vec.resize(3, Tagged { tag: "c".into(), value: 3.0 });
+    assert_eq!(vec.as_slice().tag, ["a", "b", "c"]);
+    assert_eq!(vec.as_slice().value, [1.0, 2.0, 3.0]);
+    assert_ne!(copy, vec);
+}
+
+#[test]
+fn generic_soa_vec() {
+    fn fill<V: SoAVec<Particle>>(vec: &mut V) {
+        for i in 0..10 {
+            vec.push(Particle::new("X", f64::from(i)));
+        }
+    }
+
+    let mut particles = ParticleVec::new();
+    fill(&mut particles);
+    assert_eq!(particles.len(), 10);
+    assert_eq!(particles.iter().map(|p| *p.mass).sum::<f64>(), 45.0);
+
+    particles.sort_by(|a, b| b.mass.partial_cmp(a.mass).unwrap());
+    assert_eq!(particles.as_slice().mass[0], 9.0);
+}
+
+#[derive(StructOfArray)]
+#[soa(storage = "single_buffer")]
+pub struct Tracked {
+    pub counter: Rc<Cell<usize>>,
+    pub value: u8,
+}
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        self.counter.set(self.counter.get() + 1);
+    }
+}
+
+#[test]
+fn drop_elements() {
+    let counter = Rc::new(Cell::new(0));
+    {
+        let mut vec = TrackedVec::new();
+        for value in 0..8 {
+            vec.push(Tracked { counter: counter.clone(), value });
+        }
+        assert_eq!(Rc::strong_count(&counter), 9);
+
+        vec.truncate(5);
+        assert_eq!(Rc::strong_count(&counter), 6);
+
+        let removed = vec.remove(0);
+        assert_eq!(removed.value, 0);
+        drop(removed);
+        assert_eq!(counter.get(), 1);
+    }
+    assert_eq!(Rc::strong_count(&counter), 1);
+}
+
+#[test]
+fn drain_splice_extract() {
+    let counter = Rc::new(Cell::new(0));
+    let mut vec = TrackedVec::new();
+    for value in 0..8 {
+        vec.push(Tracked { counter: counter.clone(), value });
+    }
+
+    let drained = vec.drain(2..4).map(|tracked| tracked.value).collect::<Vec<_>>();
+    assert_eq!(drained, [2, 3]);
+    assert_eq!(counter.get(), 2);
+    assert_eq!(vec.as_slice().value, [0, 1, 4, 5, 6, 7]);
+
+    let new = (10..13).map(|value| Tracked { counter: counter.clone(), value });
+    drop(vec.splice(1..2, new));
+    assert_eq!(counter.get(), 3);
+    assert_eq!(vec.as_slice().value, [0, 10, 11, 12, 4, 5, 6, 7]);
+
+    let odd = vec.extract_if(.., |tracked| *tracked.value % 2 == 1).count();
+    assert_eq!(odd, 3);
+    assert_eq!(counter.get(), 6);
+    assert_eq!(vec.as_slice().value, [0, 10, 12, 4, 6]);
+
+    drop(vec);
+    assert_eq!(Rc::strong_count(&counter), 1);
+}
+
+#[test]
+fn into_iter() {
+    let counter = Rc::new(Cell::new(0));
+    let mut vec = TrackedVec::new();
+    for value in 0..5 {
+        vec.push(Tracked { counter: counter.clone(), value });
+    }
+
+    let mut iter = vec.into_iter();
+    assert_eq!(iter.len(), 5);
+    assert_eq!(iter.next().unwrap().value, 0);
+    assert_eq!(iter.next_back().unwrap().value, 4);
+    assert_eq!(counter.get(), 2);
+    drop(iter);
+    assert_eq!(counter.get(), 5);
+    assert_eq!(Rc::strong_count(&counter), 1);
+
+    let vec = (0..3).map(|value| Tracked { counter: counter.clone(), value }).collect::<Vec<_>>();
+    let vec = Vec::from(TrackedVec::from(vec));
+    assert_eq!(vec.iter().map(|tracked| tracked.value).collect::<Vec<_>>(), [0, 1, 2]);
+}
+
+#[derive(Debug, Clone, PartialEq, StructOfArray)]
+#[soa(storage = "single_buffer")]
+pub struct Empty(pub (), pub [u64; 0]);
+
+#[test]
+fn zero_sized_fields() {
+    let mut vec = EmptyVec::new();
+    assert_eq!(vec.capacity(), usize::MAX);
+    for _ in 0..10 {
+        vec.push(Empty((), []));
+    }
+    vec.shrink_to_fit();
+    assert_eq!(vec.len(), 10);
+    assert_eq!(vec.capacity(), usize::MAX);
+    assert_eq!(vec.pop(), Some(Empty((), [])));
+}
+
+#[derive(Debug, Clone, PartialEq, StructOfArray)]
+#[soa_derive(Debug, Clone, PartialEq)]
+#[soa(storage = "single_buffer")]
+pub struct Pair<T: Clone>(pub T, pub u8);
+
+#[test]
+fn generic_tuple_struct() {
+    let mut pairs = PairVec::<u64>::new();
+    pairs.push(Pair(4, 1));
+    pairs.push(Pair(8, 2));
+    assert_eq!(pairs.as_slice().0, [4, 8]);
+    assert_eq!(pairs.index(1).to_owned(), Pair(8, 2));
+    assert_eq!(format!("{:?}", pairs), "PairVec([4, 8], [1, 2])");
+}
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn send_sync() {
+    assert_send_sync::<ParticleVec>();
+    assert_send_sync::<PairVec<String>>();
+}
@@ -0,0 +1,90 @@
+use std::marker::PhantomData;
+use std::mem::ManuallyDrop;
+
+use soa_derive::StructOfArray;
+
+fn unknown() -> String {
+    String::from("unknown")
+}
+
+#[derive(Debug, Clone, PartialEq, StructOfArray)]
+#[soa_derive(Debug, Clone, PartialEq)]
+pub struct Particle<T: Clone> {
+    pub name: String,
+    pub mass: T,
+    #[soa(skip)]
+    pub cache: Option<T>,
+    #[soa(skip, default = "unknown")]
+    pub description: String,
+    #[soa(skip)]
+    pub marker: PhantomData<T>,
+}
+
+impl Particle<f64> {
+    fn new(name: &str, mass: f64) -> Self {
+        Particle {
+            name: name.into(),
+            mass,
+            cache: Some(mass * 2.0),
+            description: format!("particle {}", name),
+            marker: PhantomData,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, StructOfArray)]
+pub struct Tuple(pub u32, #[soa(skip)] pub Vec<u32>);
+
+#[test]
+fn skipped_fields_are_not_stored() {
+    let mut particles = ParticleVec::<f64>::new();
+    particles.push(Particle::new("H", 1.0));
+    particles.push(Particle::new("He", 4.0));
+    particles.push(Particle::new("Li", 6.9));
+
+    assert_eq!(particles.name, ["H", "He", "Li"]);
+    assert_eq!(particles.mass, [1.0, 4.0, 6.9]);
+
+    let expected = Particle {
+        name: "He".into(),
+        mass: 4.0,
+        cache: None,
+        description: "unknown".into(),
+        marker: PhantomData,
+    };
+    assert_eq!(particles.index(1).to_owned(), expected);
+    assert_eq!(particles.index_mut(1).to_owned(), expected);
+    // the value is still owned by the vector, we should not drop it
+    let read = ManuallyDrop::new(unsafe { particles.as_ptr().add(1).read() });
+    assert_eq!(*read, expected);
+
+    assert_eq!(particles.pop().unwrap().description, "unknown");
+    assert_eq!(particles.swap_remove(0).name, "H");
+    assert_eq!(particles.remove(0), expected);
+    assert!(particles.is_empty());
+}
+
+#[test]
+fn replace_and_insert() {
+    let mut particles = ParticleVec::<f64>::new();
+    particles.push(Particle::new("H", 1.0));
+    particles.insert(0, Particle::new("O", 16.0));
+
+    let old = particles.replace(1, Particle::new("C", 12.0));
+    assert_eq!(old.name, "H");
+    assert_eq!(old.cache, None);
+
+    let old = particles.index_mut(0).replace(Particle::new("N", 14.0));
+    assert_eq!(old.name, "O");
+    assert_eq!(old.description, "unknown");
+
+    assert_eq!(particles.name, ["N", "C"]);
+}
+
+#[test]
+fn skipped_tuple_fields() {
+    let mut vec = TupleVec::new();
+    vec.push(Tuple(3, vec![1, 2, 3]));
+    assert_eq!(vec.0, [3]);
+    assert_eq!(vec.pop(), Some(Tuple(3, Vec::new())));
+}
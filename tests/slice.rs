@@ -97,3 +97,48 @@ fn get() {
         assert_eq!(particles.as_slice().get_unchecked(0).name, "Cl");
     }
 }
+
+fn sorted() -> ParticleVec {
+    let mut particles = ParticleVec::new();
+    particles.push(Particle::new(String::from("H"), 1.0));
+    particles.push(Particle::new(String::from("C"), 12.0));
+    particles.push(Particle::new(String::from("N"), 14.0));
+    particles.push(Particle::new(String::from("O"), 16.0));
+    particles.push(Particle::new(String::from("Na"), 23.0));
+    particles
+}
+
+#[test]
+fn binary_search() {
+    let particles = sorted();
+    let slice = particles.as_slice();
+
+    assert_eq!(slice.binary_search_by(|particle| particle.mass.total_cmp(&14.0)), Ok(2));
+    assert_eq!(slice.binary_search_by(|particle| particle.mass.total_cmp(&15.0)), Err(3));
+    assert_eq!(slice.binary_search_by(|particle| particle.mass.total_cmp(&0.0)), Err(0));
+    assert_eq!(slice.binary_search_by(|particle| particle.mass.total_cmp(&42.0)), Err(5));
+
+    assert_eq!(slice.binary_search_by_key(&23, |particle| *particle.mass as u32), Ok(4));
+    assert_eq!(particles.slice(1..4).binary_search_by_key(&16, |particle| *particle.mass as u32), Ok(2));
+
+    assert_eq!(slice.partition_point(|particle| *particle.mass < 13.0), 2);
+    assert_eq!(slice.partition_point(|_| true), 5);
+    assert_eq!(ParticleVec::new().as_slice().partition_point(|_| true), 0);
+}
+
+#[test]
+fn contains() {
+    let particles = sorted();
+    let slice = particles.as_slice();
+
+    let oxygen = Particle::new(String::from("O"), 16.0);
+    assert!(slice.contains(oxygen.as_ref()));
+    assert!(!particles.slice(0..3).contains(oxygen.as_ref()));
+
+    assert!(slice.starts_with(particles.slice(0..2)));
+    assert!(!slice.starts_with(particles.slice(1..3)));
+    assert!(slice.starts_with(ParticleVec::new().as_slice()));
+    assert!(slice.ends_with(particles.slice(3..5)));
+    assert!(!slice.ends_with(particles.slice(2..4)));
+    assert!(!particles.slice(0..1).ends_with(slice));
+}
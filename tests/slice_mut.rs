@@ -147,3 +147,121 @@ fn sort() {
 
     assert_eq!(particles, ordered_particles);
 }
+
+fn unsorted() -> ParticleVec {
+    let mut particles = ParticleVec::new();
+    particles.push(Particle::new(String::from("Na3"), 168.0));
+    particles.push(Particle::new(String::from("Na"), 56.0));
+    particles.push(Particle::new(String::from("Na4"), 224.0));
+    particles.push(Particle::new(String::from("Na2"), 112.0));
+    particles.push(Particle::new(String::from("Cl"), 56.0));
+    particles
+}
+
+#[test]
+fn sort_unstable() {
+    let mut particles = unsorted();
+    particles.as_mut_slice().sort_unstable_by(|j, k| j.mass.total_cmp(k.mass).then(j.name.cmp(k.name)));
+    assert_eq!(particles.name, ["Cl", "Na", "Na2", "Na3", "Na4"]);
+    assert_eq!(particles.mass, [56.0, 56.0, 112.0, 168.0, 224.0]);
+
+    particles.as_mut_slice().sort_unstable_by_key(|particle| std::cmp::Reverse(particle.name.clone()));
+    assert_eq!(particles.name, ["Na4", "Na3", "Na2", "Na", "Cl"]);
+}
+
+#[test]
+fn sort_by_cached_key() {
+    let mut particles = unsorted();
+
+    let mut calls = 0;
+    particles.as_mut_slice().sort_by_cached_key(|particle| {
+        calls += 1;
+        particle.mass.to_string()
+    });
+    assert_eq!(calls, 5);
+    // the sort is stable
+    assert_eq!(particles.name, ["Na2", "Na3", "Na4", "Na", "Cl"]);
+}
+
+#[test]
+fn sort_by_column() {
+    let mut particles = unsorted();
+    particles.as_mut_slice().sort_by_column(|slice| &slice.name[..]);
+    assert_eq!(particles.name, ["Cl", "Na", "Na2", "Na3", "Na4"]);
+    assert_eq!(particles.mass, [56.0, 56.0, 112.0, 168.0, 224.0]);
+
+    let mut particles = unsorted();
+    let mut slice = particles.slice_mut(1..5);
+    slice.sort_by_column(|slice| &slice.name[..]);
+    assert_eq!(particles.name, ["Na3", "Cl", "Na", "Na2", "Na4"]);
+    assert_eq!(particles.mass, [168.0, 56.0, 56.0, 112.0, 224.0]);
+}
+
+#[test]
+fn reverse_rotate() {
+    let mut particles = unsorted();
+    particles.as_mut_slice().reverse();
+    assert_eq!(particles.name, ["Cl", "Na2", "Na4", "Na", "Na3"]);
+    assert_eq!(particles.mass, [56.0, 112.0, 224.0, 56.0, 168.0]);
+
+    particles.as_mut_slice().rotate_left(2);
+    assert_eq!(particles.name, ["Na4", "Na", "Na3", "Cl", "Na2"]);
+    assert_eq!(particles.mass, [224.0, 56.0, 168.0, 56.0, 112.0]);
+
+    particles.slice_mut(1..4).rotate_right(1);
+    assert_eq!(particles.name, ["Na4", "Cl", "Na", "Na3", "Na2"]);
+    assert_eq!(particles.mass, [224.0, 56.0, 56.0, 168.0, 112.0]);
+}
+
+#[test]
+fn fill() {
+    let mut particles = unsorted();
+    particles.slice_mut(1..4).fill(Particle::new(String::from("Ar"), 40.0));
+    assert_eq!(particles.name, ["Na3", "Ar", "Ar", "Ar", "Cl"]);
+    assert_eq!(particles.mass, [168.0, 40.0, 40.0, 40.0, 56.0]);
+
+    ParticleVec::new().as_mut_slice().fill(Particle::new(String::from("Ar"), 40.0));
+}
+
+#[test]
+fn swap_with_slice() {
+    let mut particles = unsorted();
+    let mut other = unsorted();
+    other.as_mut_slice().reverse();
+
+    particles.slice_mut(0..2).swap_with_slice(other.slice_mut(3..5));
+    assert_eq!(particles.name, ["Na", "Na3", "Na4", "Na2", "Cl"]);
+    assert_eq!(other.name, ["Cl", "Na2", "Na4", "Na3", "Na"]);
+
+    let (mut left, mut right) = particles.as_mut_slice().split_at_mut(2);
+    left.swap_with_slice(right.index_mut(1..3));
+    assert_eq!(particles.name, ["Na2", "Cl", "Na4", "Na", "Na3"]);
+    assert_eq!(particles.mass, [112.0, 56.0, 224.0, 56.0, 168.0]);
+}
+
+#[test]
+#[should_panic(expected = "destination and source slices have different lengths")]
+fn swap_with_slice_length() {
+    let mut particles = unsorted();
+    let mut other = unsorted();
+    particles.slice_mut(0..2).swap_with_slice(other.slice_mut(0..3));
+}
+
+#[test]
+fn search() {
+    let mut particles = unsorted();
+    particles.as_mut_slice().sort_by_column(|slice| &slice.name[..]);
+
+    let slice = particles.as_mut_slice();
+    assert_eq!(slice.binary_search_by(|particle| particle.name.as_str().cmp("Na2")), Ok(2));
+    assert_eq!(slice.binary_search_by_key(&"Mg", |particle| particle.name.as_str()), Err(1));
+    assert_eq!(slice.partition_point(|particle| particle.name.len() < 3), 2);
+
+    let chlorine = Particle::new(String::from("Cl"), 56.0);
+    assert!(slice.contains(chlorine.as_ref()));
+
+    let needle = particles.slice(0..2).to_vec();
+    let slice = particles.as_mut_slice();
+    assert!(slice.starts_with(needle.as_slice()));
+    assert!(!slice.ends_with(needle.as_slice()));
+}
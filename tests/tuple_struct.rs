@@ -0,0 +1,81 @@
+#![allow(clippy::float_cmp)]
+
+use soa_derive::StructOfArray;
+
+#[derive(Debug, Clone, PartialEq, StructOfArray)]
+#[soa_derive(Debug, Clone, PartialEq)]
+pub struct Vec3(pub f32, pub f32, pub f32);
+
+#[derive(Debug, Clone, PartialEq, StructOfArray)]
+#[soa_derive(Debug, Clone, PartialEq)]
+pub struct Body(pub String, #[nested_soa] pub Vec3);
+
+#[derive(Debug, Clone, PartialEq, StructOfArray)]
+pub struct Mass<T>(pub T);
+
+#[test]
+fn tuple_struct() {
+    let mut vec = Vec3Vec::new();
+    vec.push(Vec3(1.0, 2.0, 3.0));
+    vec.push(Vec3(4.0, 5.0, 6.0));
+    vec.push(Vec3(7.0, 8.0, 9.0));
+
+    assert_eq!(vec.len(), 3);
+    assert_eq!(vec.0, [1.0, 4.0, 7.0]);
+    assert_eq!(vec.2, [3.0, 6.0, 9.0]);
+    assert_eq!(*vec.index(1).1, 5.0);
+
+    for v in vec.iter_mut() {
+        *v.0 *= 10.0;
+    }
+    assert_eq!(vec.0, [10.0, 40.0, 70.0]);
+
+    let slice = vec.slice(1..3);
+    assert_eq!(slice.first().unwrap().to_owned(), Vec3(40.0, 5.0, 6.0));
+    assert_eq!(slice.iter().map(|v| *v.2).sum::<f32>(), 15.0);
+
+    let mut slice = vec.as_mut_slice();
+    *slice.last_mut().unwrap().1 = -1.0;
+    let (first, _) = slice.split_first_mut().unwrap();
+    *first.2 = -2.0;
+
+    assert_eq!(vec.swap_remove(0), Vec3(10.0, 2.0, -2.0));
+    assert_eq!(vec.pop(), Some(Vec3(40.0, 5.0, 6.0)));
+    assert_eq!(vec.as_slice().to_vec(), Vec3Vec(vec![70.0], vec![-1.0], vec![9.0]));
+
+    let ptr = vec.as_ptr();
+    assert_eq!(unsafe { ptr.read() }, Vec3(70.0, -1.0, 9.0));
+}
+
+#[test]
+fn nested_tuple_struct() {
+    let mut bodies = BodyVec::new();
+    bodies.push(Body("sun".into(), Vec3(0.0, 0.0, 0.0)));
+    bodies.push(Body("earth".into(), Vec3(1.0, 0.0, 0.0)));
+
+    assert_eq!(bodies.0, ["sun", "earth"]);
+    assert_eq!(bodies.1.0, [0.0, 1.0]);
+    assert_eq!(bodies.index(1).to_owned(), Body("earth".into(), Vec3(1.0, 0.0, 0.0)));
+}
+
+#[test]
+fn generic_tuple_struct() {
+    let masses: MassVec<f64> = vec![Mass(1.0), Mass(2.0)].into_iter().collect();
+    assert_eq!(masses.0, [1.0, 2.0]);
+    assert_eq!(masses.iter().map(|m| *m.0).sum::<f64>(), 3.0);
+}
+
+#[test]
+fn tuple_struct_columns() {
+    let positions = Vec3Vec::from_columns(vec![1.0], vec![2.0], vec![3.0, 4.0]);
+    assert_eq!(positions.unwrap_err(), soa_derive::LengthMismatch::new("2", 1, 2));
+
+    let positions = Vec3Vec::from_columns(vec![1.0, 2.0], vec![3.0], vec![4.0, 5.0]).unwrap_err();
+    assert_eq!(positions.column, "1");
+
+    let positions = Vec3Vec::from_columns(vec![1.0, 2.0], vec![3.0, 4.0], vec![5.0, 6.0]).unwrap();
+    let bodies = BodyVec::from_columns(vec![String::from("a"), String::from("b")], positions).unwrap();
+    let (names, positions) = bodies.into_columns();
+    assert_eq!(names, ["a", "b"]);
+    assert_eq!(positions.2, [5.0, 6.0]);
+}
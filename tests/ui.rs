@@ -0,0 +1,8 @@
+#![allow(unexpected_cfgs)]
+
+#[test]
+#[cfg_attr(not(rustc_runs_ui_tests), ignore = "the expected compiler output is only checked with the compiler version set in build.rs")]
+fn ui() {
+    let tests = trybuild::TestCases::new();
+    tests.compile_fail("tests/ui/*.rs");
+}
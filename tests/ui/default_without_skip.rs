@@ -0,0 +1,13 @@
+use soa_derive::StructOfArray;
+
+fn zero() -> f64 {
+    0.0
+}
+
+#[derive(StructOfArray)]
+struct Particle {
+    #[soa(default = "zero")]
+    mass: f64,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use soa_derive::StructOfArray;
+
+#[derive(StructOfArray)]
+struct Empty {}
+
+#[derive(StructOfArray)]
+struct Unit;
+
+fn main() {}
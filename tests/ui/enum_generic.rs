@@ -0,0 +1,9 @@
+use soa_derive::StructOfArray;
+
+#[derive(StructOfArray)]
+enum Value<T> {
+    Some(T),
+    None,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use soa_derive::StructOfArray;
+
+#[derive(StructOfArray)]
+pub enum Path {
+    Super(usize),
+    Root,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use soa_derive::StructOfArray;
+
+#[derive(StructOfArray)]
+enum Entry {
+    Tags(Vec<String>),
+    Value(f64),
+}
+
+fn main() {}
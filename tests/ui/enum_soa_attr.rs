@@ -0,0 +1,9 @@
+use soa_derive::StructOfArray;
+
+#[derive(StructOfArray)]
+#[soa_attr(Vec, derive(Debug))]
+enum Shape {
+    Circle { r: f64 },
+}
+
+fn main() {}
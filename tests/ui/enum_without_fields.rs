@@ -0,0 +1,10 @@
+use soa_derive::StructOfArray;
+
+#[derive(StructOfArray)]
+enum Color {
+    Red,
+    Green,
+    Blue,
+}
+
+fn main() {}
@@ -0,0 +1,21 @@
+use soa_derive::StructOfArray;
+
+#[derive(StructOfArray)]
+struct Particle {
+    #[soa(explode)]
+    mass: f64,
+}
+
+#[derive(StructOfArray)]
+struct Empty {
+    #[soa(explode)]
+    data: [f64; 0],
+}
+
+#[derive(StructOfArray)]
+struct Unit {
+    #[soa(explode)]
+    data: (),
+}
+
+fn main() {}
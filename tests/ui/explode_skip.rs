@@ -0,0 +1,10 @@
+use soa_derive::StructOfArray;
+
+#[derive(StructOfArray)]
+struct Particle {
+    mass: f64,
+    #[soa(skip, explode)]
+    position: [f64; 3],
+}
+
+fn main() {}
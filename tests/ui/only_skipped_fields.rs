@@ -0,0 +1,9 @@
+use soa_derive::StructOfArray;
+
+#[derive(StructOfArray)]
+struct Cache {
+    #[soa(skip)]
+    value: Option<f64>,
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+mod particles {
+    use soa_derive::StructOfArray;
+
+    #[derive(StructOfArray)]
+    #[soa(private_columns)]
+    pub struct Particle {
+        pub name: String,
+        pub mass: f64,
+    }
+}
+
+use particles::ParticleVec;
+
+fn main() {
+    let mut particles = ParticleVec::new();
+    particles.mass.push(1.0);
+}
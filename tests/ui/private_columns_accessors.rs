@@ -0,0 +1,18 @@
+mod particles {
+    use soa_derive::StructOfArray;
+
+    #[derive(StructOfArray)]
+    #[soa(private_columns)]
+    pub struct Particle {
+        pub name: String,
+        pub mass: f64,
+    }
+}
+
+use particles::ParticleVec;
+
+fn main() {
+    let particles = ParticleVec::new();
+    // columns are accessed with `column_mass()`, not `mass()`
+    let _ = particles.mass();
+}
@@ -0,0 +1,10 @@
+use soa_derive::StructOfArray;
+
+#[derive(StructOfArray)]
+#[soa_derive(Debug, PartialOrd)]
+#[soa(storage = "single_buffer")]
+pub struct Particle {
+    mass: f32,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use soa_derive::StructOfArray;
+
+#[derive(StructOfArray)]
+#[soa(storage = "single_buffer")]
+pub struct Particle {
+    #[soa(explode)]
+    position: [f32; 3],
+    mass: f32,
+}
+
+fn main() {}
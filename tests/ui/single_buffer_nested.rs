@@ -0,0 +1,17 @@
+use soa_derive::StructOfArray;
+
+#[derive(StructOfArray)]
+pub struct Point {
+    x: f32,
+    y: f32,
+}
+
+#[derive(StructOfArray)]
+#[soa(storage = "single_buffer")]
+pub struct Particle {
+    #[nested_soa]
+    point: Point,
+    mass: f32,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use soa_derive::StructOfArray;
+
+#[derive(StructOfArray)]
+#[soa(storage = "single_buffer", private_columns)]
+pub struct Particle {
+    name: String,
+    mass: f64,
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use soa_derive::StructOfArray;
+
+#[derive(StructOfArray)]
+struct Point {
+    x: f64,
+}
+
+#[derive(StructOfArray)]
+struct Particle {
+    mass: f64,
+    #[nested_soa]
+    #[soa(skip)]
+    position: Point,
+}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use soa_derive::StructOfArray;
+
+#[derive(StructOfArray)]
+struct Particle(#[soa(skip)] Option<f64>, f64);
+
+fn main() {}
@@ -0,0 +1,9 @@
+use soa_derive::StructOfArray;
+
+#[derive(StructOfArray)]
+#[soa_attr(Vec)]
+struct Particle {
+    mass: f64,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use soa_derive::StructOfArray;
+
+#[derive(StructOfArray)]
+struct Particle {
+    #[soa(ignore)]
+    mass: f64,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use soa_derive::StructOfArray;
+
+#[derive(StructOfArray)]
+#[soa(storage = "linked_list")]
+pub struct Particle {
+    mass: f32,
+}
+
+fn main() {}
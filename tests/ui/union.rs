@@ -0,0 +1,9 @@
+use soa_derive::StructOfArray;
+
+#[derive(StructOfArray)]
+union Number {
+    int: u32,
+    float: f32,
+}
+
+fn main() {}
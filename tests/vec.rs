@@ -5,7 +5,7 @@ use std::rc::Rc;
 
 mod particles;
 use self::particles::{Particle, ParticleVec};
-use soa_derive::StructOfArray;
+use soa_derive::{LengthMismatch, StructOfArray};
 
 #[test]
 fn ty() {
@@ -206,3 +206,206 @@ fn drop_vec() {
     drop(vec);
     assert_eq!(counter.get(), 5);
 }
+
+#[test]
+fn dedup() {
+    let mut particles = ParticleVec::new();
+    particles.push(Particle::new(String::from("Cl"), 1.0));
+    particles.push(Particle::new(String::from("Cl"), 2.0));
+    particles.push(Particle::new(String::from("Na"), 3.0));
+    particles.push(Particle::new(String::from("Cl"), 4.0));
+    particles.push(Particle::new(String::from("Zn"), 5.0));
+    particles.push(Particle::new(String::from("Zn"), 6.0));
+
+    particles.dedup_by_key(|particle| particle.name.clone());
+    assert_eq!(particles.name, ["Cl", "Na", "Cl", "Zn"]);
+    assert_eq!(particles.mass, [1.0, 3.0, 4.0, 5.0]);
+
+    particles.dedup_by(|particle, kept| {
+        if particle.name.len() == kept.name.len() {
+            *kept.mass += *particle.mass;
+            true
+        } else {
+            false
+        }
+    });
+    assert_eq!(particles.name, ["Cl"]);
+    assert_eq!(particles.mass, [13.0]);
+
+    let mut empty = ParticleVec::new();
+    empty.dedup_by(|_, _| true);
+    assert!(empty.is_empty());
+}
+
+fn numbered(count: usize) -> ParticleVec {
+    let mut particles = ParticleVec::new();
+    for i in 0..count {
+        particles.push(Particle::new(format!("P{}", i), i as f64));
+    }
+    particles
+}
+
+#[test]
+fn drain() {
+    let mut particles = numbered(6);
+    let other = particles.drain(1..3).collect::<ParticleVec>();
+    assert_eq!(particles.name, ["P0", "P3", "P4", "P5"]);
+    assert_eq!(particles.mass, [0.0, 3.0, 4.0, 5.0]);
+    assert_eq!(other.name, ["P1", "P2"]);
+
+    let mut drain = particles.drain(1..);
+    assert_eq!(drain.len(), 3);
+    assert_eq!(drain.next_back().unwrap().name, "P5");
+    assert_eq!(drain.next().unwrap().name, "P3");
+    drop(drain);
+    assert_eq!(particles.name, ["P0"]);
+
+    assert_eq!(particles.drain(..).count(), 1);
+    assert!(particles.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "range end index 4 out of range for slice of length 3")]
+fn drain_out_of_bounds() {
+    let mut particles = numbered(3);
+    particles.drain(1..4);
+}
+
+#[test]
+fn drain_drop() {
+    let counter = Rc::new(Cell::default());
+    let mut vec = IncrOnDropVec::new();
+    for _ in 0..5 {
+        vec.push(IncrOnDrop {
+            cell: counter.clone(),
+        });
+    }
+
+    let mut drain = vec.drain(1..=3);
+    drop(drain.next());
+    assert_eq!(counter.get(), 1);
+    drop(drain);
+    assert_eq!(counter.get(), 3);
+    assert_eq!(vec.len(), 2);
+
+    // forgetting the iterator leaks the elements
+    std::mem::forget(vec.drain(..1));
+    assert_eq!(vec.len(), 0);
+    drop(vec);
+    assert_eq!(counter.get(), 3);
+}
+
+#[test]
+fn splice() {
+    let mut particles = numbered(5);
+    let removed = particles.splice(1..3, vec![Particle::new(String::from("Na"), 23.0)]).collect::<ParticleVec>();
+    assert_eq!(removed.name, ["P1", "P2"]);
+    assert_eq!(particles.name, ["P0", "Na", "P3", "P4"]);
+    assert_eq!(particles.mass, [0.0, 23.0, 3.0, 4.0]);
+
+    let replacement = numbered(3).drain(..).map(|mut particle| {
+        particle.name.push('!');
+        particle
+    }).collect::<Vec<_>>();
+    let removed = particles.splice(3.., replacement).collect::<ParticleVec>();
+    assert_eq!(removed.name, ["P4"]);
+    assert_eq!(particles.name, ["P0", "Na", "P3", "P0!", "P1!", "P2!"]);
+
+    // the replacement is inserted even if the iterator is not consumed
+    drop(particles.splice(..1, numbered(2).drain(..)));
+    assert_eq!(particles.name, ["P0", "P1", "Na", "P3", "P0!", "P1!", "P2!"]);
+    assert_eq!(particles.mass, [0.0, 1.0, 23.0, 3.0, 0.0, 1.0, 2.0]);
+
+    drop(particles.splice(2..6, std::iter::empty()));
+    assert_eq!(particles.name, ["P0", "P1", "P2!"]);
+}
+
+#[test]
+fn extract_if() {
+    let mut particles = numbered(8);
+    let even = particles.extract_if(.., |particle| *particle.mass as usize % 2 == 0).collect::<ParticleVec>();
+    assert_eq!(even.name, ["P0", "P2", "P4", "P6"]);
+    assert_eq!(particles.name, ["P1", "P3", "P5", "P7"]);
+
+    let mut extract = particles.extract_if(1.., |particle| {
+        *particle.mass *= 10.0;
+        true
+    });
+    assert_eq!(extract.next().unwrap().name, "P3");
+    drop(extract);
+    // the other elements are kept if the iterator is not consumed
+    assert_eq!(particles.name, ["P1", "P5", "P7"]);
+    assert_eq!(particles.mass, [1.0, 5.0, 7.0]);
+}
+
+#[test]
+fn into_iter() {
+    let particles = numbered(4);
+    let mut iter = particles.into_iter();
+    assert_eq!(iter.len(), 4);
+    assert_eq!(iter.next().unwrap().name, "P0");
+    assert_eq!(iter.next_back().unwrap().name, "P3");
+    let rest = iter.map(|particle| particle.mass).collect::<Vec<_>>();
+    assert_eq!(rest, [1.0, 2.0]);
+
+    let vec: Vec<Particle> = numbered(3).into();
+    assert_eq!(vec[2].name, "P2");
+    let particles = ParticleVec::from(vec);
+    assert_eq!(particles.name, ["P0", "P1", "P2"]);
+    assert_eq!(particles.mass, [0.0, 1.0, 2.0]);
+
+    let mut names = Vec::new();
+    for particle in particles {
+        names.push(particle.name);
+    }
+    assert_eq!(names, ["P0", "P1", "P2"]);
+}
+
+#[test]
+fn into_iter_drop() {
+    let counter = Rc::new(Cell::default());
+    let mut vec = IncrOnDropVec::new();
+    for _ in 0..5 {
+        vec.push(IncrOnDrop {
+            cell: counter.clone(),
+        });
+    }
+
+    let mut iter = vec.into_iter();
+    drop(iter.next());
+    assert_eq!(counter.get(), 1);
+    drop(iter);
+    assert_eq!(counter.get(), 5);
+}
+
+#[test]
+fn columns() {
+    let particles = ParticleVec::from_columns(vec![String::from("H"), String::from("He")], vec![1.0, 4.0]).unwrap();
+    assert_eq!(particles.len(), 2);
+    assert_eq!(particles.validate(), Ok(()));
+    assert_eq!(particles.index(1).to_owned(), Particle::new(String::from("He"), 4.0));
+
+    let (name, mass) = particles.into_columns();
+    assert_eq!(name, ["H", "He"]);
+    assert_eq!(mass, [1.0, 4.0]);
+
+    let error = ParticleVec::from_columns(vec![String::from("H")], vec![1.0, 4.0]).unwrap_err();
+    assert_eq!(error, LengthMismatch::new("mass", 1, 2));
+    assert_eq!(error.to_string(), "column `mass` has length 2, expected 1");
+
+    let mut particles = numbered(3);
+    particles.mass.pop();
+    assert_eq!(particles.validate(), Err(LengthMismatch::new("mass", 3, 2)));
+    particles.mass.push(2.0);
+    assert_eq!(particles.validate(), Ok(()));
+}
+
+#[test]
+fn columns_drop() {
+    let counter = Rc::new(Cell::default());
+    let cells = (0..3).map(|_| IncrOnDrop { cell: counter.clone() }).collect::<IncrOnDropVec>().into_columns().0;
+    assert_eq!(counter.get(), 0);
+    let vec = IncrOnDropVec::from_columns(cells).unwrap();
+    drop(vec);
+    assert_eq!(counter.get(), 3);
+}